@@ -46,6 +46,10 @@ fn main() {
         labels: build_image_labels,
         networkmode: "host",
         platform: "linux/x86_64",
+        target: "",
+        buildid: None,
+        version: None,
+        session: None,
     };
 
     let future = run(docker, build_image_options);
@@ -83,7 +83,10 @@ async fn run() -> Result<(), failure::Error> {
 
     &docker
         .create_container(
-            Some(CreateContainerOptions { name: "zookeeper" }),
+            Some(CreateContainerOptions {
+                name: "zookeeper",
+                ..Default::default()
+            }),
             zookeeper_config,
         )
         .await?;
@@ -106,7 +109,10 @@ async fn run() -> Result<(), failure::Error> {
 
     &docker
         .create_container(
-            Some(CreateContainerOptions { name: "kafka1" }),
+            Some(CreateContainerOptions {
+                name: "kafka1",
+                ..Default::default()
+            }),
             broker1_config,
         )
         .await?;
@@ -139,7 +145,10 @@ async fn run() -> Result<(), failure::Error> {
 
     &docker
         .create_container(
-            Some(CreateContainerOptions { name: "kafka2" }),
+            Some(CreateContainerOptions {
+                name: "kafka2",
+                ..Default::default()
+            }),
             broker2_config,
         )
         .await?;
@@ -35,7 +35,13 @@ async fn run<'a>() -> Result<(), Error> {
         } else {
             for container in containers {
                 &docker
-                    .stats(&container.id, Some(StatsOptions { stream: false }))
+                    .stats(
+                        &container.id,
+                        Some(StatsOptions {
+                            stream: false,
+                            ..Default::default()
+                        }),
+                    )
                     .take(1)
                     .map(|value| match value {
                         Ok(stats) => {
@@ -0,0 +1,171 @@
+//! Hooks to observe the requests made by the client, e.g. for audit logging.
+use http::request::Parts;
+use hyper::body::{Bytes, HttpBody};
+use hyper::{Body, Request};
+use serde_json::Value;
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::HyperResponseError;
+
+/// Placeholder substituted for redacted header values and payload fields.
+pub const REDACTED: &str = "<redacted>";
+
+/// Headers whose values carry credentials, and are never passed to a hook.
+const REDACTED_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "x-registry-auth",
+    "x-registry-config",
+];
+
+/// Request bodies larger than this, or streamed bodies of unknown size, are not captured.
+const MAX_CAPTURED_BODY: u64 = 64 * 1024;
+
+/// Hook invoked before a request is sent to the docker server.
+pub type RequestHook = Arc<dyn Fn(RequestInfo) + Send + Sync>;
+
+/// Hook invoked after the docker server responds to a request, or the request fails.
+pub type ResponseHook = Arc<dyn Fn(ResponseInfo) + Send + Sync>;
+
+/// A sanitized copy of a request, passed to a [request hook](type.RequestHook.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestInfo {
+    /// The HTTP method, e.g. `POST`.
+    pub method: String,
+    /// The request path, including the API version prefix.
+    pub path: String,
+    /// The request headers, including the default headers of the client, with credentials
+    /// redacted.
+    pub headers: Vec<(String, String)>,
+    /// The request body, if it is of a known size up to 64 KiB, with secret data redacted.
+    pub body: Option<String>,
+    /// The correlation id set with
+    /// [`Docker::with_correlation_id`](../struct.Docker.html#method.with_correlation_id).
+    pub correlation_id: Option<String>,
+}
+
+/// The outcome of a request, passed to a [response hook](type.ResponseHook.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseInfo {
+    /// The HTTP method, e.g. `POST`.
+    pub method: String,
+    /// The request path, including the API version prefix.
+    pub path: String,
+    /// The status code returned by the docker server, or `None` if no response was received.
+    pub status: Option<u16>,
+    /// The time taken until the response headers were received.
+    pub duration: Duration,
+    /// The correlation id set with
+    /// [`Docker::with_correlation_id`](../struct.Docker.html#method.with_correlation_id).
+    pub correlation_id: Option<String>,
+}
+
+/// Hooks and correlation id carried by a client.
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    pub(crate) on_request: Option<RequestHook>,
+    pub(crate) on_response: Option<ResponseHook>,
+    pub(crate) correlation_id: Option<String>,
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("on_request", &self.on_request.is_some())
+            .field("on_response", &self.on_response.is_some())
+            .field("correlation_id", &self.correlation_id)
+            .finish()
+    }
+}
+
+impl Hooks {
+    pub(crate) fn is_set(&self) -> bool {
+        self.on_request.is_some() || self.on_response.is_some()
+    }
+
+    /// Pass a sanitized copy of the request to the request hook. The body is buffered in order to
+    /// be copied, so the request is rebuilt from its parts.
+    pub(crate) async fn request(&self, request: Request<Body>) -> Result<Request<Body>, Error> {
+        let hook = match self.on_request {
+            Some(ref hook) => hook,
+            None => return Ok(request),
+        };
+
+        let (parts, body) = request.into_parts();
+        let (body, captured) = match body.size_hint().exact() {
+            Some(size) if size <= MAX_CAPTURED_BODY => {
+                let bytes = hyper::body::to_bytes(body)
+                    .await
+                    .map_err(|e| HyperResponseError { err: e })?;
+                let captured = sanitize_body(parts.uri.path(), &bytes);
+                (Body::from(bytes), captured)
+            }
+            _ => (body, None),
+        };
+
+        hook(RequestInfo {
+            method: parts.method.to_string(),
+            path: parts.uri.path().to_owned(),
+            headers: sanitize_headers(&parts),
+            body: captured,
+            correlation_id: self.correlation_id.clone(),
+        });
+
+        Ok(Request::from_parts(parts, body))
+    }
+
+    pub(crate) fn response(
+        &self,
+        method: String,
+        path: String,
+        status: Option<u16>,
+        duration: Duration,
+    ) {
+        if let Some(ref hook) = self.on_response {
+            hook(ResponseInfo {
+                method,
+                path,
+                status,
+                duration,
+                correlation_id: self.correlation_id.clone(),
+            });
+        }
+    }
+}
+
+fn sanitize_headers(parts: &Parts) -> Vec<(String, String)> {
+    parts
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_owned()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.as_str().to_owned(), value)
+        })
+        .collect()
+}
+
+/// Copy the body, redacting the `Data` field of a secret creation payload. A secret payload that
+/// cannot be parsed is not captured at all.
+fn sanitize_body(path: &str, bytes: &Bytes) -> Option<String> {
+    if bytes.is_empty() {
+        return None;
+    }
+
+    if path.ends_with("/secrets/create") {
+        let mut value: Value = serde_json::from_slice(bytes).ok()?;
+        if let Some(data) = value.get_mut("Data") {
+            *data = Value::String(REDACTED.to_owned());
+        }
+        return Some(value.to_string());
+    }
+
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
@@ -1,16 +1,258 @@
 //! Credentials management, for access to the Docker Hub or a custom Registry.
 
+use base64;
+use serde_json;
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::{
+    IOError, InvalidRegistryAuthError, JsonDeserializeError, JsonSerializeError,
+};
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 /// DockerCredentials credentials and server URI to push images using the [Push Image
 /// API](../struct.Docker.html#method.push_image) or the [Build Image
 /// API](../struct.Docker.html#method.build_image).
 pub struct DockerCredentials {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub auth: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub serveraddress: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identitytoken: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub registrytoken: Option<String>,
 }
+
+/// Registry authentication sent in the `X-Registry-Auth` header of the [Create Image
+/// API](../struct.Docker.html#method.create_image), the [Push Image
+/// API](../struct.Docker.html#method.push_image), the [Search Images
+/// API](../struct.Docker.html#method.search_images), the [Inspect Distribution
+/// API](../struct.Docker.html#method.inspect_distribution) and the [Create Service
+/// API](../struct.Docker.html#method.create_service).
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::auth::{DockerCredentials, RegistryAuth};
+///
+/// RegistryAuth::IdentityToken(String::from("eyJhbGciOi..."));
+///
+/// let auth: RegistryAuth = DockerCredentials {
+///     username: Some(String::from("user")),
+///     password: Some(String::from("secret")),
+///     serveraddress: Some(String::from("registry.example.com")),
+///     ..Default::default()
+/// }
+/// .into();
+/// ```
+#[derive(Debug, Clone)]
+pub enum RegistryAuth {
+    /// A username and password.
+    Basic {
+        /// The username.
+        username: String,
+        /// The password.
+        password: String,
+    },
+    /// An identity token, as issued by the registry's OAuth flow, exchanged by the docker server
+    /// for a registry token.
+    IdentityToken(String),
+    /// A bearer token passed to the registry as is.
+    RegistryToken(String),
+    /// A value already encoded for the `X-Registry-Auth` header, passed through verbatim.
+    Preencoded(String),
+    /// Credentials with arbitrary fields set, e.g. including the server address.
+    Credentials(DockerCredentials),
+}
+
+impl RegistryAuth {
+    /// Encode the authentication as the value of the `X-Registry-Auth` header: JSON with only the
+    /// fields of the variant, encoded with base64.
+    pub fn encode(&self) -> Result<String, Error> {
+        let credentials = match self {
+            RegistryAuth::Basic { username, password } => DockerCredentials {
+                username: Some(username.to_owned()),
+                password: Some(password.to_owned()),
+                ..Default::default()
+            },
+            RegistryAuth::IdentityToken(token) => DockerCredentials {
+                identitytoken: Some(token.to_owned()),
+                ..Default::default()
+            },
+            RegistryAuth::RegistryToken(token) => DockerCredentials {
+                registrytoken: Some(token.to_owned()),
+                ..Default::default()
+            },
+            RegistryAuth::Preencoded(value) => return Ok(value.to_owned()),
+            RegistryAuth::Credentials(credentials) => credentials.to_owned(),
+        };
+
+        let json =
+            serde_json::to_string(&credentials).map_err(|e| JsonSerializeError { err: e })?;
+        Ok(base64::encode(&json))
+    }
+}
+
+impl From<DockerCredentials> for RegistryAuth {
+    fn from(credentials: DockerCredentials) -> Self {
+        RegistryAuth::Credentials(credentials)
+    }
+}
+
+/// The value of the `X-Registry-Auth` header, the encoded empty credentials if none are given.
+pub(crate) fn registry_auth_header(credentials: Option<RegistryAuth>) -> Result<String, Error> {
+    credentials
+        .unwrap_or_else(|| RegistryAuth::Credentials(DockerCredentials::default()))
+        .encode()
+}
+
+/// Credentials for several registries, keyed by registry hostname, sent with the [Build Image
+/// API](../struct.Docker.html#method.build_image) so that a build can pull images from each of
+/// them.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use bollard::auth::{DockerCredentials, RegistryAuthConfigs};
+///
+/// let configs = RegistryAuthConfigs::from_docker_config()
+///     .unwrap()
+///     .with_credentials(
+///         "registry.example.com",
+///         DockerCredentials {
+///             username: Some(String::from("user")),
+///             password: Some(String::from("secret")),
+///             ..Default::default()
+///         },
+///     );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RegistryAuthConfigs {
+    configs: HashMap<String, DockerCredentials>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DockerConfigFile {
+    #[serde(default)]
+    auths: HashMap<String, DockerCredentials>,
+}
+
+impl RegistryAuthConfigs {
+    /// An empty set of credentials.
+    pub fn new() -> Self {
+        RegistryAuthConfigs::default()
+    }
+
+    /// Read the credentials stored in `config.json` within the `DOCKER_CONFIG` directory, or
+    /// `~/.docker` if it is not set. A missing file yields an empty set of credentials.
+    ///
+    /// Only the credentials stored in the file itself are read, those held by a credentials store
+    /// or helper are not.
+    pub fn from_docker_config() -> Result<Self, Error> {
+        match docker_config_dir() {
+            Some(dir) => RegistryAuthConfigs::from_docker_config_file(dir.join("config.json")),
+            None => Ok(RegistryAuthConfigs::default()),
+        }
+    }
+
+    /// Read the credentials stored in a docker config file. A missing file yields an empty set of
+    /// credentials.
+    pub fn from_docker_config_file<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(RegistryAuthConfigs::default())
+            }
+            Err(e) => return Err(IOError { err: e }.into()),
+        };
+
+        let file: DockerConfigFile =
+            serde_json::from_str(&content).map_err(|e| JsonDeserializeError { content, err: e })?;
+
+        let mut configs = RegistryAuthConfigs::default();
+        for (registry, mut credentials) in file.auths {
+            // the daemon only reads the username and password, as the docker CLI decodes them
+            if let Some(auth) = credentials.auth.take() {
+                let (username, password) =
+                    decode_auth(&auth).ok_or_else(|| InvalidRegistryAuthError {
+                        registry: registry.clone(),
+                    })?;
+                credentials.username = Some(username);
+                credentials.password = Some(password);
+            }
+            if credentials.serveraddress.is_none() {
+                credentials.serveraddress = Some(registry.clone());
+            }
+            configs.configs.insert(registry, credentials);
+        }
+
+        Ok(configs)
+    }
+
+    /// Add the credentials for a registry, replacing any credentials read for it.
+    pub fn with_credentials<T>(mut self, registry: T, credentials: DockerCredentials) -> Self
+    where
+        T: Into<String>,
+    {
+        self.configs.insert(registry.into(), credentials);
+        self
+    }
+
+    /// Whether no credentials are set.
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+
+    /// Encode the credentials as the value of the `X-Registry-Config` header: JSON with sorted
+    /// keys, encoded with the padded URL-safe base64 alphabet, as the docker CLI does.
+    pub fn encode(&self) -> Result<String, Error> {
+        let configs: BTreeMap<&String, &DockerCredentials> = self.configs.iter().collect();
+        let json = serde_json::to_string(&configs).map_err(|e| JsonSerializeError { err: e })?;
+        Ok(base64::encode_config(&json, base64::URL_SAFE))
+    }
+}
+
+impl From<HashMap<String, DockerCredentials>> for RegistryAuthConfigs {
+    fn from(configs: HashMap<String, DockerCredentials>) -> Self {
+        RegistryAuthConfigs { configs }
+    }
+}
+
+impl From<RegistryAuthConfigs> for HashMap<String, DockerCredentials> {
+    fn from(configs: RegistryAuthConfigs) -> Self {
+        configs.configs
+    }
+}
+
+fn docker_config_dir() -> Option<PathBuf> {
+    match env::var("DOCKER_CONFIG") {
+        Ok(dir) => Some(PathBuf::from(dir)),
+        Err(_) => dirs::home_dir().map(|home| home.join(".docker")),
+    }
+}
+
+fn decode_auth(auth: &str) -> Option<(String, String)> {
+    let decoded = base64::decode(auth).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let mut parts = decoded.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(username), Some(password)) => Some((username.to_owned(), password.to_owned())),
+        _ => None,
+    }
+}
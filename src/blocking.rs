@@ -0,0 +1,901 @@
+#![cfg(feature = "blocking")]
+//! Blocking API: a synchronous client for programs that do not run a tokio runtime of their own,
+//! such as command line tools and build scripts.
+//!
+//! The client owns a runtime, on which it runs the requests of the [asynchronous
+//! client](../struct.Docker.html) it wraps. Streaming endpoints return an
+//! [`Iter`](struct.Iter.html), yielding the items of the stream as they arrive.
+//!
+//! The methods of the client block the current thread, and must not be called from within an
+//! asynchronous context.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bollard::blocking::Docker;
+//! use bollard::container::{ListContainersOptions, LogsOptions};
+//!
+//! let docker = Docker::connect_with_local_defaults().unwrap();
+//!
+//! for container in docker
+//!     .list_containers(Some(ListContainersOptions::<String>::default()))
+//!     .unwrap()
+//! {
+//!     let logs = docker.logs(
+//!         &container.id,
+//!         Some(LogsOptions {
+//!             stdout: true,
+//!             ..Default::default()
+//!         }),
+//!     );
+//!     for line in logs {
+//!         println!("{}", line.unwrap());
+//!     }
+//! }
+//! ```
+use futures_core::Stream;
+use futures_util::stream::StreamExt;
+use hyper::{body::Bytes, Body};
+use serde::Serialize;
+use tokio::runtime::{Builder, Runtime};
+
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::auth::{DockerCredentials, RegistryAuth};
+#[cfg(feature = "experimental")]
+use crate::checkpoint::{Checkpoint, CheckpointDirQueryParams, CreateCheckpointOptions};
+use crate::container::{
+    APIContainers, Change, Config, Container, ContainerPathStat, ContainerStateChange,
+    CreateContainerQueryParams, CreateContainerResults, DownloadFromContainerQueryParams,
+    InspectContainerQueryParams, KillContainerQueryParams, ListContainersQueryParams, LogOutput,
+    LogsOptions, LogsQueryParams, PruneContainersQueryParams, PruneContainersResults,
+    ReconnectingLogOutput, RemoveContainerQueryParams, RenameContainerQueryParams,
+    ResizeContainerTtyQueryParams, RestartContainerQueryParams, RunOptions, RunResults,
+    StartContainerQueryParams, StateHealth, Stats, StatsQueryParams, StopContainerQueryParams,
+    TopQueryParams, TopResult, UpdateContainerOptions, UpdateContainerResults,
+    UploadToContainerQueryParams, WaitCondition, WaitContainerQueryParams, WaitContainerResults,
+};
+use crate::errors::Error;
+use crate::exec::{
+    CreateExecOptions, CreateExecResults, ExecInspect, StartExecOptions, StartExecResults,
+};
+use crate::image::{
+    APIImageSearch, APIImages, BuildImageQueryParams, BuildImageResults,
+    CommitContainerQueryParams, CommitContainerResults, CreateImageQueryParams, CreateImageResults,
+    Image, ImageHistory, ImportImageQueryParams, ImportImageResults, ListImagesQueryParams,
+    PruneImagesQueryParams, PruneImagesResults, PushImageQueryParams, RemoveImageQueryParams,
+    RemoveImageResults, SearchImagesQueryParams, TagImageQueryParams,
+};
+use crate::network::{
+    ConnectNetworkOptions, CreateNetworkOptions, CreateNetworkResults, DisconnectNetworkOptions,
+    InspectNetworkQueryParams, InspectNetworkResults, ListNetworksQueryParams, ListNetworksResults,
+    PruneNetworksQueryParams, PruneNetworksResults,
+};
+use crate::system::{DfResults, EventsQueryParams, EventsResults, Version};
+use crate::volume::{
+    CreateVolumeOptions, ListVolumesOptions, ListVolumesResults, PruneVolumesQueryParams,
+    PruneVolumesResults, RemoveVolumeOptions, VolumeAPI,
+};
+
+/// An iterator over the items of a stream returned by the docker server, blocking the current
+/// thread until the next item arrives.
+pub struct Iter<S> {
+    runtime: Arc<Runtime>,
+    stream: Pin<Box<S>>,
+}
+
+impl<S> fmt::Debug for Iter<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter").finish()
+    }
+}
+
+impl<S: Stream> Iterator for Iter<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stream = &mut self.stream;
+        self.runtime.handle().block_on(stream.next())
+    }
+}
+
+/// A synchronous client for the docker server, wrapping the [asynchronous
+/// client](../struct.Docker.html). Clones share the runtime and connections of the client.
+#[derive(Debug, Clone)]
+pub struct Docker {
+    inner: crate::Docker,
+    runtime: Arc<Runtime>,
+}
+
+impl Docker {
+    /// Run the requests of an asynchronous client on a runtime owned by the new client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::blocking::Docker;
+    ///
+    /// let docker = Docker::new(bollard::Docker::connect_with_unix_defaults().unwrap()).unwrap();
+    /// ```
+    pub fn new(docker: crate::Docker) -> Result<Docker, Error> {
+        let runtime = Builder::new()
+            .threaded_scheduler()
+            .core_threads(1)
+            .thread_name("bollard-blocking")
+            .enable_all()
+            .build()?;
+
+        Ok(Docker {
+            inner: docker,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Connect using the local machine connection method with default arguments, see
+    /// [`connect_with_local_defaults`](../struct.Docker.html#method.connect_with_local_defaults).
+    pub fn connect_with_local_defaults() -> Result<Docker, Error> {
+        Docker::new(crate::Docker::connect_with_local_defaults()?)
+    }
+
+    /// Connect to the docker server configured by the `DOCKER_HOST` environment variable, see
+    /// [`connect_with_defaults`](../struct.Docker.html#method.connect_with_defaults).
+    pub fn connect_with_defaults() -> Result<Docker, Error> {
+        Docker::new(crate::Docker::connect_with_defaults()?)
+    }
+
+    /// The asynchronous client wrapped by this client.
+    pub fn inner(&self) -> &crate::Docker {
+        &self.inner
+    }
+
+    /// Run a future on the runtime of this client, blocking the current thread until it
+    /// completes. This reaches the methods of the asynchronous client without a blocking version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::blocking::Docker;
+    ///
+    /// let docker = Docker::connect_with_local_defaults().unwrap();
+    /// let swarm = docker.block_on(docker.inner().inspect_swarm());
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.handle().block_on(future)
+    }
+
+    /// Open a stream within the runtime of this client, as streams may set up timers when opened.
+    fn iter<S, F>(&self, open: F) -> Iter<S>
+    where
+        S: Stream,
+        F: FnOnce() -> S,
+    {
+        Iter {
+            runtime: self.runtime.clone(),
+            stream: Box::pin(self.runtime.enter(open)),
+        }
+    }
+
+    /// Blocking [`version`](../struct.Docker.html#method.version).
+    pub fn version(&self) -> Result<Version, Error> {
+        self.block_on(self.inner.version())
+    }
+
+    /// Blocking [`ping`](../struct.Docker.html#method.ping).
+    pub fn ping(&self) -> Result<String, Error> {
+        self.block_on(self.inner.ping())
+    }
+
+    /// Blocking [`events`](../struct.Docker.html#method.events), as an iterator.
+    pub fn events<T, K, V>(
+        &self,
+        options: Option<T>,
+    ) -> Iter<impl Stream<Item = Result<EventsResults, Error>>>
+    where
+        T: EventsQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.iter(|| self.inner.events(options))
+    }
+
+    /// Blocking [`events_reconnecting`](../struct.Docker.html#method.events_reconnecting), as an
+    /// iterator.
+    pub fn events_reconnecting<T>(
+        &self,
+        filters: HashMap<T, Vec<T>>,
+    ) -> Iter<impl Stream<Item = Result<EventsResults, Error>>>
+    where
+        T: AsRef<str> + Eq + Hash,
+    {
+        self.iter(|| self.inner.events_reconnecting(filters))
+    }
+
+    /// Blocking [`df`](../struct.Docker.html#method.df).
+    pub fn df(&self) -> Result<DfResults, Error> {
+        self.block_on(self.inner.df())
+    }
+
+    /// Blocking [`list_containers`](../struct.Docker.html#method.list_containers).
+    pub fn list_containers<T, K>(&self, options: Option<T>) -> Result<Vec<APIContainers>, Error>
+    where
+        T: ListContainersQueryParams<K, String>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.list_containers(options))
+    }
+
+    /// Blocking [`create_container`](../struct.Docker.html#method.create_container).
+    pub fn create_container<T, K, V, Z>(
+        &self,
+        options: Option<T>,
+        config: Config<Z>,
+    ) -> Result<CreateContainerResults, Error>
+    where
+        T: CreateContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        Z: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.create_container(options, config))
+    }
+
+    /// Blocking [`start_container`](../struct.Docker.html#method.start_container).
+    pub fn start_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<ContainerStateChange, Error>
+    where
+        T: StartContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.start_container(container_name, options))
+    }
+
+    /// Blocking [`stop_container`](../struct.Docker.html#method.stop_container).
+    pub fn stop_container<T, K>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<ContainerStateChange, Error>
+    where
+        T: StopContainerQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.stop_container(container_name, options))
+    }
+
+    /// Blocking [`remove_container`](../struct.Docker.html#method.remove_container).
+    pub fn remove_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<(), Error>
+    where
+        T: RemoveContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.remove_container(container_name, options))
+    }
+
+    /// Blocking [`wait_container`](../struct.Docker.html#method.wait_container), as an iterator.
+    pub fn wait_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Iter<impl Stream<Item = Result<WaitContainerResults, Error>>>
+    where
+        T: WaitContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.iter(|| self.inner.wait_container(container_name, options))
+    }
+
+    /// Blocking [`wait_container_until`](../struct.Docker.html#method.wait_container_until).
+    pub fn wait_container_until(
+        &self,
+        container_name: &str,
+        condition: WaitCondition,
+    ) -> Result<WaitContainerResults, Error> {
+        self.block_on(self.inner.wait_container_until(container_name, condition))
+    }
+
+    /// Blocking [`restart_container`](../struct.Docker.html#method.restart_container).
+    pub fn restart_container<T, K>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<(), Error>
+    where
+        T: RestartContainerQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.restart_container(container_name, options))
+    }
+
+    /// Blocking [`inspect_container`](../struct.Docker.html#method.inspect_container).
+    pub fn inspect_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<Container, Error>
+    where
+        T: InspectContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.inspect_container(container_name, options))
+    }
+
+    /// Blocking [`top_processes`](../struct.Docker.html#method.top_processes).
+    pub fn top_processes<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<TopResult, Error>
+    where
+        T: TopQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.top_processes(container_name, options))
+    }
+
+    /// Blocking [`logs`](../struct.Docker.html#method.logs), as an iterator.
+    pub fn logs<T, K>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Iter<impl Stream<Item = Result<LogOutput, Error>>>
+    where
+        T: LogsQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.iter(|| self.inner.logs(container_name, options))
+    }
+
+    /// Blocking [`logs_reconnecting`](../struct.Docker.html#method.logs_reconnecting), as an
+    /// iterator.
+    pub fn logs_reconnecting(
+        &self,
+        container_name: &str,
+        options: LogsOptions,
+    ) -> Iter<impl Stream<Item = Result<ReconnectingLogOutput, Error>>> {
+        self.iter(|| self.inner.logs_reconnecting(container_name, options))
+    }
+
+    /// Blocking [`container_changes`](../struct.Docker.html#method.container_changes).
+    pub fn container_changes(&self, container_name: &str) -> Result<Option<Vec<Change>>, Error> {
+        self.block_on(self.inner.container_changes(container_name))
+    }
+
+    /// Blocking [`stats`](../struct.Docker.html#method.stats), as an iterator.
+    pub fn stats<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Iter<impl Stream<Item = Result<Stats, Error>>>
+    where
+        T: StatsQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.iter(|| self.inner.stats(container_name, options))
+    }
+
+    /// Blocking [`stats_once`](../struct.Docker.html#method.stats_once).
+    pub fn stats_once(&self, container_name: &str, one_shot: bool) -> Result<Stats, Error> {
+        self.block_on(self.inner.stats_once(container_name, one_shot))
+    }
+
+    /// Blocking [`kill_container`](../struct.Docker.html#method.kill_container).
+    pub fn kill_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<(), Error>
+    where
+        T: KillContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.kill_container(container_name, options))
+    }
+
+    /// Blocking [`update_container`](../struct.Docker.html#method.update_container).
+    pub fn update_container(
+        &self,
+        container_name: &str,
+        config: UpdateContainerOptions,
+    ) -> Result<UpdateContainerResults, Error> {
+        self.block_on(self.inner.update_container(container_name, config))
+    }
+
+    /// Blocking [`rename_container`](../struct.Docker.html#method.rename_container).
+    pub fn rename_container<T, K, V>(&self, container_name: &str, options: T) -> Result<(), Error>
+    where
+        T: RenameContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.rename_container(container_name, options))
+    }
+
+    /// Blocking [`resize_container_tty`](../struct.Docker.html#method.resize_container_tty).
+    pub fn resize_container_tty<T, K>(&self, container_name: &str, options: T) -> Result<(), Error>
+    where
+        T: ResizeContainerTtyQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.resize_container_tty(container_name, options))
+    }
+
+    /// Blocking [`pause_container`](../struct.Docker.html#method.pause_container).
+    pub fn pause_container(&self, container_name: &str) -> Result<ContainerStateChange, Error> {
+        self.block_on(self.inner.pause_container(container_name))
+    }
+
+    /// Blocking [`unpause_container`](../struct.Docker.html#method.unpause_container).
+    pub fn unpause_container(&self, container_name: &str) -> Result<ContainerStateChange, Error> {
+        self.block_on(self.inner.unpause_container(container_name))
+    }
+
+    /// Blocking [`prune_containers`](../struct.Docker.html#method.prune_containers).
+    pub fn prune_containers<T, K>(
+        &self,
+        options: Option<T>,
+    ) -> Result<PruneContainersResults, Error>
+    where
+        T: PruneContainersQueryParams<K>,
+        K: AsRef<str> + Eq + Hash,
+    {
+        self.block_on(self.inner.prune_containers(options))
+    }
+
+    /// Blocking [`upload_to_container`](../struct.Docker.html#method.upload_to_container).
+    pub fn upload_to_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+        tar: Body,
+    ) -> Result<(), Error>
+    where
+        T: UploadToContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.upload_to_container(container_name, options, tar))
+    }
+
+    /// Blocking
+    /// [`download_from_container`](../struct.Docker.html#method.download_from_container), as an
+    /// iterator.
+    pub fn download_from_container<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Iter<impl Stream<Item = Result<Bytes, Error>>>
+    where
+        T: DownloadFromContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.iter(|| self.inner.download_from_container(container_name, options))
+    }
+
+    /// Blocking [`stat_container_path`](../struct.Docker.html#method.stat_container_path).
+    pub fn stat_container_path(
+        &self,
+        container_name: &str,
+        path: &str,
+    ) -> Result<ContainerPathStat, Error> {
+        self.block_on(self.inner.stat_container_path(container_name, path))
+    }
+
+    /// Blocking [`export_container`](../struct.Docker.html#method.export_container), as an
+    /// iterator.
+    pub fn export_container(
+        &self,
+        container_name: &str,
+    ) -> Iter<impl Stream<Item = Result<Bytes, Error>>> {
+        self.iter(|| self.inner.export_container(container_name))
+    }
+
+    /// Blocking [`wait_until_healthy`](../struct.Docker.html#method.wait_until_healthy).
+    pub fn wait_until_healthy(
+        &self,
+        container_name: &str,
+        timeout: Duration,
+    ) -> Result<StateHealth, Error> {
+        self.block_on(self.inner.wait_until_healthy(container_name, timeout))
+    }
+
+    /// Blocking [`run_and_wait`](../struct.Docker.html#method.run_and_wait).
+    pub fn run_and_wait<T, K, V, Z>(
+        &self,
+        options: Option<T>,
+        config: Config<Z>,
+        run_options: RunOptions,
+    ) -> Result<RunResults, Error>
+    where
+        T: CreateContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        Z: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.run_and_wait(options, config, run_options))
+    }
+
+    /// Blocking [`create_checkpoint`](../struct.Docker.html#method.create_checkpoint).
+    #[cfg(feature = "experimental")]
+    pub fn create_checkpoint<T>(
+        &self,
+        container_name: &str,
+        options: CreateCheckpointOptions<T>,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str> + Serialize,
+    {
+        self.block_on(self.inner.create_checkpoint(container_name, options))
+    }
+
+    /// Blocking [`list_checkpoints`](../struct.Docker.html#method.list_checkpoints).
+    #[cfg(feature = "experimental")]
+    pub fn list_checkpoints<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<Vec<Checkpoint>, Error>
+    where
+        T: CheckpointDirQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.list_checkpoints(container_name, options))
+    }
+
+    /// Blocking [`delete_checkpoint`](../struct.Docker.html#method.delete_checkpoint).
+    #[cfg(feature = "experimental")]
+    pub fn delete_checkpoint<T, K, V>(
+        &self,
+        container_name: &str,
+        checkpoint_id: &str,
+        options: Option<T>,
+    ) -> Result<(), Error>
+    where
+        T: CheckpointDirQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(
+            self.inner
+                .delete_checkpoint(container_name, checkpoint_id, options),
+        )
+    }
+
+    /// Blocking [`list_images`](../struct.Docker.html#method.list_images).
+    pub fn list_images<T, K>(&self, options: Option<T>) -> Result<Vec<APIImages>, Error>
+    where
+        T: ListImagesQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.list_images(options))
+    }
+
+    /// Blocking [`create_image`](../struct.Docker.html#method.create_image), as an iterator.
+    pub fn create_image<T, K, V>(
+        &self,
+        options: Option<T>,
+        root_fs: Option<Body>,
+        credentials: Option<RegistryAuth>,
+    ) -> Iter<impl Stream<Item = Result<CreateImageResults, Error>>>
+    where
+        T: CreateImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.iter(|| self.inner.create_image(options, root_fs, credentials))
+    }
+
+    /// Blocking [`inspect_image`](../struct.Docker.html#method.inspect_image).
+    pub fn inspect_image(&self, image_name: &str) -> Result<Image, Error> {
+        self.block_on(self.inner.inspect_image(image_name))
+    }
+
+    /// Blocking [`prune_images`](../struct.Docker.html#method.prune_images).
+    pub fn prune_images<T, K>(&self, options: Option<T>) -> Result<PruneImagesResults, Error>
+    where
+        T: PruneImagesQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.prune_images(options))
+    }
+
+    /// Blocking [`image_history`](../struct.Docker.html#method.image_history).
+    pub fn image_history(&self, image_name: &str) -> Result<Vec<ImageHistory>, Error> {
+        self.block_on(self.inner.image_history(image_name))
+    }
+
+    /// Blocking [`search_images`](../struct.Docker.html#method.search_images).
+    pub fn search_images<T, K>(
+        &self,
+        options: T,
+        credentials: Option<RegistryAuth>,
+    ) -> Result<Vec<APIImageSearch>, Error>
+    where
+        T: SearchImagesQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.block_on(self.inner.search_images(options, credentials))
+    }
+
+    /// Blocking [`remove_image`](../struct.Docker.html#method.remove_image).
+    pub fn remove_image<T, K, V>(
+        &self,
+        image_name: &str,
+        options: Option<T>,
+        credentials: Option<RegistryAuth>,
+    ) -> Result<Vec<RemoveImageResults>, Error>
+    where
+        T: RemoveImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.remove_image(image_name, options, credentials))
+    }
+
+    /// Blocking [`tag_image`](../struct.Docker.html#method.tag_image).
+    pub fn tag_image<T, K, V>(&self, image_name: &str, options: Option<T>) -> Result<(), Error>
+    where
+        T: TagImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.tag_image(image_name, options))
+    }
+
+    /// Blocking [`push_image`](../struct.Docker.html#method.push_image).
+    pub fn push_image<T, K, V>(
+        &self,
+        image_name: &str,
+        options: Option<T>,
+        credentials: Option<RegistryAuth>,
+    ) -> Result<(), Error>
+    where
+        T: PushImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.push_image(image_name, options, credentials))
+    }
+
+    /// Blocking [`commit_container`](../struct.Docker.html#method.commit_container).
+    pub fn commit_container<T, K, V, Z>(
+        &self,
+        options: T,
+        config: Config<Z>,
+    ) -> Result<CommitContainerResults, Error>
+    where
+        T: CommitContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        Z: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.commit_container(options, config))
+    }
+
+    /// Blocking [`build_image`](../struct.Docker.html#method.build_image), as an iterator.
+    pub fn build_image<T, K>(
+        &self,
+        options: T,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        tar: Option<Body>,
+    ) -> Iter<impl Stream<Item = Result<BuildImageResults, Error>>>
+    where
+        T: BuildImageQueryParams<K>,
+        K: AsRef<str>,
+    {
+        self.iter(|| self.inner.build_image(options, credentials, tar))
+    }
+
+    /// Blocking [`build_image_from_stream`](../struct.Docker.html#method.build_image_from_stream),
+    /// as an iterator.
+    pub fn build_image_from_stream<T, K, S, E>(
+        &self,
+        options: T,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        context: S,
+    ) -> Iter<impl Stream<Item = Result<BuildImageResults, Error>>>
+    where
+        T: BuildImageQueryParams<K>,
+        K: AsRef<str>,
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+    {
+        self.iter(|| {
+            self.inner
+                .build_image_from_stream(options, credentials, context)
+        })
+    }
+
+    /// Blocking [`export_image`](../struct.Docker.html#method.export_image), as an iterator.
+    pub fn export_image(&self, image_name: &str) -> Iter<impl Stream<Item = Result<Bytes, Error>>> {
+        self.iter(|| self.inner.export_image(image_name))
+    }
+
+    /// Blocking [`import_image`](../struct.Docker.html#method.import_image), as an iterator.
+    pub fn import_image<K, V, T>(
+        &self,
+        options: T,
+        root_fs: Body,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+    ) -> Iter<impl Stream<Item = Result<ImportImageResults, Error>>>
+    where
+        T: ImportImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.iter(|| self.inner.import_image(options, root_fs, credentials))
+    }
+
+    /// Blocking [`create_network`](../struct.Docker.html#method.create_network).
+    pub fn create_network<T>(
+        &self,
+        config: CreateNetworkOptions<T>,
+    ) -> Result<CreateNetworkResults, Error>
+    where
+        T: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.create_network(config))
+    }
+
+    /// Blocking [`remove_network`](../struct.Docker.html#method.remove_network).
+    pub fn remove_network<N>(&self, network: N) -> Result<(), Error>
+    where
+        N: AsRef<str>,
+    {
+        self.block_on(self.inner.remove_network(network))
+    }
+
+    /// Blocking [`inspect_network`](../struct.Docker.html#method.inspect_network).
+    pub fn inspect_network<'a, N, T, V>(
+        &self,
+        network: N,
+        options: Option<T>,
+    ) -> Result<InspectNetworkResults, Error>
+    where
+        N: AsRef<str>,
+        T: InspectNetworkQueryParams<'a, V>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.inspect_network(network, options))
+    }
+
+    /// Blocking [`list_networks`](../struct.Docker.html#method.list_networks).
+    pub fn list_networks<T, K, V>(
+        &self,
+        options: Option<T>,
+    ) -> Result<Vec<ListNetworksResults>, Error>
+    where
+        T: ListNetworksQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.list_networks(options))
+    }
+
+    /// Blocking [`connect_network`](../struct.Docker.html#method.connect_network).
+    pub fn connect_network<N, T>(
+        &self,
+        network: N,
+        config: ConnectNetworkOptions<T>,
+    ) -> Result<(), Error>
+    where
+        N: AsRef<str>,
+        T: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.connect_network(network, config))
+    }
+
+    /// Blocking [`disconnect_network`](../struct.Docker.html#method.disconnect_network).
+    pub fn disconnect_network<N, T>(
+        &self,
+        network: N,
+        config: DisconnectNetworkOptions<T>,
+    ) -> Result<(), Error>
+    where
+        N: AsRef<str>,
+        T: AsRef<str> + Serialize,
+    {
+        self.block_on(self.inner.disconnect_network(network, config))
+    }
+
+    /// Blocking [`prune_networks`](../struct.Docker.html#method.prune_networks).
+    pub fn prune_networks<T, K, V>(&self, options: Option<T>) -> Result<PruneNetworksResults, Error>
+    where
+        T: PruneNetworksQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.prune_networks(options))
+    }
+
+    /// Blocking [`list_volumes`](../struct.Docker.html#method.list_volumes).
+    pub fn list_volumes<T>(
+        &self,
+        options: Option<ListVolumesOptions<T>>,
+    ) -> Result<ListVolumesResults, Error>
+    where
+        T: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.list_volumes(options))
+    }
+
+    /// Blocking [`create_volume`](../struct.Docker.html#method.create_volume).
+    pub fn create_volume<T>(&self, config: CreateVolumeOptions<T>) -> Result<VolumeAPI, Error>
+    where
+        T: AsRef<str> + Eq + Hash + Serialize,
+    {
+        self.block_on(self.inner.create_volume(config))
+    }
+
+    /// Blocking [`inspect_volume`](../struct.Docker.html#method.inspect_volume).
+    pub fn inspect_volume(&self, volume_name: &str) -> Result<VolumeAPI, Error> {
+        self.block_on(self.inner.inspect_volume(volume_name))
+    }
+
+    /// Blocking [`remove_volume`](../struct.Docker.html#method.remove_volume).
+    pub fn remove_volume(
+        &self,
+        volume_name: &str,
+        options: Option<RemoveVolumeOptions>,
+    ) -> Result<(), Error> {
+        self.block_on(self.inner.remove_volume(volume_name, options))
+    }
+
+    /// Blocking [`prune_volumes`](../struct.Docker.html#method.prune_volumes).
+    pub fn prune_volumes<T, K, V>(&self, options: Option<T>) -> Result<PruneVolumesResults, Error>
+    where
+        T: PruneVolumesQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.block_on(self.inner.prune_volumes(options))
+    }
+
+    /// Blocking [`create_exec`](../struct.Docker.html#method.create_exec).
+    pub fn create_exec<T>(
+        &self,
+        container_name: &str,
+        config: CreateExecOptions<T>,
+    ) -> Result<CreateExecResults, Error>
+    where
+        T: AsRef<str> + Serialize,
+    {
+        self.block_on(self.inner.create_exec(container_name, config))
+    }
+
+    /// Blocking [`start_exec`](../struct.Docker.html#method.start_exec), as an iterator.
+    pub fn start_exec(
+        &self,
+        container_name: &str,
+        config: Option<StartExecOptions>,
+    ) -> Iter<impl Stream<Item = Result<StartExecResults, Error>>> {
+        self.iter(|| self.inner.start_exec(container_name, config))
+    }
+
+    /// Blocking [`inspect_exec`](../struct.Docker.html#method.inspect_exec).
+    pub fn inspect_exec(&self, container_name: &str) -> Result<ExecInspect, Error> {
+        self.block_on(self.inner.inspect_exec(container_name))
+    }
+}
@@ -0,0 +1,618 @@
+//! BuildKit API: decode the progress of builds run with [BuildKit](https://github.com/moby/buildkit),
+//! the builder selected with [`BuilderVersion::V2`](../image/enum.BuilderVersion.html).
+//!
+//! BuildKit reports the progress of a build in `moby.buildkit.trace` records of the [Build Image
+//! API](../struct.Docker.html#method.build_image), each carrying a base64 encoded protobuf
+//! `StatusResponse` of the BuildKit control API. The records are decoded with
+//! [`BuildImageResults::buildkit_status`](../image/enum.BuildImageResults.html#method.buildkit_status).
+//!
+//! A BuildKit build requests files and registry credentials from the client through a session,
+//! opened with the [Start Session API](../struct.Docker.html#method.start_session). This library
+//! serves registry credentials on a session with
+//! [`Session::serve_credentials`](struct.Session.html#method.serve_credentials), but not the files
+//! of a build context.
+
+use arrayvec::ArrayVec;
+use base64;
+use chrono::{DateTime, TimeZone, Utc};
+use http::header::{HeaderMap, HeaderValue, CONNECTION, CONTENT_TYPE, UPGRADE};
+use http::request::Builder;
+use hyper::body::{Bytes, HttpBody};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::upgrade::Upgraded;
+use hyper::{Body, Method, Request, Response};
+
+use std::collections::HashMap;
+use std::convert::{Infallible, TryFrom};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use super::Docker;
+use crate::auth::DockerCredentials;
+use crate::errors::Error;
+use crate::errors::ErrorKind::{BuildKitTraceError, HyperResponseError, InvalidHeaderError};
+use crate::runtime::{Runtime, RuntimeExecutor};
+
+/// The ID of the records of a build stream carrying BuildKit progress.
+pub(crate) const TRACE_ID: &str = "moby.buildkit.trace";
+
+/// The progress of a BuildKit build, as reported in a single trace record.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusResponse {
+    /// The steps of the build that started, completed or failed.
+    pub vertexes: Vec<Vertex>,
+    /// The progress of long running operations of the steps, e.g. layer downloads.
+    pub statuses: Vec<VertexStatus>,
+    /// The output of the commands run by the steps.
+    pub logs: Vec<VertexLog>,
+    /// Warnings raised by the steps, e.g. about a deprecated `Dockerfile` instruction.
+    pub warnings: Vec<VertexWarning>,
+}
+
+/// A step of a BuildKit build, e.g. a `RUN` instruction of a `Dockerfile`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vertex {
+    /// The digest identifying the step.
+    pub digest: String,
+    /// The digests of the steps this step depends on.
+    pub inputs: Vec<String>,
+    /// The description of the step, e.g. `[2/3] RUN make`.
+    pub name: String,
+    /// Whether the result of the step was taken from the build cache.
+    pub cached: bool,
+    /// When the step started.
+    pub started: Option<DateTime<Utc>>,
+    /// When the step completed.
+    pub completed: Option<DateTime<Utc>>,
+    /// The error the step failed with, empty if it did not fail.
+    pub error: String,
+}
+
+/// The progress of an operation of a step, e.g. the download of a layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VertexStatus {
+    /// The identifier of the operation, e.g. the digest of a layer.
+    pub id: String,
+    /// The digest of the step the operation belongs to.
+    pub vertex: String,
+    /// The description of the operation.
+    pub name: String,
+    /// The progress of the operation, e.g. in bytes.
+    pub current: i64,
+    /// The total of the operation, `0` if unknown.
+    pub total: i64,
+    /// When this progress was reported.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// When the operation started.
+    pub started: Option<DateTime<Utc>>,
+    /// When the operation completed.
+    pub completed: Option<DateTime<Utc>>,
+}
+
+/// Output of a command run by a step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VertexLog {
+    /// The digest of the step the output belongs to.
+    pub vertex: String,
+    /// When the output was written.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// The file descriptor the output was written to, `1` for stdout and `2` for stderr.
+    pub stream: i64,
+    /// The raw output, which may split a line.
+    pub msg: Bytes,
+}
+
+/// A warning raised by a step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VertexWarning {
+    /// The digest of the step raising the warning.
+    pub vertex: String,
+    /// The severity of the warning.
+    pub level: i64,
+    /// A short description of the warning.
+    pub short: Bytes,
+    /// Lines detailing the warning.
+    pub detail: Vec<Bytes>,
+    /// A link to documentation about the warning.
+    pub url: String,
+}
+
+const SESSION_UUID: &str = "X-Docker-Expose-Session-Uuid";
+const SESSION_NAME: &str = "X-Docker-Expose-Session-Name";
+const SESSION_SHAREDKEY: &str = "X-Docker-Expose-Session-Sharedkey";
+const SESSION_GRPC_METHOD: &str = "X-Docker-Expose-Session-Grpc-Method";
+
+/// The gRPC method of a session serving registry credentials, to list in the `grpc_methods` of
+/// the [Session Options](struct.SessionOptions.html) of a session served with
+/// [`Session::serve_credentials`](struct.Session.html#method.serve_credentials).
+pub const AUTH_CREDENTIALS_METHOD: &str = "/moby.filesync.v1.Auth/Credentials";
+
+const GRPC_STATUS: &str = "grpc-status";
+const GRPC_OK: &str = "0";
+const GRPC_INVALID_ARGUMENT: &str = "3";
+const GRPC_UNIMPLEMENTED: &str = "12";
+
+/// Parameters to the [Start Session API](../struct.Docker.html#method.start_session)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionOptions<T>
+where
+    T: AsRef<str>,
+{
+    /// The ID of the session, passed as the `session` of the
+    /// [Build Image Options](../image/struct.BuildImageOptions.html).
+    pub id: T,
+    /// A name describing the session, e.g. the directory of the build context.
+    pub name: T,
+    /// A key shared by sessions sending the same files, with which the docker server reuses the
+    /// files it received before.
+    pub shared_key: T,
+    /// The gRPC methods the client serves on the session, e.g.
+    /// `/moby.filesync.v1.FileSync/DiffCopy` and `/moby.filesync.v1.Auth/Credentials`.
+    pub grpc_methods: Vec<T>,
+}
+
+/// A session opened with the [Start Session API](../struct.Docker.html#method.start_session).
+#[derive(Debug)]
+pub struct Session {
+    /// The ID of the session.
+    pub id: String,
+    /// The upgraded connection, on which the docker server calls the gRPC methods of the session
+    /// over HTTP/2.
+    pub connection: Upgraded,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl Session {
+    /// Serve registry credentials on the session through the `moby.filesync.v1.Auth/Credentials`
+    /// gRPC method, until the docker server closes the session. Other methods, such as the file
+    /// sync of a build context, are answered as unimplemented, and registries without
+    /// credentials are accessed anonymously.
+    ///
+    /// # Arguments
+    ///
+    ///  - Credentials keyed by registry host, e.g. `registry-1.docker.io` for the Docker Hub.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::auth::DockerCredentials;
+    /// use bollard::buildkit::{SessionOptions, AUTH_CREDENTIALS_METHOD};
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut credentials = HashMap::new();
+    /// credentials.insert(
+    ///     String::from("registry.example.com"),
+    ///     DockerCredentials {
+    ///         username: Some(String::from("bollard")),
+    ///         password: Some(String::from("hunter2")),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    ///
+    /// async move {
+    ///     let session = docker
+    ///         .start_session(SessionOptions {
+    ///             id: "3b1d8e0c",
+    ///             name: "my-app",
+    ///             shared_key: "my-app",
+    ///             grpc_methods: vec![AUTH_CREDENTIALS_METHOD],
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    ///     session.serve_credentials(credentials).await.unwrap();
+    /// };
+    /// ```
+    pub async fn serve_credentials(
+        self,
+        credentials: HashMap<String, DockerCredentials>,
+    ) -> Result<(), Error> {
+        let credentials = Arc::new(credentials);
+        let service = service_fn(move |req| {
+            let credentials = credentials.clone();
+            async move { Ok::<_, Infallible>(credentials_response(req, &credentials).await) }
+        });
+
+        Http::new()
+            .with_executor(RuntimeExecutor(self.runtime))
+            .http2_only(true)
+            .serve_connection(self.connection, service)
+            .await
+            .map_err(|e| HyperResponseError { err: e }.into())
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Start Session
+    ///
+    /// Start a session, upgrading the connection to HTTP/2 with the client acting as the server:
+    /// the docker server calls the gRPC methods of the session, e.g. to sync the files of a
+    /// BuildKit build context or to fetch registry credentials. The session lasts until the
+    /// connection is closed.
+    ///
+    /// Registry credentials are served on the session with
+    /// [`Session::serve_credentials`](buildkit/struct.Session.html#method.serve_credentials). Other
+    /// gRPC services, such as the file sync of a build context, are not implemented by this
+    /// library; they are served on the returned connection, e.g. with
+    /// `hyper::server::conn::Http::http2_only`.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Session Options](buildkit/struct.SessionOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Session](buildkit/struct.Session.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::buildkit::SessionOptions;
+    ///
+    /// let options = SessionOptions {
+    ///     id: "3b1d8e0c",
+    ///     name: "my-app",
+    ///     shared_key: "my-app",
+    ///     grpc_methods: vec!["/moby.filesync.v1.FileSync/DiffCopy"],
+    /// };
+    ///
+    /// docker.start_session(options);
+    /// ```
+    pub async fn start_session<T>(&self, options: SessionOptions<T>) -> Result<Session, Error>
+    where
+        T: AsRef<str>,
+    {
+        let url = "/session";
+
+        let mut builder = Builder::new()
+            .method(Method::POST)
+            .header(CONNECTION, "Upgrade")
+            .header(UPGRADE, "h2c")
+            .header(SESSION_UUID, session_header(SESSION_UUID, &options.id)?)
+            .header(SESSION_NAME, session_header(SESSION_NAME, &options.name)?)
+            .header(
+                SESSION_SHAREDKEY,
+                session_header(SESSION_SHAREDKEY, &options.shared_key)?,
+            );
+        for method in &options.grpc_methods {
+            builder = builder.header(
+                SESSION_GRPC_METHOD,
+                session_header(SESSION_GRPC_METHOD, method)?,
+            );
+        }
+
+        let req = self.build_request(
+            url,
+            builder,
+            Ok(None::<ArrayVec<[(&str, &str); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        let connection = self.process_into_raw_upgraded(req).await?;
+
+        Ok(Session {
+            id: options.id.as_ref().to_string(),
+            connection,
+            runtime: self.runtime.clone(),
+        })
+    }
+}
+
+fn session_header<T: AsRef<str>>(name: &str, value: &T) -> Result<HeaderValue, Error> {
+    HeaderValue::from_str(value.as_ref()).map_err(|_| {
+        InvalidHeaderError {
+            name: name.to_string(),
+        }
+        .into()
+    })
+}
+
+/// The body of a gRPC response: a single message followed by the status in the trailers, or only
+/// the status in the headers of the response.
+struct GrpcBody {
+    message: Option<Bytes>,
+    trailers: Option<HeaderMap>,
+}
+
+impl HttpBody for GrpcBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        Poll::Ready(self.get_mut().message.take().map(Ok))
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(self.get_mut().trailers.take()))
+    }
+}
+
+fn grpc_response(body: GrpcBody, status: Option<&'static str>) -> Response<GrpcBody> {
+    let mut response = Response::new(body);
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+    if let Some(status) = status {
+        headers.insert(GRPC_STATUS, HeaderValue::from_static(status));
+    }
+    response
+}
+
+/// Answer a call of the docker server on a session serving registry credentials.
+async fn credentials_response(
+    req: Request<Body>,
+    credentials: &HashMap<String, DockerCredentials>,
+) -> Response<GrpcBody> {
+    let status_only = |status| {
+        let body = GrpcBody {
+            message: None,
+            trailers: None,
+        };
+        grpc_response(body, Some(status))
+    };
+
+    if req.uri().path() != AUTH_CREDENTIALS_METHOD {
+        return status_only(GRPC_UNIMPLEMENTED);
+    }
+    let host = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(frame) => match decode_credentials_request(&frame) {
+            Ok(host) => host,
+            Err(_) => return status_only(GRPC_INVALID_ARGUMENT),
+        },
+        Err(_) => return status_only(GRPC_INVALID_ARGUMENT),
+    };
+
+    // an identity token is sent as the secret of an empty username
+    let (username, secret) = match credentials.get(&host) {
+        Some(DockerCredentials {
+            identitytoken: Some(token),
+            ..
+        }) => ("", token.as_str()),
+        Some(credentials) => (
+            credentials.username.as_deref().unwrap_or_default(),
+            credentials.password.as_deref().unwrap_or_default(),
+        ),
+        None => ("", ""),
+    };
+    let mut message = Vec::new();
+    encode_string(&mut message, 1, username);
+    encode_string(&mut message, 2, secret);
+
+    let mut frame = vec![0];
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&message);
+
+    let mut trailers = HeaderMap::new();
+    trailers.insert(GRPC_STATUS, HeaderValue::from_static(GRPC_OK));
+    let body = GrpcBody {
+        message: Some(Bytes::from(frame)),
+        trailers: Some(trailers),
+    };
+    grpc_response(body, None)
+}
+
+/// Decode the host of an uncompressed gRPC `CredentialsRequest` message.
+fn decode_credentials_request(mut frame: &[u8]) -> Result<String, Error> {
+    let prefix = read_bytes(&mut frame, 5)?;
+    if prefix[0] != 0 {
+        return Err(invalid(String::from("compressed gRPC message")));
+    }
+    let len = u32::from_be_bytes([prefix[1], prefix[2], prefix[3], prefix[4]]);
+    let message = read_bytes(&mut frame, u64::from(len))?;
+
+    decode_message(message, |host: &mut String, field, value| {
+        if field == 1 {
+            *host = value.string()?;
+        }
+        Ok(())
+    })
+}
+
+/// Encode a protobuf string field, omitted if empty.
+fn encode_string(buf: &mut Vec<u8>, field: u64, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    write_varint(buf, field << 3 | 2);
+    write_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Decode the base64 encoded `aux` field of a trace record.
+pub(crate) fn decode_trace(aux: &str) -> Result<StatusResponse, Error> {
+    let buf = base64::decode(aux).map_err(|e| invalid(e.to_string()))?;
+
+    decode_message(&buf, |status: &mut StatusResponse, field, value| {
+        match field {
+            1 => status.vertexes.push(decode_vertex(value.bytes()?)?),
+            2 => status.statuses.push(decode_vertex_status(value.bytes()?)?),
+            3 => status.logs.push(decode_vertex_log(value.bytes()?)?),
+            4 => status.warnings.push(decode_vertex_warning(value.bytes()?)?),
+            _ => (),
+        }
+        Ok(())
+    })
+}
+
+fn decode_vertex(buf: &[u8]) -> Result<Vertex, Error> {
+    decode_message(buf, |vertex: &mut Vertex, field, value| {
+        match field {
+            1 => vertex.digest = value.string()?,
+            2 => vertex.inputs.push(value.string()?),
+            3 => vertex.name = value.string()?,
+            4 => vertex.cached = value.varint()? != 0,
+            5 => vertex.started = decode_timestamp(value.bytes()?)?,
+            6 => vertex.completed = decode_timestamp(value.bytes()?)?,
+            7 => vertex.error = value.string()?,
+            _ => (),
+        }
+        Ok(())
+    })
+}
+
+fn decode_vertex_status(buf: &[u8]) -> Result<VertexStatus, Error> {
+    decode_message(buf, |status: &mut VertexStatus, field, value| {
+        match field {
+            1 => status.id = value.string()?,
+            2 => status.vertex = value.string()?,
+            3 => status.name = value.string()?,
+            4 => status.current = value.varint()? as i64,
+            5 => status.total = value.varint()? as i64,
+            6 => status.timestamp = decode_timestamp(value.bytes()?)?,
+            7 => status.started = decode_timestamp(value.bytes()?)?,
+            8 => status.completed = decode_timestamp(value.bytes()?)?,
+            _ => (),
+        }
+        Ok(())
+    })
+}
+
+fn decode_vertex_log(buf: &[u8]) -> Result<VertexLog, Error> {
+    decode_message(buf, |log: &mut VertexLog, field, value| {
+        match field {
+            1 => log.vertex = value.string()?,
+            2 => log.timestamp = decode_timestamp(value.bytes()?)?,
+            3 => log.stream = value.varint()? as i64,
+            4 => log.msg = Bytes::copy_from_slice(value.bytes()?),
+            _ => (),
+        }
+        Ok(())
+    })
+}
+
+fn decode_vertex_warning(buf: &[u8]) -> Result<VertexWarning, Error> {
+    decode_message(buf, |warning: &mut VertexWarning, field, value| {
+        match field {
+            1 => warning.vertex = value.string()?,
+            2 => warning.level = value.varint()? as i64,
+            3 => warning.short = Bytes::copy_from_slice(value.bytes()?),
+            4 => warning.detail.push(Bytes::copy_from_slice(value.bytes()?)),
+            5 => warning.url = value.string()?,
+            _ => (),
+        }
+        Ok(())
+    })
+}
+
+/// Decode a `google.protobuf.Timestamp`.
+fn decode_timestamp(buf: &[u8]) -> Result<Option<DateTime<Utc>>, Error> {
+    let (seconds, nanos) = decode_message(buf, |time: &mut (i64, i64), field, value| {
+        match field {
+            1 => time.0 = value.varint()? as i64,
+            2 => time.1 = value.varint()? as i64,
+            _ => (),
+        }
+        Ok(())
+    })?;
+
+    let nanos = u32::try_from(nanos).map_err(|_| invalid(format!("nanos {}", nanos)))?;
+    match Utc.timestamp_opt(seconds, nanos).single() {
+        Some(time) => Ok(Some(time)),
+        None => Err(invalid(format!("timestamp {}.{:09}", seconds, nanos))),
+    }
+}
+
+fn invalid(reason: String) -> Error {
+    BuildKitTraceError { reason }.into()
+}
+
+/// The value of a protobuf field.
+enum FieldValue<'a> {
+    Varint(u64),
+    LengthDelimited(&'a [u8]),
+    Fixed,
+}
+
+impl<'a> FieldValue<'a> {
+    fn varint(&self) -> Result<u64, Error> {
+        match self {
+            FieldValue::Varint(value) => Ok(*value),
+            _ => Err(invalid(String::from("expected a varint field"))),
+        }
+    }
+
+    fn bytes(&self) -> Result<&'a [u8], Error> {
+        match self {
+            FieldValue::LengthDelimited(value) => Ok(value),
+            _ => Err(invalid(String::from("expected a length-delimited field"))),
+        }
+    }
+
+    fn string(&self) -> Result<String, Error> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|e| invalid(e.to_string()))
+    }
+}
+
+/// Decode the fields of a protobuf message into a value, skipping unknown fields.
+fn decode_message<T, F>(mut buf: &[u8], mut on_field: F) -> Result<T, Error>
+where
+    T: Default,
+    F: FnMut(&mut T, u64, FieldValue<'_>) -> Result<(), Error>,
+{
+    let mut message = T::default();
+
+    while !buf.is_empty() {
+        let key = read_varint(&mut buf)?;
+        let value = match key & 0x7 {
+            0 => FieldValue::Varint(read_varint(&mut buf)?),
+            1 => {
+                read_bytes(&mut buf, 8)?;
+                FieldValue::Fixed
+            }
+            2 => {
+                let len = read_varint(&mut buf)?;
+                FieldValue::LengthDelimited(read_bytes(&mut buf, len)?)
+            }
+            5 => {
+                read_bytes(&mut buf, 4)?;
+                FieldValue::Fixed
+            }
+            wire_type => return Err(invalid(format!("unsupported wire type {}", wire_type))),
+        };
+        on_field(&mut message, key >> 3, value)?;
+    }
+
+    Ok(message)
+}
+
+fn read_varint(buf: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0;
+    for (index, byte) in buf.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            *buf = &buf[index + 1..];
+            return Ok(value);
+        }
+    }
+    Err(invalid(String::from("truncated varint")))
+}
+
+fn read_bytes<'a>(buf: &mut &'a [u8], len: u64) -> Result<&'a [u8], Error> {
+    if len > buf.len() as u64 {
+        return Err(invalid(String::from("truncated field")));
+    }
+    let (value, rest) = buf.split_at(len as usize);
+    *buf = rest;
+    Ok(value)
+}
@@ -0,0 +1,250 @@
+#![cfg(feature = "experimental")]
+//! Checkpoint API: save the state of a running container to disk with
+//! [CRIU](https://criu.org), to restore it later or on another host.
+//!
+//! Requires a docker server running in experimental mode, with CRIU installed. A container is
+//! restored from a checkpoint by starting it with the
+//! [`checkpoint`](../container/struct.StartContainerOptions.html#structfield.checkpoint) option.
+
+use arrayvec::ArrayVec;
+use http::request::Builder;
+use hyper::{Body, Method};
+use serde::Serialize;
+
+use super::Docker;
+use crate::errors::Error;
+
+/// Parameters used in the [Create Checkpoint API](../struct.Docker.html#method.create_checkpoint)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::checkpoint::CreateCheckpointOptions;
+///
+/// use std::default::Default;
+///
+/// CreateCheckpointOptions{
+///     checkpoint_id: "before-upgrade",
+///     exit: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CreateCheckpointOptions<T>
+where
+    T: AsRef<str> + Serialize,
+{
+    /// The name of the checkpoint.
+    #[serde(rename = "CheckpointID")]
+    pub checkpoint_id: T,
+    /// A custom directory in which to store the checkpoint, instead of the directory of the
+    /// container.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_dir: Option<T>,
+    /// Stop the container once the checkpoint is created.
+    pub exit: bool,
+}
+
+/// Parameters used in the [List Checkpoints API](../struct.Docker.html#method.list_checkpoints)
+/// and the [Delete Checkpoint API](../struct.Docker.html#method.delete_checkpoint)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::checkpoint::CheckpointDirOptions;
+///
+/// CheckpointDirOptions{
+///     dir: "/var/lib/checkpoints",
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointDirOptions<T>
+where
+    T: AsRef<str>,
+{
+    /// The custom directory the checkpoints were stored in when created.
+    pub dir: T,
+}
+
+/// Trait providing implementations for [Checkpoint Dir Options](struct.CheckpointDirOptions.html)
+/// struct.
+#[allow(missing_docs)]
+pub trait CheckpointDirQueryParams<K, V>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 1]>, Error>;
+}
+
+impl<'a, T: AsRef<str>> CheckpointDirQueryParams<&'a str, T> for CheckpointDirOptions<T> {
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, T); 1]>, Error> {
+        Ok(ArrayVec::from([("dir", self.dir)]))
+    }
+}
+
+/// Result type for the [List Checkpoints API](../struct.Docker.html#method.list_checkpoints)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Checkpoint {
+    /// The name of the checkpoint.
+    pub name: String,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Create Checkpoint
+    ///
+    /// Create a checkpoint of a running container. *(Experimental daemons only.)*
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Create Checkpoint Options](checkpoint/struct.CreateCheckpointOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::checkpoint::CreateCheckpointOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = CreateCheckpointOptions{
+    ///     checkpoint_id: "before-upgrade",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.create_checkpoint("hello-world", options);
+    /// ```
+    pub async fn create_checkpoint<T>(
+        &self,
+        container_name: &str,
+        options: CreateCheckpointOptions<T>,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str> + Serialize,
+    {
+        let url = format!("/containers/{}/checkpoints", container_name);
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new().method(Method::POST),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Docker::serialize_payload(Some(options)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # List Checkpoints
+    ///
+    /// List the checkpoints of a container. *(Experimental daemons only.)*
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Optional [Checkpoint Dir Options](checkpoint/struct.CheckpointDirOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Checkpoint](checkpoint/struct.Checkpoint.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::checkpoint::CheckpointDirOptions;
+    ///
+    /// docker.list_checkpoints("hello-world", None::<CheckpointDirOptions<String>>);
+    /// ```
+    pub async fn list_checkpoints<T, K, V>(
+        &self,
+        container_name: &str,
+        options: Option<T>,
+    ) -> Result<Vec<Checkpoint>, Error>
+    where
+        T: CheckpointDirQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let url = format!("/containers/{}/checkpoints", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            Docker::transpose_option(options.map(|o| o.into_array())),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Delete Checkpoint
+    ///
+    /// Delete a checkpoint of a container. *(Experimental daemons only.)*
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Checkpoint name as string slice.
+    ///  - Optional [Checkpoint Dir Options](checkpoint/struct.CheckpointDirOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::checkpoint::CheckpointDirOptions;
+    ///
+    /// docker.delete_checkpoint(
+    ///     "hello-world",
+    ///     "before-upgrade",
+    ///     None::<CheckpointDirOptions<String>>,
+    /// );
+    /// ```
+    pub async fn delete_checkpoint<T, K, V>(
+        &self,
+        container_name: &str,
+        checkpoint_id: &str,
+        options: Option<T>,
+    ) -> Result<(), Error>
+    where
+        T: CheckpointDirQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let url = format!(
+            "/containers/{}/checkpoints/{}",
+            container_name, checkpoint_id
+        );
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::DELETE),
+            Docker::transpose_option(options.map(|o| o.into_array())),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+}
@@ -1,25 +1,48 @@
 //! Container API: run docker containers and manage their lifecycle
 
 use arrayvec::ArrayVec;
+use base64;
+use bytes::BytesMut;
 use chrono::serde::ts_seconds;
 use chrono::{DateTime, Utc};
 use futures_core::Stream;
-use http::header::CONTENT_TYPE;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use http::header::{CONNECTION, CONTENT_TYPE, UPGRADE};
 use http::request::Builder;
 use hyper::{body::Bytes, Body, Method};
+use pin_project::pin_project;
 use serde::Serialize;
 use serde_json;
+use tokio::io::AsyncWrite;
+use tokio::time::Instant;
 
-use std::cmp::Eq;
-use std::collections::HashMap;
+use std::cmp::{self, Eq};
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::future::Future;
 use std::hash::Hash;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 use super::Docker;
-use crate::docker::{FALSE_STR, TRUE_STR};
-use crate::errors::Error;
-use crate::errors::ErrorKind::JsonSerializeError;
+use crate::docker::{
+    deserialize_empty_as_none, deserialize_nonoptional_map, deserialize_nonoptional_vec,
+    serialize_filters, serialize_timestamp, FALSE_STR, TRUE_STR,
+};
+use crate::errors::ErrorKind::{
+    ContainerAlreadyPausedError, ContainerHealthTimeoutError, ContainerNameConflictError,
+    ContainerNameTooShortError, ContainerNoHealthCheckError, ContainerNotPausedError,
+    ContainerPathStatError, ContainerStoppedError, ContainerWaitError, DockerResponseConflictError,
+    InvalidContainerNameError, InvalidDetachKeysError, JsonSerializeError, LogOutputLimitError,
+    PublishedPortsTimeoutError,
+};
+use crate::errors::{Error, LastHealthCheck};
 use crate::network::EndpointIPAMConfig;
+use crate::retry::{is_stream_idle, is_transient};
+use crate::service_models::{MountBindOptionsPropagation, MountType};
+use crate::system::{EventsOptions, EventsQueryParams, EventsResults};
 
 /// Parameters used in the [List Container API](../struct.Docker.html#method.list_containers)
 ///
@@ -78,6 +101,195 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// The health of a container, used to filter the [List Containers
+/// API](../struct.Docker.html#method.list_containers)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum HealthStatus {
+    Starting,
+    Healthy,
+    Unhealthy,
+    /// The container has no healthcheck.
+    None,
+}
+
+impl HealthStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            HealthStatus::Starting => "starting",
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Unhealthy => "unhealthy",
+            HealthStatus::None => "none",
+        }
+    }
+}
+
+/// The status of a container, used to filter the [List Containers
+/// API](../struct.Docker.html#method.list_containers)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum ContainerStatus {
+    Created,
+    Restarting,
+    Running,
+    Removing,
+    Paused,
+    Exited,
+    Dead,
+}
+
+impl ContainerStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            ContainerStatus::Created => "created",
+            ContainerStatus::Restarting => "restarting",
+            ContainerStatus::Running => "running",
+            ContainerStatus::Removing => "removing",
+            ContainerStatus::Paused => "paused",
+            ContainerStatus::Exited => "exited",
+            ContainerStatus::Dead => "dead",
+        }
+    }
+}
+
+/// A filter of the containers to list, added to the [List Containers
+/// Options](struct.ListContainersOptions.html) with
+/// [`ListContainersOptions::filter`](struct.ListContainersOptions.html#method.filter).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerFilter<T> {
+    /// Matches containers created from an image or its descendants, in the form
+    /// `<image-name>[:<tag>]`, `<image id>` or `<image@digest>`.
+    Ancestor(T),
+    /// Matches containers created before a container, by name or ID.
+    Before(T),
+    /// Matches containers exposing a port or range of ports, in the form `<port>[/<proto>]` or
+    /// `<startport-endport>/[<proto>]`.
+    Expose(T),
+    /// Matches containers with the given health.
+    Health(HealthStatus),
+    /// Matches containers by ID, or a prefix of it.
+    Id(T),
+    /// Matches containers that are, or are not, tasks of a swarm service.
+    IsTask(bool),
+    /// Matches containers with a label, in the form `key` or `key=value`.
+    Label(T),
+    /// Matches containers by name, or a part of it.
+    Name(T),
+    /// Matches containers connected to a network, by name or ID.
+    Network(T),
+    /// Matches containers publishing a port or range of ports, in the form `<port>[/<proto>]` or
+    /// `<startport-endport>/[<proto>]`.
+    Publish(T),
+    /// Matches containers created after a container, by name or ID.
+    Since(T),
+    /// Matches containers with the given status.
+    Status(ContainerStatus),
+    /// Matches containers mounting a volume, by volume name or mount point destination.
+    Volume(T),
+}
+
+impl<T> ListContainersOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    fn add_filter(mut self, key: &'static str, value: T) -> Self {
+        self.filters.entry(T::from(key)).or_default().push(value);
+        self
+    }
+
+    /// Only show containers matching a filter. Containers must match filters of different kinds,
+    /// and any of the filters of the same kind.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::container::{ContainerFilter, ContainerStatus, ListContainersOptions};
+    ///
+    /// let options = ListContainersOptions {
+    ///     all: true,
+    ///     size: true,
+    ///     ..Default::default()
+    /// }
+    /// .filter(ContainerFilter::Status(ContainerStatus::Exited))
+    /// .filter(ContainerFilter::Status(ContainerStatus::Dead))
+    /// .filter(ContainerFilter::Name("worker"));
+    ///
+    /// assert_eq!(options.filters["status"], vec!["exited", "dead"]);
+    /// ```
+    pub fn filter(self, filter: ContainerFilter<T>) -> Self {
+        match filter {
+            ContainerFilter::Ancestor(image) => self.add_filter("ancestor", image),
+            ContainerFilter::Before(container) => self.add_filter("before", container),
+            ContainerFilter::Expose(port) => self.add_filter("expose", port),
+            ContainerFilter::Health(health) => self.health(health),
+            ContainerFilter::Id(id) => self.add_filter("id", id),
+            ContainerFilter::IsTask(is_task) => self.add_filter(
+                "is-task",
+                T::from(if is_task { TRUE_STR } else { FALSE_STR }),
+            ),
+            ContainerFilter::Label(label) => self.add_filter("label", label),
+            ContainerFilter::Name(name) => self.add_filter("name", name),
+            ContainerFilter::Network(network) => self.add_filter("network", network),
+            ContainerFilter::Publish(port) => self.add_filter("publish", port),
+            ContainerFilter::Since(container) => self.add_filter("since", container),
+            ContainerFilter::Status(status) => self.status(status),
+            ContainerFilter::Volume(volume) => self.add_filter("volume", volume),
+        }
+    }
+
+    /// Only show containers with the given health.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::container::{ContainerStatus, HealthStatus, ListContainersOptions};
+    ///
+    /// let options = ListContainersOptions::default()
+    ///     .status(ContainerStatus::Running)
+    ///     .health(HealthStatus::Unhealthy)
+    ///     .ancestor("postgres:12");
+    ///
+    /// assert_eq!(options.filters["health"], vec!["unhealthy"]);
+    /// ```
+    pub fn health(self, health: HealthStatus) -> Self {
+        self.add_filter("health", T::from(health.as_str()))
+    }
+
+    /// Only show containers with the given status.
+    pub fn status(self, status: ContainerStatus) -> Self {
+        self.add_filter("status", T::from(status.as_str()))
+    }
+
+    /// Only show containers created from the given image or its descendants, in the form
+    /// `<image-name>[:<tag>]`, `<image id>` or `<image@digest>`.
+    pub fn ancestor(self, image: T) -> Self {
+        self.add_filter("ancestor", image)
+    }
+
+    /// Only show containers connected to the given network, by name or ID.
+    pub fn network(self, network: T) -> Self {
+        self.add_filter("network", network)
+    }
+
+    /// Only show containers with a label, in the form `key` or `key=value`.
+    pub fn label(self, label: T) -> Self {
+        self.add_filter("label", label)
+    }
+}
+
+impl<T> ListContainersOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str> + From<String>,
+{
+    /// Only show containers that exited with the given exit code. Combine with
+    /// [`all`](#structfield.all), as exited containers are not shown by default.
+    pub fn exited(self, exit_code: i32) -> Self {
+        self.add_filter("exited", T::from(exit_code.to_string()))
+    }
+}
+
 #[allow(missing_docs)]
 /// Trait providing implementations for [List Containers Options](struct.ListContainersOptions.html)
 /// struct.
@@ -104,10 +316,7 @@ where
                     .unwrap_or_else(|| String::new()),
             ),
             ("size", self.size.to_string()),
-            (
-                "filters",
-                serde_json::to_string(&self.filters).map_err(|e| JsonSerializeError { err: e })?,
-            ),
+            ("filters", serialize_filters(&self.filters)?),
         ]))
     }
 }
@@ -121,6 +330,7 @@ where
 ///
 /// CreateContainerOptions{
 ///     name: "my-new-container",
+///     platform: Some("linux/arm64"),
 /// };
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -128,8 +338,14 @@ pub struct CreateContainerOptions<T>
 where
     T: AsRef<str>,
 {
-    /// Assign the specified name to the container.
+    /// Assign the specified name to the container. A name not matching
+    /// `[a-zA-Z0-9][a-zA-Z0-9_.-]*`, optionally with a leading slash, is rejected with an [Invalid
+    /// Container Name Error](../errors/enum.ErrorKind.html#variant.InvalidContainerNameError)
+    /// before the request is sent.
     pub name: T,
+    /// Platform of the image to create the container from, e.g. `linux/arm64`, on a docker server
+    /// able to run images of several platforms. Requires API version 1.41 or later.
+    pub platform: Option<T>,
 }
 
 /// Trait providing implementations for [Create Container Options](struct.CreateContainerOptions.html)
@@ -140,29 +356,125 @@ where
     K: AsRef<str>,
     V: AsRef<str>,
 {
-    fn into_array(self) -> Result<ArrayVec<[(K, V); 1]>, Error>;
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 2]>, Error>;
 }
 
 impl<'a, T: AsRef<str>> CreateContainerQueryParams<&'a str, T> for CreateContainerOptions<T> {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, T); 1]>, Error> {
-        Ok(ArrayVec::from([("name", self.name)]))
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, T); 2]>, Error> {
+        validate_container_name(self.name.as_ref())?;
+        let mut res = ArrayVec::new();
+        res.push(("name", self.name));
+        if let Some(platform) = self.platform {
+            res.push(("platform", platform));
+        }
+        Ok(res)
+    }
+}
+
+/// Check a container name with the same rules as the docker server. An empty name lets the
+/// docker server generate one.
+fn validate_container_name(name: &str) -> Result<(), Error> {
+    let offset = if name.starts_with('/') { 1 } else { 0 };
+    let invalid = name.chars().enumerate().skip(offset).find(|(position, c)| {
+        if *position == offset {
+            !c.is_ascii_alphanumeric()
+        } else {
+            !c.is_ascii_alphanumeric() && !"_.-".contains(*c)
+        }
+    });
+
+    match invalid {
+        Some((position, character)) => Err(InvalidContainerNameError {
+            name: String::from(name),
+            character,
+            position,
+        }
+        .into()),
+        // names are at least two characters, besides the leading slash
+        None if !name.is_empty() && name.chars().count() < offset + 2 => {
+            Err(ContainerNameTooShortError {
+                name: String::from(name),
+            }
+            .into())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Extract the name and the ID of the existing container from the message of a name conflict,
+/// e.g. `Conflict. The container name "/web" is already in use by container "4e6f...". You have
+/// to remove (or rename) that container to be able to reuse that name.`
+fn parse_name_conflict(message: &str) -> Option<(String, String)> {
+    // the docker server wraps the message in a JSON object
+    let message = serde_json::from_str::<serde_json::Value>(message)
+        .ok()
+        .and_then(|body| body.get("message")?.as_str().map(String::from))
+        .unwrap_or_else(|| message.to_owned());
+
+    let quoted_after = |pattern: &str| -> Option<String> {
+        let start = message.find(pattern)? + pattern.len();
+        let end = message[start..].find('"')?;
+        Some(String::from(&message[start..start + end]))
+    };
+
+    let name = quoted_after("The container name \"")?;
+    let existing_id = quoted_after("is already in use by container \"")?;
+    Some((name.trim_start_matches('/').to_owned(), existing_id))
+}
+
+/// Map a name conflict returned by the docker server to a [Container Name Conflict
+/// Error](../errors/enum.ErrorKind.html#variant.ContainerNameConflictError).
+fn name_conflict_error(e: Error) -> Error {
+    let conflict = match e.kind() {
+        DockerResponseConflictError { message } => parse_name_conflict(message),
+        _ => None,
+    };
+    match conflict {
+        Some((name, existing_id)) => ContainerNameConflictError { name, existing_id }.into(),
+        None => e,
     }
 }
 
 /// A request for devices to be sent to device drivers
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::{DeviceRequest, HostConfig};
+///
+/// // the equivalent of `docker run --gpus all`
+/// HostConfig {
+///     device_requests: Some(vec![DeviceRequest {
+///         count: -1,
+///         capabilities: vec![vec!["gpu"]],
+///         ..Default::default()
+///     }]),
+///     ..Default::default()
+/// };
+/// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
+#[serde(
+    rename_all = "PascalCase",
+    bound(deserialize = "T: serde::Deserialize<'de>")
+)]
 pub struct DeviceRequest<T>
 where
     T: AsRef<str> + Eq + Hash,
 {
+    /// The device driver, e.g. `nvidia`, or empty to pick the driver from the capabilities.
     pub driver: T,
+    /// The number of devices to request, `-1` for all of them.
     pub count: i64,
-    #[serde(rename = "DeviceIDs")]
+    /// The IDs of the devices to request, in place of a count.
+    #[serde(
+        rename = "DeviceIDs",
+        default,
+        deserialize_with = "deserialize_nonoptional_vec"
+    )]
     pub device_ids: Vec<T>,
-    /// A list of capabilities; an OR list of AND lists of capabilities.
-    pub capabilities: Vec<T>,
+    /// A list of capabilities; an OR list of AND lists of capabilities, e.g. `[["gpu"]]`.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub capabilities: Vec<Vec<T>>,
     /// Driver-specific options, specified as a key/value pairs. These options are passed directly to the driver.
     pub options: Option<HashMap<T, T>>,
 }
@@ -249,14 +561,487 @@ where
     pub tmpfs_options: Option<MountPointTmpfsOptions>,
 }
 
-/// Ulimit definitions
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// A mount of a container with the options of its type, converted into a [Mount
+/// Point](struct.MountPoint.html) of the [`mounts`](struct.HostConfig.html#structfield.mounts)
+/// of a host configuration.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::{BindMount, HostConfig, MountSpec, TmpfsMount, VolumeMount};
+/// use bollard::service_models::MountBindOptionsPropagation;
+///
+/// use std::default::Default;
+///
+/// let mounts = vec![
+///     MountSpec::Bind(BindMount {
+///         source: "/srv/config",
+///         target: "/etc/app",
+///         read_only: true,
+///         propagation: Some(MountBindOptionsPropagation::RPrivate),
+///         ..Default::default()
+///     }),
+///     MountSpec::Volume(VolumeMount {
+///         source: Some("pgdata"),
+///         target: "/var/lib/postgresql/data",
+///         no_copy: true,
+///         ..Default::default()
+///     }),
+///     MountSpec::Tmpfs(TmpfsMount {
+///         target: "/run",
+///         size_bytes: Some(64 * 1024 * 1024),
+///         mode: Some(0o1777),
+///         ..Default::default()
+///     }),
+/// ];
+///
+/// HostConfig {
+///     mounts: Some(mounts.into_iter().map(Into::into).collect()),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum MountSpec<T>
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    /// Mount a file or directory of the host, which must exist before the container is created.
+    Bind(BindMount<T>),
+    /// Mount a volume, created with the given options if it does not exist.
+    Volume(VolumeMount<T>),
+    /// Mount a temporary filesystem in memory.
+    Tmpfs(TmpfsMount<T>),
+    /// Mount a named pipe of the host, on Windows.
+    Npipe(NpipeMount<T>),
+}
+
+/// A bind mount of a [Mount Spec](enum.MountSpec.html).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BindMount<T> {
+    /// Path on the host.
+    pub source: T,
+    /// Path in the container.
+    pub target: T,
+    /// Whether the mount is read-only.
+    pub read_only: bool,
+    /// Propagation of the mounts made under the mount point, `rprivate` if not set.
+    pub propagation: Option<MountBindOptionsPropagation>,
+    /// Disable recursive bind mount.
+    pub non_recursive: bool,
+}
+
+/// A volume mount of a [Mount Spec](enum.MountSpec.html).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VolumeMount<T>
+where
+    T: Eq + Hash,
+{
+    /// Name of the volume, or `None` for an anonymous volume.
+    pub source: Option<T>,
+    /// Path in the container.
+    pub target: T,
+    /// Whether the mount is read-only.
+    pub read_only: bool,
+    /// Do not populate a new volume with the data of the image at the target path.
+    pub no_copy: bool,
+    /// Labels of a new volume.
+    pub labels: HashMap<T, T>,
+    /// Volume driver of a new volume, the `local` driver if not set.
+    pub driver: Option<T>,
+    /// Options of the volume driver for a new volume.
+    pub driver_options: HashMap<T, T>,
+}
+
+/// A tmpfs mount of a [Mount Spec](enum.MountSpec.html).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TmpfsMount<T> {
+    /// Path in the container.
+    pub target: T,
+    /// Whether the mount is read-only.
+    pub read_only: bool,
+    /// Size of the filesystem in bytes, unlimited if not set.
+    pub size_bytes: Option<u64>,
+    /// Permission bits of the filesystem root, `0o1777` if not set.
+    pub mode: Option<u32>,
+}
+
+/// A named pipe mount of a [Mount Spec](enum.MountSpec.html).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NpipeMount<T> {
+    /// Named pipe on the host, e.g. `\\.\pipe\docker_engine`.
+    pub source: T,
+    /// Named pipe in the container.
+    pub target: T,
+    /// Whether the mount is read-only.
+    pub read_only: bool,
+}
+
+fn propagation_str(propagation: MountBindOptionsPropagation) -> &'static str {
+    match propagation {
+        MountBindOptionsPropagation::Private => "private",
+        MountBindOptionsPropagation::RPrivate => "rprivate",
+        MountBindOptionsPropagation::Shared => "shared",
+        MountBindOptionsPropagation::RShared => "rshared",
+        MountBindOptionsPropagation::Slave => "slave",
+        MountBindOptionsPropagation::RSlave => "rslave",
+    }
+}
+
+impl<T> From<MountSpec<T>> for MountPoint<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    fn from(spec: MountSpec<T>) -> Self {
+        let mount = |type_: &'static str, source: T, target: T, read_only: bool| MountPoint {
+            target,
+            source,
+            type_: T::from(type_),
+            read_only: Some(read_only),
+            consistency: T::from("default"),
+            bind_options: None,
+            volume_options: None,
+            tmpfs_options: None,
+        };
+
+        match spec {
+            MountSpec::Bind(bind) => {
+                let options = if bind.propagation.is_some() || bind.non_recursive {
+                    Some(MountPointBindOptions {
+                        propagation: T::from(bind.propagation.map(propagation_str).unwrap_or("")),
+                        non_recursive: bind.non_recursive,
+                    })
+                } else {
+                    None
+                };
+                MountPoint {
+                    bind_options: options,
+                    ..mount("bind", bind.source, bind.target, bind.read_only)
+                }
+            }
+            MountSpec::Volume(volume) => {
+                let options = if volume.no_copy
+                    || !volume.labels.is_empty()
+                    || volume.driver.is_some()
+                    || !volume.driver_options.is_empty()
+                {
+                    Some(MountPointVolumeOptions {
+                        no_copy: volume.no_copy,
+                        labels: volume.labels,
+                        driver_config: VolumeOptionsDriverConfig {
+                            name: volume.driver.unwrap_or_else(|| T::from("")),
+                            options: volume.driver_options,
+                        },
+                    })
+                } else {
+                    None
+                };
+                let source = volume.source.unwrap_or_else(|| T::from(""));
+                MountPoint {
+                    volume_options: options,
+                    ..mount("volume", source, volume.target, volume.read_only)
+                }
+            }
+            MountSpec::Tmpfs(tmpfs) => {
+                let options = if tmpfs.size_bytes.is_some() || tmpfs.mode.is_some() {
+                    Some(MountPointTmpfsOptions {
+                        size_bytes: tmpfs.size_bytes.unwrap_or(0),
+                        mode: tmpfs.mode.unwrap_or(0) as usize,
+                    })
+                } else {
+                    None
+                };
+                MountPoint {
+                    tmpfs_options: options,
+                    ..mount("tmpfs", T::from(""), tmpfs.target, tmpfs.read_only)
+                }
+            }
+            MountSpec::Npipe(npipe) => mount("npipe", npipe.source, npipe.target, npipe.read_only),
+        }
+    }
+}
+
+/// A resource limit to set in the container, for example: `{"Name": "nofile", "Soft": 1024,
+/// "Hard": 2048}`. A limit of `-1` is unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 #[allow(missing_docs)]
-pub struct Ulimits {
+pub struct Ulimit {
     pub name: String,
-    pub hard: Option<u64>,
-    pub soft: Option<u64>,
+    pub soft: i64,
+    pub hard: i64,
+}
+
+impl Ulimit {
+    /// A limit on a resource, e.g. `nofile`, with its soft and hard values.
+    pub fn new<T: Into<String>>(name: T, soft: i64, hard: i64) -> Ulimit {
+        Ulimit {
+            name: name.into(),
+            soft,
+            hard,
+        }
+    }
+}
+
+/// The cgroup namespace mode of a container, set in the [Host Config](struct.HostConfig.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CgroupnsMode {
+    /// The container has its own cgroup namespace, and only sees its own cgroup.
+    Private,
+    /// The container shares the cgroup namespace of the host.
+    Host,
+}
+
+/// A Linux kernel capability, used in the `CapAdd`, `CapDrop` and `Capabilities` fields of the
+/// [Host Config](struct.HostConfig.html).
+///
+/// Capabilities are serialized without the `CAP_` prefix, and deserialized with or without it.
+/// Capabilities unknown to this library are kept in the `Other` variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[allow(missing_docs)]
+pub enum Capability {
+    /// All capabilities, typically used as `--cap-drop ALL`.
+    All,
+    Chown,
+    DacOverride,
+    DacReadSearch,
+    Fowner,
+    Fsetid,
+    Kill,
+    Setgid,
+    Setuid,
+    Setpcap,
+    LinuxImmutable,
+    NetBindService,
+    NetBroadcast,
+    NetAdmin,
+    NetRaw,
+    IpcLock,
+    IpcOwner,
+    SysModule,
+    SysRawio,
+    SysChroot,
+    SysPtrace,
+    SysPacct,
+    SysAdmin,
+    SysBoot,
+    SysNice,
+    SysResource,
+    SysTime,
+    SysTtyConfig,
+    Mknod,
+    Lease,
+    AuditWrite,
+    AuditControl,
+    Setfcap,
+    MacOverride,
+    MacAdmin,
+    Syslog,
+    WakeAlarm,
+    BlockSuspend,
+    AuditRead,
+    Other(String),
+}
+
+impl From<String> for Capability {
+    fn from(capability: String) -> Capability {
+        let name = capability.trim_start_matches("CAP_");
+        match name {
+            "ALL" => Capability::All,
+            "CHOWN" => Capability::Chown,
+            "DAC_OVERRIDE" => Capability::DacOverride,
+            "DAC_READ_SEARCH" => Capability::DacReadSearch,
+            "FOWNER" => Capability::Fowner,
+            "FSETID" => Capability::Fsetid,
+            "KILL" => Capability::Kill,
+            "SETGID" => Capability::Setgid,
+            "SETUID" => Capability::Setuid,
+            "SETPCAP" => Capability::Setpcap,
+            "LINUX_IMMUTABLE" => Capability::LinuxImmutable,
+            "NET_BIND_SERVICE" => Capability::NetBindService,
+            "NET_BROADCAST" => Capability::NetBroadcast,
+            "NET_ADMIN" => Capability::NetAdmin,
+            "NET_RAW" => Capability::NetRaw,
+            "IPC_LOCK" => Capability::IpcLock,
+            "IPC_OWNER" => Capability::IpcOwner,
+            "SYS_MODULE" => Capability::SysModule,
+            "SYS_RAWIO" => Capability::SysRawio,
+            "SYS_CHROOT" => Capability::SysChroot,
+            "SYS_PTRACE" => Capability::SysPtrace,
+            "SYS_PACCT" => Capability::SysPacct,
+            "SYS_ADMIN" => Capability::SysAdmin,
+            "SYS_BOOT" => Capability::SysBoot,
+            "SYS_NICE" => Capability::SysNice,
+            "SYS_RESOURCE" => Capability::SysResource,
+            "SYS_TIME" => Capability::SysTime,
+            "SYS_TTY_CONFIG" => Capability::SysTtyConfig,
+            "MKNOD" => Capability::Mknod,
+            "LEASE" => Capability::Lease,
+            "AUDIT_WRITE" => Capability::AuditWrite,
+            "AUDIT_CONTROL" => Capability::AuditControl,
+            "SETFCAP" => Capability::Setfcap,
+            "MAC_OVERRIDE" => Capability::MacOverride,
+            "MAC_ADMIN" => Capability::MacAdmin,
+            "SYSLOG" => Capability::Syslog,
+            "WAKE_ALARM" => Capability::WakeAlarm,
+            "BLOCK_SUSPEND" => Capability::BlockSuspend,
+            "AUDIT_READ" => Capability::AuditRead,
+            _ => Capability::Other(capability),
+        }
+    }
+}
+
+impl From<Capability> for String {
+    fn from(capability: Capability) -> String {
+        let name = match capability {
+            Capability::All => "ALL",
+            Capability::Chown => "CHOWN",
+            Capability::DacOverride => "DAC_OVERRIDE",
+            Capability::DacReadSearch => "DAC_READ_SEARCH",
+            Capability::Fowner => "FOWNER",
+            Capability::Fsetid => "FSETID",
+            Capability::Kill => "KILL",
+            Capability::Setgid => "SETGID",
+            Capability::Setuid => "SETUID",
+            Capability::Setpcap => "SETPCAP",
+            Capability::LinuxImmutable => "LINUX_IMMUTABLE",
+            Capability::NetBindService => "NET_BIND_SERVICE",
+            Capability::NetBroadcast => "NET_BROADCAST",
+            Capability::NetAdmin => "NET_ADMIN",
+            Capability::NetRaw => "NET_RAW",
+            Capability::IpcLock => "IPC_LOCK",
+            Capability::IpcOwner => "IPC_OWNER",
+            Capability::SysModule => "SYS_MODULE",
+            Capability::SysRawio => "SYS_RAWIO",
+            Capability::SysChroot => "SYS_CHROOT",
+            Capability::SysPtrace => "SYS_PTRACE",
+            Capability::SysPacct => "SYS_PACCT",
+            Capability::SysAdmin => "SYS_ADMIN",
+            Capability::SysBoot => "SYS_BOOT",
+            Capability::SysNice => "SYS_NICE",
+            Capability::SysResource => "SYS_RESOURCE",
+            Capability::SysTime => "SYS_TIME",
+            Capability::SysTtyConfig => "SYS_TTY_CONFIG",
+            Capability::Mknod => "MKNOD",
+            Capability::Lease => "LEASE",
+            Capability::AuditWrite => "AUDIT_WRITE",
+            Capability::AuditControl => "AUDIT_CONTROL",
+            Capability::Setfcap => "SETFCAP",
+            Capability::MacOverride => "MAC_OVERRIDE",
+            Capability::MacAdmin => "MAC_ADMIN",
+            Capability::Syslog => "SYSLOG",
+            Capability::WakeAlarm => "WAKE_ALARM",
+            Capability::BlockSuspend => "BLOCK_SUSPEND",
+            Capability::AuditRead => "AUDIT_READ",
+            Capability::Other(name) => return name,
+        };
+        String::from(name)
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", String::from(self.clone()))
+    }
+}
+
+/// A signal sent to the main process of a container, in the [Kill
+/// Container](../struct.Docker.html#method.kill_container), [Stop
+/// Container](../struct.Docker.html#method.stop_container) and [Restart
+/// Container](../struct.Docker.html#method.restart_container) APIs.
+///
+/// Signals are serialized with the `SIG` prefix, and deserialized with or without it. Other
+/// signals, such as `SIGRTMIN+3` or a signal number, are sent as given with the `Custom` variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Signal {
+    /// `SIGHUP`, often used to reload configuration.
+    Hup,
+    /// `SIGINT`, as sent by Ctrl-C.
+    Int,
+    /// `SIGQUIT`.
+    Quit,
+    /// `SIGKILL`, which cannot be caught, the default of the [Kill Container
+    /// API](../struct.Docker.html#method.kill_container).
+    Kill,
+    /// `SIGUSR1`.
+    Usr1,
+    /// `SIGUSR2`.
+    Usr2,
+    /// `SIGTERM`, asking the process to exit.
+    Term,
+    /// `SIGSTOP`, which cannot be caught.
+    Stop,
+    /// `SIGCONT`.
+    Cont,
+    /// `SIGWINCH`, sent when the terminal is resized.
+    Winch,
+    /// Any other signal, by name or number.
+    Custom(String),
+}
+
+impl Default for Signal {
+    fn default() -> Signal {
+        Signal::Kill
+    }
+}
+
+impl AsRef<str> for Signal {
+    fn as_ref(&self) -> &str {
+        match self {
+            Signal::Hup => "SIGHUP",
+            Signal::Int => "SIGINT",
+            Signal::Quit => "SIGQUIT",
+            Signal::Kill => "SIGKILL",
+            Signal::Usr1 => "SIGUSR1",
+            Signal::Usr2 => "SIGUSR2",
+            Signal::Term => "SIGTERM",
+            Signal::Stop => "SIGSTOP",
+            Signal::Cont => "SIGCONT",
+            Signal::Winch => "SIGWINCH",
+            Signal::Custom(signal) => signal,
+        }
+    }
+}
+
+impl From<String> for Signal {
+    fn from(signal: String) -> Signal {
+        match signal.trim_start_matches("SIG") {
+            "HUP" => Signal::Hup,
+            "INT" => Signal::Int,
+            "QUIT" => Signal::Quit,
+            "KILL" => Signal::Kill,
+            "USR1" => Signal::Usr1,
+            "USR2" => Signal::Usr2,
+            "TERM" => Signal::Term,
+            "STOP" => Signal::Stop,
+            "CONT" => Signal::Cont,
+            "WINCH" => Signal::Winch,
+            _ => Signal::Custom(signal),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Signal {
+    fn from(signal: &'a str) -> Signal {
+        Signal::from(String::from(signal))
+    }
+}
+
+impl From<Signal> for String {
+    fn from(signal: Signal) -> String {
+        match signal {
+            Signal::Custom(signal) => signal,
+            signal => String::from(signal.as_ref()),
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
 }
 
 /// Container configuration that depends on the host we are running on
@@ -294,11 +1079,19 @@ where
     /// The length of a CPU period in microseconds.
     pub cpu_period: Option<u64>,
     /// The length of a CPU real-time period in microseconds. Set to 0 to allocate no time allocated to real-time tasks.
+    ///
+    /// The real-time scheduler is not available on cgroup v2 hosts, where the docker server
+    /// rejects a non-zero value.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_realtime_period: Option<u64>,
     /// The length of a CPU real-time runtime in microseconds. Set to 0 to allocate no time allocated to real-time tasks.
+    ///
+    /// The real-time scheduler is not available on cgroup v2 hosts, where the docker server
+    /// rejects a non-zero value.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_realtime_runtime: Option<u64>,
     /// Microseconds of CPU time that the container can get in a CPU period.
-    pub cpu_quota: Option<u64>,
+    pub cpu_quota: Option<i64>,
     /// CPUs in which to allow execution (e.g., `0-3`, `0,1`)
     pub cpuset_cpus: Option<T>,
     /// Memory nodes (MEMs) in which to allow execution (`0-3`, `0,1`). Only effective on NUMA systems.
@@ -317,7 +1110,12 @@ where
     /// Limit write rate (IO per second) to a device.
     #[serde(rename = "BlkioDeviceWriteIOps")]
     pub blkio_device_write_iops: Option<Vec<HashMap<T, T>>>,
-    /// Tune a container's memory swappiness behavior. Accepts an integer between 0 and 100.
+    /// Tune a container's memory swappiness behavior. Accepts an integer between 0 and 100, or
+    /// `-1` for the default of the host.
+    ///
+    /// Swappiness is not available on cgroup v2 hosts, where the docker server discards it with a
+    /// warning, so leave it unset for containers to behave the same on every host.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub memory_swappiness: Option<i64>,
     /// Disable OOM Killer for the container.
     pub oom_kill_disable: Option<bool>,
@@ -329,7 +1127,7 @@ where
     /// - `"host"`: use the host's PID namespace inside the container
     pub pid_mode: Option<String>,
     /// Tune a container's pids limit. Set `-1` for unlimited.
-    pub pids_limit: Option<u64>,
+    pub pids_limit: Option<i64>,
     /// PortMap describes the mapping of container ports to host ports, using the container's
     /// port-number and protocol as key in the format `<port>/<protocol`>, for example, `80/udp`.  If a
     /// container's port is mapped for multiple protocols, separate entries are added to the
@@ -342,8 +1140,10 @@ where
     /// on Linux the range is defined by `/proc/sys/net/ipv4/ip_local_port_range`.
     pub publish_all_ports: Option<bool>,
     /// Gives the container full access to the host.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub privileged: Option<bool>,
     /// Mount the container's root filesystem as read only.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub readonly_rootfs: Option<bool>,
     /// A list of DNS servers for the container to use.
     pub dns: Option<Vec<T>>,
@@ -357,16 +1157,19 @@ where
     /// Specification for mounts to be added to the container.
     pub mounts: Option<Vec<MountPoint<T>>>,
     /// A list of kernel capabilities to be available for container (this overrides the default set).  Conflicts with options 'CapAdd' and 'CapDrop'
-    pub capabilities: Option<Vec<T>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Vec<Capability>>,
     /// A list of kernel capabilities to add to the container. Conflicts with option 'Capabilities'
-    pub cap_add: Option<Vec<T>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_add: Option<Vec<Capability>>,
     /// A list of kernel capabilities to drop from the container. Conflicts with option 'Capabilities'
-    pub cap_drop: Option<Vec<T>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_drop: Option<Vec<Capability>>,
     pub group_add: Option<Vec<T>>,
     /// The behavior to apply when the container exits. The default is not to restart.
     /// An ever increasing delay (double the previous delay, starting at 100ms) is added before
     /// each restart to prevent flooding the server.
-    pub restart_policy: Option<RestartPolicy<T>>,
+    pub restart_policy: Option<RestartPolicy>,
     /// Automatically remove the container when the container's process exits. This has no effect
     /// if `RestartPolicy` is set.
     pub auto_remove: Option<bool>,
@@ -377,10 +1180,12 @@ where
     pub devices: Option<Vec<HashMap<T, T>>>,
     /// A list of resource limits to set in the container. For example: `{"Name": "nofile", "Soft":
     /// 1024, "Hard": 2048}`
-    pub ulimits: Option<Vec<Ulimits>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ulimits: Option<Vec<Ulimit>>,
     /// The logging configuration for this container.
     pub log_config: Option<LogConfig>,
     /// A list of string values to customize labels for MLS systems, such as SELinux.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub security_opt: Option<Vec<T>>,
     /// Path to `cgroups` under which the container's `cgroup` is created. If the path is not absolute,
     /// the path is considered to be relative to the `cgroups` path of the init process. Cgroups are
@@ -422,10 +1227,9 @@ where
     pub device_cgroup_rules: Option<Vec<T>>,
     /// Disk limit (in bytes).
     pub disk_quota: Option<u64>,
-    /// A list of requests for devices to be sent to device drivers
-    pub device_requests: Option<DeviceRequest<T>>,
-    /// Hard limit for kernel TCP buffer memory (in bytes).
-    pub kernet_memory_tcp: Option<i64>,
+    /// A list of requests for devices to be sent to device drivers, such as GPUs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_requests: Option<Vec<DeviceRequest<T>>>,
     /// The usable percentage of the available CPUs (Windows only).
     /// On Windows Server containers, the processor resource controls are mutually exclusive. The
     /// order of precedence is `CPUCount` first, then `CPUShares`, and `CPUPercent` last.
@@ -436,20 +1240,55 @@ where
     /// Maximum IO in bytes per second for the container system drive (Windows only).
     #[serde(rename = "IOMaximumBandwidth")]
     pub io_maximum_bandwidth: Option<u64>,
+    /// The list of paths to be masked inside the container, overriding the default set of the
+    /// docker server.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub masked_paths: Option<Vec<T>>,
+    /// The list of paths to be set as read-only inside the container, overriding the default set
+    /// of the docker server.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub readonly_paths: Option<Vec<T>>,
+    /// Run an init inside the container that forwards signals and reaps processes. The default of
+    /// the docker server is used if omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init: Option<bool>,
+    /// The cgroup namespace mode of the container. The default of the docker server is used if
+    /// omitted, which is `private` on cgroup v2 hosts and `host` on cgroup v1 hosts. Requires API
+    /// version 1.41 or later.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cgroupns_mode: Option<CgroupnsMode>,
+    /// A map of container directories to mount as tmpfs, with their mount options, e.g.
+    /// `{"/run": "rw,noexec,nosuid,size=65536k"}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmpfs: Option<HashMap<T, T>>,
+    /// Storage driver options of the container, e.g. `{"size": "120G"}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_opt: Option<HashMap<T, T>>,
+    /// A list of kernel parameters (sysctls) to set in the container. For example:
+    /// `{"net.ipv4.ip_forward": "1"}`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sysctls: Option<HashMap<T, T>>,
 }
 
 /// Storage driver name and configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct GraphDriver {
+pub struct GraphDriverData {
+    /// Name of the storage driver, e.g. `overlay2`.
     pub name: String,
-    pub data: Option<HashMap<String, String>>,
+    /// Low-level storage metadata, such as the `MergedDir` and `UpperDir` of `overlay2`. The keys
+    /// vary by storage driver.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
+    pub data: HashMap<String, String>,
 }
 
+/// Former name of [`GraphDriverData`](struct.GraphDriverData.html).
+pub type GraphDriver = GraphDriverData;
+
 /// Describes the mapping of container ports to host ports, using the container's
 /// port-number and protocol as key in the format `<port>/<protocol>`, for example, `80/udp`.  If a
 /// container's port is mapped for multiple protocols, separate entries are added to the mapping
@@ -466,84 +1305,501 @@ where
     pub host_port: T,
 }
 
-/// The behavior to apply when the container exits. The default is not to restart.  An ever
-/// increasing delay (double the previous delay, starting at 100ms) is added before each restart to
-/// prevent flooding the server.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+/// Transport protocol of a container port, as in the `<port>/<protocol>` keys of
+/// [`exposed_ports`](struct.Config.html#structfield.exposed_ports) and
+/// [`port_bindings`](struct.HostConfig.html#structfield.port_bindings).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
-pub struct RestartPolicy<T>
-where
-    T: AsRef<str>,
-{
-    pub name: Option<T>,
-    pub maximum_retry_count: Option<isize>,
+pub enum Protocol {
+    Tcp,
+    Udp,
+    Sctp,
 }
 
-/// The logging configuration for this container.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct LogConfig {
-    #[serde(rename = "Type")]
-    pub type_: Option<String>,
-    pub config: Option<HashMap<String, String>>,
+impl Default for Protocol {
+    fn default() -> Self {
+        Protocol::Tcp
+    }
 }
 
-/// This container's networking configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct NetworkingConfig {
-    pub endpoints_config: HashMap<String, ContainerNetwork>,
+impl AsRef<str> for Protocol {
+    fn as_ref(&self) -> &str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+            Protocol::Sctp => "sctp",
+        }
+    }
 }
 
-/// Configuration for a network endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct ContainerNetwork {
-    #[serde(rename = "IPAMConfig")]
-    pub ipam_config: Option<EndpointIPAMConfig<String>>,
-    pub links: Option<Vec<String>>,
-    pub aliases: Option<Vec<String>>,
-    pub mac_address: String,
-    #[serde(rename = "GlobalIPv6Address")]
-    pub global_ipv6_address: String,
-    #[serde(rename = "GlobalIPv6PrefixLen")]
-    pub global_ipv6_prefix_len: isize,
-    #[serde(rename = "IPv6Gateway")]
-    pub ipv6_gateway: String,
-    #[serde(rename = "IPAddress")]
-    pub ip_address: String,
-    #[serde(rename = "IPPrefixLen")]
-    pub ip_prefix_len: i64,
-    pub gateway: String,
-    #[serde(rename = "EndpointID")]
-    pub endpoint_id: String,
-    #[serde(rename = "NetworkID")]
-    pub network_id: String,
-    pub driver_opts: Option<HashMap<String, String>>,
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
 }
 
-/// Network Settings for a container.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct NetworkSettings {
-    pub networks: HashMap<String, ContainerNetwork>,
-    #[serde(rename = "IPAddress")]
-    pub ip_address: String,
-    #[serde(rename = "IPPrefixLen")]
-    pub ip_prefix_len: isize,
-    pub mac_address: String,
-    pub gateway: String,
-    pub bridge: String,
-    #[serde(rename = "EndpointID")]
-    pub endpoint_id: String,
-    pub sandbox_key: String,
-    #[serde(rename = "GlobalIPv6Address")]
-    pub global_ipv6_address: String,
+/// Builder of the [`exposed_ports`](struct.Config.html#structfield.exposed_ports) of a container
+/// configuration and the [`port_bindings`](struct.HostConfig.html#structfield.port_bindings) of
+/// its host configuration, in the `<port>/<protocol>` format of the docker server.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::{Config, HostConfig, PortMap};
+///
+/// use std::default::Default;
+/// use std::net::Ipv4Addr;
+///
+/// // expose 8080/tcp on port 18080 of all host interfaces, and 53/udp on an ephemeral port
+/// let ports = PortMap::new()
+///     .tcp(8080)
+///     .to_host(Ipv4Addr::UNSPECIFIED, 18080)
+///     .udp(53)
+///     .to_host(Ipv4Addr::LOCALHOST, 0);
+///
+/// Config {
+///     image: Some(String::from("my-image")),
+///     exposed_ports: Some(ports.exposed_ports()),
+///     host_config: Some(HostConfig {
+///         port_bindings: Some(ports.port_bindings()),
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortMap {
+    ports: Vec<MappedPort>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MappedPort {
+    port: u16,
+    protocol: Protocol,
+    bindings: Vec<(IpAddr, u16)>,
+}
+
+impl MappedPort {
+    fn key(&self) -> String {
+        format!("{}/{}", self.port, self.protocol)
+    }
+}
+
+impl PortMap {
+    /// An empty port map.
+    pub fn new() -> PortMap {
+        PortMap::default()
+    }
+
+    /// Expose a container port. Adding a port again keeps its host bindings.
+    pub fn port(mut self, port: u16, protocol: Protocol) -> Self {
+        let mapped = match self
+            .ports
+            .iter()
+            .position(|p| p.port == port && p.protocol == protocol)
+        {
+            Some(position) => self.ports.remove(position),
+            None => MappedPort {
+                port,
+                protocol,
+                bindings: Vec::new(),
+            },
+        };
+        self.ports.push(mapped);
+        self
+    }
+
+    /// Expose a TCP container port.
+    pub fn tcp(self, port: u16) -> Self {
+        self.port(port, Protocol::Tcp)
+    }
+
+    /// Expose a UDP container port.
+    pub fn udp(self, port: u16) -> Self {
+        self.port(port, Protocol::Udp)
+    }
+
+    /// Expose an SCTP container port.
+    pub fn sctp(self, port: u16) -> Self {
+        self.port(port, Protocol::Sctp)
+    }
+
+    /// Publish the last exposed port on a host address, with host port `0` for an ephemeral port
+    /// chosen by the docker server. A port can be published on several host addresses. Does
+    /// nothing if no port was exposed yet.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_host<A>(mut self, ip: A, port: u16) -> Self
+    where
+        A: Into<IpAddr>,
+    {
+        if let Some(mapped) = self.ports.last_mut() {
+            mapped.bindings.push((ip.into(), port));
+        }
+        self
+    }
+
+    /// The exposed ports, for the [`exposed_ports`](struct.Config.html#structfield.exposed_ports)
+    /// of a container configuration.
+    pub fn exposed_ports<T>(&self) -> HashMap<T, HashMap<(), ()>>
+    where
+        T: From<String> + Eq + Hash,
+    {
+        self.ports
+            .iter()
+            .map(|mapped| (T::from(mapped.key()), HashMap::new()))
+            .collect()
+    }
+
+    /// The published ports, for the
+    /// [`port_bindings`](struct.HostConfig.html#structfield.port_bindings) of a host
+    /// configuration. Ports that are only exposed are left out.
+    pub fn port_bindings<T>(&self) -> HashMap<T, Vec<PortBinding<T>>>
+    where
+        T: From<String> + AsRef<str> + Eq + Hash,
+    {
+        self.ports
+            .iter()
+            .filter(|mapped| !mapped.bindings.is_empty())
+            .map(|mapped| {
+                let bindings = mapped
+                    .bindings
+                    .iter()
+                    .map(|(ip, port)| PortBinding {
+                        host_ip: T::from(ip.to_string()),
+                        host_port: T::from(port.to_string()),
+                    })
+                    .collect();
+                (T::from(mapped.key()), bindings)
+            })
+            .collect()
+    }
+}
+
+/// The behavior to apply when the container exits. The default is not to restart.  An ever
+/// increasing delay (double the previous delay, starting at 100ms) is added before each restart to
+/// prevent flooding the server.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::{HostConfig, RestartPolicy};
+///
+/// use std::default::Default;
+///
+/// HostConfig::<String> {
+///     restart_policy: Some(RestartPolicy::OnFailure { max_retries: 5 }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RestartPolicyFields", into = "RestartPolicyFields")]
+pub enum RestartPolicy {
+    /// Do not restart the container.
+    No,
+    /// Always restart the container, including when the docker server starts.
+    Always,
+    /// Always restart the container, except when the docker server starts after the container
+    /// was stopped.
+    UnlessStopped,
+    /// Restart the container when it exits with a non-zero exit code.
+    OnFailure {
+        /// The number of restarts before giving up, `0` to restart without limit.
+        max_retries: u32,
+    },
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy::No
+    }
+}
+
+/// The restart policy in the form of the docker server, where the retry count only applies to
+/// the `on-failure` policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RestartPolicyFields {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    maximum_retry_count: i64,
+}
+
+impl From<RestartPolicyFields> for RestartPolicy {
+    fn from(fields: RestartPolicyFields) -> Self {
+        match fields.name.as_str() {
+            "always" => RestartPolicy::Always,
+            "unless-stopped" => RestartPolicy::UnlessStopped,
+            "on-failure" => RestartPolicy::OnFailure {
+                max_retries: cmp::max(fields.maximum_retry_count, 0) as u32,
+            },
+            _ => RestartPolicy::No,
+        }
+    }
+}
+
+impl From<RestartPolicy> for RestartPolicyFields {
+    fn from(policy: RestartPolicy) -> Self {
+        let (name, maximum_retry_count) = match policy {
+            RestartPolicy::No => ("no", 0),
+            RestartPolicy::Always => ("always", 0),
+            RestartPolicy::UnlessStopped => ("unless-stopped", 0),
+            RestartPolicy::OnFailure { max_retries } => ("on-failure", i64::from(max_retries)),
+        };
+        RestartPolicyFields {
+            name: String::from(name),
+            maximum_retry_count,
+        }
+    }
+}
+
+/// The logging configuration for this container.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::{HostConfig, LogConfig, LogDriver};
+///
+/// use std::default::Default;
+///
+/// // keep up to 3 files of 10 MB of logs
+/// let log_config = LogConfig::new(LogDriver::JsonFile)
+///     .max_size(10 * 1024 * 1024)
+///     .max_file(3)
+///     .option("labels", "app");
+///
+/// HostConfig::<String> {
+///     log_config: Some(log_config),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LogConfig {
+    /// The logging driver, or `None` for the default driver of the docker server.
+    #[serde(rename = "Type")]
+    pub type_: Option<LogDriver>,
+    /// Options of the logging driver.
+    pub config: Option<HashMap<String, String>>,
+}
+
+impl LogConfig {
+    /// The configuration of a logging driver, with the default options of the driver.
+    pub fn new(driver: LogDriver) -> LogConfig {
+        LogConfig {
+            type_: Some(driver),
+            config: None,
+        }
+    }
+
+    /// Set an option of the logging driver, as given to `docker run --log-opt`.
+    pub fn option<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.config
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the tag identifying the log messages of the container, which may use templates such
+    /// as `{{.Name}}`.
+    pub fn tag<V>(self, tag: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.option("tag", tag)
+    }
+
+    /// Set the size in bytes at which the log file is rotated, for the `json-file` and `local`
+    /// drivers.
+    pub fn max_size(self, bytes: u64) -> Self {
+        self.option("max-size", bytes.to_string())
+    }
+
+    /// Set the number of rotated log files to keep, for the `json-file` and `local` drivers.
+    pub fn max_file(self, count: u32) -> Self {
+        self.option("max-file", count.to_string())
+    }
+
+    /// Compress the rotated log files, for the `json-file` and `local` drivers.
+    pub fn compress(self, compress: bool) -> Self {
+        self.option("compress", compress.to_string())
+    }
+
+    /// Set the address of the syslog server, e.g. `udp://1.2.3.4:514`, for the `syslog` driver.
+    pub fn syslog_address<V>(self, address: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.option("syslog-address", address)
+    }
+
+    /// Set the address of the fluentd daemon, e.g. `localhost:24224`, for the `fluentd` driver.
+    pub fn fluentd_address<V>(self, address: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.option("fluentd-address", address)
+    }
+
+    /// Set the CloudWatch log group and region, for the `awslogs` driver.
+    pub fn awslogs_group<V, R>(self, group: V, region: R) -> Self
+    where
+        V: Into<String>,
+        R: Into<String>,
+    {
+        self.option("awslogs-group", group)
+            .option("awslogs-region", region)
+    }
+}
+
+/// A logging driver of a [Log Config](struct.LogConfig.html). Drivers unknown to this library,
+/// such as logging plugins, are kept in the `Other` variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum LogDriver {
+    /// `json-file`, the default driver, writing to JSON files on the host.
+    JsonFile,
+    /// `local`, writing to compact files on the host.
+    Local,
+    /// `journald`, writing to the systemd journal.
+    Journald,
+    /// `syslog`, sending to a syslog server.
+    Syslog,
+    /// `fluentd`, sending to a fluentd daemon.
+    Fluentd,
+    /// `awslogs`, sending to Amazon CloudWatch Logs.
+    Awslogs,
+    /// `gelf`, sending to a Graylog Extended Log Format endpoint.
+    Gelf,
+    /// `splunk`, sending to a Splunk HTTP Event Collector.
+    Splunk,
+    /// `gcplogs`, sending to Google Cloud Logging.
+    Gcplogs,
+    /// `etwlogs`, writing Event Tracing for Windows events.
+    Etwlogs,
+    /// `none`, discarding the logs of the container.
+    None,
+    /// Any other driver, such as a logging plugin, by name.
+    Other(String),
+}
+
+impl AsRef<str> for LogDriver {
+    fn as_ref(&self) -> &str {
+        match self {
+            LogDriver::JsonFile => "json-file",
+            LogDriver::Local => "local",
+            LogDriver::Journald => "journald",
+            LogDriver::Syslog => "syslog",
+            LogDriver::Fluentd => "fluentd",
+            LogDriver::Awslogs => "awslogs",
+            LogDriver::Gelf => "gelf",
+            LogDriver::Splunk => "splunk",
+            LogDriver::Gcplogs => "gcplogs",
+            LogDriver::Etwlogs => "etwlogs",
+            LogDriver::None => "none",
+            LogDriver::Other(driver) => driver,
+        }
+    }
+}
+
+impl From<String> for LogDriver {
+    fn from(driver: String) -> LogDriver {
+        match driver.as_str() {
+            "json-file" => LogDriver::JsonFile,
+            "local" => LogDriver::Local,
+            "journald" => LogDriver::Journald,
+            "syslog" => LogDriver::Syslog,
+            "fluentd" => LogDriver::Fluentd,
+            "awslogs" => LogDriver::Awslogs,
+            "gelf" => LogDriver::Gelf,
+            "splunk" => LogDriver::Splunk,
+            "gcplogs" => LogDriver::Gcplogs,
+            "etwlogs" => LogDriver::Etwlogs,
+            "none" => LogDriver::None,
+            _ => LogDriver::Other(driver),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for LogDriver {
+    fn from(driver: &'a str) -> LogDriver {
+        LogDriver::from(String::from(driver))
+    }
+}
+
+impl From<LogDriver> for String {
+    fn from(driver: LogDriver) -> String {
+        match driver {
+            LogDriver::Other(driver) => driver,
+            driver => String::from(driver.as_ref()),
+        }
+    }
+}
+
+impl fmt::Display for LogDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// This container's networking configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+#[allow(missing_docs)]
+pub struct NetworkingConfig {
+    pub endpoints_config: HashMap<String, ContainerNetwork>,
+}
+
+/// Configuration for a network endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+#[allow(missing_docs)]
+pub struct ContainerNetwork {
+    #[serde(rename = "IPAMConfig")]
+    pub ipam_config: Option<EndpointIPAMConfig<String>>,
+    pub links: Option<Vec<String>>,
+    pub aliases: Option<Vec<String>>,
+    pub mac_address: String,
+    #[serde(rename = "GlobalIPv6Address")]
+    pub global_ipv6_address: String,
+    #[serde(rename = "GlobalIPv6PrefixLen")]
+    pub global_ipv6_prefix_len: isize,
+    #[serde(rename = "IPv6Gateway")]
+    pub ipv6_gateway: String,
+    #[serde(rename = "IPAddress")]
+    pub ip_address: String,
+    #[serde(rename = "IPPrefixLen")]
+    pub ip_prefix_len: i64,
+    pub gateway: String,
+    #[serde(rename = "EndpointID")]
+    pub endpoint_id: String,
+    #[serde(rename = "NetworkID")]
+    pub network_id: String,
+    pub driver_opts: Option<HashMap<String, String>>,
+}
+
+/// Network Settings for a container.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+#[allow(missing_docs)]
+pub struct NetworkSettings {
+    pub networks: HashMap<String, ContainerNetwork>,
+    #[serde(rename = "IPAddress")]
+    pub ip_address: String,
+    #[serde(rename = "IPPrefixLen")]
+    pub ip_prefix_len: isize,
+    pub mac_address: String,
+    pub gateway: String,
+    pub bridge: String,
+    #[serde(rename = "EndpointID")]
+    pub endpoint_id: String,
+    pub sandbox_key: String,
+    #[serde(rename = "GlobalIPv6Address")]
+    pub global_ipv6_address: String,
     #[serde(rename = "GlobalIPv6PrefixLen")]
     pub global_ipv6_prefix_len: isize,
     #[serde(rename = "IPv6Gateway")]
@@ -562,21 +1818,29 @@ pub struct NetworkSettings {
     pub ports: HashMap<String, Option<Vec<PortBinding<String>>>>,
 }
 
-/// Specification for mounts to be added to the container.
+/// A mount point in use by a container.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct Mount {
+    /// Name of the volume, absent for bind and tmpfs mounts.
     pub name: Option<String>,
+    /// Location of the mount on the host, empty for tmpfs mounts.
     pub source: String,
+    /// Location of the mount within the container.
     pub destination: String,
+    /// Name of the volume driver, absent for bind and tmpfs mounts.
     pub driver: Option<String>,
+    /// Mode passed when the mount was created, e.g. `z` or `ro`. Usually empty.
     pub mode: String,
+    /// Whether the mount is writable.
     #[serde(rename = "RW")]
     pub rw: bool,
+    /// The mount type.
     #[serde(rename = "Type")]
-    pub type_: String,
-    pub propagation: String,
+    pub type_: MountType,
+    /// The propagation mode of a bind mount, absent for other mount types.
+    #[serde(default, deserialize_with = "deserialize_empty_as_none")]
+    pub propagation: Option<MountBindOptionsPropagation>,
 }
 
 /// Log of the health of a running container.
@@ -586,7 +1850,9 @@ pub struct Mount {
 pub struct LogStateHealth {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
-    pub exit_code: u16,
+    /// Exit code of the check: `0` if healthy, `1` if unhealthy, or `-1` if the check could not
+    /// be run.
+    pub exit_code: i64,
     pub output: String,
 }
 
@@ -597,6 +1863,8 @@ pub struct LogStateHealth {
 pub struct StateHealth {
     pub status: String,
     pub failing_streak: u64,
+    /// The last results of the health check, oldest first.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
     pub log: Vec<LogStateHealth>,
 }
 
@@ -613,7 +1881,9 @@ pub struct State {
     pub oomkilled: bool,
     pub dead: bool,
     pub pid: isize,
-    pub exit_code: u16,
+    /// Exit code of the last run of the container, which is negative or above 255 for some
+    /// failures and on Windows.
+    pub exit_code: i64,
     pub error: String,
     pub started_at: DateTime<Utc>,
     pub finished_at: DateTime<Utc>,
@@ -658,7 +1928,11 @@ pub struct APIContainers {
     pub status: String,
     pub ports: Vec<APIPort>,
     pub labels: HashMap<String, String>,
+    /// Size of the files created or changed by the container, in bytes. Only returned when
+    /// listing with [`size`](struct.ListContainersOptions.html#structfield.size).
     pub size_rw: Option<i64>,
+    /// Total size of all the files in the container, in bytes. Only returned when listing with
+    /// [`size`](struct.ListContainersOptions.html#structfield.size).
     pub size_root_fs: Option<i64>,
     pub mounts: Vec<Mount>,
     pub network_settings: NetworkList,
@@ -693,11 +1967,31 @@ pub struct Container {
     pub app_armor_profile: String,
     #[serde(rename = "ExecIDs")]
     pub exec_ids: Option<Vec<String>>,
-    pub graph_driver: GraphDriver,
+    pub graph_driver: GraphDriverData,
+    /// Size of the files created or changed by the container, in bytes. Only returned when
+    /// inspecting with [`size`](struct.InspectContainerOptions.html#structfield.size).
+    pub size_rw: Option<i64>,
+    /// Total size of all the files in the container, in bytes. Only returned when inspecting with
+    /// [`size`](struct.InspectContainerOptions.html#structfield.size).
+    pub size_root_fs: Option<i64>,
 }
 
 /// A test to perform to check that the container is healthy.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::{HealthCheckTest, HealthConfig};
+///
+/// use std::time::Duration;
+///
+/// HealthConfig::new(HealthCheckTest::CmdShell(String::from("curl -f http://localhost/")))
+///     .interval(Duration::from_secs(30))
+///     .timeout(Duration::from_secs(5))
+///     .start_period(Duration::from_secs(10))
+///     .retries(3);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct HealthConfig {
     /// The test to perform. Possible values are:
@@ -720,6 +2014,89 @@ pub struct HealthConfig {
     pub start_period: Option<u64>,
 }
 
+impl HealthConfig {
+    /// A health check running a test, with the interval, timeout, retries and start period
+    /// inherited from the image.
+    pub fn new(test: HealthCheckTest) -> HealthConfig {
+        HealthConfig {
+            test: Some(test.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the time to wait between checks.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(duration_nanos(interval));
+        self
+    }
+
+    /// Set the time to wait before considering the check to have hung.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(duration_nanos(timeout));
+        self
+    }
+
+    /// Set the number of consecutive failures needed to consider the container as unhealthy.
+    pub fn retries(mut self, retries: u64) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+
+    /// Set the time for the container to initialize before failed checks count towards the
+    /// retries.
+    pub fn start_period(mut self, start_period: Duration) -> Self {
+        self.start_period = Some(duration_nanos(start_period));
+        self
+    }
+
+    /// The test of the health check, or `None` if it is not set or not in a known form.
+    pub fn check(&self) -> Option<HealthCheckTest> {
+        let test = self.test.as_ref()?;
+        match test.split_first() {
+            None => Some(HealthCheckTest::Inherit),
+            Some((kind, args)) => match kind.as_str() {
+                "NONE" if args.is_empty() => Some(HealthCheckTest::None),
+                "CMD" => Some(HealthCheckTest::Cmd(args.to_vec())),
+                "CMD-SHELL" if args.len() == 1 => Some(HealthCheckTest::CmdShell(args[0].clone())),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// The test of a [Health Config](struct.HealthConfig.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthCheckTest {
+    /// Inherit the health check of the image.
+    Inherit,
+    /// Disable the health check of the image.
+    None,
+    /// Run a command, with its arguments, directly.
+    Cmd(Vec<String>),
+    /// Run a command with the default shell of the container.
+    CmdShell(String),
+}
+
+impl From<HealthCheckTest> for Vec<String> {
+    fn from(test: HealthCheckTest) -> Self {
+        match test {
+            HealthCheckTest::Inherit => Vec::new(),
+            HealthCheckTest::None => vec![String::from("NONE")],
+            HealthCheckTest::Cmd(args) => {
+                let mut test = vec![String::from("CMD")];
+                test.extend(args);
+                test
+            }
+            HealthCheckTest::CmdShell(command) => vec![String::from("CMD-SHELL"), command],
+        }
+    }
+}
+
+/// Convert a duration to the nanoseconds of a health check.
+fn duration_nanos(duration: Duration) -> u64 {
+    cmp::min(duration.as_nanos(), u128::from(u64::max_value())) as u64
+}
+
 /// Container to create.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -790,25 +2167,74 @@ where
 /// Result type for the [Create Container API](../struct.Docker.html#method.create_container)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct CreateContainerResults {
+    /// The ID of the created container.
     pub id: String,
-    pub warnings: Option<Vec<String>>,
+    /// Warnings encountered when creating the container, for example host configuration options
+    /// unsupported by the server's kernel.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub warnings: Vec<String>,
+}
+
+/// Result type for requests that move a container into a new state, such as the [Stop Container
+/// API](../struct.Docker.html#method.stop_container).
+///
+/// The docker server responds with a `304 Not Modified` if the container is already in the
+/// requested state, which is not treated as an error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ContainerStateChange {
+    /// The container transitioned into the requested state.
+    Changed,
+    /// The container was already in the requested state.
+    AlreadyInState,
+}
+
+impl ContainerStateChange {
+    /// Whether the container was already in the requested state.
+    pub fn already_in_state(&self) -> bool {
+        *self == ContainerStateChange::AlreadyInState
+    }
 }
 
 /// Parameters used in the [Stop Container API](../struct.Docker.html#method.stop_container)
 ///
 /// ## Examples
 ///
-/// use bollard::container::StopContainerOptions;
+/// ```rust
+/// use bollard::container::{Signal, StopContainerOptions};
+///
+/// use std::time::Duration;
 ///
 /// StopContainerOptions{
 ///     t: 30,
+///     signal: Some(Signal::Int),
 /// };
-#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+///
+/// StopContainerOptions::timeout(Duration::from_secs(90));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StopContainerOptions {
     /// Number of seconds to wait before killing the container
     pub t: i64,
+    /// Signal to stop the container with, instead of its `StopSignal` or `SIGTERM`. Requires API
+    /// version 1.42 or later.
+    pub signal: Option<Signal>,
+}
+
+impl StopContainerOptions {
+    /// Wait up to the given duration, rounded up to whole seconds, before killing the container.
+    pub fn timeout(timeout: Duration) -> StopContainerOptions {
+        StopContainerOptions {
+            t: timeout_secs(timeout),
+            ..Default::default()
+        }
+    }
+}
+
+/// Round a timeout up to the whole seconds of the `t` query parameter.
+fn timeout_secs(timeout: Duration) -> i64 {
+    let secs = timeout.as_secs() + if timeout.subsec_nanos() > 0 { 1 } else { 0 };
+    cmp::min(secs, i64::max_value() as u64) as i64
 }
 
 /// Trait providing implementations for [Stop Container Options](struct.StopContainerOptions.html).
@@ -817,12 +2243,33 @@ pub trait StopContainerQueryParams<K>
 where
     K: AsRef<str>,
 {
-    fn into_array(self) -> Result<ArrayVec<[(K, String); 1]>, Error>;
-}
+    fn into_array(self) -> Result<ArrayVec<[(K, String); 2]>, Error>;
 
-impl<'a> StopContainerQueryParams<&'a str> for StopContainerOptions {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 1]>, Error> {
-        Ok(ArrayVec::from([("t", self.t.to_string())]))
+    /// How long the docker server waits for the container to stop before killing it, by which
+    /// the timeout of the request is extended.
+    fn stop_timeout(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl<'a> StopContainerQueryParams<&'a str> for StopContainerOptions {
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 2]>, Error> {
+        let mut output = ArrayVec::new();
+
+        output.push(("t", self.t.to_string()));
+        if let Some(signal) = self.signal {
+            output.push(("signal", String::from(signal)));
+        }
+
+        Ok(output)
+    }
+
+    fn stop_timeout(&self) -> Option<Duration> {
+        if self.t > 0 {
+            Some(Duration::from_secs(self.t as u64))
+        } else {
+            None
+        }
     }
 }
 
@@ -833,8 +2280,11 @@ impl<'a> StopContainerQueryParams<&'a str> for StopContainerOptions {
 /// ```rust
 /// use bollard::container::StartContainerOptions;
 ///
+/// use std::default::Default;
+///
 /// StartContainerOptions{
-///     detach_keys: "ctrl-^"
+///     detach_keys: "ctrl-^",
+///     ..Default::default()
 /// };
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -842,9 +2292,16 @@ pub struct StartContainerOptions<T>
 where
     T: AsRef<str>,
 {
-    /// Override the key sequence for detaching a container. Format is a single character `[a-Z]` or
-    /// `ctrl-<value>` where `<value>` is one of: `a-z`, `@`, `^`, `[`, `,` or `_`.
+    /// Override the key sequence for detaching a container. Format is a comma separated list of
+    /// single characters `[a-Z]` or `ctrl-<value>` where `<value>` is one of: `a-z`, `@`, `^`,
+    /// `[`, `\`, `]` or `_`. An invalid sequence is rejected with an [Invalid Detach Keys
+    /// Error](../errors/enum.ErrorKind.html#variant.InvalidDetachKeysError) before the request
+    /// is sent.
     pub detach_keys: T,
+    /// The checkpoint to restore the container from. *(Experimental daemons only.)*
+    pub checkpoint: Option<T>,
+    /// A custom directory in which the checkpoint is stored. *(Experimental daemons only.)*
+    pub checkpoint_dir: Option<T>,
 }
 
 /// Trait providing implementations for [Start Container Options](struct.StartContainerOptions.html).
@@ -854,12 +2311,72 @@ where
     K: AsRef<str>,
     V: AsRef<str>,
 {
-    fn into_array(self) -> Result<ArrayVec<[(K, V); 1]>, Error>;
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 3]>, Error>;
 }
 
 impl<'a, T: AsRef<str>> StartContainerQueryParams<&'a str, T> for StartContainerOptions<T> {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, T); 1]>, Error> {
-        Ok(ArrayVec::from([("detachKeys", self.detach_keys)]))
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, T); 3]>, Error> {
+        let mut output = ArrayVec::new();
+
+        if !self.detach_keys.as_ref().is_empty() {
+            validate_detach_keys(self.detach_keys.as_ref())?;
+            output.push(("detachKeys", self.detach_keys));
+        }
+        if let Some(checkpoint) = self.checkpoint {
+            output.push(("checkpoint", checkpoint));
+        }
+        if let Some(checkpoint_dir) = self.checkpoint_dir {
+            output.push(("checkpoint-dir", checkpoint_dir));
+        }
+
+        Ok(output)
+    }
+}
+
+/// Check a detach key sequence with the same rules as the docker server.
+fn validate_detach_keys(detach_keys: &str) -> Result<(), Error> {
+    let valid = detach_keys.split(',').all(|key| {
+        if key.chars().count() == 1 {
+            return true;
+        }
+        let key = key.to_lowercase();
+        if !key.starts_with("ctrl-") {
+            return false;
+        }
+        let mut chars = key["ctrl-".len()..].chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.is_ascii_lowercase() || "@[\\]^_".contains(c),
+            _ => false,
+        }
+    });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(InvalidDetachKeysError {
+            detach_keys: detach_keys.to_owned(),
+        }
+        .into())
+    }
+}
+
+/// Unpauses a container paused by
+/// [`with_container_paused`](../struct.Docker.html#method.with_container_paused) in the
+/// background, if its future is dropped before it completes.
+struct UnpauseOnDrop {
+    docker: Option<Docker>,
+    container_name: String,
+}
+
+impl Drop for UnpauseOnDrop {
+    fn drop(&mut self) {
+        if let Some(docker) = self.docker.take() {
+            let container_name = std::mem::take(&mut self.container_name);
+            let runtime = docker.runtime.clone();
+            runtime.spawn(Box::pin(async move {
+                let _ = docker.unpause_container(&container_name).await;
+            }));
+        }
     }
 }
 
@@ -912,11 +2429,15 @@ impl<'a> RemoveContainerQueryParams<&'a str, &'a str> for RemoveContainerOptions
 /// ## Examples
 ///
 /// ```rust
-/// use bollard::container::WaitContainerOptions;
+/// use bollard::container::{WaitCondition, WaitContainerOptions};
 ///
 /// WaitContainerOptions{
 ///     condition: "not-running",
 /// };
+///
+/// WaitContainerOptions{
+///     condition: WaitCondition::Removed,
+/// };
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WaitContainerOptions<T>
@@ -924,10 +2445,39 @@ where
     T: AsRef<str>,
 {
     /// Wait until a container state reaches the given condition, either 'not-running' (default),
-    /// 'next-exit', or 'removed'.
+    /// 'next-exit', or 'removed'. See the [Wait Condition](enum.WaitCondition.html) enum.
     pub condition: T,
 }
 
+/// The state a container is waited on to reach in the [Wait Container
+/// API](../struct.Docker.html#method.wait_container).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WaitCondition {
+    /// The container is not running, returning immediately if it is already stopped.
+    NotRunning,
+    /// The container exits after the request is made, e.g. following a restart.
+    NextExit,
+    /// The container is removed, e.g. a container created with `auto_remove`.
+    Removed,
+}
+
+impl Default for WaitCondition {
+    fn default() -> WaitCondition {
+        WaitCondition::NotRunning
+    }
+}
+
+impl AsRef<str> for WaitCondition {
+    fn as_ref(&self) -> &str {
+        match self {
+            WaitCondition::NotRunning => "not-running",
+            WaitCondition::NextExit => "next-exit",
+            WaitCondition::Removed => "removed",
+        }
+    }
+}
+
 /// Trait providing implementations for [Wait Container Options](struct.WaitContainerOptions.html).
 #[allow(missing_docs)]
 pub trait WaitContainerQueryParams<K, V>
@@ -955,9 +2505,11 @@ pub struct WaitContainerResultsError {
 /// Result type for the [Wait Container API](../struct.Docker.html#method.wait_container)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct WaitContainerResults {
-    pub status_code: u64,
+    /// The exit code of the container, or `-1` if the daemon failed to determine it.
+    pub status_code: i64,
+    /// Set if the wait itself failed, e.g. as the container could not be removed.
+    #[serde(default)]
     pub error: Option<WaitContainerResultsError>,
 }
 
@@ -968,14 +2520,33 @@ pub struct WaitContainerResults {
 /// ```rust
 /// use bollard::container::RestartContainerOptions;
 ///
+/// use std::default::Default;
+/// use std::time::Duration;
+///
 /// RestartContainerOptions{
 ///     t: 30,
+///     ..Default::default()
 /// };
+///
+/// RestartContainerOptions::timeout(Duration::from_secs(90));
 /// ```
-#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RestartContainerOptions {
     /// Number of seconds to wait before killing the container.
     pub t: isize,
+    /// Signal to stop the container with, instead of its `StopSignal` or `SIGTERM`. Requires API
+    /// version 1.42 or later.
+    pub signal: Option<Signal>,
+}
+
+impl RestartContainerOptions {
+    /// Wait up to the given duration, rounded up to whole seconds, before killing the container.
+    pub fn timeout(timeout: Duration) -> RestartContainerOptions {
+        RestartContainerOptions {
+            t: cmp::min(timeout_secs(timeout), isize::max_value() as i64) as isize,
+            ..Default::default()
+        }
+    }
 }
 
 /// Trait providing implementations for [Restart Container Options](struct.RestartContainerOptions.html).
@@ -984,12 +2555,33 @@ pub trait RestartContainerQueryParams<K>
 where
     K: AsRef<str>,
 {
-    fn into_array(self) -> Result<ArrayVec<[(K, String); 1]>, Error>;
+    fn into_array(self) -> Result<ArrayVec<[(K, String); 2]>, Error>;
+
+    /// How long the docker server waits for the container to stop before killing it, by which
+    /// the timeout of the request is extended.
+    fn stop_timeout(&self) -> Option<Duration> {
+        None
+    }
 }
 
 impl<'a> RestartContainerQueryParams<&'a str> for RestartContainerOptions {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 1]>, Error> {
-        Ok(ArrayVec::from([("t", self.t.to_string())]))
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 2]>, Error> {
+        let mut output = ArrayVec::new();
+
+        output.push(("t", self.t.to_string()));
+        if let Some(signal) = self.signal {
+            output.push(("signal", String::from(signal)));
+        }
+
+        Ok(output)
+    }
+
+    fn stop_timeout(&self) -> Option<Duration> {
+        if self.t > 0 {
+            Some(Duration::from_secs(self.t as u64))
+        } else {
+            None
+        }
     }
 }
 
@@ -1068,14 +2660,148 @@ impl<'a, T: AsRef<str>> TopQueryParams<&'a str, T> for TopOptions<T> {
 }
 
 /// Result type for the [Top Processes API](../struct.Docker.html#method.top_processes)
+///
+/// A table of the output of `ps`, whose columns depend on the `ps_args` given. Use
+/// [`processes`](#method.processes) to read the columns of each process by title.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct TopResult {
+    /// The titles of the columns, e.g. `UID`, `PID` and `CMD`.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
     pub titles: Vec<String>,
+    /// A row of values for each process, in the order of the titles.
     pub processes: Option<Vec<Vec<String>>>,
 }
 
+impl TopResult {
+    /// The processes of the table, reading their columns by title.
+    pub fn processes(&self) -> impl Iterator<Item = TopProcess<'_>> {
+        let titles = &self.titles[..];
+        self.processes
+            .iter()
+            .flatten()
+            .map(move |values| TopProcess { titles, values })
+    }
+
+    /// The IDs of the processes, read from the `PID` column, in the PID namespace of the host.
+    pub fn pids(&self) -> Vec<u32> {
+        self.processes()
+            .filter_map(|process| process.pid())
+            .collect()
+    }
+}
+
+/// A process listed in a [Top Result](struct.TopResult.html).
+#[derive(Debug, Copy, Clone)]
+pub struct TopProcess<'a> {
+    titles: &'a [String],
+    values: &'a [String],
+}
+
+impl<'a> TopProcess<'a> {
+    /// The value of the column with the given title, if `ps` printed it.
+    pub fn get(&self, title: &str) -> Option<&'a str> {
+        self.titles
+            .iter()
+            .position(|t| t == title)
+            .and_then(|column| self.values.get(column))
+            .map(|value| value.as_str())
+    }
+
+    /// The ID of the process, from the `PID` column.
+    pub fn pid(&self) -> Option<u32> {
+        self.get("PID").and_then(|pid| pid.trim().parse().ok())
+    }
+
+    /// The command of the process, from the `CMD`, `COMMAND` or, on Windows, `Name` column.
+    pub fn command(&self) -> Option<&'a str> {
+        self.get("CMD")
+            .or_else(|| self.get("COMMAND"))
+            .or_else(|| self.get("Name"))
+    }
+}
+
+/// Parameters used in the [Attach Container API](../struct.Docker.html#method.attach_container)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::AttachContainerOptions;
+///
+/// use std::default::Default;
+///
+/// AttachContainerOptions::<String>{
+///     stdin: true,
+///     stdout: true,
+///     stderr: true,
+///     stream: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttachContainerOptions<T>
+where
+    T: AsRef<str>,
+{
+    /// Attach to `stdin`.
+    pub stdin: bool,
+    /// Attach to `stdout`.
+    pub stdout: bool,
+    /// Attach to `stderr`.
+    pub stderr: bool,
+    /// Stream the output of the container from the time of the request onwards.
+    pub stream: bool,
+    /// Replay the logs of the container before streaming, subject to the same `stdout` and
+    /// `stderr` selection.
+    pub logs: bool,
+    /// Override the key sequence for detaching from the container, in the format of the [Start
+    /// Container Options](struct.StartContainerOptions.html#structfield.detach_keys).
+    pub detach_keys: Option<T>,
+}
+
+/// Trait providing implementations for [Attach Container Options](struct.AttachContainerOptions.html).
+#[allow(missing_docs)]
+pub trait AttachContainerQueryParams<K>
+where
+    K: AsRef<str>,
+{
+    fn into_array(self) -> Result<ArrayVec<[(K, String); 6]>, Error>;
+}
+
+impl<'a, T: AsRef<str>> AttachContainerQueryParams<&'a str> for AttachContainerOptions<T> {
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 6]>, Error> {
+        let mut output = ArrayVec::new();
+
+        output.push(("stdin", self.stdin.to_string()));
+        output.push(("stdout", self.stdout.to_string()));
+        output.push(("stderr", self.stderr.to_string()));
+        output.push(("stream", self.stream.to_string()));
+        output.push(("logs", self.logs.to_string()));
+        if let Some(detach_keys) = self.detach_keys {
+            validate_detach_keys(detach_keys.as_ref())?;
+            output.push(("detachKeys", detach_keys.as_ref().to_owned()));
+        }
+
+        Ok(output)
+    }
+}
+
+/// Result type for the [Attach Container API](../struct.Docker.html#method.attach_container)
+pub struct AttachContainerResults {
+    /// The output of the container, as frames of `stdout` and `stderr`, or of the console if the
+    /// container has a TTY.
+    pub output: Pin<Box<dyn Stream<Item = Result<LogOutput, Error>> + Send>>,
+    /// Writes to the `stdin` of the container, if attached. Shutting it down closes the write half
+    /// of the connection, which closes `stdin` of a container created with `stdin_once`.
+    pub input: Pin<Box<dyn AsyncWrite + Send>>,
+}
+
+impl fmt::Debug for AttachContainerResults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttachContainerResults").finish()
+    }
+}
+
 /// Parameters used in the [Logs API](../struct.Docker.html#method.logs)
 ///
 /// ## Examples
@@ -1090,6 +2816,22 @@ pub struct TopResult {
 ///     ..Default::default()
 /// };
 /// ```
+///
+/// ```rust
+/// # extern crate chrono;
+/// use bollard::container::LogsOptions;
+/// use chrono::{Duration, Utc};
+///
+/// use std::default::Default;
+///
+/// # fn main() {
+/// LogsOptions{
+///     stdout: true,
+///     since: Some(Utc::now() - Duration::minutes(20)),
+///     ..Default::default()
+/// };
+/// # }
+/// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LogsOptions {
     /// Return the logs as a finite stream.
@@ -1098,10 +2840,11 @@ pub struct LogsOptions {
     pub stdout: bool,
     /// Return logs from `stderr`.
     pub stderr: bool,
-    /// Only return logs since this time, as a UNIX timestamp.
-    pub since: i64,
-    /// Only return logs before this time, as a UNIX timestamp.
-    pub until: i64,
+    /// Only return logs since this time, with sub-second precision. A `SystemTime` converts
+    /// into a time with `into()`.
+    pub since: Option<DateTime<Utc>>,
+    /// Only return logs before this time, with sub-second precision.
+    pub until: Option<DateTime<Utc>>,
     /// Add timestamps to every log line.
     pub timestamps: bool,
     /// Only return this number of log lines from the end of the logs. Specify as an integer or all
@@ -1120,55 +2863,496 @@ where
 
 impl<'a> LogsQueryParams<&'a str> for LogsOptions {
     fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 7]>, Error> {
-        Ok(ArrayVec::from([
-            ("follow", self.follow.to_string()),
-            ("stdout", self.stdout.to_string()),
-            ("stderr", self.stderr.to_string()),
-            ("since", self.since.to_string()),
-            ("until", self.until.to_string()),
-            ("timestamps", self.timestamps.to_string()),
-            ("tail", self.tail),
-        ]))
+        let mut res = ArrayVec::new();
+        res.push(("follow", self.follow.to_string()));
+        res.push(("stdout", self.stdout.to_string()));
+        res.push(("stderr", self.stderr.to_string()));
+        if let Some(since) = self.since {
+            res.push(("since", serialize_timestamp(&since)));
+        }
+        if let Some(until) = self.until {
+            res.push(("until", serialize_timestamp(&until)));
+        }
+        res.push(("timestamps", self.timestamps.to_string()));
+        res.push(("tail", self.tail));
+        Ok(res)
     }
 }
 
 /// Result type for the [Logs API](../struct.Docker.html#method.logs)
+///
+/// Each variant holds a raw frame of output, which may split a line or a multi-byte UTF-8
+/// character across frames. Use the [Log Output Stream
+/// Ext](trait.LogOutputStreamExt.html#method.lines) adapters to reassemble lines of text.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum LogOutput {
-    StdErr { message: String },
-    StdOut { message: String },
-    StdIn { message: String },
-    Console { message: String },
+    StdErr { message: Bytes },
+    StdOut { message: Bytes },
+    StdIn { message: Bytes },
+    Console { message: Bytes },
+}
+
+impl LogOutput {
+    /// The raw bytes of this frame.
+    pub fn as_bytes(&self) -> &Bytes {
+        match self {
+            LogOutput::StdErr { message } => message,
+            LogOutput::StdOut { message } => message,
+            LogOutput::StdIn { message } => message,
+            LogOutput::Console { message } => message,
+        }
+    }
+
+    /// Consume this frame, returning its raw bytes.
+    pub fn into_bytes(self) -> Bytes {
+        match self {
+            LogOutput::StdErr { message } => message,
+            LogOutput::StdOut { message } => message,
+            LogOutput::StdIn { message } => message,
+            LogOutput::Console { message } => message,
+        }
+    }
 }
 
 impl fmt::Display for LogOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = self.as_bytes();
+        let message = if message.ends_with(b"\n") {
+            &message[..message.len() - 1]
+        } else {
+            &message[..]
+        };
+        write!(f, "{}", String::from_utf8_lossy(message))
+    }
+}
+
+/// A line of text reassembled by the [Lines](trait.LogOutputStreamExt.html#method.lines)
+/// adapter, preserving the source of the frames it was read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum LogLine {
+    StdErr { line: String },
+    StdOut { line: String },
+    StdIn { line: String },
+    Console { line: String },
+}
+
+impl fmt::Display for LogLine {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
-            LogOutput::StdErr { message } => write!(f, "{}", message),
-            LogOutput::StdOut { message } => write!(f, "{}", message),
-            LogOutput::StdIn { message } => write!(f, "{}", message),
-            LogOutput::Console { message } => write!(f, "{}", message),
+            LogLine::StdErr { line } => write!(f, "{}", line),
+            LogLine::StdOut { line } => write!(f, "{}", line),
+            LogLine::StdIn { line } => write!(f, "{}", line),
+            LogLine::Console { line } => write!(f, "{}", line),
+        }
+    }
+}
+
+/// Adapters over a stream of [Log Output](enum.LogOutput.html) frames, as returned by the [Logs
+/// API](../struct.Docker.html#method.logs), or of [Start Exec
+/// Results](../exec/enum.StartExecResults.html), as returned by the [Start Exec
+/// API](../struct.Docker.html#method.start_exec).
+pub trait LogOutputStreamExt<T>: Stream<Item = Result<T, Error>> + Sized
+where
+    T: Into<Option<LogOutput>>,
+{
+    /// Reassemble frames into lines of text, buffering partial lines across frames separately for
+    /// each source. Lines are converted lossily from UTF-8 and stripped of their line ending. A
+    /// trailing unterminated line is emitted when the stream ends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::{LogOutputStreamExt, LogsOptions};
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// let options = Some(LogsOptions {
+    ///     stdout: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// async move {
+    ///     let lines = docker
+    ///         .logs("hello-world", options)
+    ///         .lines()
+    ///         .try_collect::<Vec<_>>()
+    ///         .await;
+    /// };
+    /// ```
+    fn lines(self) -> LogOutputLines<Self> {
+        LogOutputLines {
+            stream: self,
+            buffers: Default::default(),
+            lines: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Concatenate the raw bytes of all frames, regardless of their source. A
+    /// [LogOutputLimitError](../errors/enum.ErrorKind.html#variant.LogOutputLimitError) is
+    /// returned if the output exceeds `limit` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::{LogOutputStreamExt, LogsOptions};
+    ///
+    /// let options = Some(LogsOptions {
+    ///     stdout: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// async move {
+    ///     let output = docker
+    ///         .logs("hello-world", options)
+    ///         .bytes_concatenated(1024 * 1024)
+    ///         .await;
+    /// };
+    /// ```
+    fn bytes_concatenated(self, limit: usize) -> BytesConcatenated<Self> {
+        BytesConcatenated {
+            stream: self,
+            buffer: BytesMut::new(),
+            limit,
+        }
+    }
+}
+
+impl<S, T> LogOutputStreamExt<T> for S
+where
+    S: Stream<Item = Result<T, Error>>,
+    T: Into<Option<LogOutput>>,
+{
+}
+
+/// Stream returned by the [Lines](trait.LogOutputStreamExt.html#method.lines) adapter.
+#[pin_project]
+#[derive(Debug)]
+pub struct LogOutputLines<S> {
+    #[pin]
+    stream: S,
+    buffers: [BytesMut; 4],
+    lines: VecDeque<LogLine>,
+    done: bool,
+}
+
+impl<S> LogOutputLines<S> {
+    fn buffer_index(output: &LogOutput) -> usize {
+        match output {
+            LogOutput::StdErr { .. } => 0,
+            LogOutput::StdOut { .. } => 1,
+            LogOutput::StdIn { .. } => 2,
+            LogOutput::Console { .. } => 3,
+        }
+    }
+
+    fn log_line(index: usize, bytes: &[u8]) -> LogLine {
+        let bytes = if bytes.ends_with(b"\r") {
+            &bytes[..bytes.len() - 1]
+        } else {
+            bytes
+        };
+        let line = String::from_utf8_lossy(bytes).to_string();
+        match index {
+            0 => LogLine::StdErr { line },
+            1 => LogLine::StdOut { line },
+            2 => LogLine::StdIn { line },
+            _ => LogLine::Console { line },
+        }
+    }
+}
+
+impl<S, T> Stream for LogOutputLines<S>
+where
+    S: Stream<Item = Result<T, Error>>,
+    T: Into<Option<LogOutput>>,
+{
+    type Item = Result<LogLine, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(line) = this.lines.pop_front() {
+                return Poll::Ready(Some(Ok(line)));
+            }
+            if *this.done {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    if let Some(output) = item.into() {
+                        let index = LogOutputLines::<S>::buffer_index(&output);
+                        let buffer = &mut this.buffers[index];
+
+                        // Only scan the newly received bytes for a line ending.
+                        let mut from = buffer.len();
+                        buffer.extend_from_slice(output.as_bytes());
+                        while let Some(pos) = buffer[from..].iter().position(|b| *b == b'\n') {
+                            let line = buffer.split_to(from + pos + 1);
+                            this.lines.push_back(LogOutputLines::<S>::log_line(
+                                index,
+                                &line[..line.len() - 1],
+                            ));
+                            from = 0;
+                        }
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    for (index, buffer) in this.buffers.iter_mut().enumerate() {
+                        if !buffer.is_empty() {
+                            let line = buffer.split();
+                            this.lines
+                                .push_back(LogOutputLines::<S>::log_line(index, &line));
+                        }
+                    }
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Future returned by the [Bytes
+/// Concatenated](trait.LogOutputStreamExt.html#method.bytes_concatenated) adapter.
+#[pin_project]
+#[derive(Debug)]
+pub struct BytesConcatenated<S> {
+    #[pin]
+    stream: S,
+    buffer: BytesMut,
+    limit: usize,
+}
+
+impl<S, T> Future for BytesConcatenated<S>
+where
+    S: Stream<Item = Result<T, Error>>,
+    T: Into<Option<LogOutput>>,
+{
+    type Output = Result<Bytes, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    if let Some(output) = item.into() {
+                        let bytes = output.into_bytes();
+                        if this.buffer.len() + bytes.len() > *this.limit {
+                            return Poll::Ready(Err(
+                                LogOutputLimitError { limit: *this.limit }.into()
+                            ));
+                        }
+                        this.buffer.extend_from_slice(&bytes);
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(this.buffer.split().freeze())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Item of the stream returned by the [Logs Reconnecting
+/// API](../struct.Docker.html#method.logs_reconnecting).
+#[derive(Debug, Clone)]
+pub enum ReconnectingLogOutput {
+    /// A frame of output, as returned by the [Logs API](../struct.Docker.html#method.logs).
+    Output(LogOutput),
+    /// The log stream was interrupted, and subscribed again with the logs since the last
+    /// timestamp received. Output written in the meantime follows this marker.
+    Reconnected {
+        /// The time the logs are subscribed again from, if any.
+        since: Option<DateTime<Utc>>,
+    },
+}
+
+impl From<ReconnectingLogOutput> for Option<LogOutput> {
+    fn from(output: ReconnectingLogOutput) -> Option<LogOutput> {
+        match output {
+            ReconnectingLogOutput::Output(log) => Some(log),
+            ReconnectingLogOutput::Reconnected { .. } => None,
+        }
+    }
+}
+
+/// Interval between two attempts to reach the docker server, while reconnecting a log stream.
+const LOGS_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of the stream returned by the [Logs Reconnecting
+/// API](../struct.Docker.html#method.logs_reconnecting).
+struct ReconnectingLogs {
+    docker: Docker,
+    container_name: String,
+    options: LogsOptions,
+    // whether the caller asked for timestamps, which are always requested to resume the stream
+    timestamps: bool,
+    stream: Pin<Box<dyn Stream<Item = Result<LogOutput, Error>> + Send>>,
+    done: bool,
+}
+
+impl ReconnectingLogs {
+    fn subscribe(&mut self) {
+        let options = LogsOptions {
+            follow: true,
+            timestamps: true,
+            ..self.options.clone()
+        };
+        self.stream = Box::pin(self.docker.logs(&self.container_name, Some(options)));
+    }
+
+    /// Record the timestamp of a frame, stripping it unless the caller asked for timestamps.
+    fn observe(&mut self, output: LogOutput) -> LogOutput {
+        let bytes = output.as_bytes();
+        let pos = match bytes.iter().position(|b| *b == b' ') {
+            Some(pos) => pos,
+            None => return output,
+        };
+        let timestamp = match std::str::from_utf8(&bytes[..pos])
+            .ok()
+            .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        {
+            Some(timestamp) => timestamp.with_timezone(&Utc),
+            None => return output,
+        };
+
+        // the docker server includes the logs written at the time given by `since`
+        self.options.since = Some(timestamp + chrono::Duration::nanoseconds(1));
+        // a `tail` only applies to the logs written before the first subscription
+        self.options.tail = String::from("all");
+
+        if self.timestamps {
+            return output;
+        }
+        let message = bytes.slice(pos + 1..);
+        match output {
+            LogOutput::StdErr { .. } => LogOutput::StdErr { message },
+            LogOutput::StdOut { .. } => LogOutput::StdOut { message },
+            LogOutput::StdIn { .. } => LogOutput::StdIn { message },
+            LogOutput::Console { .. } => LogOutput::Console { message },
+        }
+    }
+
+    /// Wait until the docker server is reachable, returning whether the container is running.
+    async fn running(&self) -> Result<bool, Error> {
+        loop {
+            self.docker.runtime.delay(LOGS_RECONNECT_INTERVAL).await;
+            match self
+                .docker
+                .inspect_container_state(&self.container_name)
+                .await
+            {
+                Ok(state) => return Ok(state.running),
+                Err(e) if is_transient(&e) => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn next(mut self) -> Option<(Result<ReconnectingLogOutput, Error>, ReconnectingLogs)> {
+        if self.done {
+            return None;
+        }
+
+        let running = match self.stream.next().await {
+            Some(Ok(output)) => {
+                let output = self.observe(output);
+                return Some((Ok(ReconnectingLogOutput::Output(output)), self));
+            }
+            // the connection dropped: subscribe again to catch up, even if the container stopped
+            Some(Err(ref e)) if is_transient(e) || is_stream_idle(e) => {
+                self.running().await.map(|_| true)
+            }
+            Some(Err(e)) => Err(e),
+            // the stream ended: the container stopped, or the docker server shut down
+            None => self.running().await,
+        };
+
+        match running {
+            Ok(true) => {
+                self.subscribe();
+                let since = self.options.since;
+                Some((Ok(ReconnectingLogOutput::Reconnected { since }), self))
+            }
+            Ok(false) => None,
+            Err(e) => {
+                self.done = true;
+                Some((Err(e), self))
+            }
         }
     }
 }
 
 /// Result type for the [Container Changes API](../struct.Docker.html#method.container_changes)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct Change {
+    /// The path in the filesystem of the container.
     pub path: String,
-    pub kind: isize,
+    /// How the path changed, relative to the image of the container.
+    pub kind: ChangeKind,
 }
 
 impl fmt::Display for Change {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.kind {
-            0 => write!(f, "C {}", self.path),
-            1 => write!(f, "A {}", self.path),
-            2 => write!(f, "D {}", self.path),
-            _ => unreachable!(),
+        write!(f, "{} {}", self.kind, self.path)
+    }
+}
+
+/// The kind of a [Change](struct.Change.html) to the filesystem of a container, serialized as the
+/// integer used by the docker server.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum ChangeKind {
+    /// The path was modified, `0`.
+    Modified,
+    /// The path was added, `1`.
+    Added,
+    /// The path was deleted, `2`.
+    Deleted,
+    /// A kind unknown to this library.
+    Other(u8),
+}
+
+impl From<u8> for ChangeKind {
+    fn from(kind: u8) -> ChangeKind {
+        match kind {
+            0 => ChangeKind::Modified,
+            1 => ChangeKind::Added,
+            2 => ChangeKind::Deleted,
+            kind => ChangeKind::Other(kind),
+        }
+    }
+}
+
+impl From<ChangeKind> for u8 {
+    fn from(kind: ChangeKind) -> u8 {
+        match kind {
+            ChangeKind::Modified => 0,
+            ChangeKind::Added => 1,
+            ChangeKind::Deleted => 2,
+            ChangeKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    /// The letter printed by `docker diff`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::Modified => write!(f, "C"),
+            ChangeKind::Added => write!(f, "A"),
+            ChangeKind::Deleted => write!(f, "D"),
+            ChangeKind::Other(kind) => write!(f, "{}", kind),
         }
     }
 }
@@ -1182,12 +3366,16 @@ impl fmt::Display for Change {
 ///
 /// StatsOptions{
 ///     stream: false,
+///     one_shot: true,
 /// };
 /// ```
 #[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 pub struct StatsOptions {
     /// Stream the output. If false, the stats will be output once and then it will disconnect.
     pub stream: bool,
+    /// Only get a single stat instead of waiting for 2 cycles, in which case the `precpu_stats` are
+    /// empty. Only sent with `stream: false`. Requires API version 1.41 or later.
+    pub one_shot: bool,
 }
 
 /// Trait providing implementations for [Stats Options](struct.StatsOptions.html).
@@ -1197,20 +3385,24 @@ where
     K: AsRef<str>,
     V: AsRef<str>,
 {
-    fn into_array(self) -> Result<ArrayVec<[(K, V); 1]>, Error>;
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 2]>, Error>;
 }
 
 impl<'a> StatsQueryParams<&'a str, &'a str> for StatsOptions {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, &'a str); 1]>, Error> {
-        Ok(ArrayVec::from([(
-            "stream",
-            if self.stream { TRUE_STR } else { FALSE_STR },
-        )]))
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, &'a str); 2]>, Error> {
+        let mut output = ArrayVec::new();
+        output.push(("stream", if self.stream { TRUE_STR } else { FALSE_STR }));
+        if self.one_shot && !self.stream {
+            output.push(("one-shot", TRUE_STR));
+        }
+        Ok(output)
     }
 }
 
-/// Granular memory statistics for the container.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+/// Granular memory statistics for the container. Hosts using cgroup v2 report a different set of
+/// statistics, so those that are absent default to zero.
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[allow(missing_docs)]
 pub struct MemoryStatsStats {
     pub cache: u64,
@@ -1248,7 +3440,7 @@ pub struct MemoryStatsStats {
 }
 
 /// General memory statistics for the container.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct MemoryStats {
     pub stats: Option<MemoryStatsStats>,
@@ -1264,15 +3456,16 @@ pub struct MemoryStats {
 }
 
 /// Process ID statistics for the container.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct PidsStats {
     pub current: Option<u64>,
     pub limit: Option<u64>,
 }
 
-/// I/O statistics for the container.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// I/O statistics for the container. On hosts using cgroup v2 only `io_service_bytes_recursive`
+/// is reported, the other entries are `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct BlkioStats {
     pub io_service_bytes_recursive: Option<Vec<BlkioStatsEntry>>,
@@ -1285,8 +3478,30 @@ pub struct BlkioStats {
     pub sectors_recursive: Option<Vec<BlkioStatsEntry>>,
 }
 
-/// File I/O statistics for the container.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+impl BlkioStats {
+    /// The total number of bytes read by the container across all devices.
+    pub fn read_bytes_total(&self) -> u64 {
+        self.io_service_bytes_total("read")
+    }
+
+    /// The total number of bytes written by the container across all devices.
+    pub fn write_bytes_total(&self) -> u64 {
+        self.io_service_bytes_total("write")
+    }
+
+    // the op is capitalized on cgroup v1 hosts, e.g. `Read`, and lowercase on cgroup v2 hosts
+    fn io_service_bytes_total(&self, op: &str) -> u64 {
+        self.io_service_bytes_recursive
+            .iter()
+            .flatten()
+            .filter(|entry| entry.op.eq_ignore_ascii_case(op))
+            .map(|entry| entry.value)
+            .sum()
+    }
+}
+
+/// File I/O statistics for the container (Windows only).
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct StorageStats {
     pub read_count_normalized: Option<u64>,
@@ -1296,26 +3511,123 @@ pub struct StorageStats {
 }
 
 /// Statistics for the container.
+///
+/// Windows daemons return a different payload shape: the `memory_stats` carry commit and private
+/// working set bytes instead of usage and limit, `storage_stats` and `num_procs` are populated,
+/// and the `preread` timestamp on the first sample is the zero time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct Stats {
     pub read: DateTime<Utc>,
     pub preread: DateTime<Utc>,
+    /// The number of processors available to the container (Windows only).
+    #[serde(default)]
     pub num_procs: u32,
+    #[serde(default)]
     pub pids_stats: PidsStats,
     pub network: Option<NetworkStats>,
     pub networks: Option<HashMap<String, NetworkStats>>,
+    #[serde(default)]
     pub memory_stats: MemoryStats,
+    #[serde(default)]
     pub blkio_stats: BlkioStats,
+    #[serde(default)]
     pub cpu_stats: CPUStats,
+    /// The CPU statistics of the previous sample, which are zero or absent on the first sample.
+    #[serde(default)]
     pub precpu_stats: CPUStats,
+    #[serde(default)]
     pub storage_stats: StorageStats,
     pub name: String,
     pub id: String,
 }
 
-/// Network statistics for the container.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+impl Stats {
+    /// The CPU usage of the container as a percentage, computed from the difference between
+    /// `cpu_stats` and `precpu_stats`.
+    ///
+    /// On Linux this is relative to the host's system CPU usage multiplied by the number of online
+    /// CPUs. On Windows, which does not report a system CPU usage, this is relative to the time
+    /// elapsed between the `preread` and `read` timestamps across `num_procs` processors.
+    ///
+    /// Returns `None` if the inputs needed for the computation are absent, for example on the first
+    /// sample of a stream.
+    pub fn cpu_percentage(&self) -> Option<f64> {
+        let cpu_delta = self
+            .cpu_stats
+            .cpu_usage
+            .total_usage
+            .checked_sub(self.precpu_stats.cpu_usage.total_usage)?;
+
+        match (
+            self.cpu_stats.system_cpu_usage,
+            self.precpu_stats.system_cpu_usage,
+        ) {
+            (Some(system), Some(presystem)) => {
+                let system_delta = system.checked_sub(presystem)?;
+                let online_cpus = self.cpu_stats.online_cpus.or_else(|| {
+                    self.cpu_stats
+                        .cpu_usage
+                        .percpu_usage
+                        .as_ref()
+                        .map(|v| v.len() as u64)
+                })?;
+                if system_delta == 0 || online_cpus == 0 {
+                    return None;
+                }
+                Some(cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0)
+            }
+            _ => {
+                if self.num_procs == 0 || self.preread.timestamp() <= 0 {
+                    return None;
+                }
+                // Windows reports CPU usage in 100ns intervals
+                let elapsed = self
+                    .read
+                    .signed_duration_since(self.preread)
+                    .num_nanoseconds()?;
+                let possible_intervals = (elapsed / 100) as f64 * f64::from(self.num_procs);
+                if possible_intervals <= 0.0 {
+                    return None;
+                }
+                Some(cpu_delta as f64 / possible_intervals * 100.0)
+            }
+        }
+    }
+
+    /// The memory usage of the container in bytes.
+    ///
+    /// On Linux this is the `usage`, excluding the page cache when it is reported. On Windows this
+    /// is the `privateworkingset`. Returns `None` if neither is present.
+    pub fn memory_usage(&self) -> Option<u64> {
+        match self.memory_stats.usage {
+            Some(usage) => {
+                let cache = self.memory_stats.stats.map(|s| s.cache).unwrap_or(0);
+                Some(usage.saturating_sub(cache))
+            }
+            None => self.memory_stats.privateworkingset,
+        }
+    }
+
+    /// The memory usage of the container as a percentage of its limit.
+    ///
+    /// Returns `None` if the usage or limit is absent, which is always the case on Windows.
+    pub fn memory_percentage(&self) -> Option<f64> {
+        let limit = self.memory_stats.limit?;
+        if limit == 0 {
+            return None;
+        }
+        self.memory_stats
+            .usage
+            .and_then(|_| self.memory_usage())
+            .map(|usage| usage as f64 / limit as f64 * 100.0)
+    }
+}
+
+/// Network statistics for the container. Counters that a daemon does not report, such as the
+/// errors of some Windows network endpoints, are zero.
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[allow(missing_docs)]
 pub struct NetworkStats {
     pub rx_dropped: u64,
@@ -1329,7 +3641,8 @@ pub struct NetworkStats {
 }
 
 /// CPU usage statistics for the container.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[allow(missing_docs)]
 pub struct CPUUsage {
     pub percpu_usage: Option<Vec<u64>>,
@@ -1339,7 +3652,7 @@ pub struct CPUUsage {
 }
 
 /// CPU throttling statistics.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct ThrottlingData {
     pub periods: u64,
@@ -1348,12 +3661,14 @@ pub struct ThrottlingData {
 }
 
 /// General CPU statistics for the container.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct CPUStats {
+    #[serde(default)]
     pub cpu_usage: CPUUsage,
     pub system_cpu_usage: Option<u64>,
     pub online_cpus: Option<u64>,
+    #[serde(default)]
     pub throttling_data: ThrottlingData,
 }
 
@@ -1371,7 +3686,11 @@ pub struct BlkioStatsEntry {
 /// ## Examples
 ///
 /// ```rust
-/// use bollard::container::KillContainerOptions;
+/// use bollard::container::{KillContainerOptions, Signal};
+///
+/// KillContainerOptions{
+///     signal: Signal::Hup,
+/// };
 ///
 /// KillContainerOptions{
 ///     signal: "SIGINT",
@@ -1382,7 +3701,8 @@ pub struct KillContainerOptions<T>
 where
     T: AsRef<str>,
 {
-    /// Signal to send to the container as an integer or string (e.g. `SIGINT`)
+    /// Signal to send to the container, as a [Signal](enum.Signal.html), or as an integer or
+    /// string (e.g. `SIGINT`)
     pub signal: T,
 }
 
@@ -1430,30 +3750,19 @@ pub struct UpdateContainerOptionsDevices {
     pub cgroup_permissions: String,
 }
 
-/// A list of resource limits to set in the container.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct UpdateContainerOptionsUlimits {
-    pub name: String,
-    pub soft: isize,
-    pub hard: isize,
-}
+/// Former name of the [`Ulimit`](struct.Ulimit.html) of the [Update Container
+/// API](../struct.Docker.html#method.update_container).
+pub type UpdateContainerOptionsUlimits = Ulimit;
 
-/// The behavior to apply when the container exits. The default is not to restart.
-///
-/// An ever increasing delay (double the previous delay, starting at 100ms) is added before each
-/// restart to prevent flooding the server.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
-pub struct UpdateContainerOptionsRestartPolicy {
-    pub name: String,
-    pub maximum_retry_count: isize,
-}
+/// Former name of the [`RestartPolicy`](enum.RestartPolicy.html) of the [Update Container
+/// API](../struct.Docker.html#method.update_container).
+pub type UpdateContainerOptionsRestartPolicy = RestartPolicy;
 
 /// Configuration for the [Update Container API](../struct.Docker.html#method.update_container)
 ///
+/// Only the fields that are set are sent, leaving the other resources of the container as they
+/// are.
+///
 /// ## Examples
 ///
 /// ```rust
@@ -1470,88 +3779,129 @@ pub struct UpdateContainerOptionsRestartPolicy {
 #[serde(rename_all = "PascalCase")]
 pub struct UpdateContainerOptions {
     /// An integer value representing this container's relative CPU weight versus other containers.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_shares: Option<isize>,
     /// Memory limit in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub memory: Option<u64>,
     /// Path to `cgroups` under which the container's `cgroup` is created. If the path is not absolute,
     /// the path is considered to be relative to the `cgroups` path of the init process. Cgroups are
     /// created if they do not already exist.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cgroup_parent: Option<String>,
     /// Block IO weight (relative weight).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub blkio_weight: Option<isize>,
     /// Block IO weight (relative device weight).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub blkio_weight_device: Vec<UpdateContainerOptionsBlkioWeight>,
     /// Limit read rate (bytes per second) from a device.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub blkio_device_read_bps: Vec<UpdateContainerOptionsBlkioDeviceRate>,
     /// Limit write rate (bytes per second) to a device.
-    #[serde(rename = "BlkioDeviceWriteIOps")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blkio_device_write_bps: Vec<UpdateContainerOptionsBlkioDeviceRate>,
+    /// Limit write rate (IO per second) to a device.
+    #[serde(rename = "BlkioDeviceWriteIOps", skip_serializing_if = "Vec::is_empty")]
     pub blkio_device_write_iops: Vec<UpdateContainerOptionsBlkioDeviceRate>,
     /// Limit read rate (IO per second) from a device.
-    #[serde(rename = "BlkioDeviceReadIOps")]
+    #[serde(rename = "BlkioDeviceReadIOps", skip_serializing_if = "Vec::is_empty")]
     pub blkio_device_read_iops: Vec<UpdateContainerOptionsBlkioDeviceRate>,
     /// The length of a CPU period in microseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_period: Option<u64>,
-    /// Microseconds of CPU time that the container can get in a CPU period.
-    pub cpu_quota: Option<u64>,
+    /// Microseconds of CPU time that the container can get in a CPU period. Set as `-1` to remove
+    /// the quota.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_quota: Option<i64>,
     /// The length of a CPU real-time period in microseconds. Set to 0 to allocate no time allocated to real-time tasks.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_realtime_period: Option<u64>,
     /// The length of a CPU real-time runtime in microseconds. Set to 0 to allocate no time allocated to real-time tasks.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_realtime_runtime: Option<u64>,
     /// CPUs in which to allow execution (e.g., `0-3`, `0,1`)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpuset_cpus: Option<String>,
     /// Memory nodes (MEMs) in which to allow execution (0-3, 0,1). Only effective on NUMA systems.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpuset_mems: Option<String>,
     /// A list of devices to add to the container.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub devices: Option<Vec<UpdateContainerOptionsDevices>>,
     /// A list of cgroup rules to apply to the container.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub device_cgroup_rules: Option<Vec<String>>,
     /// Disk limit (in bytes).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub disk_quota: Option<u64>,
     /// A list of requests for devices to be sent to device drivers
-    pub device_requests: Option<DeviceRequest<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_requests: Option<Vec<DeviceRequest<String>>>,
     /// Kernel memory limit in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kernel_memory: Option<u64>,
     /// Hard limit for kernel TCP buffer memory (in bytes).
-    #[serde(rename = "KernelMemoryTCP")]
+    #[serde(rename = "KernelMemoryTCP", skip_serializing_if = "Option::is_none")]
     pub kernel_memory_tcp: Option<i64>,
     /// Memory soft limit in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub memory_reservation: Option<u64>,
     /// Total memory limit (memory + swap). Set as `-1` to enable unlimited swap.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub memory_swap: Option<i64>,
     /// Tune a container's memory swappiness behavior. Accepts an integer between 0 and 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub memory_swappiness: Option<i64>,
     /// CPU quota in units of 10<sup>-9</sup> CPUs.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nano_cpus: Option<u64>,
     /// Disable OOM Killer for the container.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub oom_kill_disable: Option<bool>,
-    /// Run an init inside the container that forwards signals and reaps processes. This field is
-    /// omitted if empty, and the default (as configured on the daemon) is used.
+    /// Run an init inside the container that forwards signals and reaps processes. Only set when
+    /// the container is created, the docker server ignores it in an update.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub init: Option<bool>,
-    /// Tune a container's PIDs limit. Set `0` or `-1` for unlimited, or `null` to not change.
-    pub pids_limit: Option<u64>,
+    /// Tune a container's PIDs limit. Set `0` or `-1` for unlimited, or leave unset to not change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pids_limit: Option<i64>,
     /// A list of resource limits to set in the container.
-    pub ulimits: Vec<UpdateContainerOptionsUlimits>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ulimits: Vec<Ulimit>,
     /// The number of usable CPUs (Windows only).
     ///
     /// On Windows Server containers, the processor resource controls are mutually exclusive. The
     /// order of precedence is `CPUCount` first, then `CPUShares`, and `CPUPercent` last.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_count: Option<u64>,
     /// The usable percentage of the available CPUs (Windows only).
     ///
     /// On Windows Server containers, the processor resource controls are mutually exclusive. The
     /// order of precedence is `CPUCount` first, then `CPUShares`, and `CPUPercent` last.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cpu_percent: Option<u64>,
     /// Maximum IOps for the container system drive (Windows only).
-    #[serde(rename = "IOMaximumIOps")]
+    #[serde(rename = "IOMaximumIOps", skip_serializing_if = "Option::is_none")]
     pub io_maximum_iops: Option<u64>,
     /// Maximum IO in bytes per second for the container system drive (Windows only).
-    #[serde(rename = "IOMaximumBandwidth")]
+    #[serde(rename = "IOMaximumBandwidth", skip_serializing_if = "Option::is_none")]
     pub io_maximum_bandwidth: Option<u64>,
     /// The behavior to apply when the container exits. The default is not to restart.
     ///
     /// An ever increasing delay (double the previous delay, starting at 100ms) is added before
     /// each restart to prevent flooding the server.
-    pub restart_policy: Option<UpdateContainerOptionsRestartPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+}
+
+/// Result type for the [Update Container API](../struct.Docker.html#method.update_container)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UpdateContainerResults {
+    /// Warnings encountered when updating the container's resources.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub warnings: Vec<String>,
 }
 
 /// Parameters used in the [Rename Container API](../struct.Docker.html#method.rename_container)
@@ -1570,7 +3920,10 @@ pub struct RenameContainerOptions<T>
 where
     T: AsRef<str>,
 {
-    /// New name for the container.
+    /// New name for the container. A name not matching `[a-zA-Z0-9][a-zA-Z0-9_.-]*`, optionally
+    /// with a leading slash, is rejected with an [Invalid Container Name
+    /// Error](../errors/enum.ErrorKind.html#variant.InvalidContainerNameError) before the request
+    /// is sent.
     pub name: T,
 }
 
@@ -1586,10 +3939,51 @@ where
 
 impl<'a, T: AsRef<str>> RenameContainerQueryParams<&'a str, T> for RenameContainerOptions<T> {
     fn into_array(self) -> Result<ArrayVec<[(&'a str, T); 1]>, Error> {
+        validate_container_name(self.name.as_ref())?;
         Ok(ArrayVec::from([("name", self.name)]))
     }
 }
 
+/// Parameters used in the [Resize Container Tty
+/// API](../struct.Docker.html#method.resize_container_tty)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::ResizeContainerTtyOptions;
+///
+/// ResizeContainerTtyOptions {
+///     width: 80,
+///     height: 24,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResizeContainerTtyOptions {
+    /// Width of the TTY, in columns.
+    pub width: u16,
+    /// Height of the TTY, in rows.
+    pub height: u16,
+}
+
+/// Trait providing implementations for [Resize Container Tty
+/// Options](struct.ResizeContainerTtyOptions.html).
+#[allow(missing_docs)]
+pub trait ResizeContainerTtyQueryParams<K>
+where
+    K: AsRef<str>,
+{
+    fn into_array(self) -> Result<ArrayVec<[(K, String); 2]>, Error>;
+}
+
+impl<'a> ResizeContainerTtyQueryParams<&'a str> for ResizeContainerTtyOptions {
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 2]>, Error> {
+        Ok(ArrayVec::from([
+            ("h", self.height.to_string()),
+            ("w", self.width.to_string()),
+        ]))
+    }
+}
+
 /// Parameters used in the [Prune Containers API](../struct.Docker.html#method.prune_containers)
 ///
 /// ## Examples
@@ -1619,6 +4013,51 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// A filter of the containers to prune, added to the [Prune Containers
+/// Options](struct.PruneContainersOptions.html) with
+/// [`PruneContainersOptions::filter`](struct.PruneContainersOptions.html#method.filter).
+#[derive(Debug, Clone, PartialEq)]
+pub enum PruneContainersFilter<T> {
+    /// Matches containers created before a timestamp, either a Unix timestamp, a date formatted
+    /// timestamp, or a Go duration string (e.g. `10m`, `1h30m`) relative to the time of the docker
+    /// server.
+    Until(T),
+    /// Matches containers with a label, in the form `key` or `key=value`.
+    Label(T),
+    /// Matches containers without a label, in the form `key` or `key=value`.
+    LabelNot(T),
+}
+
+impl<T> PruneContainersOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    /// Only prune the stopped containers matching a filter. Containers must match all the filters
+    /// added.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::container::{PruneContainersFilter, PruneContainersOptions};
+    ///
+    /// let options = PruneContainersOptions::default()
+    ///     .filter(PruneContainersFilter::Until("24h"))
+    ///     .filter(PruneContainersFilter::Label("janitor=allowed"))
+    ///     .filter(PruneContainersFilter::LabelNot("keep"));
+    ///
+    /// assert_eq!(options.filters["label!"], vec!["keep"]);
+    /// ```
+    pub fn filter(mut self, filter: PruneContainersFilter<T>) -> Self {
+        let (key, value) = match filter {
+            PruneContainersFilter::Until(until) => ("until", until),
+            PruneContainersFilter::Label(label) => ("label", label),
+            PruneContainersFilter::LabelNot(label) => ("label!", label),
+        };
+        self.filters.entry(T::from(key)).or_default().push(value);
+        self
+    }
+}
+
 /// Trait providing implementations for [Prune Containers Options](struct.PruneContainersOptions.html).
 #[allow(missing_docs)]
 pub trait PruneContainersQueryParams<K>
@@ -1643,9 +4082,11 @@ impl<'a, T: AsRef<str> + Eq + Hash + Serialize> PruneContainersQueryParams<&'a s
 /// Result type for the [Prune Containers API](../struct.Docker.html#method.prune_containers)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct PruneContainersResults {
+    /// The IDs of the deleted containers, `None` if no container was deleted.
     pub containers_deleted: Option<Vec<String>>,
+    /// Disk space reclaimed, in bytes.
+    #[serde(default)]
     pub space_reclaimed: u64,
 }
 
@@ -1733,6 +4174,266 @@ impl<'a, T: AsRef<str>> DownloadFromContainerQueryParams<&'a str, T>
         Ok(ArrayVec::from([("path", self.path)]))
     }
 }
+
+/// Header of the [Stat Container Path API](../struct.Docker.html#method.stat_container_path)
+/// response, carrying the base64 encoded stat of the path.
+const PATH_STAT_HEADER: &str = "X-Docker-Container-Path-Stat";
+
+/// Mode bit of a [Container Path Stat](struct.ContainerPathStat.html) for a directory.
+const MODE_DIR: u32 = 1 << 31;
+
+/// Mode bit of a [Container Path Stat](struct.ContainerPathStat.html) for a symbolic link.
+const MODE_SYMLINK: u32 = 1 << 27;
+
+/// Result type for the [Stat Container Path
+/// API](../struct.Docker.html#method.stat_container_path)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPathStat {
+    /// Base name of the path.
+    pub name: String,
+    /// Size of the file, in bytes.
+    pub size: i64,
+    /// File mode, with the type bits of a Go `os.FileMode` above the permission bits.
+    pub mode: u32,
+    /// Time of the last modification.
+    pub mtime: DateTime<Utc>,
+    /// Target of the path if it is a symbolic link, empty otherwise.
+    #[serde(default)]
+    pub link_target: String,
+}
+
+impl ContainerPathStat {
+    /// Whether the path is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.mode & MODE_DIR != 0
+    }
+
+    /// Whether the path is a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.mode & MODE_SYMLINK != 0
+    }
+
+    /// The unix permission bits of the path.
+    pub fn permissions(&self) -> u32 {
+        self.mode & 0o777
+    }
+}
+
+/// Action of an event returned by the [Container Events
+/// API](../struct.Docker.html#method.container_events)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerEventAction {
+    /// The container was created.
+    Create,
+    /// The container started.
+    Start,
+    /// The container was restarted.
+    Restart,
+    /// The container was sent a signal.
+    Kill {
+        /// The signal sent, as found in the `signal` attribute.
+        signal: Option<String>,
+    },
+    /// The container ran out of memory.
+    Oom,
+    /// The container exited.
+    Die {
+        /// The exit code of the container, as found in the `exitCode` attribute.
+        exit_code: Option<i32>,
+    },
+    /// The container was stopped.
+    Stop,
+    /// The container was removed.
+    Destroy,
+    /// The health status of the container changed.
+    HealthStatus(HealthStatus),
+    /// Any other action, e.g. `exec_start: sh`.
+    Other(String),
+}
+
+/// Event returned by the [Container Events API](../struct.Docker.html#method.container_events)
+#[derive(Debug, Clone)]
+pub struct ContainerEvent {
+    /// The ID of the container.
+    pub id: String,
+    /// The name of the container at the time of the event.
+    pub name: Option<String>,
+    /// The typed action of the event.
+    pub action: ContainerEventAction,
+    /// The attributes of the event, e.g. the image and labels of the container.
+    pub attributes: HashMap<String, String>,
+    /// The time of the event.
+    pub time: DateTime<Utc>,
+}
+
+impl From<EventsResults> for ContainerEvent {
+    fn from(event: EventsResults) -> Self {
+        let attributes = event.actor.attributes;
+        let action = match event.action.as_str() {
+            "create" => ContainerEventAction::Create,
+            "start" => ContainerEventAction::Start,
+            "restart" => ContainerEventAction::Restart,
+            "kill" => ContainerEventAction::Kill {
+                signal: attributes.get("signal").cloned(),
+            },
+            "oom" => ContainerEventAction::Oom,
+            "die" => ContainerEventAction::Die {
+                exit_code: attributes.get("exitCode").and_then(|c| c.parse().ok()),
+            },
+            "stop" => ContainerEventAction::Stop,
+            "destroy" => ContainerEventAction::Destroy,
+            // the status follows a colon and a space, e.g. `health_status: healthy`
+            action if action.starts_with("health_status:") => {
+                match action["health_status:".len()..].trim() {
+                    "starting" => ContainerEventAction::HealthStatus(HealthStatus::Starting),
+                    "healthy" => ContainerEventAction::HealthStatus(HealthStatus::Healthy),
+                    "unhealthy" => ContainerEventAction::HealthStatus(HealthStatus::Unhealthy),
+                    _ => ContainerEventAction::Other(event.action.clone()),
+                }
+            }
+            _ => ContainerEventAction::Other(event.action.clone()),
+        };
+
+        ContainerEvent {
+            id: event.actor.id,
+            name: attributes.get("name").cloned(),
+            action,
+            attributes,
+            time: event.time_nano,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ContainerId {
+    #[serde(rename = "Id")]
+    id: String,
+}
+
+/// Interval between two inspections of a container, while waiting for its published ports.
+const PUBLISHED_PORTS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerPorts {
+    network_settings: ContainerPortsNetworkSettings,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerPortsNetworkSettings {
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
+    ports: HashMap<String, Option<Vec<PortBinding<String>>>>,
+}
+
+/// Interval between two inspections of a container, while waiting for it to become healthy.
+const HEALTH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The state of a container, as returned by the [Inspect Container
+/// API](../struct.Docker.html#method.inspect_container).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerInspectState {
+    state: ContainerStateSummary,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerStateSummary {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    running: bool,
+    health: Option<StateHealth>,
+}
+
+/// The result of a wait on a condition, failing with the error returned by the daemon.
+fn wait_condition_result(
+    result: Option<WaitContainerResults>,
+) -> Result<WaitContainerResults, Error> {
+    match result {
+        Some(WaitContainerResults {
+            error: Some(WaitContainerResultsError { message }),
+            status_code,
+        }) if !message.is_empty() => Err(ContainerWaitError {
+            status_code,
+            message,
+        }
+        .into()),
+        Some(result) => Ok(result),
+        None => Err(ContainerWaitError {
+            status_code: -1,
+            message: String::from("the daemon closed the connection before the condition"),
+        }
+        .into()),
+    }
+}
+
+/// Default maximum number of bytes collected from each of stdout and stderr by the [Run And Wait
+/// API](../struct.Docker.html#method.run_and_wait).
+const RUN_OUTPUT_LIMIT: usize = 1024 * 1024;
+
+/// Parameters used in the [Run And Wait API](../struct.Docker.html#method.run_and_wait)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::RunOptions;
+///
+/// RunOptions{
+///     remove: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunOptions {
+    /// Remove the container once it exited, or if running it failed.
+    pub remove: bool,
+    /// Maximum number of bytes collected from each of stdout and stderr, after which the output
+    /// is dropped and the results are marked as truncated. Defaults to 1 MiB.
+    pub output_limit: usize,
+}
+
+impl Default for RunOptions {
+    fn default() -> RunOptions {
+        RunOptions {
+            remove: false,
+            output_limit: RUN_OUTPUT_LIMIT,
+        }
+    }
+}
+
+/// Result type for the [Run And Wait API](../struct.Docker.html#method.run_and_wait)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunResults {
+    /// The ID of the container, which no longer exists if it was removed.
+    pub id: String,
+    /// The exit code of the container.
+    pub exit_code: i64,
+    /// The output of the container on stdout, or its console output if it has a TTY.
+    pub stdout: Bytes,
+    /// The output of the container on stderr.
+    pub stderr: Bytes,
+    /// Whether output was dropped after reaching the output limit.
+    pub truncated: bool,
+}
+
+/// The host address of a port binding, or `None` if the daemon has not assigned the port yet.
+fn published_address(binding: &PortBinding<String>) -> Option<SocketAddr> {
+    let port = binding
+        .host_port
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)?;
+    let ip = if binding.host_ip.is_empty() {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        binding.host_ip.parse().ok()?
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
 impl Docker {
     /// ---
     ///
@@ -1815,6 +4516,7 @@ impl Docker {
     ///
     /// let options = Some(CreateContainerOptions{
     ///     name: "my-new-container",
+    ///     ..Default::default()
     /// });
     ///
     /// let config = Config {
@@ -1845,7 +4547,13 @@ impl Docker {
             Docker::serialize_payload(Some(config)),
         );
 
-        self.process_into_value(req).await
+        let result: CreateContainerResults = self
+            .process_into_value(req)
+            .await
+            .map_err(name_conflict_error)?;
+        self.emit_warnings(url, &result.warnings);
+
+        Ok(result)
     }
 
     /// ---
@@ -1862,7 +4570,7 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Container State Change](container/enum.ContainerStateChange.html), wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -1877,7 +4585,7 @@ impl Docker {
         &self,
         container_name: &str,
         options: Option<T>,
-    ) -> Result<(), Error>
+    ) -> Result<ContainerStateChange, Error>
     where
         T: StartContainerQueryParams<K, V>,
         K: AsRef<str>,
@@ -1892,14 +4600,15 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_unit(req).await
+        self.process_into_state_change(req).await
     }
 
     /// ---
     ///
     /// # Stop Container
     ///
-    /// Stops a container.
+    /// Stops a container. The timeout of the request is extended by the time the docker server
+    /// waits for the container to stop, given in the options.
     ///
     /// # Arguments
     ///
@@ -1908,7 +4617,7 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Container State Change](container/enum.ContainerStateChange.html), wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -1919,6 +4628,7 @@ impl Docker {
     ///
     /// let options = Some(StopContainerOptions{
     ///     t: 30,
+    ///     ..Default::default()
     /// });
     ///
     /// docker.stop_container("hello-world", options);
@@ -1927,12 +4637,13 @@ impl Docker {
         &self,
         container_name: &str,
         options: Option<T>,
-    ) -> Result<(), Error>
+    ) -> Result<ContainerStateChange, Error>
     where
         T: StopContainerQueryParams<K>,
         K: AsRef<str>,
     {
         let url = format!("/containers/{}/stop", container_name);
+        let stop_timeout = options.as_ref().and_then(|o| o.stop_timeout());
 
         let req = self.build_request(
             &url,
@@ -1941,7 +4652,9 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_unit(req).await
+        self.with_stop_timeout(stop_timeout)
+            .process_into_state_change(req)
+            .await
     }
 
     /// ---
@@ -2051,11 +4764,71 @@ impl Docker {
         self.process_into_stream(req)
     }
 
+    /// ---
+    ///
+    /// # Wait Container Until
+    ///
+    /// Wait for a container to reach a condition, resolving once it does. Unlike the [Wait
+    /// Container](struct.Docker.html#method.wait_container) stream, an error returned by the
+    /// daemon in the body of the response fails the future.
+    ///
+    /// # Arguments
+    ///
+    /// - Container name as string slice.
+    /// - [Wait Condition](container/enum.WaitCondition.html) enum.
+    ///
+    /// # Returns
+    ///
+    ///  - [Wait Container Results](container/struct.WaitContainerResults.html), wrapped in a
+    ///  Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::WaitCondition;
+    ///
+    /// docker.wait_container_until("hello-world", WaitCondition::Removed);
+    /// ```
+    pub async fn wait_container_until(
+        &self,
+        container_name: &str,
+        condition: WaitCondition,
+    ) -> Result<WaitContainerResults, Error> {
+        let mut results =
+            Box::pin(self.wait_container(container_name, Some(WaitContainerOptions { condition })));
+
+        wait_condition_result(results.try_next().await?)
+    }
+
+    /// Start waiting for a container to reach a condition, returning once the docker server
+    /// waits, with a future resolving once the container reaches the condition.
+    async fn wait_container_registered(
+        &self,
+        container_name: &str,
+        condition: WaitCondition,
+    ) -> Result<impl Future<Output = Result<WaitContainerResults, Error>>, Error> {
+        let url = format!("/containers/{}/wait", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Docker::transpose_option(Some(WaitContainerOptions { condition }.into_array())),
+            Ok(Body::empty()),
+        );
+
+        let mut results = self.process_into_stream_on_headers(req).await?;
+        Ok(async move { wait_condition_result(results.try_next().await?) })
+    }
+
     /// ---
     ///
     /// # Restart Container
     ///
-    /// Restart a container.
+    /// Restart a container. The timeout of the request is extended by the time the docker server
+    /// waits for the container to stop, given in the options.
     ///
     /// # Arguments
     ///
@@ -2076,6 +4849,7 @@ impl Docker {
     ///
     /// let options = Some(RestartContainerOptions{
     ///     t: 30,
+    ///     ..Default::default()
     /// });
     ///
     /// docker.restart_container("postgres", options);
@@ -2090,6 +4864,7 @@ impl Docker {
         K: AsRef<str>,
     {
         let url = format!("/containers/{}/restart", container_name);
+        let stop_timeout = options.as_ref().and_then(|o| o.stop_timeout());
 
         let req = self.build_request(
             &url,
@@ -2098,7 +4873,20 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_unit(req).await
+        self.with_stop_timeout(stop_timeout)
+            .process_into_unit(req)
+            .await
+    }
+
+    /// A client whose request timeout is extended by the time the docker server waits for a
+    /// container to stop, so that a graceful stop is not cut short by the client.
+    fn with_stop_timeout(&self, stop_timeout: Option<Duration>) -> Docker {
+        match (self.client_timeout, stop_timeout) {
+            (Some(timeout), Some(stop_timeout)) => {
+                self.clone().with_timeout(timeout + stop_timeout)
+            }
+            _ => self.clone(),
+        }
     }
 
     /// ---
@@ -2203,72 +4991,92 @@ impl Docker {
 
     /// ---
     ///
-    /// # Logs
+    /// # Attach Container
     ///
-    /// Get container logs.
+    /// Attach to a container, upgrading the connection to a raw stream carrying its `stdin`,
+    /// `stdout` and `stderr` in both directions.
     ///
     /// # Arguments
     ///
     ///  - Container name as string slice.
-    ///  - Optional [Logs Options](container/struct.LogsOptions.html) struct.
+    ///  - Optional [Attach Container Options](container/struct.AttachContainerOptions.html) struct.
     ///
     /// # Returns
     ///
-    ///  - [Log Output](container/enum.LogOutput.html) enum, wrapped in a
-    ///  Stream.
+    ///  - [Attach Container Results](container/struct.AttachContainerResults.html), wrapped in a
+    ///  Future. The output is demultiplexed into [Log Output](container/enum.LogOutput.html)
+    ///  frames, and the input is written to `stdin` of the container.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use bollard::Docker;
     /// # let docker = Docker::connect_with_http_defaults().unwrap();
     ///
-    /// use bollard::container::LogsOptions;
+    /// use bollard::container::{AttachContainerOptions, AttachContainerResults};
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    /// use tokio::io::AsyncWriteExt;
     ///
     /// use std::default::Default;
     ///
-    /// let options = Some(LogsOptions{
+    /// let options = Some(AttachContainerOptions::<String>{
+    ///     stdin: true,
     ///     stdout: true,
+    ///     stream: true,
     ///     ..Default::default()
     /// });
     ///
-    /// docker.logs("hello-world", options);
+    /// async move {
+    ///     let AttachContainerResults { mut output, mut input } =
+    ///         docker.attach_container("hello-world", options).await?;
+    ///
+    ///     input.write_all(b"ls\n").await?;
+    ///     while let Some(frame) = output.try_next().await? {
+    ///         print!("{}", frame);
+    ///     }
+    ///     Ok::<_, bollard::errors::Error>(())
+    /// };
     /// ```
-    pub fn logs<T, K>(
+    pub async fn attach_container<T, K>(
         &self,
         container_name: &str,
         options: Option<T>,
-    ) -> impl Stream<Item = Result<LogOutput, Error>>
+    ) -> Result<AttachContainerResults, Error>
     where
-        T: LogsQueryParams<K>,
+        T: AttachContainerQueryParams<K>,
         K: AsRef<str>,
     {
-        let url = format!("/containers/{}/logs", container_name);
+        let url = format!("/containers/{}/attach", container_name);
 
         let req = self.build_request(
             &url,
-            Builder::new().method(Method::GET),
+            Builder::new()
+                .method(Method::POST)
+                .header(CONNECTION, "Upgrade")
+                .header(UPGRADE, "tcp"),
             Docker::transpose_option(options.map(|o| o.into_array())),
             Ok(Body::empty()),
         );
 
-        self.process_into_stream_string(req)
+        self.process_into_upgraded(req).await
     }
 
     /// ---
     ///
-    /// # Container Changes
+    /// # Logs
     ///
-    /// Get changes on a container's filesystem.
+    /// Get container logs.
     ///
     /// # Arguments
     ///
     ///  - Container name as string slice.
+    ///  - Optional [Logs Options](container/struct.LogsOptions.html) struct.
     ///
     /// # Returns
     ///
-    ///  - An Option of Vector of [Change](container/struct.Change.html) structs, wrapped in a
-    ///  Future.
+    ///  - [Log Output](container/enum.LogOutput.html) enum, wrapped in a
+    ///  Stream.
     ///
     /// # Examples
     ///
@@ -2276,14 +5084,138 @@ impl Docker {
     /// # use bollard::Docker;
     /// # let docker = Docker::connect_with_http_defaults().unwrap();
     ///
-    /// docker.container_changes("hello-world");
+    /// use bollard::container::LogsOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = Some(LogsOptions{
+    ///     stdout: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.logs("hello-world", options);
     /// ```
-    pub async fn container_changes(
+    pub fn logs<T, K>(
         &self,
         container_name: &str,
-    ) -> Result<Option<Vec<Change>>, Error> {
-        let url = format!("/containers/{}/changes", container_name);
-
+        options: Option<T>,
+    ) -> impl Stream<Item = Result<LogOutput, Error>>
+    where
+        T: LogsQueryParams<K>,
+        K: AsRef<str>,
+    {
+        let url = format!("/containers/{}/logs", container_name);
+
+        let query = Docker::transpose_option(options.map(|o| o.into_array()));
+        let follow = match query {
+            Ok(Some(ref query)) => query
+                .iter()
+                .any(|(key, value)| key.as_ref() == "follow" && value == "true"),
+            _ => false,
+        };
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            query,
+            Ok(Body::empty()),
+        );
+
+        self.process_into_stream_string(req, follow)
+    }
+
+    /// ---
+    ///
+    /// # Logs Reconnecting
+    ///
+    /// Follow the logs of a container like the [Logs API](struct.Docker.html#method.logs), and
+    /// subscribe again when the stream is interrupted, e.g. by a restart of the docker server or
+    /// a dropped connection, with the logs since the last timestamp received. A
+    /// [Reconnected](container/enum.ReconnectingLogOutput.html#variant.Reconnected) marker is
+    /// returned on every new subscription.
+    ///
+    /// Timestamps are always requested from the docker server, and stripped from the output
+    /// unless set in the options. The stream ends once the container is no longer running, and
+    /// returns an error, e.g. if the container is removed, without retrying.
+    ///
+    /// A [stream idle timeout](struct.Docker.html#method.with_stream_idle_timeout) set on the
+    /// client also subscribes again, as a connection may drop without the client noticing.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Logs Options](container/struct.LogsOptions.html) struct, with `follow` implied.
+    ///
+    /// # Returns
+    ///
+    ///  - [Reconnecting Log Output](container/enum.ReconnectingLogOutput.html) enum, wrapped in a
+    ///  Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::LogsOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = LogsOptions{
+    ///     stdout: true,
+    ///     stderr: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.logs_reconnecting("hello-world", options);
+    /// ```
+    pub fn logs_reconnecting(
+        &self,
+        container_name: &str,
+        options: LogsOptions,
+    ) -> impl Stream<Item = Result<ReconnectingLogOutput, Error>> {
+        let mut state = ReconnectingLogs {
+            docker: self.clone(),
+            container_name: container_name.to_owned(),
+            timestamps: options.timestamps,
+            options,
+            stream: Box::pin(stream::empty()),
+            done: false,
+        };
+        state.subscribe();
+
+        stream::unfold(state, ReconnectingLogs::next)
+    }
+
+    /// ---
+    ///
+    /// # Container Changes
+    ///
+    /// Get changes on a container's filesystem.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - An Option of Vector of [Change](container/struct.Change.html) structs, wrapped in a
+    ///  Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.container_changes("hello-world");
+    /// ```
+    pub async fn container_changes(
+        &self,
+        container_name: &str,
+    ) -> Result<Option<Vec<Change>>, Error> {
+        let url = format!("/containers/{}/changes", container_name);
+
         let req = self.build_request::<_, String, String>(
             &url,
             Builder::new().method(Method::GET),
@@ -2320,6 +5252,7 @@ impl Docker {
     ///
     /// let options = Some(StatsOptions{
     ///     stream: false,
+    ///     one_shot: true,
     /// });
     ///
     /// docker.stats("hello-world", options);
@@ -2336,14 +5269,70 @@ impl Docker {
     {
         let url = format!("/containers/{}/stats", container_name);
 
+        let query = Docker::transpose_option(options.map(|o| o.into_array()));
+        // the docker server streams stats unless asked not to
+        let follow = match query {
+            Ok(Some(ref query)) => !query
+                .iter()
+                .any(|(key, value)| key.as_ref() == "stream" && value.as_ref() == "false"),
+            _ => true,
+        };
+
         let req = self.build_request(
             &url,
             Builder::new().method(Method::GET),
-            Docker::transpose_option(options.map(|o| o.into_array())),
+            query,
             Ok(Body::empty()),
         );
 
-        self.process_into_stream(req)
+        self.process_into_followed_stream(req, follow)
+    }
+
+    /// ---
+    ///
+    /// # Stats Once
+    ///
+    /// Get a single sample of the container stats, without streaming.
+    ///
+    /// # Arguments
+    ///
+    /// - Container name as string slice.
+    /// - Whether to return the first sample at once, in which case the `precpu_stats` are empty,
+    /// instead of waiting for a second sample to compute the CPU usage from. Requires API version
+    /// 1.41 or later.
+    ///
+    /// # Returns
+    ///
+    ///  - [Stats](container/struct.Stats.html) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// async move {
+    ///     let stats = docker.stats_once("hello-world", false).await?;
+    ///     println!("{:?}", stats.cpu_percentage());
+    ///     Ok::<_, bollard::errors::Error>(())
+    /// };
+    /// ```
+    pub async fn stats_once(&self, container_name: &str, one_shot: bool) -> Result<Stats, Error> {
+        let url = format!("/containers/{}/stats", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            StatsOptions {
+                stream: false,
+                one_shot,
+            }
+            .into_array()
+            .map(Some),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
     }
 
     /// ---
@@ -2410,7 +5399,7 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Update Container Results](container/struct.UpdateContainerResults.html), wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -2433,7 +5422,7 @@ impl Docker {
         &self,
         container_name: &str,
         config: UpdateContainerOptions,
-    ) -> Result<(), Error> {
+    ) -> Result<UpdateContainerResults, Error> {
         let url = format!("/containers/{}/update", container_name);
 
         let req = self.build_request::<_, String, String>(
@@ -2443,14 +5432,19 @@ impl Docker {
             Docker::serialize_payload(Some(config)),
         );
 
-        self.process_into_unit(req).await
+        let result: UpdateContainerResults = self.process_into_value(req).await?;
+        self.emit_warnings(&url, &result.warnings);
+
+        Ok(result)
     }
 
     /// ---
     ///
     /// # Rename Container
     ///
-    /// Rename a container.
+    /// Rename a container. If the new name is in use by another container, a [Container Name
+    /// Conflict Error](errors/enum.ErrorKind.html#variant.ContainerNameConflictError) is
+    /// returned.
     ///
     /// # Arguments
     ///
@@ -2494,6 +5488,63 @@ impl Docker {
             Ok(Body::empty()),
         );
 
+        self.process_into_unit(req)
+            .await
+            .map_err(name_conflict_error)
+    }
+
+    /// ---
+    ///
+    /// # Resize Container Tty
+    ///
+    /// Resize the TTY of a container, so that programs running in it match the size of the local
+    /// terminal. The container must have been created with a TTY.
+    ///
+    /// The client is cheap to clone, so a clone can be moved into the task handling `SIGWINCH`,
+    /// calling this method with the new size of the terminal.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Resize Container Tty Options](container/struct.ResizeContainerTtyOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::ResizeContainerTtyOptions;
+    ///
+    /// let options = ResizeContainerTtyOptions {
+    ///     width: 80,
+    ///     height: 24,
+    /// };
+    ///
+    /// docker.resize_container_tty("hello-world", options);
+    /// ```
+    pub async fn resize_container_tty<T, K>(
+        &self,
+        container_name: &str,
+        options: T,
+    ) -> Result<(), Error>
+    where
+        T: ResizeContainerTtyQueryParams<K>,
+        K: AsRef<str>,
+    {
+        let url = format!("/containers/{}/resize", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Docker::transpose_option(Some(options.into_array())),
+            Ok(Body::empty()),
+        );
+
         self.process_into_unit(req).await
     }
 
@@ -2509,7 +5560,11 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Container State Change](container/enum.ContainerStateChange.html), wrapped in a Future.
+    ///
+    /// A [Container Already Paused
+    /// Error](errors/enum.ErrorKind.html#variant.ContainerAlreadyPausedError) is returned if the
+    /// container is already paused.
     ///
     /// # Examples
     ///
@@ -2519,7 +5574,10 @@ impl Docker {
     ///
     /// docker.pause_container("postgres");
     /// ```
-    pub async fn pause_container(&self, container_name: &str) -> Result<(), Error> {
+    pub async fn pause_container(
+        &self,
+        container_name: &str,
+    ) -> Result<ContainerStateChange, Error> {
         let url = format!("/containers/{}/pause", container_name);
 
         let req = self.build_request::<_, String, String>(
@@ -2529,7 +5587,17 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_unit(req).await
+        self.process_into_state_change(req)
+            .await
+            .map_err(|e| match Docker::error_message(&e) {
+                Some(message) if message.contains("is already paused") => {
+                    ContainerAlreadyPausedError {
+                        message: message.to_owned(),
+                    }
+                    .into()
+                }
+                _ => e,
+            })
     }
 
     /// ---
@@ -2544,7 +5612,10 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Container State Change](container/enum.ContainerStateChange.html), wrapped in a Future.
+    ///
+    /// A [Container Not Paused Error](errors/enum.ErrorKind.html#variant.ContainerNotPausedError)
+    /// is returned if the container is not paused.
     ///
     /// # Examples
     ///
@@ -2554,7 +5625,10 @@ impl Docker {
     ///
     /// docker.unpause_container("postgres");
     /// ```
-    pub async fn unpause_container(&self, container_name: &str) -> Result<(), Error> {
+    pub async fn unpause_container(
+        &self,
+        container_name: &str,
+    ) -> Result<ContainerStateChange, Error> {
         let url = format!("/containers/{}/unpause", container_name);
 
         let req = self.build_request::<_, String, String>(
@@ -2564,7 +5638,89 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_unit(req).await
+        self.process_into_state_change(req)
+            .await
+            .map_err(|e| match Docker::error_message(&e) {
+                Some(message) if message.contains("is not paused") => ContainerNotPausedError {
+                    message: message.to_owned(),
+                }
+                .into(),
+                _ => e,
+            })
+    }
+
+    /// ---
+    ///
+    /// # With Container Paused
+    ///
+    /// Pause a container while the given future runs, e.g. to take a consistent snapshot of its
+    /// filesystem, and unpause it once the future completes, whether it succeeded or not. A
+    /// container that is already paused is left paused. If the returned future is dropped before
+    /// it completes, the container is unpaused by a task spawned on the client's runtime.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - A function returning the future to run while the container is paused.
+    ///
+    /// # Returns
+    ///
+    ///  - The output of the future, wrapped in a Future. An error pausing the container is returned
+    ///  without running the future, and an error unpausing it is returned if the future succeeded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::DownloadFromContainerOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// let snapshot = docker.with_container_paused("postgres", || {
+    ///     docker
+    ///         .download_from_container(
+    ///             "postgres",
+    ///             Some(DownloadFromContainerOptions {
+    ///                 path: "/var/lib/postgresql/data",
+    ///             }),
+    ///         )
+    ///         .map_ok(|chunk| chunk.to_vec())
+    ///         .try_concat()
+    /// });
+    /// ```
+    pub async fn with_container_paused<F, R, T>(
+        &self,
+        container_name: &str,
+        f: F,
+    ) -> Result<T, Error>
+    where
+        F: FnOnce() -> R,
+        R: Future<Output = Result<T, Error>>,
+    {
+        let paused = match self.pause_container(container_name).await {
+            Ok(_) => true,
+            Err(e) => match e.kind() {
+                ContainerAlreadyPausedError { .. } => false,
+                _ => return Err(e),
+            },
+        };
+        let mut guard = UnpauseOnDrop {
+            docker: if paused { Some(self.clone()) } else { None },
+            container_name: container_name.to_owned(),
+        };
+
+        let result = f().await;
+
+        if guard.docker.take().is_some() {
+            let unpaused = self.unpause_container(container_name).await;
+            if result.is_ok() {
+                unpaused?;
+            }
+        }
+
+        result
     }
 
     /// ---
@@ -2730,4 +5886,509 @@ impl Docker {
 
         self.process_into_body(req)
     }
+
+    /// ---
+    ///
+    /// # Stat Container Path
+    ///
+    /// Get information about a file or directory in the filesystem of a container, without
+    /// downloading it.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - Path in the filesystem of the container as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Container Path Stat](container/struct.ContainerPathStat.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.stat_container_path("my-container", "/etc/hosts");
+    /// ```
+    pub async fn stat_container_path(
+        &self,
+        container_name: &str,
+        path: &str,
+    ) -> Result<ContainerPathStat, Error> {
+        let url = format!("/containers/{}/archive", container_name);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::HEAD),
+            Ok(Some(ArrayVec::from([("path", path)]))),
+            Ok(Body::empty()),
+        );
+
+        let headers = self.process_into_headers(req).await?;
+        headers
+            .get(PATH_STAT_HEADER)
+            .and_then(|stat| base64::decode(stat.as_bytes()).ok())
+            .and_then(|stat| serde_json::from_slice(&stat).ok())
+            .ok_or_else(|| {
+                ContainerPathStatError {
+                    container: String::from(container_name),
+                    path: String::from(path),
+                }
+                .into()
+            })
+    }
+
+    /// ---
+    ///
+    /// # Export Container
+    ///
+    /// Export the filesystem of a container as a tarball. The chunks of the archive are passed on
+    /// as they are received, so that it can be written out without holding it in memory.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - An uncompressed TAR archive, as a Stream of chunks of
+    ///  [Bytes](https://docs.rs/bytes/0.5.4/bytes/struct.Bytes.html).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// async move {
+    ///     let mut file = tokio::fs::File::create("hello-world.tar").await?;
+    ///     let mut archive = docker.export_container("hello-world");
+    ///     while let Some(chunk) = archive.try_next().await? {
+    ///         file.write_all(&chunk).await?;
+    ///     }
+    ///     Ok::<_, bollard::errors::Error>(())
+    /// };
+    /// ```
+    pub fn export_container(
+        &self,
+        container_name: &str,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + Unpin {
+        let url = format!("/containers/{}/export", container_name);
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new().method(Method::GET),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_body(req)
+    }
+
+    /// ---
+    ///
+    /// # Container Events
+    ///
+    /// Stream real-time events of a single container. The container name is resolved to an ID
+    /// before subscribing, so that the events keep flowing if the container is renamed.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Optional [Events Options](../system/struct.EventsOptions.html) struct, whose filters are
+    ///  combined with the filters on the container.
+    ///
+    /// # Returns
+    ///
+    ///  - [Container Event](container/struct.ContainerEvent.html), wrapped in a Stream, wrapped in
+    ///  a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::ContainerEventAction;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let mut events = docker.container_events("hello-world", None).await?;
+    ///     while let Some(event) = events.try_next().await? {
+    ///         if let ContainerEventAction::Die { exit_code } = event.action {
+    ///             println!("exited with {:?}", exit_code);
+    ///         }
+    ///     }
+    ///     Ok::<_, bollard::errors::Error>(())
+    /// };
+    /// ```
+    pub async fn container_events(
+        &self,
+        container_name: &str,
+        options: Option<EventsOptions<String>>,
+    ) -> Result<impl Stream<Item = Result<ContainerEvent, Error>>, Error> {
+        let url = format!("/containers/{}/json", container_name);
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new().method(Method::GET),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        let ContainerId { id } = self.process_into_value(req).await?;
+
+        let mut filters = options
+            .as_ref()
+            .map(|o| o.filters.clone())
+            .unwrap_or_default();
+        filters.insert(String::from("type"), vec![String::from("container")]);
+        filters.insert(String::from("container"), vec![id]);
+
+        let query: Vec<_> = match options {
+            Some(options) => EventsOptions { filters, ..options }
+                .into_array()?
+                .into_iter()
+                .collect(),
+            None => vec![(
+                "filters",
+                serde_json::to_string(&filters).map_err(|e| JsonSerializeError { err: e })?,
+            )],
+        };
+
+        let req = self.build_request(
+            "/events",
+            Builder::new().method(Method::GET),
+            Ok(Some(query)),
+            Ok(Body::empty()),
+        );
+
+        Ok(self
+            .process_into_followed_stream::<EventsResults>(req, true)
+            .map_ok(ContainerEvent::from))
+    }
+
+    /// ---
+    ///
+    /// # Published Ports
+    ///
+    /// Inspect a container for the host addresses of its published ports, including those
+    /// assigned dynamically when publishing with host port `0`.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - A map from the container port, in the form `<port>/<protocol>`, to the host addresses it
+    ///  is published on, wrapped in a Future. A port published on both IPv4 and IPv6 has an
+    ///  address for each, and ports that are not published are absent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.published_ports("hello-world");
+    /// ```
+    pub async fn published_ports(
+        &self,
+        container_name: &str,
+    ) -> Result<HashMap<String, Vec<SocketAddr>>, Error> {
+        let url = format!("/containers/{}/json", container_name);
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new().method(Method::GET),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        let ContainerPorts { network_settings } = self.process_into_value(req).await?;
+
+        Ok(network_settings
+            .ports
+            .into_iter()
+            .filter_map(|(port, bindings)| {
+                let addresses: Vec<_> = bindings
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(published_address)
+                    .collect();
+                if addresses.is_empty() {
+                    None
+                } else {
+                    Some((port, addresses))
+                }
+            })
+            .collect())
+    }
+
+    /// ---
+    ///
+    /// # Wait For Published Ports
+    ///
+    /// Inspect a container for its published ports as with the [Published Ports
+    /// API](struct.Docker.html#method.published_ports), retrying until at least one port is
+    /// published. Some daemons only report a dynamically assigned port shortly after the
+    /// container started.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Timeout after which the wait fails with a `PublishedPortsTimeoutError`.
+    ///
+    /// # Returns
+    ///
+    ///  - A map from the container port to the host addresses it is published on, wrapped in a
+    ///  Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::time::Duration;
+    ///
+    /// docker.wait_for_published_ports("hello-world", Duration::from_secs(5));
+    /// ```
+    pub async fn wait_for_published_ports(
+        &self,
+        container_name: &str,
+        timeout: Duration,
+    ) -> Result<HashMap<String, Vec<SocketAddr>>, Error> {
+        let deadline = Instant::now() + timeout;
+        self.poll_until(deadline, PUBLISHED_PORTS_INTERVAL, || async move {
+            let ports = self.published_ports(container_name).await?;
+            Ok(if ports.is_empty() { Err(()) } else { Ok(ports) })
+        })
+        .await?
+        .map_err(|()| {
+            PublishedPortsTimeoutError {
+                container: container_name.to_owned(),
+                timeout,
+            }
+            .into()
+        })
+    }
+
+    /// ---
+    ///
+    /// # Wait Until Healthy
+    ///
+    /// Inspect a container until its health check reports it as `healthy`. Fails as soon as the
+    /// container stops running, or if it has no health check.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Timeout after which the wait fails with a `ContainerHealthTimeoutError`, carrying the
+    ///  output of the last health check.
+    ///
+    /// # Returns
+    ///
+    ///  - The [State Health](container/struct.StateHealth.html) of the healthy container, wrapped
+    ///  in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::time::Duration;
+    ///
+    /// docker.wait_until_healthy("postgres", Duration::from_secs(60));
+    /// ```
+    pub async fn wait_until_healthy(
+        &self,
+        container_name: &str,
+        timeout: Duration,
+    ) -> Result<StateHealth, Error> {
+        let deadline = Instant::now() + timeout;
+        self.poll_until(deadline, HEALTH_INTERVAL, || async move {
+            let state = self.inspect_container_state(container_name).await?;
+
+            let health = match state.health {
+                Some(health) => health,
+                None => {
+                    return Err(ContainerNoHealthCheckError {
+                        container: container_name.to_owned(),
+                    }
+                    .into())
+                }
+            };
+            if health.status == "healthy" {
+                return Ok(Ok(health));
+            }
+            if !state.running {
+                return Err(ContainerStoppedError {
+                    container: container_name.to_owned(),
+                    status: state.status,
+                }
+                .into());
+            }
+
+            Ok(Err(health))
+        })
+        .await?
+        .map_err(|health| {
+            let output = health
+                .log
+                .last()
+                .map(|entry| entry.output.trim_end().to_owned())
+                .unwrap_or_default();
+            ContainerHealthTimeoutError {
+                container: container_name.to_owned(),
+                timeout,
+                last_check: Box::new(LastHealthCheck {
+                    status: health.status,
+                    output,
+                }),
+            }
+            .into()
+        })
+    }
+
+    async fn inspect_container_state(
+        &self,
+        container_name: &str,
+    ) -> Result<ContainerStateSummary, Error> {
+        let url = format!("/containers/{}/json", container_name);
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new().method(Method::GET),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        let ContainerInspectState { state } = self.process_into_value(req).await?;
+        Ok(state)
+    }
+
+    /// ---
+    ///
+    /// # Run And Wait
+    ///
+    /// Create a container, start it, and wait for it to exit, collecting its output. This is the
+    /// equivalent of `docker run` without `--detach`.
+    ///
+    /// The output is read from the logs of the container, which requires a logging driver able to
+    /// read them back, e.g. the default `json-file` driver. The container is waited on before it
+    /// starts, so that the exit code of a container created with `auto_remove` is not missed.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [Create Container Options](container/struct.CreateContainerOptions.html)
+    ///  struct.
+    ///  - Container [Config](container/struct.Config.html) struct.
+    ///  - [Run Options](container/struct.RunOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Run Results](container/struct.RunResults.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::{Config, CreateContainerOptions, RunOptions};
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = Config {
+    ///     image: Some("alpine:3.11"),
+    ///     cmd: Some(vec!["uname", "-a"]),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let options = RunOptions {
+    ///     remove: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.run_and_wait(None::<CreateContainerOptions<String>>, config, options);
+    /// ```
+    pub async fn run_and_wait<T, K, V, Z>(
+        &self,
+        options: Option<T>,
+        config: Config<Z>,
+        run_options: RunOptions,
+    ) -> Result<RunResults, Error>
+    where
+        T: CreateContainerQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        Z: AsRef<str> + Eq + Hash + Serialize,
+    {
+        let id = self.create_container(options, config).await?.id;
+
+        let results = self.run_created(&id, run_options.output_limit).await;
+
+        if run_options.remove {
+            let removed = self
+                .remove_container(
+                    &id,
+                    Some(RemoveContainerOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                )
+                .await;
+            // the error of the run takes precedence over the failed removal
+            if results.is_ok() {
+                removed?;
+            }
+        }
+
+        results
+    }
+
+    async fn run_created(&self, id: &str, output_limit: usize) -> Result<RunResults, Error> {
+        let exit = self
+            .wait_container_registered(id, WaitCondition::NextExit)
+            .await?;
+
+        self.start_container(id, None::<StartContainerOptions<String>>)
+            .await?;
+
+        let mut logs = Box::pin(self.logs(
+            id,
+            Some(LogsOptions {
+                follow: true,
+                stdout: true,
+                stderr: true,
+                tail: String::from("all"),
+                ..Default::default()
+            }),
+        ));
+        let mut stdout = BytesMut::new();
+        let mut stderr = BytesMut::new();
+        let mut truncated = false;
+        while let Some(output) = logs.try_next().await? {
+            let buffer = match output {
+                LogOutput::StdErr { .. } => &mut stderr,
+                _ => &mut stdout,
+            };
+            let bytes = output.as_bytes();
+            let len = cmp::min(bytes.len(), output_limit - buffer.len());
+            truncated |= len < bytes.len();
+            buffer.extend_from_slice(&bytes[..len]);
+        }
+
+        let exit = exit.await?;
+
+        Ok(RunResults {
+            id: id.to_owned(),
+            exit_code: exit.status_code,
+            stdout: stdout.freeze(),
+            stderr: stderr.freeze(),
+            truncated,
+        })
+    }
 }
@@ -0,0 +1,181 @@
+#![cfg(feature = "tokio")]
+//! Resolution of the endpoints of docker CLI contexts, from the context store in the docker
+//! config directory.
+
+use serde_json;
+use sha2::{Digest, Sha256};
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::Docker;
+use crate::errors::Error;
+use crate::errors::ErrorKind::{
+    ContextNotFoundError, DockerConfigDirError, JsonDeserializeError,
+    UnsupportedContextEndpointError, UnsupportedDockerHostError,
+};
+
+/// The context using `DOCKER_HOST`, or the local socket, which is not kept in the store.
+const DEFAULT_CONTEXT: &str = "default";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CliConfig {
+    current_context: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextMetadata {
+    #[serde(default)]
+    endpoints: HashMap<String, ContextEndpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextEndpoint {
+    host: Option<String>,
+}
+
+/// The docker config directory, `DOCKER_CONFIG` or `~/.docker`.
+fn config_dir() -> Result<PathBuf, Error> {
+    match env::var("DOCKER_CONFIG") {
+        Ok(path) => Ok(PathBuf::from(path)),
+        Err(_) => dirs::home_dir()
+            .map(|home| home.join(".docker"))
+            .ok_or_else(|| DockerConfigDirError.into()),
+    }
+}
+
+/// Read a JSON file, or `None` if it does not exist.
+fn read_json<T>(path: &Path) -> Result<Option<T>, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    serde_json::from_str(&contents).map(Some).map_err(|e| {
+        JsonDeserializeError {
+            content: contents.clone(),
+            err: e,
+        }
+        .into()
+    })
+}
+
+/// The name of the current context, following the precedence of the docker CLI: the
+/// `DOCKER_CONTEXT` environment variable, then the default context if `DOCKER_HOST` is set, then
+/// the `currentContext` of the config file.
+fn current_context(config_dir: &Path) -> Result<String, Error> {
+    if let Ok(name) = env::var("DOCKER_CONTEXT") {
+        if !name.is_empty() {
+            return Ok(name);
+        }
+    }
+
+    if env::var("DOCKER_HOST").is_ok() {
+        return Ok(String::from(DEFAULT_CONTEXT));
+    }
+
+    let config: CliConfig = read_json(&config_dir.join("config.json"))?.unwrap_or_default();
+    Ok(config
+        .current_context
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from(DEFAULT_CONTEXT)))
+}
+
+impl Docker {
+    /// Connect to the docker endpoint of the current docker CLI context, selected by the
+    /// `DOCKER_CONTEXT` environment variable, or else by the `currentContext` of the docker
+    /// config file. If neither is set, or `DOCKER_HOST` is set without `DOCKER_CONTEXT`, the
+    /// default context is used.
+    ///
+    /// See [`connect_with_context`](struct.Docker.html#method.connect_with_context).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// let connection = Docker::connect_with_current_context().unwrap();
+    /// ```
+    pub fn connect_with_current_context() -> Result<Docker, Error> {
+        let config_dir = config_dir()?;
+        let name = current_context(&config_dir)?;
+        Docker::connect_with_context_in(&config_dir, &name)
+    }
+
+    /// Connect to the docker endpoint of a named docker CLI context, as created with `docker
+    /// context create`. The context store is read from the `contexts` directory of the docker
+    /// config directory, `DOCKER_CONFIG` or `~/.docker`.
+    ///
+    /// The transport follows the scheme of the endpoint: `unix://`, `npipe://`, `tcp://` with the
+    /// certificates of the context if it has any, or `ssh://` with the `ssh` feature. A
+    /// `tcp://` endpoint with certificates requires the `ssl` or `rustls` feature. The
+    /// certificates are always verified.
+    ///
+    /// The `default` context connects with
+    /// [`connect_with_defaults`](struct.Docker.html#method.connect_with_defaults).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// let connection = Docker::connect_with_context("staging").unwrap();
+    /// ```
+    pub fn connect_with_context(name: &str) -> Result<Docker, Error> {
+        Docker::connect_with_context_in(&config_dir()?, name)
+    }
+
+    fn connect_with_context_in(config_dir: &Path, name: &str) -> Result<Docker, Error> {
+        if name == DEFAULT_CONTEXT {
+            return Docker::connect_with_defaults();
+        }
+
+        let id = hex::encode(Sha256::digest(name.as_bytes()));
+        let contexts_dir = config_dir.join("contexts");
+
+        let metadata: ContextMetadata = read_json(
+            &contexts_dir.join("meta").join(&id).join("meta.json"),
+        )?
+        .ok_or_else(|| ContextNotFoundError {
+            name: String::from(name),
+        })?;
+
+        let unsupported = |host: &str| UnsupportedContextEndpointError {
+            name: String::from(name),
+            host: String::from(host),
+        };
+
+        let host = metadata
+            .endpoints
+            .get("docker")
+            .and_then(|endpoint| endpoint.host.clone())
+            .ok_or_else(|| unsupported(""))?;
+
+        let cert_dir = contexts_dir.join("tls").join(&id).join("docker");
+        let cert_dir = if cert_dir.join("ca.pem").exists() {
+            Some(cert_dir)
+        } else {
+            None
+        };
+
+        // inherited file descriptors belong to a process, not to a context
+        if host.starts_with("fd://") {
+            return Err(unsupported(&host).into());
+        }
+
+        Docker::connect_with_host(&host, cert_dir).map_err(|e| match e.kind() {
+            UnsupportedDockerHostError { .. } => unsupported(&host).into(),
+            _ => e,
+        })
+    }
+}
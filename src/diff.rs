@@ -0,0 +1,282 @@
+//! Diff API: compare a desired container or service specification with the inspected one.
+//!
+//! Only the fields set in the desired specification are compared, so defaults filled in by the
+//! docker server are not reported as drift.
+use serde::Serialize;
+use serde_json::{self, Value};
+
+use std::hash::Hash;
+
+use crate::container::{Config, Container};
+use crate::errors::Error;
+use crate::errors::ErrorKind::JsonSerializeError;
+use crate::service_models::{Service, ServiceSpec};
+
+/// Fields that are not compared by default, as the inspected container reports them in a
+/// different shape.
+pub const DEFAULT_IGNORED_FIELDS: [&str; 1] = ["NetworkingConfig"];
+
+/// Fields compared as sets, to which the docker server may append entries, e.g. the environment
+/// of the image is added to the environment of a container.
+const UNORDERED_FIELDS: [&str; 1] = ["Env"];
+
+/// A field whose inspected value does not match the desired value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    /// The path to the field, with the names of the docker API separated by dots, e.g.
+    /// `HostConfig.Memory`.
+    pub path: String,
+    /// The desired value.
+    pub desired: Value,
+    /// The inspected value, `null` if the field is absent.
+    pub actual: Value,
+}
+
+/// Options for the comparison of a desired specification with an inspected one.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::diff::DriftOptions;
+///
+/// DriftOptions::default().ignore("HostConfig.RestartPolicy");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DriftOptions {
+    ignored: Vec<String>,
+}
+
+impl Default for DriftOptions {
+    fn default() -> Self {
+        DriftOptions {
+            ignored: DEFAULT_IGNORED_FIELDS
+                .iter()
+                .map(|field| String::from(*field))
+                .collect(),
+        }
+    }
+}
+
+impl DriftOptions {
+    /// Do not compare a field, or any field nested within it, given as a path in the form
+    /// reported by a [Drift](struct.Drift.html), e.g. `Labels` or `HostConfig.CpuShares`.
+    pub fn ignore<T>(mut self, path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.ignored.push(path.into());
+        self
+    }
+
+    fn is_ignored(&self, path: &str) -> bool {
+        self.ignored.iter().any(|ignored| {
+            path == ignored
+                || (path.starts_with(ignored.as_str()) && path[ignored.len()..].starts_with('.'))
+        })
+    }
+}
+
+/// Compare the configuration a container was created with against an inspected container, with
+/// the default [Drift Options](struct.DriftOptions.html).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::container::{Config, InspectContainerOptions};
+/// use bollard::diff::container_config_drift;
+///
+/// let desired = Config {
+///     image: Some("redis:6"),
+///     env: Some(vec!["REDIS_PORT=6379"]),
+///     ..Default::default()
+/// };
+///
+/// async move {
+///     let actual = docker
+///         .inspect_container("redis", None::<InspectContainerOptions>)
+///         .await
+///         .unwrap();
+///     let drift = container_config_drift(&desired, &actual).unwrap();
+/// };
+/// ```
+pub fn container_config_drift<T>(
+    desired: &Config<T>,
+    actual: &Container,
+) -> Result<Vec<Drift>, Error>
+where
+    T: AsRef<str> + Eq + Hash + Serialize,
+{
+    container_config_drift_with_options(desired, actual, &DriftOptions::default())
+}
+
+/// Compare the configuration a container was created with against an inspected container.
+pub fn container_config_drift_with_options<T>(
+    desired: &Config<T>,
+    actual: &Container,
+    options: &DriftOptions,
+) -> Result<Vec<Drift>, Error>
+where
+    T: AsRef<str> + Eq + Hash + Serialize,
+{
+    let desired = to_value(desired)?;
+
+    // the host config is reported next to the config of an inspected container, not within it
+    let mut actual_config = to_value(&actual.config)?;
+    if let Value::Object(ref mut map) = actual_config {
+        map.insert(String::from("HostConfig"), to_value(&actual.host_config)?);
+    }
+
+    let mut drifts = Vec::new();
+    compare(
+        String::new(),
+        &desired,
+        &actual_config,
+        options,
+        &mut drifts,
+    );
+    Ok(drifts)
+}
+
+/// Compare the specification of a service against an inspected service, with the default [Drift
+/// Options](struct.DriftOptions.html).
+pub fn service_spec_drift<T, U>(
+    desired: &ServiceSpec<T>,
+    actual: &Service<U>,
+) -> Result<Vec<Drift>, Error>
+where
+    T: AsRef<str> + Eq + Hash + Serialize,
+    U: AsRef<str> + Eq + Hash + Serialize,
+{
+    service_spec_drift_with_options(desired, actual, &DriftOptions::default())
+}
+
+/// Compare the specification of a service against an inspected service.
+pub fn service_spec_drift_with_options<T, U>(
+    desired: &ServiceSpec<T>,
+    actual: &Service<U>,
+    options: &DriftOptions,
+) -> Result<Vec<Drift>, Error>
+where
+    T: AsRef<str> + Eq + Hash + Serialize,
+    U: AsRef<str> + Eq + Hash + Serialize,
+{
+    let desired = to_value(desired)?;
+    let actual = to_value(&actual.spec)?;
+
+    let mut drifts = Vec::new();
+    compare(String::new(), &desired, &actual, options, &mut drifts);
+    Ok(drifts)
+}
+
+fn to_value<T>(value: &T) -> Result<Value, Error>
+where
+    T: Serialize,
+{
+    serde_json::to_value(value).map_err(|e| JsonSerializeError { err: e }.into())
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+fn child_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        String::from(field)
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+fn last_field(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+/// Normalize a scalar inspected value into the form it was likely specified in.
+fn normalize(path: &str, desired: &Value, actual: &Value) -> Value {
+    match (last_field(path), desired, actual) {
+        // the docker server pins the image of a service to a digest
+        ("Image", Value::String(desired), Value::String(actual))
+            if !desired.contains('@') && actual.contains('@') =>
+        {
+            Value::String(actual.splitn(2, '@').next().unwrap_or_default().to_owned())
+        }
+        // `default` is the name of the bridge network on Linux
+        ("NetworkMode", Value::String(desired), Value::String(actual))
+            if desired == "default" && actual == "bridge" =>
+        {
+            Value::String(String::from("default"))
+        }
+        _ => actual.clone(),
+    }
+}
+
+fn compare(
+    path: String,
+    desired: &Value,
+    actual: &Value,
+    options: &DriftOptions,
+    drifts: &mut Vec<Drift>,
+) {
+    if options.is_ignored(&path) || desired.is_null() {
+        return;
+    }
+
+    if is_empty(desired) && is_empty(actual) {
+        return;
+    }
+
+    match (desired, actual) {
+        (Value::Object(desired), _) => {
+            for (field, value) in desired {
+                compare(
+                    child_path(&path, field),
+                    value,
+                    actual.get(field).unwrap_or(&Value::Null),
+                    options,
+                    drifts,
+                );
+            }
+        }
+        (Value::Array(desired_items), Value::Array(actual_items))
+            if UNORDERED_FIELDS.contains(&last_field(&path)) =>
+        {
+            if !desired_items.iter().all(|item| actual_items.contains(item)) {
+                drifts.push(Drift {
+                    path,
+                    desired: desired.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+        (Value::Array(desired_items), Value::Array(actual_items))
+            if desired_items.len() == actual_items.len() =>
+        {
+            for (index, (desired, actual)) in desired_items.iter().zip(actual_items).enumerate() {
+                compare(
+                    format!("{}[{}]", path, index),
+                    desired,
+                    actual,
+                    options,
+                    drifts,
+                );
+            }
+        }
+        _ => {
+            let normalized = normalize(&path, desired, actual);
+            if *desired != normalized {
+                drifts.push(Drift {
+                    path,
+                    desired: desired.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+    }
+}
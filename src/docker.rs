@@ -1,73 +1,154 @@
 use std::cmp;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "tokio")]
 use std::env;
+use std::error::Error as StdError;
 use std::fmt;
+#[cfg(any(feature = "openssl", feature = "tls"))]
+use std::fs;
 use std::future::Future;
-#[cfg(any(feature = "ssl", feature = "tls"))]
-use std::path::{Path, PathBuf};
+use std::hash::Hash;
+#[cfg(all(unix, feature = "tokio"))]
+use std::os::unix::fs::FileTypeExt;
+#[cfg(all(unix, feature = "tokio"))]
+use std::os::unix::net::UnixStream as StdUnixStream;
+#[cfg(any(
+    all(unix, feature = "tokio"),
+    feature = "ssl",
+    feature = "tls",
+    feature = "rustls"
+))]
+use std::path::Path;
+#[cfg(feature = "tokio")]
+use std::path::PathBuf;
 use std::str::from_utf8;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use arrayvec::ArrayVec;
-#[cfg(any(feature = "ssl", feature = "tls"))]
+use chrono::{DateTime, Utc};
+#[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
 use dirs;
 use futures_core::Stream;
 use futures_util::future::FutureExt;
 use futures_util::future::TryFutureExt;
 use futures_util::stream;
 use futures_util::stream::TryStreamExt;
-use http::header::CONTENT_TYPE;
+use http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT};
 use http::request::Builder;
+use hyper::client::connect::Connect;
+#[cfg(feature = "tokio")]
 use hyper::client::HttpConnector;
+use hyper::client::{self, ResponseFuture};
+use hyper::upgrade::Upgraded;
 use hyper::{self, body::Bytes, Body, Client, Method, Request, Response, StatusCode};
 #[cfg(feature = "openssl")]
 use hyper_openssl::HttpsConnector;
 #[cfg(feature = "tls")]
 use hyper_tls;
-#[cfg(unix)]
+#[cfg(all(unix, feature = "tokio"))]
 use hyperlocal::UnixClient as UnixConnector;
 #[cfg(feature = "tls")]
 use native_tls::{Certificate, Identity, TlsConnector};
 #[cfg(feature = "openssl")]
+use openssl::pkcs12::Pkcs12;
+#[cfg(feature = "openssl")]
+use openssl::pkey::PKey;
+#[cfg(feature = "openssl")]
 use openssl::ssl::SslConnector;
 #[cfg(feature = "openssl")]
 use openssl::ssl::{SslFiletype, SslMethod};
+#[cfg(feature = "rustls")]
+use tokio_rustls::rustls::{self, internal::pemfile, ClientConfig};
 use tokio_util::codec::FramedRead;
 
-use crate::container::LogOutput;
+use crate::audit::{Hooks, RequestHook, ResponseHook};
+use crate::container::{AttachContainerResults, ContainerStateChange, LogOutput};
 use crate::errors::Error;
+#[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
+use crate::errors::ErrorKind::NoCertPathError;
+#[cfg(feature = "openssl")]
+use crate::errors::ErrorKind::SSLError;
+#[cfg(feature = "tokio")]
+use crate::errors::ErrorKind::UnsupportedDockerHostError;
 use crate::errors::ErrorKind::{
     APIVersionParseError, DockerResponseBadParameterError, DockerResponseConflictError,
     DockerResponseNotFoundError, DockerResponseNotModifiedError, DockerResponseServerError,
-    HttpClientError, HyperResponseError, JsonDataError, JsonDeserializeError, JsonSerializeError,
-    RequestTimeoutError, StrParseError,
+    HttpClientError, HyperResponseError, InvalidHeaderError, JsonDataError, JsonDeserializeError,
+    JsonSerializeError, RequestTimeoutError, StrParseError,
 };
-#[cfg(feature = "openssl")]
-use crate::errors::ErrorKind::{NoCertPathError, SSLError};
-#[cfg(windows)]
+#[cfg(feature = "rustls")]
+use crate::errors::ErrorKind::{CertificateParseError, RustlsError};
+#[cfg(feature = "tls")]
+use crate::errors::ErrorKind::{TLSError, TLSIdentityError};
+#[cfg(all(unix, feature = "tokio"))]
+use crate::fd::{inherited_stream, FdConnector};
+use crate::layer::{self, DockerService, Layer, Service};
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+use crate::linux_socket::{SocketAddress, SocketConnector};
+#[cfg(all(windows, feature = "tokio"))]
 use crate::named_pipe::NamedPipeConnector;
-use crate::read::{JsonLineDecoder, NewlineLogOutputDecoder, StreamReader};
+#[cfg(feature = "tokio")]
+use crate::proxy::{Proxy, ProxyConnector};
+use crate::read::{IdleTimeout, JsonLineDecoder, NewlineLogOutputDecoder, StreamReader};
+use crate::retry::RetryPolicy;
+#[cfg(not(feature = "tokio"))]
+use crate::runtime::NoRuntime;
+#[cfg(feature = "tokio")]
+use crate::runtime::TokioRuntime;
+use crate::runtime::{Runtime, RuntimeExecutor};
+use crate::shutdown::ClientState;
+#[cfg(feature = "ssh")]
+use crate::ssh::{SshConnector, SshDestination};
 use crate::system::Version;
 use crate::uri::Uri;
 
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, Deserializer, IntoDeserializer};
 use serde::ser::Serialize;
+use serde::Deserialize;
 use serde_json;
 
 /// The default `DOCKER_SOCKET` address that we will try to connect to.
-#[cfg(unix)]
+#[cfg(all(unix, feature = "tokio"))]
 pub const DEFAULT_SOCKET: &'static str = "unix:///var/run/docker.sock";
 
+/// The sockets of rootless docker and podman servers, relative to `XDG_RUNTIME_DIR`, in the order
+/// they are tried before `DEFAULT_SOCKET`.
+#[cfg(all(unix, feature = "tokio"))]
+const ROOTLESS_SOCKETS: [&str; 2] = ["docker.sock", "podman/podman.sock"];
+
+/// The first existing socket of a rootless server, or else `DEFAULT_SOCKET`.
+#[cfg(all(unix, feature = "tokio"))]
+fn default_unix_socket() -> String {
+    env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .and_then(|dir| {
+            ROOTLESS_SOCKETS
+                .iter()
+                .map(|socket| Path::new(&dir).join(socket))
+                .find(|socket| {
+                    socket
+                        .metadata()
+                        .map(|metadata| metadata.file_type().is_socket())
+                        .unwrap_or(false)
+                })
+        })
+        .map(|socket| format!("unix://{}", socket.display()))
+        .unwrap_or_else(|| String::from(DEFAULT_SOCKET))
+}
+
 /// The default `DOCKER_NAMED_PIPE` address that a windows client will try to connect to.
-#[cfg(windows)]
+#[cfg(all(windows, feature = "tokio"))]
 pub const DEFAULT_NAMED_PIPE: &'static str = "npipe:////./pipe/docker_engine";
 
 /// The default `DOCKER_HOST` address that we will try to connect to.
+#[cfg(feature = "tokio")]
 pub const DEFAULT_DOCKER_HOST: &'static str = "tcp://localhost:2375";
 
 /// Default timeout for all requests is 2 minutes.
+#[cfg(feature = "tokio")]
 const DEFAULT_TIMEOUT: u64 = 120;
 
 /// Default Client Version to communicate with the server.
@@ -79,9 +160,71 @@ pub const API_DEFAULT_VERSION: &'static ClientVersion = &ClientVersion {
 pub(crate) const TRUE_STR: &'static str = "true";
 pub(crate) const FALSE_STR: &'static str = "false";
 
+/// Deserialize a JSON `null` or a missing array into an empty `Vec`, as the docker server is
+/// inconsistent in how it returns empty lists.
+pub(crate) fn deserialize_nonoptional_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<Vec<T>>::deserialize(deserializer).map(|v| v.unwrap_or_default())
+}
+
+/// Deserialize a JSON `null` or a missing object into an empty `HashMap`, as the docker server
+/// returns `null` for empty maps on some endpoints.
+pub(crate) fn deserialize_nonoptional_map<'de, D, K, V>(
+    deserializer: D,
+) -> Result<HashMap<K, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    Option::<HashMap<K, V>>::deserialize(deserializer).map(|v| v.unwrap_or_default())
+}
+
+/// Deserialize a JSON `null`, a missing value or an empty string into `None`, as the docker server
+/// returns `""` for unset enumerated values on some endpoints.
+pub(crate) fn deserialize_empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(ref value) if value.is_empty() => Ok(None),
+        Some(value) => T::deserialize(value.into_deserializer()).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Encode a time as a UNIX timestamp in the `<seconds>.<nanoseconds>` form of the `since` and
+/// `until` query parameters.
+pub(crate) fn serialize_timestamp(time: &DateTime<Utc>) -> String {
+    format!("{}.{:09}", time.timestamp(), time.timestamp_subsec_nanos())
+}
+
+/// Encode filters in the format sent by the docker CLI: a JSON object mapping each filter name to
+/// an object of its values set to `true`, with sorted keys.
+pub(crate) fn serialize_filters<T>(filters: &HashMap<T, Vec<T>>) -> Result<String, Error>
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    let filters: BTreeMap<&str, BTreeMap<&str, bool>> = filters
+        .iter()
+        .map(|(name, values)| {
+            (
+                name.as_ref(),
+                values.iter().map(|value| (value.as_ref(), true)).collect(),
+            )
+        })
+        .collect();
+
+    serde_json::to_string(&filters).map_err(|e| JsonSerializeError { err: e }.into())
+}
+
 /// The default directory in which to look for our Docker certificate
 /// files.
-#[cfg(any(feature = "ssl", feature = "tls"))]
+#[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
 pub fn default_cert_path() -> Result<PathBuf, Error> {
     let from_env = env::var("DOCKER_CERT_PATH").or_else(|_| env::var("DOCKER_CONFIG"));
     if let Ok(ref path) = from_env {
@@ -94,13 +237,15 @@ pub fn default_cert_path() -> Result<PathBuf, Error> {
 
 #[derive(Debug, Clone)]
 pub(crate) enum ClientType {
-    #[cfg(unix)]
+    #[cfg(all(unix, feature = "tokio"))]
     Unix,
     Http,
-    #[cfg(any(feature = "ssl", feature = "tls"))]
+    #[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
     SSL,
-    #[cfg(windows)]
+    #[cfg(all(windows, feature = "tokio"))]
     NamedPipe,
+    #[cfg(feature = "ssh")]
+    Ssh,
 }
 
 /// Transport is the type representing the means of communication
@@ -109,39 +254,318 @@ pub(crate) enum ClientType {
 /// Each transport usually encapsulate a hyper client
 /// with various Connect traits fulfilled.
 pub(crate) enum Transport {
+    #[cfg(feature = "tokio")]
     Http {
-        client: Client<HttpConnector>,
+        client: Client<ProxyConnector>,
+        connector: ProxyConnector,
     },
     #[cfg(feature = "openssl")]
     Https {
-        client: Client<HttpsConnector<HttpConnector>>,
+        client: Client<HttpsConnector<ProxyConnector>>,
+        connector: HttpsConnector<ProxyConnector>,
     },
     #[cfg(feature = "tls")]
     Tls {
-        client: Client<hyper_tls::HttpsConnector<HttpConnector>>,
+        client: Client<hyper_tls::HttpsConnector<ProxyConnector>>,
+        connector: hyper_tls::HttpsConnector<ProxyConnector>,
+    },
+    #[cfg(feature = "rustls")]
+    Rustls {
+        client: Client<hyper_rustls::HttpsConnector<ProxyConnector>>,
+        connector: hyper_rustls::HttpsConnector<ProxyConnector>,
     },
-    #[cfg(unix)]
+    #[cfg(all(unix, feature = "tokio"))]
     Unix {
         client: Client<UnixConnector>,
+        connector: UnixConnector,
     },
-    #[cfg(windows)]
+    #[cfg(all(unix, feature = "tokio"))]
+    Fd {
+        client: Client<FdConnector>,
+        connector: FdConnector,
+    },
+    #[cfg(all(target_os = "linux", feature = "tokio"))]
+    Socket {
+        client: Client<SocketConnector>,
+        connector: SocketConnector,
+    },
+    #[cfg(all(windows, feature = "tokio"))]
     NamedPipe {
         client: Client<NamedPipeConnector>,
+        connector: NamedPipeConnector,
+    },
+    #[cfg(feature = "ssh")]
+    Ssh {
+        client: Client<SshConnector>,
+        connector: SshConnector,
+    },
+    Custom {
+        request: RequestFn,
+        build: Arc<dyn Fn(&client::Builder) -> RequestFn + Send + Sync>,
+    },
+}
+
+/// Sends a request through a client of a custom connector.
+pub(crate) type RequestFn = Box<dyn Fn(Request<Body>) -> ResponseFuture + Send + Sync>;
+
+impl Transport {
+    /// A transport over the same connector, with a client built from the pool options that
+    /// spawns its connections on the runtime.
+    fn rebuild(&self, pool: &PoolOptions, runtime: &Arc<dyn Runtime>) -> Transport {
+        let mut builder = pool.client_builder();
+        builder.executor(RuntimeExecutor(runtime.clone()));
+        match self {
+            #[cfg(feature = "tokio")]
+            Transport::Http { connector, .. } => Transport::Http {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            #[cfg(feature = "openssl")]
+            Transport::Https { connector, .. } => Transport::Https {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            #[cfg(feature = "tls")]
+            Transport::Tls { connector, .. } => Transport::Tls {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            #[cfg(feature = "rustls")]
+            Transport::Rustls { connector, .. } => Transport::Rustls {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            #[cfg(all(unix, feature = "tokio"))]
+            Transport::Unix { connector, .. } => {
+                // connections to the unix socket are never reused, whatever the pool options
+                builder.pool_max_idle_per_host(0);
+                Transport::Unix {
+                    client: builder.build(connector.clone()),
+                    connector: connector.clone(),
+                }
+            }
+            #[cfg(all(unix, feature = "tokio"))]
+            Transport::Fd { connector, .. } => Transport::Fd {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            #[cfg(all(target_os = "linux", feature = "tokio"))]
+            Transport::Socket { connector, .. } => Transport::Socket {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            #[cfg(all(windows, feature = "tokio"))]
+            Transport::NamedPipe { connector, .. } => {
+                builder.pool_max_idle_per_host(0);
+                builder.http1_title_case_headers(true);
+                Transport::NamedPipe {
+                    client: builder.build(connector.clone()),
+                    connector: connector.clone(),
+                }
+            }
+            #[cfg(feature = "ssh")]
+            Transport::Ssh { connector, .. } => Transport::Ssh {
+                client: builder.build(connector.clone()),
+                connector: connector.clone(),
+            },
+            Transport::Custom { build, .. } => Transport::Custom {
+                request: build(&builder),
+                build: build.clone(),
+            },
+        }
+    }
+}
+
+/// The client certificate and private key presented to the docker server by
+/// [`Docker::connect_with_ssl_identity`](struct.Docker.html#method.connect_with_ssl_identity).
+#[cfg(feature = "openssl")]
+#[derive(Debug, Copy, Clone)]
+pub enum SslIdentity<'a> {
+    /// PEM encoded private key and certificate files, with the passphrase of the private key if
+    /// it is encrypted.
+    Pem {
+        /// The private key.
+        key: &'a Path,
+        /// The certificate.
+        cert: &'a Path,
+        /// The passphrase of an encrypted private key.
+        passphrase: Option<&'a str>,
     },
+    /// A PKCS #12 archive of the private key and certificate chain, e.g. a `.pfx` or `.p12`
+    /// file, with its password.
+    Pkcs12 {
+        /// The PKCS #12 archive.
+        archive: &'a Path,
+        /// The password of the archive, which may be empty.
+        password: &'a str,
+    },
+}
+
+/// Health check shared by the clones of a client, set with
+/// [`Docker::with_health_check`](struct.Docker.html#method.with_health_check).
+#[derive(Debug)]
+pub(crate) struct HealthCheck {
+    interval: Duration,
+    state: Mutex<HealthState>,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    // the transport replacing the one of the client, once reconnected
+    transport: Option<Arc<Transport>>,
+    last_success: Option<Instant>,
+}
+
+impl HealthCheck {
+    fn new(interval: Duration) -> HealthCheck {
+        HealthCheck {
+            interval,
+            state: Mutex::new(HealthState::default()),
+        }
+    }
+
+    /// The reconnected transport, if any, and whether a request succeeded within the interval.
+    fn current(&self) -> (Option<Arc<Transport>>, bool) {
+        let state = self.state.lock().unwrap();
+        let healthy = state
+            .last_success
+            .map_or(false, |last_success| last_success.elapsed() < self.interval);
+        (state.transport.clone(), healthy)
+    }
+
+    fn report(&self, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.last_success = if success { Some(Instant::now()) } else { None };
+    }
+
+    fn reconnected(&self, transport: Arc<Transport>) {
+        let mut state = self.state.lock().unwrap();
+        state.transport = Some(transport);
+        state.last_success = None;
+    }
+}
+
+/// Sends a request to the docker server once past the hooks and layers of a client, checking
+/// that the server is reachable and retrying the request according to the client options.
+#[derive(Debug)]
+pub(crate) struct Dispatch {
+    transport: Arc<Transport>,
+    timeout: Option<Duration>,
+    retry: Option<Arc<RetryPolicy>>,
+    pool: PoolOptions,
+    runtime: Arc<dyn Runtime>,
+    health_check: Option<(Arc<HealthCheck>, Result<Request<Body>, Error>)>,
+}
+
+impl Dispatch {
+    pub(crate) async fn send(self, request: Request<Body>) -> Result<Response<Body>, Error> {
+        let Dispatch {
+            transport,
+            timeout,
+            retry,
+            pool,
+            runtime,
+            health_check,
+        } = self;
+
+        let (health_check, transport) = match health_check {
+            Some((health_check, ping)) => {
+                let transport = Docker::checked_transport(
+                    &health_check,
+                    transport,
+                    ping?,
+                    pool,
+                    timeout,
+                    &runtime,
+                )
+                .await;
+                (Some(health_check), transport)
+            }
+            None => (None, transport),
+        };
+        let response =
+            Docker::execute_with_retry(transport, request, timeout, retry, &runtime).await;
+        if let Some(health_check) = health_check {
+            health_check.report(response.is_ok());
+        }
+        response
+    }
+}
+
+/// Connection pool settings of a client, set with
+/// [`Docker::with_pool_options`](struct.Docker.html#method.with_pool_options).
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::PoolOptions;
+///
+/// use std::time::Duration;
+///
+/// PoolOptions {
+///     max_idle_per_host: 8,
+///     idle_timeout: Some(Duration::from_secs(30)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PoolOptions {
+    /// The maximum number of idle connections kept open to the docker server. Defaults to no
+    /// limit.
+    pub max_idle_per_host: usize,
+    /// How long an idle connection is kept open, or indefinitely if `None`. Defaults to 90
+    /// seconds.
+    pub idle_timeout: Option<Duration>,
+    /// Whether connections are kept open to be reused by later requests. Defaults to `true`,
+    /// while `false` opens a connection for every request.
+    pub keep_alive: bool,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        PoolOptions {
+            max_idle_per_host: usize::max_value(),
+            idle_timeout: Some(Duration::from_secs(90)),
+            keep_alive: true,
+        }
+    }
+}
+
+impl PoolOptions {
+    fn client_builder(&self) -> client::Builder {
+        let mut builder = Client::builder();
+        builder.pool_idle_timeout(self.idle_timeout);
+        builder.pool_max_idle_per_host(if self.keep_alive {
+            self.max_idle_per_host
+        } else {
+            0
+        });
+        builder
+    }
 }
 
 impl fmt::Debug for Transport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "tokio")]
             Transport::Http { .. } => write!(f, "HTTP"),
             #[cfg(feature = "openssl")]
             Transport::Https { .. } => write!(f, "HTTPS(openssl)"),
             #[cfg(feature = "tls")]
             Transport::Tls { .. } => write!(f, "HTTPS(native)"),
-            #[cfg(unix)]
+            #[cfg(feature = "rustls")]
+            Transport::Rustls { .. } => write!(f, "HTTPS(rustls)"),
+            #[cfg(all(unix, feature = "tokio"))]
             Transport::Unix { .. } => write!(f, "Unix"),
-            #[cfg(windows)]
+            #[cfg(all(unix, feature = "tokio"))]
+            Transport::Fd { .. } => write!(f, "Fd"),
+            #[cfg(all(target_os = "linux", feature = "tokio"))]
+            Transport::Socket { .. } => write!(f, "Socket"),
+            #[cfg(all(windows, feature = "tokio"))]
             Transport::NamedPipe { .. } => write!(f, "NamedPipe"),
+            #[cfg(feature = "ssh")]
+            Transport::Ssh { .. } => write!(f, "SSH"),
+            Transport::Custom { .. } => write!(f, "Custom"),
         }
     }
 }
@@ -216,15 +640,27 @@ impl From<&(AtomicUsize, AtomicUsize)> for ClientVersion {
 ///  - [`Docker::connect_with_http_defaults`](struct.Docker.html#method.connect_with_http_defaults)
 ///  - [`Docker::connect_with_named_pipe_defaults`](struct.Docker.html#method.connect_with_pipe_defaults)
 ///  - [`Docker::connect_with_ssl_defaults`](struct.Docker.html#method.connect_with_ssl_defaults)
+///  - [`Docker::connect_with_rustls_defaults`](struct.Docker.html#method.connect_with_rustls_defaults)
 ///  - [`Docker::connect_with_unix_defaults`](struct.Docker.html#method.connect_with_unix_defaults)
 ///  - [`Docker::connect_with_tls_defaults`](struct.Docker.html#method.connect_with_tls_defaults)
 ///  - [`Docker::connect_with_local_defaults`](struct.Docker.html#method.connect_with_local_defaults)
+///  - [`Docker::connect_with_connector`](struct.Docker.html#method.connect_with_connector)
 pub struct Docker {
     pub(crate) transport: Arc<Transport>,
     pub(crate) client_type: ClientType,
     pub(crate) client_addr: String,
-    pub(crate) client_timeout: u64,
+    pub(crate) client_timeout: Option<Duration>,
+    pub(crate) stream_idle_timeout: Option<Duration>,
+    pub(crate) retry: Option<Arc<RetryPolicy>>,
+    pub(crate) pool: PoolOptions,
+    pub(crate) runtime: Arc<dyn Runtime>,
+    pub(crate) health_check: Option<Arc<HealthCheck>>,
+    pub(crate) default_headers: Arc<HeaderMap>,
+    pub(crate) service: Option<DockerService>,
+    pub(crate) log_warnings: bool,
     pub(crate) version: Arc<(AtomicUsize, AtomicUsize)>,
+    pub(crate) state: Arc<ClientState>,
+    pub(crate) hooks: Hooks,
 }
 
 impl Clone for Docker {
@@ -234,7 +670,53 @@ impl Clone for Docker {
             client_type: self.client_type.clone(),
             client_addr: self.client_addr.clone(),
             client_timeout: self.client_timeout,
+            stream_idle_timeout: self.stream_idle_timeout,
+            retry: self.retry.clone(),
+            pool: self.pool,
+            runtime: self.runtime.clone(),
+            health_check: self.health_check.clone(),
+            default_headers: self.default_headers.clone(),
+            service: self.service.clone(),
+            log_warnings: self.log_warnings,
             version: self.version.clone(),
+            state: self.state.clone(),
+            hooks: self.hooks.clone(),
+        }
+    }
+}
+
+impl Docker {
+    /// A client over a transport, with the defaults of every connect method.
+    fn new_with(
+        transport: Transport,
+        client_type: ClientType,
+        client_addr: String,
+        pool: PoolOptions,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Docker {
+        Docker {
+            transport: Arc::new(transport),
+            client_type,
+            client_addr,
+            client_timeout: Some(Duration::from_secs(timeout)),
+            stream_idle_timeout: None,
+            retry: None,
+            pool,
+            #[cfg(feature = "tokio")]
+            runtime: Arc::new(TokioRuntime),
+            #[cfg(not(feature = "tokio"))]
+            runtime: Arc::new(NoRuntime),
+            health_check: None,
+            default_headers: Arc::new(HeaderMap::new()),
+            service: None,
+            log_warnings: false,
+            version: Arc::new((
+                AtomicUsize::new(client_version.major_version),
+                AtomicUsize::new(client_version.minor_version),
+            )),
+            state: Arc::new(ClientState::default()),
+            hooks: Hooks::default(),
         }
     }
 }
@@ -326,131 +808,185 @@ impl Docker {
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        // This ensures that using docker-machine-esque addresses work with Hyper.
-        let client_addr = addr.replacen("tcp://", "", 1);
-
-        let mut ssl_connector_builder = SslConnector::builder(SslMethod::tls())
-            .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
-
-        ssl_connector_builder
-            .set_ca_file(ssl_ca)
-            .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
-        ssl_connector_builder
-            .set_certificate_file(ssl_cert, SslFiletype::PEM)
-            .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
-        ssl_connector_builder
-            .set_private_key_file(ssl_key, SslFiletype::PEM)
-            .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
-
-        let mut http_connector = HttpConnector::new();
-        http_connector.enforce_http(false);
-
-        let https_connector: HttpsConnector<HttpConnector> =
-            HttpsConnector::with_connector(http_connector, ssl_connector_builder)
-                .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
-
-        let client_builder = Client::builder();
-        let client = client_builder.build(https_connector);
-        let transport = Transport::Https { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::SSL,
-            client_addr: client_addr.to_owned(),
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
+        let identity = SslIdentity::Pem {
+            key: ssl_key,
+            cert: ssl_cert,
+            passphrase: None,
         };
-
-        Ok(docker)
+        Docker::connect_with_ssl_identity(addr, identity, ssl_ca, timeout, client_version)
     }
-}
 
-/// A Docker implementation typed to connect to an unsecure Http connection.
-impl Docker {
-    /// Connect using unsecured HTTP using defaults that are signalled by environment variables.
+    /// Connect using secure HTTPS, presenting a client identity that may be protected by a
+    /// passphrase: an encrypted PEM private key, or a PKCS #12 archive.
     ///
-    /// # Defaults
+    /// # Arguments
     ///
-    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, and defaults
-    ///  to `localhost:2375`.
-    ///  - The number of threads used for the HTTP connection pool defaults to 1.
-    ///  - The request timeout defaults to 2 minutes.
+    ///  - `addr`: connection url including scheme and port.
+    ///  - `identity`: the [SSL Identity](struct.SslIdentity.html) of the client.
+    ///  - `ssl_ca`: the certificate chain.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use bollard::Docker;
+    /// use bollard::{API_DEFAULT_VERSION, Docker, SslIdentity};
+    ///
+    /// use std::path::Path;
     ///
     /// use futures_util::future::TryFutureExt;
     ///
-    /// let connection = Docker::connect_with_http_defaults().unwrap();
+    /// let identity = SslIdentity::Pkcs12 {
+    ///     archive: Path::new("/certs/identity.p12"),
+    ///     password: "my_secret_password",
+    /// };
+    /// let connection = Docker::connect_with_ssl_identity(
+    ///     "localhost:2375", identity, Path::new("/certs/ca.pem"), 120, API_DEFAULT_VERSION)
+    ///     .unwrap();
     /// connection.ping()
     ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
-    pub fn connect_with_http_defaults() -> Result<Docker, Error> {
-        let host = env::var("DOCKER_HOST").unwrap_or(DEFAULT_DOCKER_HOST.to_string());
-        Docker::connect_with_http(&host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    pub fn connect_with_ssl_identity(
+        addr: &str,
+        identity: SslIdentity<'_>,
+        ssl_ca: &Path,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        Docker::connect_with_ssl_proxy(
+            addr,
+            identity,
+            ssl_ca,
+            Proxy::from_env(),
+            timeout,
+            client_version,
+        )
     }
 
-    /// Connect using unsecured HTTP.
+    /// Connect using secure HTTPS through an explicit [Proxy](proxy/struct.Proxy.html), in place
+    /// of the proxy configured by the `HTTPS_PROXY` and `NO_PROXY` environment variables.
     ///
     /// # Arguments
     ///
     ///  - `addr`: connection url including scheme and port.
+    ///  - `identity`: the [SSL Identity](struct.SslIdentity.html) of the client.
+    ///  - `ssl_ca`: the certificate chain.
+    ///  - `proxy`: the proxy to connect through.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
     ///  - `client_version`: the client version to communicate with the server.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    /// use bollard::{API_DEFAULT_VERSION, Docker, SslIdentity};
+    /// use bollard::proxy::Proxy;
     ///
-    /// use futures_util::future::TryFutureExt;
+    /// use std::path::Path;
     ///
-    /// let connection = Docker::connect_with_http(
-    ///                    "http://my-custom-docker-server:2735", 4, API_DEFAULT_VERSION)
-    ///                    .unwrap();
-    /// connection.ping()
-    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let identity = SslIdentity::Pem {
+    ///     key: Path::new("/certs/key.pem"),
+    ///     cert: Path::new("/certs/cert.pem"),
+    ///     passphrase: None,
+    /// };
+    /// let proxy = Proxy::all("http://proxy.example.com:3128").unwrap();
+    /// let connection = Docker::connect_with_ssl_proxy(
+    ///     "docker.example.com:2376", identity, Path::new("/certs/ca.pem"), proxy, 120,
+    ///     API_DEFAULT_VERSION).unwrap();
     /// ```
-    pub fn connect_with_http(
+    pub fn connect_with_ssl_proxy(
         addr: &str,
+        identity: SslIdentity<'_>,
+        ssl_ca: &Path,
+        proxy: Proxy,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
         // This ensures that using docker-machine-esque addresses work with Hyper.
         let client_addr = addr.replacen("tcp://", "", 1);
 
-        let http_connector = HttpConnector::new();
+        let mut ssl_connector_builder = SslConnector::builder(SslMethod::tls())
+            .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+
+        ssl_connector_builder
+            .set_ca_file(ssl_ca)
+            .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+        match identity {
+            SslIdentity::Pem {
+                key,
+                cert,
+                passphrase,
+            } => {
+                ssl_connector_builder
+                    .set_certificate_file(cert, SslFiletype::PEM)
+                    .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+                match passphrase {
+                    Some(passphrase) => {
+                        let key = PKey::private_key_from_pem_passphrase(
+                            &fs::read(key)?,
+                            passphrase.as_bytes(),
+                        )
+                        .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+                        ssl_connector_builder.set_private_key(&key)
+                    }
+                    None => ssl_connector_builder.set_private_key_file(key, SslFiletype::PEM),
+                }
+                .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+            }
+            SslIdentity::Pkcs12 { archive, password } => {
+                let pkcs12 = Pkcs12::from_der(&fs::read(archive)?)
+                    .and_then(|pkcs12| pkcs12.parse(password))
+                    .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+                ssl_connector_builder
+                    .set_certificate(&pkcs12.cert)
+                    .and_then(|_| ssl_connector_builder.set_private_key(&pkcs12.pkey))
+                    .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+                for cert in pkcs12.chain.into_iter().flatten() {
+                    ssl_connector_builder
+                        .add_extra_chain_cert(cert)
+                        .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
+                }
+            }
+        }
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let https_connector: HttpsConnector<ProxyConnector> = HttpsConnector::with_connector(
+            ProxyConnector::new(http_connector, proxy),
+            ssl_connector_builder,
+        )
+        .map_err::<Error, _>(|e| SSLError { err: e }.into())?;
 
         let client_builder = Client::builder();
-        let client = client_builder.build(http_connector);
-        let transport = Transport::Http { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Http,
-            client_addr: client_addr.to_owned(),
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
+        let client = client_builder.build(https_connector.clone());
+        let transport = Transport::Https {
+            client,
+            connector: https_connector,
         };
-
-        Ok(docker)
+        Ok(Docker::new_with(
+            transport,
+            ClientType::SSL,
+            client_addr,
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
     }
 }
 
-#[cfg(unix)]
-/// A Docker implementation typed to connect to a Unix socket.
+#[cfg(feature = "rustls")]
+/// A Docker implementation typed to connect to a secure HTTPS connection using the `rustls`
+/// library.
 impl Docker {
-    /// Connect using a Unix socket using defaults that are signalled by environment variables.
+    /// Connect using secure HTTPS with rustls, using defaults that are signalled by environment
+    /// variables.
     ///
     /// # Defaults
     ///
-    ///  - The socket location defaults to `/var/run/docker.sock`.
+    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable.
+    ///  - The certificate directory is sourced from the `DOCKER_CERT_PATH` environment variable.
+    ///  - Certificates are named `key.pem`, `cert.pem` and `ca.pem` to indicate the private key,
+    ///  the client certificate and the certificate authority chain respectively.
     ///  - The request timeout defaults to 2 minutes.
     ///
     /// # Examples
@@ -460,18 +996,35 @@ impl Docker {
     ///
     /// use futures_util::future::TryFutureExt;
     ///
-    /// let connection = Docker::connect_with_unix_defaults().unwrap();
-    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_rustls_defaults().unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
-    pub fn connect_with_unix_defaults() -> Result<Docker, Error> {
-        Docker::connect_with_unix(DEFAULT_SOCKET, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    pub fn connect_with_rustls_defaults() -> Result<Docker, Error> {
+        let cert_path = default_cert_path()?;
+        let host = env::var("DOCKER_HOST").unwrap_or_else(|_| DEFAULT_DOCKER_HOST.to_string());
+        Docker::connect_with_rustls(
+            &host,
+            &cert_path.join("key.pem"),
+            &cert_path.join("cert.pem"),
+            &cert_path.join("ca.pem"),
+            DEFAULT_TIMEOUT,
+            API_DEFAULT_VERSION,
+        )
     }
 
-    /// Connect using a Unix socket.
+    /// Connect using secure HTTPS with rustls. The arguments are the same as those of
+    /// `connect_with_ssl` with the `ssl` feature, so that either backend can be used. The docker
+    /// server is verified with
+    /// webpki, which checks the certificate against the host name, so the connection url must
+    /// name the host as it appears in the certificate rather than by IP address.
     ///
     /// # Arguments
     ///
-    ///  - `addr`: connection socket path.
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key path, in PKCS #8 or RSA PEM format.
+    ///  - `ssl_cert`: the client certificate path.
+    ///  - `ssl_ca`: the certificate authority chain path.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
     ///  - `client_version`: the client version to communicate with the server.
     ///
@@ -480,72 +1033,175 @@ impl Docker {
     /// ```rust,no_run
     /// use bollard::{API_DEFAULT_VERSION, Docker};
     ///
+    /// use std::path::Path;
+    ///
     /// use futures_util::future::TryFutureExt;
     ///
-    /// let connection = Docker::connect_with_unix("/var/run/docker.sock", 120, API_DEFAULT_VERSION).unwrap();
-    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_rustls(
+    ///     "docker.example.com:2376",
+    ///     Path::new("/certs/key.pem"),
+    ///     Path::new("/certs/cert.pem"),
+    ///     Path::new("/certs/ca.pem"),
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
-    pub fn connect_with_unix(
+    pub fn connect_with_rustls(
         addr: &str,
+        ssl_key: &Path,
+        ssl_cert: &Path,
+        ssl_ca: &Path,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        let client_addr = addr.replacen("unix://", "", 1);
-
-        let unix_connector = UnixConnector;
-
-        let mut client_builder = Client::builder();
-        client_builder.pool_max_idle_per_host(0);
-
-        let client = client_builder.build(unix_connector);
-        let transport = Transport::Unix { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Unix,
-            client_addr: client_addr.to_owned(),
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+        Docker::connect_with_rustls_proxy(
+            addr,
+            ssl_key,
+            ssl_cert,
+            ssl_ca,
+            Proxy::from_env(),
+            timeout,
+            client_version,
+        )
     }
-}
 
-#[cfg(windows)]
-/// A Docker implementation typed to connect to a Windows Named Pipe, exclusive to the windows
-/// target.
-impl Docker {
-    /// Connect using a Windows Named Pipe using defaults that are signalled by environment
-    /// variables.
+    /// Connect using secure HTTPS with rustls through an explicit
+    /// [Proxy](proxy/struct.Proxy.html), in place of the proxy configured by the `HTTPS_PROXY`
+    /// and `NO_PROXY` environment variables.
     ///
-    /// # Defaults
+    /// # Arguments
     ///
-    ///  - The socket location defaults to `//./pipe/docker_engine`.
-    ///  - The request timeout defaults to 2 minutes.
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key path, in PKCS #8 or RSA PEM format.
+    ///  - `ssl_cert`: the client certificate path.
+    ///  - `ssl_ca`: the certificate authority chain path.
+    ///  - `proxy`: the proxy to connect through.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use bollard::Docker;
-    ///
-    /// use futures_util::future::TryFutureExt;
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    /// use bollard::proxy::Proxy;
     ///
-    /// let connection = Docker::connect_with_named_pipe_defaults().unwrap();
-    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// use std::path::Path;
     ///
+    /// let proxy = Proxy::all("http://proxy.example.com:3128").unwrap();
+    /// let connection = Docker::connect_with_rustls_proxy(
+    ///     "docker.example.com:2376",
+    ///     Path::new("/certs/key.pem"),
+    ///     Path::new("/certs/cert.pem"),
+    ///     Path::new("/certs/ca.pem"),
+    ///     proxy,
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
     /// ```
-    pub fn connect_with_named_pipe_defaults() -> Result<Docker, Error> {
-        Docker::connect_with_named_pipe(DEFAULT_NAMED_PIPE, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    pub fn connect_with_rustls_proxy(
+        addr: &str,
+        ssl_key: &Path,
+        ssl_cert: &Path,
+        ssl_ca: &Path,
+        proxy: Proxy,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        use std::fs::File;
+        use std::io::BufReader;
+
+        // This ensures that using docker-machine-esque addresses work with Hyper.
+        let client_addr = addr.replacen("tcp://", "", 1);
+
+        let parse_error = |path: &Path| CertificateParseError {
+            path: path.to_owned(),
+        };
+
+        let mut config = ClientConfig::new();
+        match config
+            .root_store
+            .add_pem_file(&mut BufReader::new(File::open(ssl_ca)?))
+        {
+            Ok((added, _)) if added > 0 => (),
+            _ => return Err(parse_error(ssl_ca).into()),
+        }
+
+        let certs = pemfile::certs(&mut BufReader::new(File::open(ssl_cert)?))
+            .map_err(|_| parse_error(ssl_cert))?;
+
+        let mut keys = pemfile::pkcs8_private_keys(&mut BufReader::new(File::open(ssl_key)?))
+            .map_err(|_| parse_error(ssl_key))?;
+        if keys.is_empty() {
+            keys = pemfile::rsa_private_keys(&mut BufReader::new(File::open(ssl_key)?))
+                .map_err(|_| parse_error(ssl_key))?;
+        }
+        let key = keys
+            .into_iter()
+            .next()
+            .ok_or_else(|| parse_error(ssl_key))?;
+
+        config
+            .set_single_client_cert(certs, key)
+            .map_err(|err: rustls::TLSError| RustlsError { err })?;
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let https_connector = hyper_rustls::HttpsConnector::from((
+            ProxyConnector::new(http_connector, proxy),
+            config,
+        ));
+
+        let client_builder = Client::builder();
+        let client = client_builder.build(https_connector.clone());
+        let transport = Transport::Rustls {
+            client,
+            connector: https_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::SSL,
+            client_addr,
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
     }
+}
 
-    /// Connect using a Windows Named Pipe.
+/// A Docker implementation typed to connect to an unsecure Http connection.
+#[cfg(feature = "tokio")]
+impl Docker {
+    /// Connect using unsecured HTTP using defaults that are signalled by environment variables.
+    ///
+    /// # Defaults
+    ///
+    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, and defaults
+    ///  to `localhost:2375`.
+    ///  - The number of threads used for the HTTP connection pool defaults to 1.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_http_defaults().unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_http_defaults() -> Result<Docker, Error> {
+        let host = env::var("DOCKER_HOST").unwrap_or(DEFAULT_DOCKER_HOST.to_string());
+        Docker::connect_with_http(&host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    }
+
+    /// Connect using unsecured HTTP.
     ///
     /// # Arguments
     ///
-    ///  - `addr`: socket location.
+    ///  - `addr`: connection url including scheme and port.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
     ///  - `client_version`: the client version to communicate with the server.
     ///
@@ -556,338 +1212,1545 @@ impl Docker {
     ///
     /// use futures_util::future::TryFutureExt;
     ///
-    /// let connection = Docker::connect_with_named_pipe(
-    ///     "//./pipe/docker_engine", 120, API_DEFAULT_VERSION).unwrap();
-    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
-    ///
+    /// let connection = Docker::connect_with_http(
+    ///                    "http://my-custom-docker-server:2735", 4, API_DEFAULT_VERSION)
+    ///                    .unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
-    pub fn connect_with_named_pipe(
+    pub fn connect_with_http(
         addr: &str,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        let client_addr = addr.replacen("npipe://", "", 1);
-
-        let named_pipe_connector = NamedPipeConnector;
-
-        let mut client_builder = Client::builder();
-        client_builder.pool_max_idle_per_host(0);
-        client_builder.http1_title_case_headers(true);
-        let client = client_builder.build(named_pipe_connector);
-        let transport = Transport::NamedPipe { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::NamedPipe,
-            client_addr: client_addr.to_owned(),
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+        Docker::connect_with_http_proxy(addr, Proxy::from_env(), timeout, client_version)
     }
-}
 
-/// A Docker implementation that wraps away which local implementation we are calling.
-#[cfg(any(unix, windows))]
-impl Docker {
-    /// Connect using the local machine connection method with default arguments.
+    /// Connect using unsecured HTTP through an explicit [Proxy](proxy/struct.Proxy.html), in
+    /// place of the proxy configured by the `HTTP_PROXY` and `NO_PROXY` environment variables.
     ///
-    /// This is a simple wrapper over the OS specific handlers:
-    ///  * Unix: [`Docker::connect_with_unix_defaults`]
-    ///  * Windows: [`Docker::connect_with_named_pipe_defaults`]
+    /// # Arguments
     ///
-    /// [`Docker::connect_with_unix_defaults`]: struct.Docker.html#method.connect_with_unix_defaults
-    /// [`Docker::connect_with_named_pipe_defaults`]: struct.Docker.html#method.connect_with_named_pipe_defaults
-    pub fn connect_with_local_defaults() -> Result<Docker, Error> {
-        #[cfg(unix)]
-        return Docker::connect_with_unix_defaults();
-        #[cfg(windows)]
-        return Docker::connect_with_named_pipe_defaults();
-    }
-
-    /// Connect using the local machine connection method with supplied arguments.
+    ///  - `addr`: connection url including scheme and port.
+    ///  - `proxy`: the proxy to connect through.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
     ///
-    /// This is a simple wrapper over the OS specific handlers:
-    ///  * Unix: [`Docker::connect_with_unix`]
-    ///  * Windows: [`Docker::connect_with_named_pipe`]
+    /// # Examples
     ///
-    /// [`Docker::connect_with_unix`]: struct.Docker.html#method.connect_with_unix
-    /// [`Docker::connect_with_named_pipe`]: struct.Docker.html#method.connect_with_named_pipe
-    pub fn connect_with_local(
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    /// use bollard::proxy::Proxy;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let proxy = Proxy::all("http://proxy.example.com:3128").unwrap();
+    /// let connection = Docker::connect_with_http_proxy(
+    ///                    "http://my-custom-docker-server:2735", proxy, 4, API_DEFAULT_VERSION)
+    ///                    .unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_http_proxy(
         addr: &str,
+        proxy: Proxy,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        #[cfg(unix)]
-        return Docker::connect_with_unix(addr, timeout, client_version);
-        #[cfg(windows)]
-        return Docker::connect_with_named_pipe(addr, timeout, client_version);
+        // This ensures that using docker-machine-esque addresses work with Hyper.
+        let client_addr = addr.replacen("tcp://", "", 1);
+
+        let http_connector = HttpConnector::new();
+        let proxy_connector = ProxyConnector::new(http_connector, proxy);
+
+        let client_builder = Client::builder();
+        let client = client_builder.build(proxy_connector.clone());
+        let transport = Transport::Http {
+            client,
+            connector: proxy_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::Http,
+            client_addr,
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
     }
 }
 
-/// A Docker implementation typed to connect to a secure HTTPS connection, using the native rust
-/// TLS library.
-#[cfg(feature = "tls")]
+#[cfg(all(unix, feature = "tokio"))]
+/// A Docker implementation typed to connect to a Unix socket.
 impl Docker {
-    /// Connect using secure HTTPS using native TLS and defaults that are signalled by environment
-    /// variables.
+    /// Connect using a Unix socket using defaults that are signalled by environment variables.
     ///
     /// # Defaults
     ///
-    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable.
-    ///  - The certificate directory is sourced from the `DOCKER_CERT_PATH` environment variable.
-    ///  - Certificate PKCS #12 archive is named `identity.pfx` and the certificate chain is named `ca.pem`.
-    ///  - The password for the PKCS #12 archive defaults to an empty password.
-    ///  - The number of threads used for the HTTP connection pool defaults to 1.
+    ///  - The socket location is the first existing socket of:
+    ///    - `$XDG_RUNTIME_DIR/docker.sock`, of a rootless docker server.
+    ///    - `$XDG_RUNTIME_DIR/podman/podman.sock`, of a rootless podman service.
+    ///    - `/var/run/docker.sock`, which is also the default if none exist.
     ///  - The request timeout defaults to 2 minutes.
     ///
-    ///  # PKCS12
-    ///
-    ///  PKCS #12 archives can be created with OpenSSL:
-    ///
-    ///  ```bash
-    ///  openssl pkcs12 -export -out identity.pfx -inkey key.pem -in cert.pem -certfile
-    ///  chain_certs.pem
-    ///  ```
+    /// The chosen socket is returned by
+    /// [`client_addr`](struct.Docker.html#method.client_addr).
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use bollard::Docker;
     ///
-    /// use futures_util::try_future::TryFutureExt;
+    /// use futures_util::future::TryFutureExt;
     ///
-    /// let connection = Docker::connect_with_tls_defaults().unwrap();
-    /// connection.ping()
-    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_unix_defaults().unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
-    pub fn connect_with_tls_defaults() -> Result<Docker, Error> {
-        let cert_path = default_cert_path()?;
-        if let Ok(ref host) = env::var("DOCKER_HOST") {
-            Docker::connect_with_tls(
-                host,
-                &cert_path.join("identity.pfx"),
-                &cert_path.join("ca.pem"),
-                "",
-                DEFAULT_TIMEOUT,
-                API_DEFAULT_VERSION,
-            )
-        } else {
-            Docker::connect_with_tls(
-                DEFAULT_DOCKER_HOST,
-                &cert_path.join("identity.pfx"),
-                &cert_path.join("ca.pem"),
-                "",
-                DEFAULT_TIMEOUT,
-                API_DEFAULT_VERSION,
-            )
-        }
+    pub fn connect_with_unix_defaults() -> Result<Docker, Error> {
+        let socket = default_unix_socket();
+        Docker::connect_with_unix(&socket, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
     }
 
-    /// Connect using secure HTTPS using native TLS.
+    /// Connect using a Unix socket.
     ///
     /// # Arguments
     ///
-    ///  - `addr`: the connection url.
-    ///  - `pkcs12_file`: the PKCS #12 archive.
-    ///  - `ca_file`: the certificate chain.
-    ///  - `pkcs12_password`: the password to the PKCS #12 archive.
+    ///  - `addr`: connection socket path.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
     ///  - `client_version`: the client version to communicate with the server.
     ///
-    ///  # PKCS12
-    ///
-    ///  PKCS #12 archives can be created with OpenSSL:
-    ///
-    ///  ```bash
-    ///  openssl pkcs12 -export -out identity.pfx -inkey key.pem -in cert.pem -certfile
-    ///  chain_certs.pem
-    ///  ```
-    ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use bollard::Docker;
-    ///
-    /// use std::path::Path;
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
     ///
-    /// use futures_util::try_future::TryFutureExt;
+    /// use futures_util::future::TryFutureExt;
     ///
-    /// let connection = Docker::connect_with_tls(
-    ///     "localhost:2375",
-    ///     Path::new("/certs/identity.pfx"),
-    ///     Path::new("/certs/ca.pem"),
-    ///     "my_secret_password",
-    ///     1,
-    ///     120
-    /// ).unwrap();
-    /// connection.ping()
-    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_unix("/var/run/docker.sock", 120, API_DEFAULT_VERSION).unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
     /// ```
-    pub fn connect_with_tls(
+    pub fn connect_with_unix(
         addr: &str,
-        pkcs12_file: &Path,
-        ca_file: &Path,
-        pkcs12_password: &str,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        let client_addr = addr.replacen("tcp://", "", 1);
+        let client_addr = addr.replacen("unix://", "", 1);
 
-        let mut tls_connector_builder = TlsConnector::builder();
+        #[cfg(target_os = "linux")]
+        {
+            if client_addr.starts_with('@') {
+                let address = SocketAddress::Abstract(client_addr[1..].to_owned());
+                return Docker::connect_with_socket(address, timeout, client_version);
+            }
+        }
 
-        use crate::errors::ErrorKind;
-        use std::fs::File;
-        use std::io::Read;
-        let mut file = File::open(pkcs12_file)?;
-        let mut buf = vec![];
-        file.read_to_end(&mut buf)?;
-        let identity = Identity::from_pkcs12(&buf, pkcs12_password)
-            .map_err(|err| ErrorKind::TLSError { err })?;
-
-        let mut file = File::open(ca_file)?;
-        let mut buf = vec![];
-        file.read_to_end(&mut buf)?;
-        let ca = Certificate::from_pem(&buf).map_err(|err| ErrorKind::TLSError { err })?;
+        let unix_connector = UnixConnector;
 
-        let tls_connector_builder = tls_connector_builder.identity(identity);
-        tls_connector_builder.add_root_certificate(ca);
+        let mut client_builder = Client::builder();
+        client_builder.pool_max_idle_per_host(0);
 
-        let mut http_connector = HttpConnector::new();
-        http_connector.enforce_http(false);
+        let client = client_builder.build(unix_connector.clone());
+        let transport = Transport::Unix {
+            client,
+            connector: unix_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::Unix,
+            client_addr,
+            PoolOptions {
+                keep_alive: false,
+                ..Default::default()
+            },
+            timeout,
+            client_version,
+        ))
+    }
 
-        let tls_connector = tls_connector_builder
-            .build()
-            .map_err(|err| ErrorKind::TLSError { err })?;
-        let https_connector: hyper_tls::HttpsConnector<HttpConnector> =
-            hyper_tls::HttpsConnector::from((http_connector, tls_connector.into()));
+    /// Connect over a unix socket inherited as a file descriptor, e.g. from systemd socket
+    /// activation with `Accept=yes`. The client takes ownership of the connected socket, e.g.
+    /// built with
+    /// [`FromRawFd::from_raw_fd`](https://doc.rust-lang.org/std/os/unix/io/trait.FromRawFd.html).
+    ///
+    /// A file descriptor carries a single connection, so concurrent requests connect to the path
+    /// of the peer socket, if it is bound to one, and fail otherwise.
+    ///
+    /// # Arguments
+    ///
+    ///  - `stream`: the connected socket.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// use std::os::unix::io::FromRawFd;
+    /// use std::os::unix::net::UnixStream;
+    ///
+    /// // the connection to the docker server passed by systemd
+    /// let stream = unsafe { UnixStream::from_raw_fd(3) };
+    /// let connection = Docker::connect_with_fd(stream, 120, API_DEFAULT_VERSION).unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_fd(
+        stream: StdUnixStream,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let fd_connector = FdConnector::new(stream)?;
+
+        let client_builder = Client::builder();
+        let client = client_builder.build(fd_connector.clone());
+        let transport = Transport::Fd {
+            client,
+            connector: fd_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::Http,
+            String::from("localhost"),
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+/// A Docker implementation typed to connect to a vsock or abstract unix socket, exclusive to the
+/// linux target.
+impl Docker {
+    /// Connect to a vsock socket, e.g. of a docker server running in a virtual machine.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: connection url of the form `vsock://<cid>:<port>`.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_vsock("vsock://3:2375", 120, API_DEFAULT_VERSION)
+    ///     .unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_vsock(
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let address = SocketAddress::parse_vsock(addr)?;
+        Docker::connect_with_socket(address, timeout, client_version)
+    }
+
+    fn connect_with_socket(
+        address: SocketAddress,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let socket_connector = SocketConnector::new(address);
+
+        let client_builder = Client::builder();
+        let client = client_builder.build(socket_connector.clone());
+        let transport = Transport::Socket {
+            client,
+            connector: socket_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::Http,
+            String::from("localhost"),
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+#[cfg(all(windows, feature = "tokio"))]
+/// A Docker implementation typed to connect to a Windows Named Pipe, exclusive to the windows
+/// target.
+impl Docker {
+    /// Connect using a Windows Named Pipe using defaults that are signalled by environment
+    /// variables.
+    ///
+    /// # Defaults
+    ///
+    ///  - The socket location defaults to `//./pipe/docker_engine`.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_named_pipe_defaults().unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    ///
+    /// ```
+    pub fn connect_with_named_pipe_defaults() -> Result<Docker, Error> {
+        Docker::connect_with_named_pipe(DEFAULT_NAMED_PIPE, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    }
+
+    /// Connect using a Windows Named Pipe.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: socket location.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_named_pipe(
+    ///     "//./pipe/docker_engine", 120, API_DEFAULT_VERSION).unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    ///
+    /// ```
+    pub fn connect_with_named_pipe(
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let client_addr = addr.replacen("npipe://", "", 1);
+
+        let named_pipe_connector = NamedPipeConnector;
+
+        let mut client_builder = Client::builder();
+        client_builder.pool_max_idle_per_host(0);
+        client_builder.http1_title_case_headers(true);
+        let client = client_builder.build(named_pipe_connector.clone());
+        let transport = Transport::NamedPipe {
+            client,
+            connector: named_pipe_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::NamedPipe,
+            client_addr,
+            PoolOptions {
+                keep_alive: false,
+                ..Default::default()
+            },
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+#[cfg(feature = "ssh")]
+/// A Docker implementation typed to connect through ssh to a remote host.
+impl Docker {
+    /// Connect through ssh using defaults that are signalled by environment variables.
+    ///
+    /// # Defaults
+    ///
+    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, in the form
+    ///  `ssh://[user@]host[:port]`.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_ssh_defaults().unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_ssh_defaults() -> Result<Docker, Error> {
+        let host = env::var("DOCKER_HOST").unwrap_or_default();
+        Docker::connect_with_ssh(&host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    }
+
+    /// Connect through ssh, by running `docker system dial-stdio` on the remote host with the
+    /// `ssh` command. Every connection spawns an `ssh` process, so the command must be able to
+    /// log in without a prompt, e.g. with a key loaded in an agent.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: connection url of the form `ssh://[user@]host[:port]`.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_ssh(
+    ///     "ssh://deploy@build-01.example.com", 120, API_DEFAULT_VERSION).unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_ssh(
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let destination = SshDestination::parse(addr)?;
+        let client_addr = destination.host.clone();
+
+        let ssh_connector = SshConnector::new(destination);
+
+        let client_builder = Client::builder();
+        let client = client_builder.build(ssh_connector.clone());
+        let transport = Transport::Ssh {
+            client,
+            connector: ssh_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::Ssh,
+            client_addr,
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+/// A Docker implementation connecting through a user provided hyper connector.
+impl Docker {
+    /// Connect through a custom [hyper
+    /// connector](https://docs.rs/hyper/0.13/hyper/client/connect/trait.Connect.html), e.g. to
+    /// reach the docker server over a transport that is not supported by this crate. Requests are
+    /// sent over HTTP, with `http://<addr>` as the uri passed to the connector.
+    ///
+    /// # Arguments
+    ///
+    ///  - `connector`: the connector opening connections to the docker server.
+    ///  - `addr`: the host of the uri passed to the connector.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    /// use hyper::client::HttpConnector;
+    ///
+    /// let connection = Docker::connect_with_connector(
+    ///     HttpConnector::new(), "localhost:2375", 120, API_DEFAULT_VERSION).unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_connector<C>(
+        connector: C,
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error>
+    where
+        C: Connect + Clone + Send + Sync + 'static,
+    {
+        let build = move |builder: &client::Builder| -> RequestFn {
+            let client = builder.build::<_, Body>(connector.clone());
+            Box::new(move |req| client.request(req))
+        };
+        let transport = Transport::Custom {
+            request: build(&Client::builder()),
+            build: Arc::new(build),
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::Http,
+            addr.to_owned(),
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Docker {
+    /// Connect according to the scheme of an address in the form of `DOCKER_HOST`, with the
+    /// certificates in `cert_dir` if given.
+    pub(crate) fn connect_with_host(
+        host: &str,
+        cert_dir: Option<PathBuf>,
+    ) -> Result<Docker, Error> {
+        let scheme = if host.contains("://") {
+            host.splitn(2, "://").next().unwrap_or_default()
+        } else {
+            "tcp"
+        };
+
+        match (scheme, cert_dir) {
+            #[cfg(unix)]
+            ("unix", _) => Docker::connect_with_unix(host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION),
+            #[cfg(target_os = "linux")]
+            ("vsock", _) => Docker::connect_with_vsock(host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION),
+            #[cfg(unix)]
+            ("fd", _) => Docker::connect_with_fd(
+                inherited_stream(host)?,
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            ),
+            #[cfg(windows)]
+            ("npipe", _) => {
+                Docker::connect_with_named_pipe(host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+            }
+            #[cfg(feature = "ssh")]
+            ("ssh", _) => Docker::connect_with_ssh(host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION),
+            #[cfg(feature = "openssl")]
+            ("tcp", Some(cert_dir)) | ("https", Some(cert_dir)) => Docker::connect_with_ssl(
+                &host.replacen("https://", "", 1),
+                &cert_dir.join("key.pem"),
+                &cert_dir.join("cert.pem"),
+                &cert_dir.join("ca.pem"),
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            ),
+            #[cfg(all(feature = "rustls", not(feature = "openssl")))]
+            ("tcp", Some(cert_dir)) | ("https", Some(cert_dir)) => Docker::connect_with_rustls(
+                &host.replacen("https://", "", 1),
+                &cert_dir.join("key.pem"),
+                &cert_dir.join("cert.pem"),
+                &cert_dir.join("ca.pem"),
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            ),
+            #[cfg(all(feature = "tls", not(any(feature = "openssl", feature = "rustls"))))]
+            ("tcp", Some(cert_dir)) | ("https", Some(cert_dir)) => Docker::connect_with_tls_pem(
+                &host.replacen("https://", "", 1),
+                &cert_dir.join("key.pem"),
+                &cert_dir.join("cert.pem"),
+                &cert_dir.join("ca.pem"),
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            ),
+            ("tcp", None) => Docker::connect_with_http(host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION),
+            ("http", _) => Docker::connect_with_http(
+                &host.replacen("http://", "", 1),
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            ),
+            _ => Err(UnsupportedDockerHostError {
+                host: String::from(host),
+            }
+            .into()),
+        }
+    }
+}
+
+/// A Docker implementation that wraps away which local implementation we are calling.
+#[cfg(all(any(unix, windows), feature = "tokio"))]
+impl Docker {
+    /// Connect using the local machine connection method with default arguments.
+    ///
+    /// This is a simple wrapper over the OS specific handlers:
+    ///  * Unix: [`Docker::connect_with_unix_defaults`]
+    ///  * Windows: [`Docker::connect_with_named_pipe_defaults`]
+    ///
+    /// [`Docker::connect_with_unix_defaults`]: struct.Docker.html#method.connect_with_unix_defaults
+    /// [`Docker::connect_with_named_pipe_defaults`]: struct.Docker.html#method.connect_with_named_pipe_defaults
+    pub fn connect_with_local_defaults() -> Result<Docker, Error> {
+        #[cfg(unix)]
+        return Docker::connect_with_unix_defaults();
+        #[cfg(windows)]
+        return Docker::connect_with_named_pipe_defaults();
+    }
+
+    /// Connect according to the scheme of the `DOCKER_HOST` environment variable, or to the
+    /// local machine if it is not set.
+    ///
+    /// # Schemes
+    ///
+    ///  - `unix://` connects to a Unix socket, and `npipe://` to a Windows named pipe.
+    ///  - `tcp://` connects with unsecured HTTP, or with HTTPS if `DOCKER_TLS_VERIFY` is set.
+    ///  - `http://` connects with unsecured HTTP.
+    ///  - `https://` connects with HTTPS.
+    ///  - `ssh://` connects through ssh, with the `ssh` feature.
+    ///  - `vsock://<cid>:<port>` connects to a vsock socket, and `unix://@<name>` to an abstract
+    ///  unix socket, on linux.
+    ///  - `fd://` connects over the first file descriptor passed through `LISTEN_FDS` by socket
+    ///  activation, and `fd://<fd>` over the given inherited file descriptor, on unix.
+    ///
+    /// HTTPS uses the `ssl` feature, or else the `rustls` feature, or else the `tls` feature, with
+    /// the `key.pem`, `cert.pem` and `ca.pem` certificates found in the `DOCKER_CERT_PATH`
+    /// directory. The `tls` feature requires a PKCS #8 `key.pem`. An address without a scheme is
+    /// treated as `tcp://`. A scheme whose transport is not enabled fails with an [Unsupported Docker Host
+    /// Error](errors/enum.ErrorKind.html#variant.UnsupportedDockerHostError).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_defaults().unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_defaults() -> Result<Docker, Error> {
+        let host = match env::var("DOCKER_HOST") {
+            Ok(ref host) if !host.is_empty() => host.to_owned(),
+            _ => return Docker::connect_with_local_defaults(),
+        };
+
+        let tls = host.starts_with("https://")
+            || env::var("DOCKER_TLS_VERIFY").map_or(false, |verify| !verify.is_empty());
+
+        #[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
+        let cert_dir = if tls {
+            Some(default_cert_path()?)
+        } else {
+            None
+        };
+        #[cfg(not(any(feature = "ssl", feature = "tls", feature = "rustls")))]
+        let cert_dir = if tls { Some(PathBuf::new()) } else { None };
+
+        Docker::connect_with_host(&host, cert_dir)
+    }
+
+    /// Connect using the local machine connection method with supplied arguments.
+    ///
+    /// This is a simple wrapper over the OS specific handlers:
+    ///  * Unix: [`Docker::connect_with_unix`]
+    ///  * Windows: [`Docker::connect_with_named_pipe`]
+    ///
+    /// [`Docker::connect_with_unix`]: struct.Docker.html#method.connect_with_unix
+    /// [`Docker::connect_with_named_pipe`]: struct.Docker.html#method.connect_with_named_pipe
+    pub fn connect_with_local(
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        #[cfg(unix)]
+        return Docker::connect_with_unix(addr, timeout, client_version);
+        #[cfg(windows)]
+        return Docker::connect_with_named_pipe(addr, timeout, client_version);
+    }
+}
+
+/// A Docker implementation typed to connect to a secure HTTPS connection, using the native rust
+/// TLS library.
+#[cfg(feature = "tls")]
+impl Docker {
+    /// Connect using secure HTTPS using native TLS and defaults that are signalled by environment
+    /// variables.
+    ///
+    /// # Defaults
+    ///
+    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable.
+    ///  - The certificate directory is sourced from the `DOCKER_CERT_PATH` environment variable.
+    ///  - Certificate PKCS #12 archive is named `identity.pfx` and the certificate chain is named `ca.pem`.
+    ///  - The password for the PKCS #12 archive defaults to an empty password.
+    ///  - The number of threads used for the HTTP connection pool defaults to 1.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    ///  # PKCS12
+    ///
+    ///  PKCS #12 archives can be created with OpenSSL:
+    ///
+    ///  ```bash
+    ///  openssl pkcs12 -export -out identity.pfx -inkey key.pem -in cert.pem -certfile
+    ///  chain_certs.pem
+    ///  ```
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_tls_defaults().unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_tls_defaults() -> Result<Docker, Error> {
+        let cert_path = default_cert_path()?;
+        if let Ok(ref host) = env::var("DOCKER_HOST") {
+            Docker::connect_with_tls(
+                host,
+                &cert_path.join("identity.pfx"),
+                &cert_path.join("ca.pem"),
+                "",
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            )
+        } else {
+            Docker::connect_with_tls(
+                DEFAULT_DOCKER_HOST,
+                &cert_path.join("identity.pfx"),
+                &cert_path.join("ca.pem"),
+                "",
+                DEFAULT_TIMEOUT,
+                API_DEFAULT_VERSION,
+            )
+        }
+    }
+
+    /// Connect using secure HTTPS using native TLS.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: the connection url.
+    ///  - `pkcs12_file`: the PKCS #12 archive.
+    ///  - `ca_file`: the certificate chain.
+    ///  - `pkcs12_password`: the password to the PKCS #12 archive.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    ///  # PKCS12
+    ///
+    ///  PKCS #12 archives can be created with OpenSSL:
+    ///
+    ///  ```bash
+    ///  openssl pkcs12 -export -out identity.pfx -inkey key.pem -in cert.pem -certfile
+    ///  chain_certs.pem
+    ///  ```
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use std::path::Path;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_tls(
+    ///     "localhost:2375",
+    ///     Path::new("/certs/identity.pfx"),
+    ///     Path::new("/certs/ca.pem"),
+    ///     "my_secret_password",
+    ///     120,
+    ///     API_DEFAULT_VERSION
+    /// ).unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_tls(
+        addr: &str,
+        pkcs12_file: &Path,
+        ca_file: &Path,
+        pkcs12_password: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let identity = Identity::from_pkcs12(&fs::read(pkcs12_file)?, pkcs12_password)
+            .map_err(|err| TLSError { err })?;
+
+        Docker::connect_with_tls_identity(
+            addr,
+            identity,
+            ca_file,
+            Proxy::from_env(),
+            timeout,
+            client_version,
+        )
+    }
+
+    /// Connect using secure HTTPS using native TLS, with a certificate and private key in PEM
+    /// files, as generated for the docker daemon.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key, which must be in PKCS #8 format.
+    ///  - `ssl_cert`: the certificate.
+    ///  - `ssl_ca`: the certificate chain.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    ///  # PKCS8
+    ///
+    ///  A private key can be converted to PKCS #8 with OpenSSL:
+    ///
+    ///  ```bash
+    ///  openssl pkcs8 -topk8 -nocrypt -in key.pem -out key.pkcs8.pem
+    ///  ```
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use std::path::Path;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_tls_pem(
+    ///     "localhost:2375",
+    ///     Path::new("/certs/key.pem"),
+    ///     Path::new("/certs/cert.pem"),
+    ///     Path::new("/certs/ca.pem"),
+    ///     120,
+    ///     API_DEFAULT_VERSION
+    /// ).unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_tls_pem(
+        addr: &str,
+        ssl_key: &Path,
+        ssl_cert: &Path,
+        ssl_ca: &Path,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        Docker::connect_with_tls_proxy(
+            addr,
+            ssl_key,
+            ssl_cert,
+            ssl_ca,
+            Proxy::from_env(),
+            timeout,
+            client_version,
+        )
+    }
+
+    /// Connect using secure HTTPS using native TLS, with a certificate and private key in PEM
+    /// files, through an explicit [Proxy](proxy/struct.Proxy.html), in place of the proxy
+    /// configured by the `HTTPS_PROXY` and `NO_PROXY` environment variables.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key, which must be in PKCS #8 format.
+    ///  - `ssl_cert`: the certificate.
+    ///  - `ssl_ca`: the certificate chain.
+    ///  - `proxy`: the proxy to connect through.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    /// use bollard::proxy::Proxy;
+    ///
+    /// use std::path::Path;
+    ///
+    /// let proxy = Proxy::all("http://proxy.example.com:3128").unwrap();
+    /// let connection = Docker::connect_with_tls_proxy(
+    ///     "docker.example.com:2376",
+    ///     Path::new("/certs/key.pem"),
+    ///     Path::new("/certs/cert.pem"),
+    ///     Path::new("/certs/ca.pem"),
+    ///     proxy,
+    ///     120,
+    ///     API_DEFAULT_VERSION
+    /// ).unwrap();
+    /// ```
+    pub fn connect_with_tls_proxy(
+        addr: &str,
+        ssl_key: &Path,
+        ssl_cert: &Path,
+        ssl_ca: &Path,
+        proxy: Proxy,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let identity =
+            Identity::from_pkcs8(&fs::read(ssl_cert)?, &fs::read(ssl_key)?).map_err(|err| {
+                TLSIdentityError {
+                    cert: ssl_cert.to_owned(),
+                    key: ssl_key.to_owned(),
+                    err,
+                }
+            })?;
+
+        Docker::connect_with_tls_identity(addr, identity, ssl_ca, proxy, timeout, client_version)
+    }
+
+    fn connect_with_tls_identity(
+        addr: &str,
+        identity: Identity,
+        ca_file: &Path,
+        proxy: Proxy,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let client_addr = addr.replacen("tcp://", "", 1);
+
+        let mut tls_connector_builder = TlsConnector::builder();
+
+        let ca = Certificate::from_pem(&fs::read(ca_file)?).map_err(|err| TLSError { err })?;
+
+        let tls_connector_builder = tls_connector_builder.identity(identity);
+        tls_connector_builder.add_root_certificate(ca);
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let tls_connector = tls_connector_builder
+            .build()
+            .map_err(|err| TLSError { err })?;
+        let https_connector: hyper_tls::HttpsConnector<ProxyConnector> =
+            hyper_tls::HttpsConnector::from((
+                ProxyConnector::new(http_connector, proxy),
+                tls_connector.into(),
+            ));
+
+        let client_builder = Client::builder();
+        let client = client_builder.build(https_connector.clone());
+        let transport = Transport::Tls {
+            client,
+            connector: https_connector,
+        };
+        Ok(Docker::new_with(
+            transport,
+            ClientType::SSL,
+            client_addr,
+            PoolOptions::default(),
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+// The implementation block for Docker requests
+impl Docker {
+    pub(crate) fn process_into_value<T>(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<T, Error>>
+    where
+        T: DeserializeOwned,
+    {
+        let fut = self.process_request(req);
+        ClientState::track_future(&self.state, async move {
+            let response = fut.await?;
+            Docker::decode_response(response).await
+        })
+    }
+
+    pub(crate) fn process_into_string(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<String, Error>> {
+        let fut = self.process_request(req);
+        ClientState::track_future(&self.state, async move {
+            let response = fut.await?;
+            Docker::decode_into_string(response).await
+        })
+    }
+
+    pub(crate) fn process_into_stream<T>(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Stream<Item = Result<T, Error>> + Unpin
+    where
+        T: DeserializeOwned,
+    {
+        self.process_into_followed_stream(req, false)
+    }
+
+    /// A stream of the response, failing once idle for the stream idle timeout of the client if
+    /// the response is followed, e.g. events. Other streams, such as a pull or a wait, may stay
+    /// quiet for as long as the docker server works.
+    pub(crate) fn process_into_followed_stream<T>(
+        &self,
+        req: Result<Request<Body>, Error>,
+        follow: bool,
+    ) -> impl Stream<Item = Result<T, Error>> + Unpin
+    where
+        T: DeserializeOwned,
+    {
+        let idle = self.idle_timeout(follow);
+        let runtime = self.runtime.clone();
+        ClientState::track_stream(
+            &self.state,
+            self.process_request(req)
+                .map_ok(move |response| Docker::decode_into_stream::<T>(response, idle, runtime))
+                .into_stream()
+                .try_flatten(),
+        )
+    }
+
+    /// A stream of the response, returned once the docker server sent the response headers, e.g.
+    /// once it started waiting on a container.
+    pub(crate) async fn process_into_stream_on_headers<T>(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> Result<impl Stream<Item = Result<T, Error>> + Unpin, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let runtime = self.runtime.clone();
+        let response = ClientState::track_future(&self.state, self.process_request(req)).await?;
+        Ok(ClientState::track_stream(
+            &self.state,
+            Docker::decode_into_stream::<T>(response, None, runtime),
+        ))
+    }
+
+    pub(crate) fn process_into_stream_string(
+        &self,
+        req: Result<Request<Body>, Error>,
+        follow: bool,
+    ) -> impl Stream<Item = Result<LogOutput, Error>> + Unpin {
+        let idle = self.idle_timeout(follow);
+        let runtime = self.runtime.clone();
+        ClientState::track_stream(
+            &self.state,
+            self.process_request(req)
+                .map_ok(move |response| Docker::decode_into_stream_string(response, idle, runtime))
+                .try_flatten_stream(),
+        )
+    }
+
+    pub(crate) fn process_into_unit(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<(), Error>> {
+        let fut = self.process_request(req);
+        ClientState::track_future(&self.state, async move {
+            let response = fut.await?;
+            // Drain any body without parsing it, so that the connection can be reused.
+            hyper::body::to_bytes(response.into_body())
+                .await
+                .map_err(|e| HyperResponseError { err: e })?;
+            Ok(())
+        })
+    }
+
+    pub(crate) fn process_into_headers(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<HeaderMap, Error>> {
+        let fut = self.process_request(req);
+        ClientState::track_future(&self.state, async move {
+            let (parts, body) = fut.await?.into_parts();
+            hyper::body::to_bytes(body)
+                .await
+                .map_err(|e| HyperResponseError { err: e })?;
+            Ok(parts.headers)
+        })
+    }
+
+    pub(crate) fn process_into_state_change(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<ContainerStateChange, Error>> {
+        let fut = self.process_request(req);
+        ClientState::track_future(&self.state, async move {
+            match fut.await {
+                Ok(_) => Ok(ContainerStateChange::Changed),
+                Err(e) => match e.kind() {
+                    DockerResponseNotModifiedError { .. } => {
+                        Ok(ContainerStateChange::AlreadyInState)
+                    }
+                    _ => Err(e),
+                },
+            }
+        })
+    }
+
+    fn idle_timeout(&self, follow: bool) -> Option<Duration> {
+        if follow {
+            self.stream_idle_timeout
+        } else {
+            None
+        }
+    }
+
+    /// The message returned by the docker server along with an error status code.
+    pub(crate) fn error_message(error: &Error) -> Option<&str> {
+        match error.kind() {
+            DockerResponseServerError { message, .. }
+            | DockerResponseConflictError { message }
+            | DockerResponseBadParameterError { message }
+            | DockerResponseNotFoundError { message }
+            | DockerResponseNotModifiedError { message } => Some(message),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn process_into_body(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + Unpin {
+        let runtime = self.runtime.clone();
+        ClientState::track_stream(
+            &self.state,
+            self.process_request(req)
+                .map_ok(move |response| Docker::decode_into_body(response, None, runtime))
+                .into_stream()
+                .try_flatten(),
+        )
+    }
+
+    pub(crate) fn process_upgraded_stream_string<'a>(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Stream<Item = Result<LogOutput, Error>> {
+        let fut = self.process_request(req);
+        ClientState::track_stream(
+            &self.state,
+            stream::once(async move { fut.await.map(Docker::decode_into_upgraded_stream_string) })
+                .try_flatten(),
+        )
+    }
+
+    pub(crate) fn process_into_raw_upgraded(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<Upgraded, Error>> {
+        let fut = self.process_request(req);
+        ClientState::track_future(&self.state, async move {
+            fut.await?
+                .into_body()
+                .on_upgrade()
+                .await
+                .map_err(|e| HyperResponseError { err: e }.into())
+        })
+    }
+
+    pub(crate) fn process_into_upgraded(
+        &self,
+        req: Result<Request<Body>, Error>,
+    ) -> impl Future<Output = Result<AttachContainerResults, Error>> {
+        let fut = self.process_into_raw_upgraded(req);
+        let state = self.state.clone();
+        ClientState::track_future(&self.state, async move {
+            let (read, write) = tokio::io::split(fut.await?);
+
+            // interactive sessions are idle while waiting on input, so the idle timeout does not
+            // apply to the output
+            let output = FramedRead::new(read, NewlineLogOutputDecoder::new());
+            Ok(AttachContainerResults {
+                output: Box::pin(ClientState::track_stream(&state, output)),
+                input: Box::pin(write),
+            })
+        })
+    }
+
+    pub(crate) fn transpose_option<T>(
+        option: Option<Result<T, Error>>,
+    ) -> Result<Option<T>, Error> {
+        option.transpose()
+    }
+
+    pub(crate) fn serialize_payload<S>(body: Option<S>) -> Result<Body, Error>
+    where
+        S: Serialize,
+    {
+        match body.map(|inst| serde_json::to_string(&inst)) {
+            Some(Ok(res)) => Ok(Some(res)),
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+        .map_err(|e| JsonSerializeError { err: e }.into())
+        .map(|payload| {
+            debug!("{}", payload.clone().unwrap_or_else(String::new));
+            payload
+                .map(|content| content.into())
+                .unwrap_or(Body::empty())
+        })
+    }
+
+    /// Return the address the client connects to, e.g. the path of the socket chosen by
+    /// [`connect_with_unix_defaults`](struct.Docker.html#method.connect_with_unix_defaults).
+    pub fn client_addr(&self) -> &str {
+        &self.client_addr
+    }
+
+    /// Return the currently set client version.
+    pub fn client_version(&self) -> ClientVersion {
+        self.version.as_ref().into()
+    }
+
+    /// Log the `Warnings` returned by the server from create and update endpoints through the
+    /// `log` crate at the `warn` level. Warnings are always returned in the result structs,
+    /// regardless of this setting, which defaults to `false`.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults().unwrap().with_log_warnings(true);
+    /// ```
+    pub fn with_log_warnings(mut self, log_warnings: bool) -> Self {
+        self.log_warnings = log_warnings;
+        self
+    }
+
+    /// Invoke a hook with a sanitized copy of every request, before it is sent to the docker
+    /// server. Credentials in the `Authorization`, `Proxy-Authorization`, `X-Registry-Auth` and
+    /// `X-Registry-Config` headers, including those set with
+    /// [`with_header`](#method.with_header), and the `Data` of a secret creation payload are
+    /// redacted. The hook receives a copy, so it cannot change the request.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     use std::sync::Arc;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_request_hook(Arc::new(|info| println!("{} {}", info.method, info.path)));
+    /// ```
+    pub fn with_request_hook(mut self, hook: RequestHook) -> Self {
+        self.hooks.on_request = Some(hook);
+        self
+    }
+
+    /// Invoke a hook with the status code and duration of every request, once the docker server
+    /// responds or the request fails.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     use std::sync::Arc;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_response_hook(Arc::new(|info| println!("{:?} {:?}", info.status, info.duration)));
+    /// ```
+    pub fn with_response_hook(mut self, hook: ResponseHook) -> Self {
+        self.hooks.on_response = Some(hook);
+        self
+    }
+
+    /// Attach a correlation id, passed to the request and response hooks of every request made
+    /// through this client. Clone the client to set an id for a single call.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults().unwrap();
+    ///     let traced = docker.clone().with_correlation_id("deploy-42");
+    /// ```
+    pub fn with_correlation_id<T: Into<String>>(mut self, correlation_id: T) -> Self {
+        self.hooks.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Fail followed streaming responses, i.e. followed logs, events and streamed stats, with a
+    /// [Stream Idle Error](errors/enum.ErrorKind.html#variant.StreamIdleError) if no data is
+    /// received for the given duration. A connection dropped by a gateway without closing it
+    /// would otherwise leave the stream waiting forever. Streams without a timeout wait
+    /// indefinitely, which is the default, as a followed stream may legitimately stay quiet.
+    ///
+    /// Other streaming responses, such as a wait, a pull or a build, are never timed out, as the
+    /// docker server may work for long without reporting progress. The [Logs
+    /// Reconnecting](struct.Docker.html#method.logs_reconnecting) and [Events
+    /// Reconnecting](struct.Docker.html#method.events_reconnecting) APIs subscribe again once
+    /// idle instead of failing.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     use std::time::Duration;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_stream_idle_timeout(Duration::from_secs(90));
+    /// ```
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Override the timeout of requests made through this client, which otherwise defaults to
+    /// the timeout given when connecting. The timeout applies until the response headers are
+    /// received, so it does not limit how long a streaming response is read. Clone the client to
+    /// set a timeout for a single call.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     use std::time::Duration;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults().unwrap();
+    ///     let impatient = docker.clone().with_timeout(Duration::from_secs(5));
+    ///     let ping = impatient.ping();
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client_timeout = Some(timeout);
+        self
+    }
 
-        let client_builder = Client::builder();
-        let client = client_builder.build(https_connector);
-        let transport = Transport::Tls { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::SSL,
-            client_addr: client_addr.to_owned(),
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
+    /// Tune the connection pool of this client, e.g. to keep connections open for longer to a
+    /// docker server receiving frequent requests, or to disable pooling altogether. Clients
+    /// cloned before the call keep their pool.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::{Docker, PoolOptions};
+    ///
+    ///     use std::time::Duration;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_pool_options(PoolOptions {
+    ///             idle_timeout: Some(Duration::from_secs(300)),
+    ///             ..Default::default()
+    ///         });
+    /// ```
+    pub fn with_pool_options(mut self, options: PoolOptions) -> Self {
+        self.transport = Arc::new(self.transport.rebuild(&options, &self.runtime));
+        self.pool = options;
+        if let Some(health_check) = self.health_check.take() {
+            self.health_check = Some(Arc::new(HealthCheck::new(health_check.interval)));
+        }
+        self
+    }
 
-        Ok(docker)
+    /// Run the background tasks and timers of this client on an asynchronous runtime other than
+    /// tokio, see the [Runtime API](runtime/index.html) for what is behind the `tokio` feature. The
+    /// connections of the client are dropped. Without the `tokio` feature, a runtime must be set
+    /// before making requests.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///     use bollard::runtime::TokioRuntime;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_runtime(TokioRuntime);
+    /// ```
+    pub fn with_runtime<R: Runtime>(mut self, runtime: R) -> Self {
+        self.runtime = Arc::new(runtime);
+        self.transport = Arc::new(self.transport.rebuild(&self.pool, &self.runtime));
+        if let Some(health_check) = self.health_check.take() {
+            self.health_check = Some(Arc::new(HealthCheck::new(health_check.interval)));
+        }
+        self
     }
-}
 
-// The implementation block for Docker requests
-impl Docker {
-    pub(crate) fn process_into_value<T>(
-        &self,
-        req: Result<Request<Body>, Error>,
-    ) -> impl Future<Output = Result<T, Error>>
+    /// Wrap the requests made through this client in a [tower](https://docs.rs/tower) layer, see
+    /// the [Layer API](layer/index.html). A layer set later wraps the layers set before.
+    ///
+    /// The layered service is cloned for every request, so layers keeping state across requests,
+    /// such as a concurrency limit, must share it between clones.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///     use bollard::layer::{DockerService, Layer};
+    ///
+    ///     #[derive(Debug)]
+    ///     struct Identity;
+    ///
+    ///     impl Layer<DockerService> for Identity {
+    ///         type Service = DockerService;
+    ///
+    ///         fn layer(&self, service: DockerService) -> DockerService {
+    ///             service
+    ///         }
+    ///     }
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_layer(Identity);
+    /// ```
+    pub fn with_layer<L>(mut self, layer: L) -> Self
     where
-        T: DeserializeOwned,
+        L: Layer<DockerService>,
+        L::Service:
+            Service<Request<Body>, Response = Response<Body>> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request<Body>>>::Error: Into<Box<dyn StdError + Send + Sync>>,
+        <L::Service as Service<Request<Body>>>::Future: Send + 'static,
     {
-        let fut = self.process_request(req);
-        async move {
-            let response = fut.await?;
-            Docker::decode_response(response).await
-        }
+        let service = self.service.take().unwrap_or_else(DockerService::root);
+        self.service = Some(DockerService::new(layer.layer(service)));
+        self
     }
 
-    pub(crate) fn process_into_stream<T>(
-        &self,
-        req: Result<Request<Body>, Error>,
-    ) -> impl Stream<Item = Result<T, Error>> + Unpin
+    /// Check that the docker server is reachable before sending a request, if no request
+    /// succeeded within the `interval`, by pinging it. If the ping fails to connect, e.g. as the
+    /// docker server restarted, the connections of the client are dropped and the request is
+    /// sent over a new connection, rather than failing on a stale one. Requests failing to
+    /// connect are still returned as errors, and the next request is checked again.
+    ///
+    /// The check is shared by clones made after the call.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     use std::time::Duration;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_health_check(Duration::from_secs(30));
+    /// ```
+    pub fn with_health_check(mut self, interval: Duration) -> Self {
+        self.health_check = Some(Arc::new(HealthCheck::new(interval)));
+        self
+    }
+
+    /// Send a header with every request made through this client, e.g. to authenticate with a
+    /// reverse proxy in front of the docker server. A header of the same name set by a request
+    /// takes precedence, and setting a header again replaces its value.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_header("Authorization", "Bearer 0123456789")
+    ///         .unwrap();
+    /// ```
+    pub fn with_header<K, V>(mut self, name: K, value: V) -> Result<Self, Error>
     where
-        T: DeserializeOwned,
+        K: AsRef<str>,
+        V: AsRef<str>,
     {
-        Box::pin(
-            self.process_request(req)
-                .map_ok(Docker::decode_into_stream::<T>)
-                .into_stream()
-                .try_flatten(),
-        )
-    }
+        let invalid = || InvalidHeaderError {
+            name: String::from(name.as_ref()),
+        };
+        let header_name =
+            HeaderName::from_bytes(name.as_ref().as_bytes()).map_err(|_| invalid())?;
+        let header_value = HeaderValue::from_str(value.as_ref()).map_err(|_| invalid())?;
 
-    pub(crate) fn process_into_stream_string(
-        &self,
-        req: Result<Request<Body>, Error>,
-    ) -> impl Stream<Item = Result<LogOutput, Error>> + Unpin {
-        Box::pin(
-            self.process_request(req)
-                .map_ok(Docker::decode_into_stream_string)
-                .try_flatten_stream(),
-        )
+        Arc::make_mut(&mut self.default_headers).insert(header_name, header_value);
+        Ok(self)
     }
 
-    pub(crate) fn process_into_unit(
-        &self,
-        req: Result<Request<Body>, Error>,
-    ) -> impl Future<Output = Result<(), Error>> {
-        let fut = self.process_request(req);
-        async move {
-            fut.await?;
-            Ok(())
-        }
+    /// Identify this client to the docker server with a `User-Agent` header.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_user_agent("my-auditor/1.0")
+    ///         .unwrap();
+    /// ```
+    pub fn with_user_agent<T: AsRef<str>>(self, user_agent: T) -> Result<Self, Error> {
+        self.with_header(USER_AGENT.as_str(), user_agent)
     }
 
-    pub(crate) fn process_into_body(
-        &self,
-        req: Result<Request<Body>, Error>,
-    ) -> impl Stream<Item = Result<Bytes, Error>> + Unpin {
-        Box::pin(
-            self.process_request(req)
-                .map_ok(|response| {
-                    response
-                        .into_body()
-                        .map_err::<Error, _>(|e: hyper::Error| HyperResponseError { err: e }.into())
-                })
-                .into_stream()
-                .try_flatten(),
-        )
+    /// Retry requests made through this client that fail with a transient error, according to
+    /// a [Retry Policy](retry/struct.RetryPolicy.html). Requests are not retried by default.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///     use bollard::retry::RetryPolicy;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults()
+    ///         .unwrap()
+    ///         .with_retry_policy(RetryPolicy::default().with_max_attempts(5));
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(Arc::new(policy));
+        self
     }
 
-    pub(crate) fn process_upgraded_stream_string<'a>(
-        &self,
-        req: Result<Request<Body>, Error>,
-    ) -> impl Stream<Item = Result<LogOutput, Error>> {
-        let fut = self.process_request(req);
-        stream::once(async move { fut.await.map(Docker::decode_into_upgraded_stream_string) })
-            .try_flatten()
+    /// Disable the timeout of requests made through this client, e.g. to pull a large image or
+    /// build from a large context, where the docker server may take longer than the timeout to
+    /// respond. Clone the client to disable the timeout for a single call.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults().unwrap();
+    ///     let untimed = docker.clone().without_timeout();
+    /// ```
+    pub fn without_timeout(mut self) -> Self {
+        self.client_timeout = None;
+        self
     }
 
-    pub(crate) fn transpose_option<T>(
-        option: Option<Result<T, Error>>,
-    ) -> Result<Option<T>, Error> {
-        option.transpose()
+    pub(crate) fn emit_warnings(&self, endpoint: &str, warnings: &[String]) {
+        if self.log_warnings {
+            for warning in warnings {
+                warn!("{}: {}", endpoint, warning);
+            }
+        }
     }
 
-    pub(crate) fn serialize_payload<S>(body: Option<S>) -> Result<Body, Error>
+    /// Poll `f` every `interval` until it resolves to `Ok(Ok(_))`, or fails. Once another
+    /// interval would pass the `deadline`, returns the last pending value as `Ok(Err(_))`.
+    pub(crate) async fn poll_until<F, Fut, T, P>(
+        &self,
+        deadline: tokio::time::Instant,
+        interval: Duration,
+        mut f: F,
+    ) -> Result<Result<T, P>, Error>
     where
-        S: Serialize,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Result<T, P>, Error>>,
     {
-        match body.map(|inst| serde_json::to_string(&inst)) {
-            Some(Ok(res)) => Ok(Some(res)),
-            Some(Err(e)) => Err(e),
-            None => Ok(None),
+        loop {
+            let pending = match f().await? {
+                Ok(done) => return Ok(Ok(done)),
+                Err(pending) => pending,
+            };
+
+            if tokio::time::Instant::now() + interval > deadline {
+                return Ok(Err(pending));
+            }
+
+            self.runtime.delay(interval).await;
         }
-        .map_err(|e| JsonSerializeError { err: e }.into())
-        .map(|payload| {
-            debug!("{}", payload.clone().unwrap_or_else(String::new));
-            payload
-                .map(|content| content.into())
-                .unwrap_or(Body::empty())
-        })
     }
 
-    /// Return the currently set client version.
-    pub fn client_version(&self) -> ClientVersion {
-        self.version.as_ref().into()
+    /// Shut the client down gracefully, across all of its clones.
+    ///
+    /// New requests fail immediately with a
+    /// [ClientShuttingDownError](errors/enum.ErrorKind.html#variant.ClientShuttingDownError).
+    /// In-flight requests are given up to the `grace` period to complete, while streaming
+    /// requests, such as followed logs or events, are left open until the grace period ends. Any
+    /// remaining requests and streams are then aborted, yielding a `ClientShuttingDownError`.
+    ///
+    /// Returns whether all non-streaming requests completed within the grace period.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///
+    ///     use std::time::Duration;
+    ///
+    ///     let docker = Docker::connect_with_local_defaults().unwrap();
+    ///     async move {
+    ///         docker.shutdown(Duration::from_secs(10)).await;
+    ///     };
+    /// ```
+    pub async fn shutdown(&self, grace: Duration) -> bool {
+        self.state.shutdown(grace, &*self.runtime).await
     }
 
     /// Check with the server for a supported version, and downgrade the client version if
@@ -934,16 +2797,151 @@ impl Docker {
         Ok(self)
     }
 
+    /// Stream the response of an endpoint that is not otherwise wrapped by this crate,
+    /// deserializing each item into `T`.
+    ///
+    /// The response body is decoded as newline-delimited JSON, the format used by the events,
+    /// image pull and build endpoints: each item is a single JSON value terminated by a `\n`.
+    /// Endpoints that multiplex length-prefixed frames, such as attached logs, should be consumed
+    /// with [`raw_stream_bytes`](#method.raw_stream_bytes) instead.
+    ///
+    /// The `path` excludes the API version prefix, which is added from the
+    /// [client version](#method.client_version). The `body` is serialized as JSON. Status codes
+    /// outside of the 2xx range are returned as errors, in the same way as the wrapped endpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use hyper::Method;
+    /// use serde_json::Value;
+    ///
+    /// // Pull a plugin, without granting it any privileges.
+    /// docker.raw_stream::<Value, _, _, _, _>(
+    ///     Method::POST,
+    ///     "/plugins/pull",
+    ///     Some(vec![("remote", "vieux/sshfs")]),
+    ///     Some(Vec::<Value>::new()),
+    /// );
+    /// ```
+    pub fn raw_stream<T, O, K, V, B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<O>,
+        body: Option<B>,
+    ) -> impl Stream<Item = Result<T, Error>> + Unpin
+    where
+        T: DeserializeOwned,
+        O: IntoIterator,
+        O::Item: ::std::borrow::Borrow<(K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        B: Serialize,
+    {
+        let req = self.build_request(
+            path,
+            Builder::new().method(method),
+            Ok(query),
+            Docker::serialize_payload(body),
+        );
+
+        self.process_into_stream(req)
+    }
+
+    /// Stream the raw bytes of the response of an endpoint that is not otherwise wrapped by this
+    /// crate. Chunks are passed on as they are received, without regard to item boundaries.
+    ///
+    /// See [`raw_stream`](#method.raw_stream) for the handling of the path, body and status
+    /// codes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use hyper::Method;
+    ///
+    /// // Export a container's filesystem as a tarball.
+    /// docker.raw_stream_bytes(
+    ///     Method::GET,
+    ///     "/containers/hello-world/export",
+    ///     None::<Vec<(&str, &str)>>,
+    ///     None::<()>,
+    /// );
+    /// ```
+    pub fn raw_stream_bytes<O, K, V, B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<O>,
+        body: Option<B>,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + Unpin
+    where
+        O: IntoIterator,
+        O::Item: ::std::borrow::Borrow<(K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        B: Serialize,
+    {
+        let req = self.build_request(
+            path,
+            Builder::new().method(method),
+            Ok(query),
+            Docker::serialize_payload(body),
+        );
+
+        self.process_into_body(req)
+    }
+
     fn process_request(
         &self,
         request: Result<Request<Body>, Error>,
     ) -> impl Future<Output = Result<Response<Body>, Error>> {
-        let transport = self.transport.clone();
-        let timeout = self.client_timeout;
+        let dispatch = Dispatch {
+            transport: self.transport.clone(),
+            timeout: self.client_timeout,
+            retry: self.retry.clone(),
+            pool: self.pool,
+            runtime: self.runtime.clone(),
+            health_check: self.health_check.clone().map(|health_check| {
+                let ping = self.build_request::<_, String, String>(
+                    "/_ping",
+                    Builder::new().method(Method::GET),
+                    Ok(None::<ArrayVec<[(_, _); 0]>>),
+                    Ok(Body::empty()),
+                );
+                (health_check, ping)
+            }),
+        };
+        let service = self.service.clone();
+        let hooks = if self.hooks.is_set() {
+            Some(self.hooks.clone())
+        } else {
+            None
+        };
 
         async move {
             let request = request?;
-            let response = Docker::execute_request(transport, request, timeout).await?;
+            let response = match hooks {
+                Some(hooks) => {
+                    let request = hooks.request(request).await?;
+                    let method = request.method().to_string();
+                    let path = request.uri().path().to_owned();
+                    let start = Instant::now();
+                    let response = layer::send(service, dispatch, request).await;
+                    hooks.response(
+                        method,
+                        path,
+                        response.as_ref().ok().map(|r| r.status().as_u16()),
+                        start.elapsed(),
+                    );
+                    response
+                }
+                None => layer::send(service, dispatch, request).await,
+            };
+            let response = response?;
 
             let status = response.status();
             match status {
@@ -1014,65 +3012,165 @@ impl Docker {
                 )?;
                 let request_uri: hyper::Uri = uri.into();
                 let builder_string = format!("{:?}", builder);
-                Ok(builder
+                let mut builder = builder
                     .uri(request_uri)
-                    .header(CONTENT_TYPE, "application/json")
-                    .body(body)
-                    .map_err::<Error, _>(|e| {
-                        HttpClientError {
-                            builder: builder_string,
-                            err: e,
+                    .header(CONTENT_TYPE, "application/json");
+                if let Some(headers) = builder.headers_mut() {
+                    for (name, value) in self.default_headers.iter() {
+                        if !headers.contains_key(name) {
+                            headers.insert(name.clone(), value.clone());
                         }
-                        .into()
-                    })?)
+                    }
+                }
+                Ok(builder.body(body).map_err::<Error, _>(|e| {
+                    HttpClientError {
+                        builder: builder_string,
+                        err: e,
+                    }
+                    .into()
+                })?)
             })
     }
 
+    /// The transport to send a request over, after pinging the docker server if no request
+    /// succeeded recently, and dropping the connections of the client if the ping failed.
+    async fn checked_transport(
+        health_check: &HealthCheck,
+        transport: Arc<Transport>,
+        ping: Request<Body>,
+        pool: PoolOptions,
+        timeout: Option<Duration>,
+        runtime: &Arc<dyn Runtime>,
+    ) -> Arc<Transport> {
+        let (current, healthy) = health_check.current();
+        let transport = current.unwrap_or(transport);
+        if healthy {
+            return transport;
+        }
+
+        if Docker::execute_request(transport.clone(), ping, timeout, runtime)
+            .await
+            .is_ok()
+        {
+            health_check.report(true);
+            return transport;
+        }
+
+        debug!("Ping failed, reconnecting to the docker server");
+        let reconnected = Arc::new(transport.rebuild(&pool, runtime));
+        health_check.reconnected(reconnected.clone());
+        reconnected
+    }
+
+    async fn execute_with_retry(
+        transport: Arc<Transport>,
+        req: Request<Body>,
+        timeout: Option<Duration>,
+        retry: Option<Arc<RetryPolicy>>,
+        runtime: &Arc<dyn Runtime>,
+    ) -> Result<Response<Body>, Error> {
+        let retry = match retry {
+            Some(ref retry) if retry.applies(&req) => retry,
+            _ => return Docker::execute_request(transport, req, timeout, runtime).await,
+        };
+
+        // the request has no body, so it is replayed from its head
+        let (parts, _) = req.into_parts();
+        let mut attempt = 1;
+        loop {
+            let mut req = Request::new(Body::empty());
+            *req.method_mut() = parts.method.clone();
+            *req.uri_mut() = parts.uri.clone();
+            *req.version_mut() = parts.version;
+            *req.headers_mut() = parts.headers.clone();
+
+            match Docker::execute_request(transport.clone(), req, timeout, runtime).await {
+                Err(e) if retry.retries(attempt, &e) => {
+                    let backoff = retry.backoff(attempt);
+                    debug!(
+                        "Retrying {} {} in {:?} after: {}",
+                        parts.method, parts.uri, backoff, e
+                    );
+                    runtime.delay(backoff).await;
+                    attempt += 1;
+                }
+                response => return response,
+            }
+        }
+    }
+
     async fn execute_request(
         transport: Arc<Transport>,
         req: Request<Body>,
-        timeout: u64,
+        timeout: Option<Duration>,
+        runtime: &Arc<dyn Runtime>,
     ) -> Result<Response<Body>, Error> {
         // This is where we determine to which transport we issue the request.
         let request = match *transport {
-            Transport::Http { ref client } => client.request(req),
+            #[cfg(feature = "tokio")]
+            Transport::Http { ref client, .. } => client.request(req),
             #[cfg(feature = "openssl")]
-            Transport::Https { ref client } => client.request(req),
+            Transport::Https { ref client, .. } => client.request(req),
             #[cfg(feature = "tls")]
-            Transport::Tls { ref client } => client.request(req),
-            #[cfg(unix)]
-            Transport::Unix { ref client } => client.request(req),
-            #[cfg(windows)]
-            Transport::NamedPipe { ref client } => client.request(req),
+            Transport::Tls { ref client, .. } => client.request(req),
+            #[cfg(feature = "rustls")]
+            Transport::Rustls { ref client, .. } => client.request(req),
+            #[cfg(all(unix, feature = "tokio"))]
+            Transport::Unix { ref client, .. } => client.request(req),
+            #[cfg(all(unix, feature = "tokio"))]
+            Transport::Fd { ref client, .. } => client.request(req),
+            #[cfg(all(target_os = "linux", feature = "tokio"))]
+            Transport::Socket { ref client, .. } => client.request(req),
+            #[cfg(all(windows, feature = "tokio"))]
+            Transport::NamedPipe { ref client, .. } => client.request(req),
+            #[cfg(feature = "ssh")]
+            Transport::Ssh { ref client, .. } => client.request(req),
+            Transport::Custom { ref request, .. } => request(req),
         };
 
-        match tokio::time::timeout(Duration::from_secs(timeout), request).await {
-            Ok(v) => v.map_err(|err| HyperResponseError { err }.into()),
-            Err(_) => Err(RequestTimeoutError.into()),
-        }
+        let response = match timeout {
+            Some(timeout) => crate::runtime::timeout(&**runtime, timeout, request)
+                .await
+                .ok_or_else(|| Error::from(RequestTimeoutError))?,
+            None => request.await,
+        };
+        response.map_err(|err| HyperResponseError { err }.into())
+    }
+
+    fn decode_into_body(
+        res: Response<Body>,
+        idle: Option<Duration>,
+        runtime: Arc<dyn Runtime>,
+    ) -> impl Stream<Item = Result<Bytes, Error>> {
+        IdleTimeout::new(
+            res.into_body()
+                .map_err::<Error, _>(|e: hyper::Error| HyperResponseError { err: e }.into()),
+            idle,
+            runtime,
+        )
     }
 
-    fn decode_into_stream<T>(res: Response<Body>) -> impl Stream<Item = Result<T, Error>>
+    fn decode_into_stream<T>(
+        res: Response<Body>,
+        idle: Option<Duration>,
+        runtime: Arc<dyn Runtime>,
+    ) -> impl Stream<Item = Result<T, Error>>
     where
         T: DeserializeOwned,
     {
         FramedRead::new(
-            StreamReader::new(
-                res.into_body()
-                    .map_err::<Error, _>(|e: hyper::Error| HyperResponseError { err: e }.into()),
-            ),
+            StreamReader::new(Docker::decode_into_body(res, idle, runtime)),
             JsonLineDecoder::new(),
         )
     }
 
     fn decode_into_stream_string(
         res: Response<Body>,
+        idle: Option<Duration>,
+        runtime: Arc<dyn Runtime>,
     ) -> impl Stream<Item = Result<LogOutput, Error>> {
         FramedRead::new(
-            StreamReader::new(
-                res.into_body()
-                    .map_err::<Error, _>(|e: hyper::Error| HyperResponseError { err: e }.into()),
-            ),
+            StreamReader::new(Docker::decode_into_body(res, idle, runtime)),
             NewlineLogOutputDecoder::new(),
         )
     }
@@ -1106,10 +3204,19 @@ impl Docker {
     where
         T: DeserializeOwned,
     {
+        let no_content = response.status() == StatusCode::NO_CONTENT;
         let contents = Docker::decode_into_string(response).await?;
 
         debug!("Decoded into string: {}", &contents);
-        serde_json::from_str::<T>(&contents).map_err(|e| {
+
+        // An empty body is decoded as `null`, so that it can be returned as a unit or `None`.
+        let json = if no_content || contents.trim().is_empty() {
+            "null"
+        } else {
+            &contents
+        };
+
+        serde_json::from_str::<T>(json).map_err(|e| {
             if e.is_data() {
                 JsonDataError {
                     message: e.to_string(),
@@ -1,6 +1,7 @@
 //! Errors for this module.
 use std::cmp;
 use std::fmt::{Display, Formatter, Result};
+use std::time::Duration;
 
 use failure::Context;
 
@@ -124,6 +125,296 @@ pub enum ErrorKind {
     /// Error emitted when a request times out.
     #[fail(display = "Timeout error")]
     RequestTimeoutError,
+    /// Error emitted when no data is received on a streaming response for the [stream idle
+    /// timeout](../struct.Docker.html#method.with_stream_idle_timeout).
+    #[fail(display = "No data received from the docker server for {:?}", idle)]
+    StreamIdleError {
+        /// The idle timeout that elapsed.
+        idle: Duration,
+    },
+    /// Error emitted when the docker server reports an error within a streaming response.
+    #[fail(display = "Docker stream error: {}", error)]
+    DockerStreamError {
+        /// The error message reported by the docker server.
+        error: String,
+    },
+    /// Error emitted when an image reference cannot be parsed or lacks a required component.
+    #[fail(display = "Invalid image reference: {}", reference)]
+    InvalidImageReferenceError {
+        /// The image reference that failed to parse.
+        reference: String,
+    },
+    /// Error emitted when a pulled image does not carry the expected digest.
+    #[fail(
+        display = "Image digest mismatch for {}: local digests {:?}",
+        reference, digests
+    )]
+    ImageDigestMismatchError {
+        /// The digest pinned image reference that was pulled.
+        reference: String,
+        /// The repository digests found on the local image.
+        digests: Vec<String>,
+    },
+    /// Error emitted when a detach key sequence is not a comma separated list of single
+    /// characters or `ctrl-<value>` combinations.
+    #[fail(
+        display = "Invalid detach keys {:?}: expected a single character or `ctrl-<value>`, where \
+                   `<value>` is one of `a-z`, `@`, `[`, `\\`, `]`, `^` or `_`",
+        detach_keys
+    )]
+    InvalidDetachKeysError {
+        /// The detach key sequence that failed to validate.
+        detach_keys: String,
+    },
+    /// Error emitted when a container name has a character not matching
+    /// `/?[a-zA-Z0-9][a-zA-Z0-9_.-]+`, before the request is sent.
+    #[fail(
+        display = "Invalid character {:?} at position {} of container name {:?}, names must match \
+                   /?[a-zA-Z0-9][a-zA-Z0-9_.-]+",
+        character, position, name
+    )]
+    InvalidContainerNameError {
+        /// The container name that failed to validate.
+        name: String,
+        /// The offending character.
+        character: char,
+        /// The position of the offending character, in characters.
+        position: usize,
+    },
+    /// Error emitted when a container name is a single character, which the docker server
+    /// rejects, before the request is sent.
+    #[fail(
+        display = "Container name {:?} is too short, names must match /?[a-zA-Z0-9][a-zA-Z0-9_.-]+",
+        name
+    )]
+    ContainerNameTooShortError {
+        /// The container name that failed to validate.
+        name: String,
+    },
+    /// Error emitted by the docker server when the name of a created or renamed container is
+    /// already in use.
+    #[fail(
+        display = "Container name {} is already in use by container {}",
+        name, existing_id
+    )]
+    ContainerNameConflictError {
+        /// The container name, without a leading slash.
+        name: String,
+        /// The ID of the container using the name.
+        existing_id: String,
+    },
+    /// Error emitted by the docker server when pausing a container that is already paused.
+    #[fail(display = "Container is already paused: {}", message)]
+    ContainerAlreadyPausedError {
+        /// Message returned by the docker server.
+        message: String,
+    },
+    /// Error emitted by the docker server when unpausing a container that is not paused.
+    #[fail(display = "Container is not paused: {}", message)]
+    ContainerNotPausedError {
+        /// Message returned by the docker server.
+        message: String,
+    },
+    /// Error emitted when waiting on a container fails, as returned in the body of the [Wait
+    /// Container](../struct.Docker.html#method.wait_container_until) response.
+    #[fail(display = "Failed to wait on container: {}", message)]
+    ContainerWaitError {
+        /// The exit code returned alongside the error.
+        status_code: i64,
+        /// Message returned by the docker server.
+        message: String,
+    },
+    /// Error emitted when the stat of a path in a container is missing from the response of the
+    /// docker server, or is not valid base64 encoded JSON.
+    #[fail(display = "Invalid stat of path {} in container {}", path, container)]
+    ContainerPathStatError {
+        /// The container of the path.
+        container: String,
+        /// The path that was stat'ed.
+        path: String,
+    },
+    /// Error emitted when the certificate issuer of a swarm has not changed within the timeout of
+    /// a CA rotation.
+    #[fail(display = "Swarm CA rotation did not complete within {:?}", timeout)]
+    SwarmCARotationTimeoutError {
+        /// The timeout that elapsed.
+        timeout: Duration,
+    },
+    /// Error emitted when an `auth` entry of a docker config file is not a base64 encoded
+    /// `username:password` pair.
+    #[fail(display = "Invalid auth for registry {} in docker config", registry)]
+    InvalidRegistryAuthError {
+        /// The registry of the invalid entry.
+        registry: String,
+    },
+    /// Error emitted through a build stream when the build is cancelled after its timeout elapsed.
+    #[fail(display = "Build {} cancelled after {:?}", build_id, timeout)]
+    BuildCancelledError {
+        /// The ID of the cancelled build.
+        build_id: String,
+        /// The timeout that elapsed.
+        timeout: Duration,
+    },
+    /// Error emitted when a container has no published ports within the timeout of a wait.
+    #[fail(
+        display = "Container {} published no ports within {:?}",
+        container, timeout
+    )]
+    PublishedPortsTimeoutError {
+        /// The container name or ID.
+        container: String,
+        /// The timeout that elapsed.
+        timeout: Duration,
+    },
+    /// Error emitted when a container does not become healthy within the timeout of a wait.
+    #[fail(
+        display = "Container {} not healthy within {:?}, {}",
+        container, timeout, last_check
+    )]
+    ContainerHealthTimeoutError {
+        /// The container name or ID.
+        container: String,
+        /// The timeout that elapsed.
+        timeout: Duration,
+        /// The last health status and check of the container.
+        last_check: Box<LastHealthCheck>,
+    },
+    /// Error emitted when waiting for a container without a health check to become healthy.
+    #[fail(display = "Container {} has no health check", container)]
+    ContainerNoHealthCheckError {
+        /// The container name or ID.
+        container: String,
+    },
+    /// Error emitted when a container stops while waiting for it to become healthy.
+    #[fail(
+        display = "Container {} stopped with status {} while waiting for it to become healthy",
+        container, status
+    )]
+    ContainerStoppedError {
+        /// The container name or ID.
+        container: String,
+        /// The status of the container, e.g. `exited`.
+        status: String,
+    },
+    /// Error emitted when a network with the requested name exists, but differs from the requested
+    /// configuration.
+    #[fail(display = "{}", mismatch)]
+    NetworkMismatchError {
+        /// The field of the network that differs.
+        mismatch: Box<NetworkMismatch>,
+    },
+    /// Error emitted when an image archive has no readable `manifest.json`.
+    #[fail(display = "Image archive {} has no valid manifest.json", path)]
+    ImageArchiveManifestError {
+        /// The path of the image archive.
+        path: String,
+    },
+    /// Error emitted when an image archive contains images other than the expected ones.
+    #[fail(display = "Image archive contains unexpected images: {:?}", images)]
+    UnexpectedArchiveImagesError {
+        /// The unexpected image names.
+        images: Vec<String>,
+    },
+    /// Error emitted when a partially exported image archive is longer than the resumed export.
+    #[fail(display = "Partial export {} does not match the exported image", path)]
+    ExportResumeMismatchError {
+        /// The path of the partial export, which is removed.
+        path: String,
+    },
+    /// Error emitted when a request is made after, or aborted by, a client shutdown.
+    #[fail(display = "Client is shutting down")]
+    ClientShuttingDownError,
+    /// Error emitted when log output exceeds the limit given to a collector.
+    #[fail(display = "Log output exceeded the limit of {} bytes", limit)]
+    LogOutputLimitError {
+        /// The maximum number of bytes allowed.
+        limit: usize,
+    },
+    /// Error emitted when a BuildKit trace record of a build stream cannot be decoded.
+    #[fail(display = "Could not decode BuildKit trace: {}", reason)]
+    BuildKitTraceError {
+        /// The reason the record is invalid.
+        reason: String,
+    },
+    /// Error emitted when an address to connect through ssh is not of the form
+    /// `ssh://[user@]host[:port]`.
+    #[cfg(feature = "ssh")]
+    #[fail(
+        display = "Invalid ssh address {:?}, expected ssh://[user@]host[:port]",
+        addr
+    )]
+    InvalidSshAddressError {
+        /// The address that failed to parse.
+        addr: String,
+    },
+    /// Error emitted when a vsock address is not of the form `vsock://<cid>:<port>`.
+    #[fail(
+        display = "Invalid vsock address {:?}, expected vsock://<cid>:<port>",
+        addr
+    )]
+    InvalidVsockAddressError {
+        /// The address that failed to parse.
+        addr: String,
+    },
+    /// Error emitted when an address of an IPAM configuration does not parse, before the request
+    /// is sent.
+    #[fail(display = "Invalid {} {:?} in IPAM configuration", field, value)]
+    InvalidIPAMConfigError {
+        /// The field of the IPAM configuration, e.g. `Subnet`.
+        field: String,
+        /// The value that failed to parse.
+        value: String,
+    },
+    /// Error emitted when a default header set on the client is not a valid HTTP header.
+    #[fail(display = "Invalid header {:?}", name)]
+    InvalidHeaderError {
+        /// The name of the header.
+        name: String,
+    },
+    /// Error emitted by a [layer](../layer/index.html) wrapping the requests of the client.
+    #[fail(display = "Layer error: {}", err)]
+    LayerError {
+        /// The error returned by the layer.
+        err: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// Error emitted when the scheme of a docker host is not supported, or its transport is not
+    /// enabled by a cargo feature.
+    #[fail(display = "Unsupported docker host {:?}", host)]
+    UnsupportedDockerHostError {
+        /// The docker host.
+        host: String,
+    },
+    /// Error emitted when an `fd://` address does not name an inherited file descriptor.
+    #[fail(display = "No inherited file descriptor for {}", host)]
+    InheritedFdError {
+        /// The `fd://` address.
+        host: String,
+    },
+    /// Error emitted when the url of a proxy is not an HTTP url with a host.
+    #[fail(display = "Invalid proxy {}", proxy)]
+    InvalidProxyError {
+        /// The url of the proxy.
+        proxy: String,
+    },
+    /// Error emitted when the docker config directory cannot be found, as neither `DOCKER_CONFIG`
+    /// nor a home directory is set.
+    #[fail(display = "Could not find the docker config directory, set DOCKER_CONFIG")]
+    DockerConfigDirError,
+    /// Error emitted when a docker CLI context does not exist in the context store.
+    #[fail(display = "Docker context {} not found", name)]
+    ContextNotFoundError {
+        /// The name of the context.
+        name: String,
+    },
+    /// Error emitted when the docker endpoint of a context is missing, or uses a transport that
+    /// is not supported or not enabled by a cargo feature.
+    #[fail(display = "Unsupported endpoint {:?} of docker context {}", host, name)]
+    UnsupportedContextEndpointError {
+        /// The name of the context.
+        name: String,
+        /// The host of the docker endpoint, empty if it is missing.
+        host: String,
+    },
     /// Error emitted when an SSL context fails to configure.
     #[cfg(feature = "openssl")]
     #[fail(display = "SSL error: {:?}", err)]
@@ -131,6 +422,23 @@ pub enum ErrorKind {
         /// The original error emitted.
         err: openssl::error::ErrorStack,
     },
+    /// Error emitted when a certificate or private key cannot be read from a PEM file.
+    #[cfg(feature = "rustls")]
+    #[fail(
+        display = "Could not parse the certificates or private key in {:?}",
+        path
+    )]
+    CertificateParseError {
+        /// The path to the PEM file.
+        path: std::path::PathBuf,
+    },
+    /// Error emitted when a rustls context fails to configure.
+    #[cfg(feature = "rustls")]
+    #[fail(display = "Rustls error: {}", err)]
+    RustlsError {
+        /// The original error emitted.
+        err: tokio_rustls::rustls::TLSError,
+    },
     /// Error emitted when a TLS context fails to configure.
     #[cfg(feature = "tls")]
     #[fail(display = "TLS error: {:?}", err)]
@@ -138,6 +446,61 @@ pub enum ErrorKind {
         /// The original error emitted.
         err: native_tls::Error,
     },
+    /// Error emitted when a TLS identity cannot be built from a certificate and a private key,
+    /// e.g. because the key is not in PKCS #8 format.
+    #[cfg(feature = "tls")]
+    #[fail(
+        display = "Could not build a TLS identity from {:?} and the PKCS #8 key {:?}: {:?}",
+        cert, key, err
+    )]
+    TLSIdentityError {
+        /// The path to the certificate.
+        cert: std::path::PathBuf,
+        /// The path to the private key.
+        key: std::path::PathBuf,
+        /// The original error emitted.
+        err: native_tls::Error,
+    },
+}
+
+/// The last health check of a container that did not become healthy, carried by a
+/// [ContainerHealthTimeoutError](enum.ErrorKind.html#variant.ContainerHealthTimeoutError).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastHealthCheck {
+    /// The last health status of the container, e.g. `starting` or `unhealthy`.
+    pub status: String,
+    /// The output of the last health check, empty if none ran.
+    pub output: String,
+}
+
+impl Display for LastHealthCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "health status {}: {}", self.status, self.output)
+    }
+}
+
+/// A field of an existing network that differs from the requested configuration, carried by a
+/// [NetworkMismatchError](enum.ErrorKind.html#variant.NetworkMismatchError).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkMismatch {
+    /// The network name.
+    pub network: String,
+    /// The field that differs, e.g. `Driver` or `IPAM.Driver`.
+    pub field: String,
+    /// The requested value.
+    pub expected: String,
+    /// The value of the existing network.
+    pub actual: String,
+}
+
+impl Display for NetworkMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Network {} exists with {} {}, expected {}",
+            self.network, self.field, self.actual, self.expected
+        )
+    }
 }
 
 impl Display for Error {
@@ -175,6 +538,7 @@ impl std::error::Error for Error {
             ErrorKind::StrFmtError { err, .. } => Some(err),
             ErrorKind::HttpClientError { err, .. } => Some(err),
             ErrorKind::HyperResponseError { err, .. } => Some(err),
+            ErrorKind::LayerError { err } => Some(&**err),
             _ => None,
         }
     }
@@ -204,6 +568,10 @@ impl From<Context<ErrorKind>> for Error {
 /// Needed due to tokio's Decoder implementation
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
+        // errors of this crate passed through a reader, such as an idle stream, are unwrapped
+        if err.get_ref().map_or(false, |inner| inner.is::<Error>()) {
+            return *err.into_inner().unwrap().downcast::<Error>().unwrap();
+        }
         Error {
             inner: ErrorKind::IOError { err: err }.into(),
         }
@@ -67,6 +67,15 @@ pub enum StartExecResults {
     Detached,
 }
 
+impl From<StartExecResults> for Option<LogOutput> {
+    fn from(results: StartExecResults) -> Option<LogOutput> {
+        match results {
+            StartExecResults::Attached { log } => Some(log),
+            StartExecResults::Detached => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct ExecProcessConfig {
@@ -0,0 +1,167 @@
+#![cfg(all(unix, feature = "tokio"))]
+//! Reach the docker server over a file descriptor inherited from the parent process, e.g. a
+//! socket passed by systemd socket activation.
+
+use hyper::client::connect::Connected;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::UnixStream;
+
+use std::env;
+use std::future::Future;
+use std::io;
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::os::unix::net::UnixStream as StdUnixStream;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::InheritedFdError;
+
+/// The first file descriptor passed by systemd socket activation.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// The file descriptor of an `fd://` address: the given descriptor for `fd://<fd>`, or the first
+/// one passed through `LISTEN_FDS` for `fd://`.
+fn parse_fd(addr: &str) -> Result<RawFd, Error> {
+    let invalid = || InheritedFdError {
+        host: String::from(addr),
+    };
+
+    match addr.trim_start_matches("fd://") {
+        "" => {
+            // the variables are meant for another process unless LISTEN_PID is ours
+            let pid_matches = env::var("LISTEN_PID")
+                .map(|pid| pid == process::id().to_string())
+                .unwrap_or(false);
+            let fds = env::var("LISTEN_FDS")
+                .ok()
+                .and_then(|fds| fds.parse::<RawFd>().ok())
+                .unwrap_or(0);
+            if pid_matches && fds > 0 {
+                Ok(SD_LISTEN_FDS_START)
+            } else {
+                Err(invalid().into())
+            }
+        }
+        fd => match fd.parse::<RawFd>() {
+            Ok(fd) if fd >= 0 => Ok(fd),
+            _ => Err(invalid().into()),
+        },
+    }
+}
+
+/// Take ownership of the socket inherited through an `fd://` address.
+pub(crate) fn inherited_stream(addr: &str) -> Result<StdUnixStream, Error> {
+    let fd = parse_fd(addr)?;
+    // an `fd://` address names a file descriptor inherited by this process for the client, which
+    // nothing else in the process owns
+    Ok(unsafe { StdUnixStream::from_raw_fd(fd) })
+}
+
+/// A connection to the docker server over a stream socket.
+#[derive(Debug)]
+pub struct FdStream {
+    stream: UnixStream,
+}
+
+impl FdStream {
+    pub(crate) fn new(stream: UnixStream) -> FdStream {
+        FdStream { stream }
+    }
+}
+
+impl AsyncRead for FdStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for FdStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+impl hyper::client::connect::Connection for FdStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+/// Hands out the inherited connection first. As a file descriptor carries a single connection,
+/// later connections are made to the path of its peer socket, if it is bound to one.
+#[derive(Debug, Clone)]
+pub struct FdConnector {
+    inherited: Arc<Mutex<Option<StdUnixStream>>>,
+    peer: Option<PathBuf>,
+}
+
+impl FdConnector {
+    /// Take ownership of a connected unix socket, closed when the connector and its connection
+    /// are dropped.
+    pub(crate) fn new(stream: StdUnixStream) -> Result<FdConnector, Error> {
+        let peer = stream
+            .peer_addr()?
+            .as_pathname()
+            .map(|path| path.to_path_buf());
+        stream.set_nonblocking(true)?;
+
+        Ok(FdConnector {
+            inherited: Arc::new(Mutex::new(Some(stream))),
+            peer,
+        })
+    }
+}
+
+impl hyper::service::Service<hyper::Uri> for FdConnector {
+    type Response = FdStream;
+    type Error = io::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _destination: hyper::Uri) -> Self::Future {
+        let inherited = self
+            .inherited
+            .lock()
+            .map(|mut inherited| inherited.take())
+            .unwrap_or_default();
+        let peer = self.peer.clone();
+
+        Box::pin(async move {
+            let stream = match (inherited, peer) {
+                (Some(stream), _) => UnixStream::from_std(stream)?,
+                (None, Some(peer)) => UnixStream::connect(peer).await?,
+                (None, None) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotConnected,
+                        "inherited connection already in use",
+                    ))
+                }
+            };
+            Ok(FdStream::new(stream))
+        })
+    }
+}
@@ -4,23 +4,60 @@ use base64;
 use chrono::serde::ts_seconds;
 use chrono::{DateTime, Utc};
 use futures_core::Stream;
-use futures_util::{stream, stream::StreamExt};
+#[cfg(feature = "tokio")]
+use futures_util::future;
+#[cfg(feature = "tokio")]
+use futures_util::stream::TryStreamExt;
+use futures_util::stream::{self, StreamExt};
 use http::header::CONTENT_TYPE;
 use http::request::Builder;
 use hyper::{body::Bytes, Body, Method};
+use pin_project::pin_project;
 use serde::Serialize;
 use serde_json;
+#[cfg(feature = "tokio")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncWriteExt;
+#[cfg(feature = "tokio")]
+use tokio_util::codec::{BytesCodec, FramedRead};
 
 use super::Docker;
-use crate::auth::DockerCredentials;
-use crate::container::{Config, GraphDriver};
-use crate::docker::{FALSE_STR, TRUE_STR};
+use crate::auth::{registry_auth_header, DockerCredentials, RegistryAuth, RegistryAuthConfigs};
+use crate::buildkit::{decode_trace, StatusResponse, TRACE_ID};
+use crate::container::{Config, GraphDriverData};
+use crate::docker::{
+    deserialize_nonoptional_map, deserialize_nonoptional_vec, FALSE_STR, TRUE_STR,
+};
 use crate::errors::Error;
-use crate::errors::ErrorKind::JsonSerializeError;
-
+use crate::errors::ErrorKind::{
+    BuildCancelledError, DockerResponseNotFoundError, DockerStreamError, ImageDigestMismatchError,
+    InvalidImageReferenceError, JsonSerializeError,
+};
+#[cfg(feature = "tokio")]
+use crate::errors::ErrorKind::{
+    ExportResumeMismatchError, ImageArchiveManifestError, UnexpectedArchiveImagesError,
+};
+
+#[cfg(feature = "tokio")]
+use std::cmp;
 use std::cmp::Eq;
 use std::collections::HashMap;
+use std::error::Error as StdError;
+#[cfg(feature = "tokio")]
+use std::fs::File;
+use std::future::Future;
 use std::hash::Hash;
+#[cfg(feature = "tokio")]
+use std::io;
+#[cfg(feature = "tokio")]
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "tokio")]
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Image type returned by the [Inspect Image API](../struct.Docker.html#method.inspect_image)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,7 +83,7 @@ pub struct Image {
     pub docker_version: String,
     pub virtual_size: u64,
     pub author: String,
-    pub graph_driver: GraphDriver,
+    pub graph_driver: GraphDriverData,
     pub metadata: Metadata,
 }
 
@@ -74,16 +111,25 @@ pub struct RootFS {
 #[allow(missing_docs)]
 pub struct APIImages {
     pub id: String,
-    pub repo_tags: Option<Vec<String>>,
+    /// Repository tags of the image, empty if the server returns `null`.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub repo_tags: Vec<String>,
     #[serde(with = "ts_seconds")]
     pub created: DateTime<Utc>,
     pub size: u64,
     pub virtual_size: u64,
     pub parent_id: String,
-    pub repo_digests: Option<Vec<String>>,
-    pub labels: Option<HashMap<String, String>>,
-    pub containers: isize,
-    pub shared_size: i64,
+    /// Repository digests of the image, empty if the server returns `null`.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub repo_digests: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
+    pub labels: HashMap<String, String>,
+    /// Number of containers using this image, or `-1` if it was not calculated.
+    pub containers: i64,
+    /// Size shared with other images, or `-1` if it was not calculated. Only present when
+    /// requested with the `shared_size` option on API version 1.42 and later.
+    #[serde(default)]
+    pub shared_size: Option<i64>,
 }
 
 /// Parameters available for pulling an image, used in the [Create Image
@@ -131,6 +177,62 @@ where
     pub platform: T,
 }
 
+impl CreateImageOptions<String> {
+    /// Construct the options to pull an image from a reference in the form
+    /// `[registry/]name[:tag][@digest]`.
+    ///
+    /// The docker server expects a digest in the `tag` parameter, so a digest takes precedence over
+    /// a tag when both are present. A reference without a tag or digest pulls the `latest` tag.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::image::CreateImageOptions;
+    ///
+    /// let options = CreateImageOptions::from_reference(
+    ///     "localhost:5000/alpine:3.11@sha256:cb8a924afdf0229ef7515d9e5b3024e23b3eb03ddbba287f4a19c6ac90b8d221"
+    /// );
+    ///
+    /// assert_eq!(options.from_image, "localhost:5000/alpine");
+    /// assert_eq!(
+    ///     options.tag,
+    ///     "sha256:cb8a924afdf0229ef7515d9e5b3024e23b3eb03ddbba287f4a19c6ac90b8d221"
+    /// );
+    ///
+    /// let options = CreateImageOptions::from_reference("localhost:5000/alpine");
+    ///
+    /// assert_eq!(options.from_image, "localhost:5000/alpine");
+    /// assert_eq!(options.tag, "latest");
+    /// ```
+    pub fn from_reference(reference: &str) -> CreateImageOptions<String> {
+        let (name, digest) = split_digest(reference);
+        let (name, tag) = split_tag(name);
+
+        CreateImageOptions {
+            from_image: String::from(name),
+            tag: String::from(digest.or(tag).unwrap_or("latest")),
+            ..Default::default()
+        }
+    }
+}
+
+/// Split an image reference into the name (including any tag) and the digest.
+fn split_digest(reference: &str) -> (&str, Option<&str>) {
+    match reference.find('@') {
+        Some(idx) => (&reference[..idx], Some(&reference[idx + 1..])),
+        None => (reference, None),
+    }
+}
+
+/// Split an image name into the repository and tag, taking care not to confuse a registry port
+/// with a tag.
+fn split_tag(name: &str) -> (&str, Option<&str>) {
+    match name.rfind(':') {
+        Some(idx) if !name[idx..].contains('/') => (&name[..idx], Some(&name[idx + 1..])),
+        _ => (name, None),
+    }
+}
+
 /// Trait providing implementations for [Create Image Options](struct.CreateImageOptions.html)
 #[allow(missing_docs)]
 pub trait CreateImageQueryParams<K, V>
@@ -198,6 +300,252 @@ pub enum CreateImageResults {
     },
 }
 
+/// The default minimum interval between two items of a [Pull Progress](struct.PullProgress.html)
+/// stream.
+pub const PULL_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The phase of an image pull, as reported by a [Pull Progress](struct.PullProgress.html) item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PullPhase {
+    /// The image manifest is being resolved, no layers are known yet.
+    Resolving,
+    /// Layers are being downloaded.
+    Downloading,
+    /// All layers are downloaded and are being extracted.
+    Extracting,
+    /// All layers are pulled.
+    Complete,
+}
+
+/// Overall progress of an image pull, yielded by the [Aggregate Pull
+/// Progress](fn.aggregate_pull_progress.html) adapter.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PullProgress {
+    /// The number of layers that are pulled, or already existed locally.
+    pub layers_done: usize,
+    /// The number of layers of the image.
+    pub layers_total: usize,
+    /// The number of bytes downloaded so far.
+    pub bytes_current: u64,
+    /// The total size of the layers being downloaded, as far as it is known. Layers that already
+    /// exist locally are not counted.
+    pub bytes_total: u64,
+    /// The phase of the pull.
+    pub phase: PullPhase,
+}
+
+impl PullProgress {
+    /// The percentage of bytes downloaded, or `None` if no sizes are known yet.
+    pub fn percentage(&self) -> Option<f64> {
+        if self.bytes_total == 0 {
+            return None;
+        }
+        Some(self.bytes_current as f64 / self.bytes_total as f64 * 100.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum LayerState {
+    Pending,
+    Downloading,
+    Downloaded,
+    Done,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct LayerProgress {
+    current: u64,
+    total: Option<u64>,
+    state: LayerState,
+}
+
+/// Stream returned by the [Aggregate Pull Progress](fn.aggregate_pull_progress.html) adapter.
+#[pin_project]
+#[derive(Debug)]
+pub struct PullProgressStream<S> {
+    #[pin]
+    stream: S,
+    layers: HashMap<String, LayerProgress>,
+    interval: Duration,
+    last: Option<(Instant, PullProgress)>,
+    done: bool,
+}
+
+/// Consolidate the records of a [Create Image](../struct.Docker.html#method.create_image) stream,
+/// which are emitted per layer, into the overall progress of the pull.
+///
+/// An item is yielded whenever the phase changes, and otherwise at most once per
+/// [`PULL_PROGRESS_INTERVAL`](constant.PULL_PROGRESS_INTERVAL.html). The last item, with a
+/// `Complete` phase, is always yielded when the pull finishes. An error reported by the docker
+/// server is yielded as a `DockerStreamError`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::image::{aggregate_pull_progress, CreateImageOptions};
+///
+/// use futures_util::stream::TryStreamExt;
+///
+/// let options = Some(CreateImageOptions{
+///   from_image: "hello-world",
+///   tag: "latest",
+///   ..Default::default()
+/// });
+///
+/// async move {
+///     aggregate_pull_progress(docker.create_image(options, None, None))
+///         .try_for_each(|progress| async move {
+///             println!("{}/{} layers", progress.layers_done, progress.layers_total);
+///             Ok(())
+///         })
+///         .await
+/// };
+/// ```
+pub fn aggregate_pull_progress<S>(stream: S) -> PullProgressStream<S>
+where
+    S: Stream<Item = Result<CreateImageResults, Error>>,
+{
+    PullProgressStream {
+        stream,
+        layers: HashMap::new(),
+        interval: PULL_PROGRESS_INTERVAL,
+        last: None,
+        done: false,
+    }
+}
+
+impl<S> PullProgressStream<S> {
+    /// Set the minimum interval between two items that share the same phase.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    fn update(
+        layers: &mut HashMap<String, LayerProgress>,
+        id: String,
+        status: &str,
+        progress_detail: Option<CreateImageProgressDetail>,
+    ) {
+        let state = match status {
+            "Pulling fs layer" | "Waiting" => LayerState::Pending,
+            "Downloading" | "Verifying Checksum" => LayerState::Downloading,
+            "Download complete" | "Extracting" => LayerState::Downloaded,
+            "Pull complete" | "Already exists" => LayerState::Done,
+            // e.g. `Retrying in 5 seconds`, for a layer that is already known
+            _ if layers.contains_key(&id) => LayerState::Pending,
+            // e.g. `Pulling from library/ubuntu`, whose id is the tag
+            _ => return,
+        };
+
+        let layer = layers.entry(id).or_insert(LayerProgress {
+            current: 0,
+            total: None,
+            state,
+        });
+        layer.state = state;
+
+        match (status, progress_detail) {
+            // the progress of an extraction counts the extracted bytes, not the downloaded bytes
+            ("Downloading", Some(detail)) => {
+                layer.current = detail.current.unwrap_or(layer.current);
+                layer.total = detail.total.or(layer.total);
+            }
+            ("Download complete", _) | ("Extracting", _) | ("Pull complete", _) => {
+                if let Some(total) = layer.total {
+                    layer.current = total;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn progress(
+        layers: &HashMap<String, LayerProgress>,
+        last: Option<PullPhase>,
+        done: bool,
+    ) -> PullProgress {
+        let states = || layers.values().map(|layer| layer.state);
+        let phase = if done {
+            PullPhase::Complete
+        } else if states().any(|s| s == LayerState::Pending || s == LayerState::Downloading) {
+            PullPhase::Downloading
+        } else if states().any(|s| s == LayerState::Downloaded) {
+            PullPhase::Extracting
+        } else {
+            // all layers known so far are pulled, which does not mean there are no more layers
+            last.unwrap_or(PullPhase::Resolving)
+        };
+
+        PullProgress {
+            layers_done: states().filter(|s| *s == LayerState::Done).count(),
+            layers_total: layers.len(),
+            bytes_current: layers.values().map(|layer| layer.current).sum(),
+            bytes_total: layers.values().filter_map(|layer| layer.total).sum(),
+            phase,
+        }
+    }
+}
+
+impl<S> Stream for PullProgressStream<S>
+where
+    S: Stream<Item = Result<CreateImageResults, Error>>,
+{
+    type Item = Result<PullProgress, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if *this.done {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(CreateImageResults::CreateImageProgressResponse {
+                    status,
+                    progress_detail,
+                    id: Some(id),
+                    ..
+                }))) => {
+                    PullProgressStream::<S>::update(this.layers, id, &status, progress_detail);
+                }
+                // records without an id, e.g. the digest or the final status
+                Poll::Ready(Some(Ok(CreateImageResults::CreateImageProgressResponse {
+                    ..
+                }))) => continue,
+                Poll::Ready(Some(Ok(CreateImageResults::CreateImageError { error, .. }))) => {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(DockerStreamError { error }.into())));
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    let progress = PullProgressStream::<S>::progress(this.layers, None, true);
+                    return Poll::Ready(Some(Ok(progress)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+
+            let last_phase = this.last.map(|(_, last)| last.phase);
+            let progress = PullProgressStream::<S>::progress(this.layers, last_phase, false);
+            let now = Instant::now();
+            let emit = match this.last {
+                None => true,
+                Some((at, last)) => {
+                    last.phase != progress.phase
+                        || (*last != progress && now.duration_since(*at) >= *this.interval)
+                }
+            };
+            if emit {
+                *this.last = Some((now, progress));
+                return Poll::Ready(Some(Ok(progress)));
+            }
+        }
+    }
+}
+
 /// Parameters to the [List Images
 /// API](../struct.Docker.html#method.list_images)
 ///
@@ -243,6 +591,63 @@ where
     pub filters: HashMap<T, Vec<T>>,
     /// Show digest information as a RepoDigests field on each image.
     pub digests: bool,
+    /// Compute and show the shared size as a SharedSize field on each image. Requires API version
+    /// 1.42 or later.
+    pub shared_size: bool,
+}
+
+impl<T> ListImagesOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    fn add_filter(mut self, key: &'static str, value: T) -> Self {
+        self.filters.entry(T::from(key)).or_default().push(value);
+        self
+    }
+
+    /// Only show images matching a reference, in the form `<image-name>[:<tag>]`. Wildcards are
+    /// accepted, for example `alpine:3.*` or `localhost:5000/*`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::image::ListImagesOptions;
+    ///
+    /// let options = ListImagesOptions::default()
+    ///     .reference("alpine:3.*")
+    ///     .reference("busybox")
+    ///     .dangling(false);
+    ///
+    /// assert_eq!(options.filters["reference"], vec!["alpine:3.*", "busybox"]);
+    /// ```
+    pub fn reference(self, reference: T) -> Self {
+        self.add_filter("reference", reference)
+    }
+
+    /// Only show images created before the given image, in the form `<image-name>[:<tag>]`,
+    /// `<image id>` or `<image@digest>`.
+    pub fn before(self, image: T) -> Self {
+        self.add_filter("before", image)
+    }
+
+    /// Only show images created since the given image, in the form `<image-name>[:<tag>]`,
+    /// `<image id>` or `<image@digest>`.
+    pub fn since(self, image: T) -> Self {
+        self.add_filter("since", image)
+    }
+
+    /// Only show images with a label, in the form `key` or `key=value`.
+    pub fn label(self, label: T) -> Self {
+        self.add_filter("label", label)
+    }
+
+    /// Only show images that are, or are not, dangling.
+    pub fn dangling(self, dangling: bool) -> Self {
+        self.add_filter(
+            "dangling",
+            T::from(if dangling { TRUE_STR } else { FALSE_STR }),
+        )
+    }
 }
 
 /// Trait providing implementations for [List Images Options](struct.ListImagesOptions.html).
@@ -251,22 +656,26 @@ pub trait ListImagesQueryParams<K>
 where
     K: AsRef<str>,
 {
-    fn into_array(self) -> Result<ArrayVec<[(K, String); 3]>, Error>;
+    fn into_array(self) -> Result<ArrayVec<[(K, String); 4]>, Error>;
 }
 
 impl<'a, T: AsRef<str> + Eq + Hash + Serialize> ListImagesQueryParams<&'a str>
     for ListImagesOptions<T>
 {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 3]>, Error> {
-        Ok(ArrayVec::from([
-            ("all", self.all.to_string()),
-            (
-                "filters",
-                serde_json::to_string(&self.filters)
-                    .map_err::<Error, _>(|e| JsonSerializeError { err: e }.into())?,
-            ),
-            ("digests", self.digests.to_string()),
-        ]))
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 4]>, Error> {
+        let mut params = ArrayVec::new();
+        params.push(("all", self.all.to_string()));
+        params.push((
+            "filters",
+            serde_json::to_string(&self.filters)
+                .map_err::<Error, _>(|e| JsonSerializeError { err: e }.into())?,
+        ));
+        params.push(("digests", self.digests.to_string()));
+        // Only sent when requested, as the parameter was introduced in API version 1.42.
+        if self.shared_size {
+            params.push(("shared-size", self.shared_size.to_string()));
+        }
+        Ok(params)
     }
 }
 
@@ -465,6 +874,53 @@ pub struct APIImageSearch {
     pub star_count: u64,
 }
 
+/// Descriptor of the manifest of an image, returned in the [Inspect Distribution
+/// API](../struct.Docker.html#method.inspect_distribution)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistributionInspectDescriptor {
+    /// Media type of the manifest, e.g. a manifest list or a single platform manifest.
+    pub media_type: String,
+    /// Digest of the manifest.
+    pub digest: String,
+    /// Size of the manifest, in bytes.
+    pub size: i64,
+    /// Alternative locations of the manifest.
+    pub urls: Option<Vec<String>>,
+}
+
+/// A platform supported by an image, returned in the [Inspect Distribution
+/// API](../struct.Docker.html#method.inspect_distribution)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionInspectPlatform {
+    /// CPU architecture, e.g. `amd64`.
+    pub architecture: String,
+    /// Operating system, e.g. `linux`.
+    pub os: String,
+    /// Version of the operating system, only set for Windows images.
+    #[serde(rename = "os.version")]
+    pub os_version: Option<String>,
+    /// Required operating system features.
+    #[serde(rename = "os.features")]
+    pub os_features: Option<Vec<String>>,
+    /// Variant of the CPU architecture, e.g. `v7` for `arm`.
+    pub variant: Option<String>,
+    /// Required CPU features.
+    pub features: Option<Vec<String>>,
+}
+
+/// Result type for the [Inspect Distribution
+/// API](../struct.Docker.html#method.inspect_distribution)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DistributionInspect {
+    /// Descriptor of the manifest.
+    pub descriptor: DistributionInspectDescriptor,
+    /// Platforms supported by the image.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub platforms: Vec<DistributionInspectPlatform>,
+}
+
 /// Parameters to the [Remove Image API](../struct.Docker.html#method.remove_image)
 ///
 /// ## Examples
@@ -605,6 +1061,40 @@ impl<'a, T: AsRef<str>> PushImageQueryParams<&'a str, T> for PushImageOptions<T>
     }
 }
 
+/// Subtype for the [Push Image Results](enum.PushImageResults.html) type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PushImageAuxDetail {
+    /// The pushed tag.
+    pub tag: String,
+    /// The digest of the pushed manifest.
+    pub digest: String,
+    /// The size of the pushed manifest, in bytes.
+    pub size: u64,
+}
+
+/// Progress of an image push, reported to a [Progress
+/// Handler](../progress/trait.ProgressHandler.html).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+#[allow(missing_docs)]
+pub enum PushImageResults {
+    #[serde(rename_all = "camelCase")]
+    PushImageProgressResponse {
+        status: String,
+        progress_detail: Option<CreateImageProgressDetail>,
+        id: Option<String>,
+        progress: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PushImageAux { aux: PushImageAuxDetail },
+    #[serde(rename_all = "camelCase")]
+    PushImageError {
+        error_detail: CreateImageErrorDetail,
+        error: String,
+    },
+}
+
 /// Parameters to the [Commit Container API](../struct.Docker.html#method.commit_container)
 ///
 /// ## Examples
@@ -640,8 +1130,9 @@ pub struct CommitContainerOptions<T> {
     pub author: T,
     /// Whether to pause the container before committing.
     pub pause: bool,
-    /// `Dockerfile` instructions to apply while committing
-    pub changes: Option<T>,
+    /// `Dockerfile` instructions to apply while committing, e.g. `CMD ["/bin/sh"]`, one per
+    /// instruction.
+    pub changes: Vec<T>,
 }
 
 /// Trait providing implementations for [Commit Container Options](struct.CommitContainerOptions.html)
@@ -665,9 +1156,7 @@ impl<'a> CommitContainerQueryParams<&'a str, &'a str> for CommitContainerOptions
             ("author", self.author),
             ("pause", if self.pause { TRUE_STR } else { FALSE_STR }),
         ];
-        if let Some(c) = self.changes {
-            res.push(("changes", c));
-        }
+        res.extend(self.changes.into_iter().map(|c| ("changes", c)));
         Ok(res)
     }
 }
@@ -682,9 +1171,7 @@ impl<'a> CommitContainerQueryParams<&'a str, String> for CommitContainerOptions<
             ("author", self.author),
             ("pause", self.pause.to_string()),
         ];
-        if let Some(c) = self.changes {
-            res.push(("changes", c));
-        }
+        res.extend(self.changes.into_iter().map(|c| ("changes", c)));
         Ok(res)
     }
 }
@@ -692,11 +1179,32 @@ impl<'a> CommitContainerQueryParams<&'a str, String> for CommitContainerOptions<
 /// Result type for the [Commit Container API](../struct.Docker.html#method.commit_container)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct CommitContainerResults {
+    /// The ID of the created image.
     pub id: String,
 }
 
+/// The builder backend used by the [Build Image API](../struct.Docker.html#method.build_image)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuilderVersion {
+    /// The classic builder.
+    #[serde(rename = "1")]
+    V1,
+    /// [BuildKit](https://github.com/moby/buildkit), which reports its progress in
+    /// [trace](struct.BuildImageResults.html#method.buildkit_status) records.
+    #[serde(rename = "2")]
+    V2,
+}
+
+impl BuilderVersion {
+    fn as_str(self) -> &'static str {
+        match self {
+            BuilderVersion::V1 => "1",
+            BuilderVersion::V2 => "2",
+        }
+    }
+}
+
 /// Parameters to the [Build Image API](../struct.Docker.html#method.build_image)
 ///
 /// ## Examples
@@ -718,7 +1226,7 @@ pub struct CommitContainerResults {
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct BuildImageOptions<T>
 where
     T: AsRef<str> + Eq + Hash,
@@ -738,7 +1246,8 @@ where
     /// `dockerfile` parameter is also specified, there must be a file with the corresponding path
     /// inside the tarball.
     pub remote: T,
-    /// Suppress verbose build output.
+    /// Suppress verbose build output. The per-step stream is omitted, and only a record with the
+    /// final image ID is returned.
     pub q: bool,
     /// Do not use the cache when building the image.
     pub nocache: bool,
@@ -746,7 +1255,8 @@ where
     pub cachefrom: Vec<T>,
     /// Attempt to pull the image even if an older image exists locally.
     pub pull: bool,
-    /// Remove intermediate containers after a successful build.
+    /// Remove intermediate containers after a successful build. Defaults to `true`, matching the
+    /// docker CLI.
     pub rm: bool,
     /// Always remove intermediate containers, even upon failure.
     pub forcerm: bool,
@@ -778,6 +1288,53 @@ where
     pub networkmode: T,
     /// Platform in the format `os[/arch[/variant]]`
     pub platform: T,
+    /// The stage of a multi-stage `Dockerfile` to build, the last stage if empty.
+    pub target: T,
+    /// An identifier for the build, which can be passed to
+    /// [`Docker::cancel_build`](../struct.Docker.html#method.cancel_build) to cancel it.
+    pub buildid: Option<T>,
+    /// The builder backend, left to the docker server if unset.
+    pub version: Option<BuilderVersion>,
+    /// The ID of a session opened with
+    /// [`Docker::start_session`](../struct.Docker.html#method.start_session), through which a
+    /// BuildKit build requests files and registry credentials from the client.
+    pub session: Option<T>,
+}
+
+impl<T> Default for BuildImageOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + Default,
+{
+    fn default() -> Self {
+        BuildImageOptions {
+            dockerfile: T::default(),
+            t: T::default(),
+            extrahosts: None,
+            remote: T::default(),
+            q: false,
+            nocache: false,
+            cachefrom: Vec::new(),
+            pull: false,
+            rm: true,
+            forcerm: false,
+            memory: None,
+            memswap: None,
+            cpushares: None,
+            cpusetcpus: T::default(),
+            cpuperiod: None,
+            cpuquota: None,
+            buildargs: HashMap::new(),
+            shmsize: None,
+            squash: false,
+            labels: HashMap::new(),
+            networkmode: T::default(),
+            platform: T::default(),
+            target: T::default(),
+            buildid: None,
+            version: None,
+            session: None,
+        }
+    }
 }
 
 /// Trait providing implementations for [Build Image Options](struct.BuildImageOptions.html)
@@ -824,11 +1381,18 @@ impl<'a> BuildImageQueryParams<&'a str> for BuildImageOptions<&'a str> {
 
         output.extend(
             vec![
+                Some(self.target)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| ("target", v.to_string())),
                 self.extrahosts.map(|v| ("extrahosts", v.to_string())),
+                self.buildid.map(|v| ("buildid", v.to_string())),
+                self.version.map(|v| ("version", v.as_str().to_string())),
+                self.session.map(|v| ("session", v.to_string())),
                 self.memory.map(|v| ("memory", v.to_string())),
+                self.memswap.map(|v| ("memswap", v.to_string())),
                 self.cpushares.map(|v| ("cpushares", v.to_string())),
                 self.cpuperiod.map(|v| ("cpuperiod", v.to_string())),
-                self.cpuquota.map(|v| ("cpuperiod", v.to_string())),
+                self.cpuquota.map(|v| ("cpuquota", v.to_string())),
                 self.shmsize.map(|v| ("shmsize", v.to_string())),
             ]
             .into_iter()
@@ -873,11 +1437,18 @@ impl<'a> BuildImageQueryParams<&'a str> for BuildImageOptions<String> {
 
         output.extend(
             vec![
+                Some(self.target)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| ("target", v)),
                 self.extrahosts.map(|v| ("extrahosts", v)),
+                self.buildid.map(|v| ("buildid", v)),
+                self.version.map(|v| ("version", v.as_str().to_string())),
+                self.session.map(|v| ("session", v)),
                 self.memory.map(|v| ("memory", v.to_string())),
+                self.memswap.map(|v| ("memswap", v.to_string())),
                 self.cpushares.map(|v| ("cpushares", v.to_string())),
                 self.cpuperiod.map(|v| ("cpuperiod", v.to_string())),
-                self.cpuquota.map(|v| ("cpuperiod", v.to_string())),
+                self.cpuquota.map(|v| ("cpuquota", v.to_string())),
                 self.shmsize.map(|v| ("shmsize", v.to_string())),
             ]
             .into_iter()
@@ -920,6 +1491,12 @@ pub enum BuildImageResults {
     BuildImageStream {
         stream: String,
     },
+    /// A record of a BuildKit build, see
+    /// [`buildkit_status`](enum.BuildImageResults.html#method.buildkit_status).
+    BuildImageTrace {
+        id: String,
+        aux: String,
+    },
     BuildImageAux {
         aux: BuildImageAuxDetail,
     },
@@ -938,6 +1515,72 @@ pub enum BuildImageResults {
     BuildImageNone {},
 }
 
+impl BuildImageResults {
+    /// Decode the progress of a BuildKit build carried by a `moby.buildkit.trace` record,
+    /// returning `None` for any other record.
+    pub fn buildkit_status(&self) -> Option<Result<StatusResponse, Error>> {
+        match self {
+            BuildImageResults::BuildImageTrace { id, aux } if id == TRACE_ID => {
+                Some(decode_trace(aux))
+            }
+            _ => None,
+        }
+    }
+}
+
+static BUILD_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Generate an identifier for a build that is unique across the builds started by this process.
+fn generate_build_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let count = BUILD_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+    format!("bollard-{:x}-{:x}", nanos, count)
+}
+
+/// Build stream that ends with an error once the future cancelling the build resolves.
+#[pin_project]
+#[derive(Debug)]
+struct CancelOnTimeout<S, F> {
+    #[pin]
+    stream: S,
+    #[pin]
+    cancel: Option<F>,
+    done: bool,
+}
+
+impl<S, F> Stream for CancelOnTimeout<S, F>
+where
+    S: Stream<Item = Result<BuildImageResults, Error>>,
+    F: Future<Output = Error>,
+{
+    type Item = Result<BuildImageResults, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        if let Some(cancel) = this.cancel.as_pin_mut() {
+            if let Poll::Ready(e) = cancel.poll(cx) {
+                *this.done = true;
+                return Poll::Ready(Some(Err(e)));
+            }
+        }
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(None) => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+            poll => poll,
+        }
+    }
+}
+
 /// Parameters to the [Import Image API](../struct.Docker.html#method.import_image)
 ///
 /// ## Examples
@@ -1051,6 +1694,7 @@ impl Docker {
     ///  - An optional [Create Image Options](image/struct.CreateImageOptions.html) struct.
     ///  - An optional request body consisting of a tar or tar.gz archive with the root file system
     ///    for the image. If this argument is used, the value of the `from_src` option must be "-".
+    ///  - An optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
     ///
     /// # Returns
     ///
@@ -1084,7 +1728,7 @@ impl Docker {
         &self,
         options: Option<T>,
         root_fs: Option<Body>,
-        credentials: Option<DockerCredentials>,
+        credentials: Option<RegistryAuth>,
     ) -> impl Stream<Item = Result<CreateImageResults, Error>>
     where
         T: CreateImageQueryParams<K, V>,
@@ -1093,15 +1737,13 @@ impl Docker {
     {
         let url = "/images/create";
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
+        match registry_auth_header(credentials) {
+            Ok(auth) => {
                 let req = self.build_request(
                     url,
                     Builder::new()
                         .method(Method::POST)
-                        .header("X-Registry-Auth", base64::encode(&ser_cred)),
+                        .header("X-Registry-Auth", auth),
                     Docker::transpose_option(options.map(|o| o.into_array())),
                     match root_fs {
                         Some(body) => Ok(body),
@@ -1110,9 +1752,7 @@ impl Docker {
                 );
                 self.process_into_stream(req).boxed()
             }
-            Err(e) => {
-                stream::once(async move { Err(JsonSerializeError { err: e }.into()) }).boxed()
-            }
+            Err(e) => stream::once(async move { Err(e) }).boxed(),
         }
     }
 
@@ -1153,6 +1793,111 @@ impl Docker {
         self.process_into_value(req).await
     }
 
+    /// ---
+    ///
+    /// # Image Digest
+    ///
+    /// Return the repository digests of a local image, parsed into `(repository, digest)` pairs.
+    ///
+    /// # Arguments
+    ///
+    /// - Image name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of `(repository, digest)` tuples, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.image_digest("hello-world");
+    /// ```
+    pub async fn image_digest(&self, image_name: &str) -> Result<Vec<(String, String)>, Error> {
+        let image = self.inspect_image(image_name).await?;
+
+        Ok(image
+            .repo_digests
+            .iter()
+            .filter_map(|repo_digest| match split_digest(repo_digest) {
+                (repository, Some(digest)) => Some((repository.to_owned(), digest.to_owned())),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// ---
+    ///
+    /// # Pull Pinned
+    ///
+    /// Pull an image by a digest pinned reference, and verify that the local image carries the
+    /// requested digest once the pull completes.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image reference in the form `[registry/]name[:tag]@digest` as a string slice.
+    ///  - An optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
+    ///
+    /// # Returns
+    ///
+    ///  - The matching `(repository, digest)` tuple, wrapped in a Future.
+    ///
+    /// An [ImageDigestMismatchError](errors/enum.ErrorKind.html#variant.ImageDigestMismatchError)
+    /// is returned if the local image does not carry the requested digest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.pull_pinned(
+    ///     "alpine@sha256:cb8a924afdf0229ef7515d9e5b3024e23b3eb03ddbba287f4a19c6ac90b8d221",
+    ///     None
+    /// );
+    /// ```
+    pub async fn pull_pinned(
+        &self,
+        reference: &str,
+        credentials: Option<RegistryAuth>,
+    ) -> Result<(String, String), Error> {
+        let digest = match split_digest(reference) {
+            (_, Some(digest)) if !digest.is_empty() => digest,
+            _ => {
+                return Err(InvalidImageReferenceError {
+                    reference: String::from(reference),
+                }
+                .into())
+            }
+        };
+
+        let options = CreateImageOptions::from_reference(reference);
+        let pinned = format!("{}@{}", options.from_image, digest);
+
+        let mut stream = self.create_image(Some(options), None, credentials);
+        while let Some(result) = stream.next().await {
+            if let CreateImageResults::CreateImageError { error, .. } = result? {
+                return Err(DockerStreamError { error }.into());
+            }
+        }
+
+        let digests = self.image_digest(&pinned).await?;
+
+        match digests.iter().find(|(_, local)| local == digest) {
+            Some(pair) => Ok(pair.to_owned()),
+            None => Err(ImageDigestMismatchError {
+                reference: String::from(reference),
+                digests: digests
+                    .into_iter()
+                    .map(|(repository, digest)| format!("{}@{}", repository, digest))
+                    .collect(),
+            }
+            .into()),
+        }
+    }
+
     /// ---
     ///
     /// # Prune Images
@@ -1247,6 +1992,8 @@ impl Docker {
     /// # Arguments
     ///
     ///  - [Search Image Options](struct.SearchImagesOptions.html) struct.
+    ///  - An optional [Registry Auth](auth/enum.RegistryAuth.html) enum, for registries that
+    ///  require authentication to search.
     ///
     /// # Returns
     ///
@@ -1272,18 +2019,25 @@ impl Docker {
     ///     ..Default::default()
     /// };
     ///
-    /// docker.search_images(search_options);
+    /// docker.search_images(search_options, None);
     /// ```
-    pub async fn search_images<T, K>(&self, options: T) -> Result<Vec<APIImageSearch>, Error>
+    pub async fn search_images<T, K>(
+        &self,
+        options: T,
+        credentials: Option<RegistryAuth>,
+    ) -> Result<Vec<APIImageSearch>, Error>
     where
         T: SearchImagesQueryParams<K>,
         K: AsRef<str>,
     {
         let url = "/images/search";
+        let auth = registry_auth_header(credentials)?;
 
         let req = self.build_request(
             url,
-            Builder::new().method(Method::GET),
+            Builder::new()
+                .method(Method::GET)
+                .header("X-Registry-Auth", auth),
             Docker::transpose_option(Some(options.into_array())),
             Ok(Body::empty()),
         );
@@ -1293,26 +2047,71 @@ impl Docker {
 
     /// ---
     ///
-    /// # Remove Image
+    /// # Inspect Distribution
     ///
-    /// Remove an image, along with any untagged parent images that were referenced by that image.
+    /// Return the descriptor of an image manifest and the platforms it supports, as reported by
+    /// the registry, without pulling the image.
     ///
     /// # Arguments
     ///
-    ///  - Image name as a string slice.
-    ///  - An optional [Remove Image Options](image/struct.RemoveImageOptions.html) struct.
+    ///  - Image name or reference as a string slice.
+    ///  - An optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
     ///
     /// # Returns
     ///
-    ///  - Vector of [Remove Image Results](image/struct.RemoveImageResults.html), wrapped in a
-    ///  Future.
+    ///  - [Distribution Inspect](image/struct.DistributionInspect.html), wrapped in a Future.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
     ///
-    /// use bollard::image::RemoveImageOptions;
+    /// docker.inspect_distribution("hello-world", None);
+    /// ```
+    pub async fn inspect_distribution(
+        &self,
+        image_name: &str,
+        credentials: Option<RegistryAuth>,
+    ) -> Result<DistributionInspect, Error> {
+        let url = format!("/distribution/{}/json", image_name);
+        let auth = registry_auth_header(credentials)?;
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new()
+                .method(Method::GET)
+                .header("X-Registry-Auth", auth),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Remove Image
+    ///
+    /// Remove an image, along with any untagged parent images that were referenced by that image.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - An optional [Remove Image Options](image/struct.RemoveImageOptions.html) struct.
+    ///  - An optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Remove Image Results](image/struct.RemoveImageResults.html), wrapped in a
+    ///  Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    ///
+    /// use bollard::image::RemoveImageOptions;
     /// use std::default::Default;
     ///
     /// # let docker = Docker::connect_with_http_defaults().unwrap();
@@ -1327,7 +2126,7 @@ impl Docker {
         &self,
         image_name: &str,
         options: Option<T>,
-        credentials: Option<DockerCredentials>,
+        credentials: Option<RegistryAuth>,
     ) -> Result<Vec<RemoveImageResults>, Error>
     where
         T: RemoveImageQueryParams<K, V>,
@@ -1336,22 +2135,17 @@ impl Docker {
     {
         let url = format!("/images/{}", image_name);
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
-                let req = self.build_request(
-                    &url,
-                    Builder::new()
-                        .method(Method::DELETE)
-                        .header("X-Registry-Auth", base64::encode(&ser_cred)),
-                    Docker::transpose_option(options.map(|o| o.into_array())),
-                    Ok(Body::empty()),
-                );
-                self.process_into_value(req).await
-            }
-            Err(e) => Err(JsonSerializeError { err: e }.into()),
-        }
+        let auth = registry_auth_header(credentials)?;
+
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::DELETE)
+                .header("X-Registry-Auth", auth),
+            Docker::transpose_option(options.map(|o| o.into_array())),
+            Ok(Body::empty()),
+        );
+        self.process_into_value(req).await
     }
 
     /// ---
@@ -1417,7 +2211,7 @@ impl Docker {
     ///
     ///  - Image name as a string slice.
     ///  - Optional [Push Image Options](struct.PushImageOptions.html) struct.
-    ///  - Optional [Docker Credentials](auth/struct.DockerCredentials.html) struct.
+    ///  - Optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
     ///
     /// # Returns
     ///
@@ -1428,7 +2222,7 @@ impl Docker {
     /// ```rust
     /// # use bollard::Docker;
     ///
-    /// use bollard::auth::DockerCredentials;
+    /// use bollard::auth::RegistryAuth;
     /// use bollard::image::PushImageOptions;
     ///
     /// use std::default::Default;
@@ -1438,10 +2232,9 @@ impl Docker {
     ///     tag: "v1.0.1",
     /// });
     ///
-    /// let credentials = Some(DockerCredentials {
-    ///     username: Some("Jack".to_string()),
-    ///     password: Some("myverysecretpassword".to_string()),
-    ///     ..Default::default()
+    /// let credentials = Some(RegistryAuth::Basic {
+    ///     username: "Jack".to_string(),
+    ///     password: "myverysecretpassword".to_string(),
     /// });
     ///
     /// docker.push_image("hello-world", push_options, credentials);
@@ -1450,7 +2243,7 @@ impl Docker {
         &self,
         image_name: &str,
         options: Option<T>,
-        credentials: Option<DockerCredentials>,
+        credentials: Option<RegistryAuth>,
     ) -> Result<(), Error>
     where
         T: PushImageQueryParams<K, V>,
@@ -1459,23 +2252,49 @@ impl Docker {
     {
         let url = format!("/images/{}/push", image_name);
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
+        let auth = registry_auth_header(credentials)?;
+
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::POST)
+                .header(CONTENT_TYPE, "application/json")
+                .header("X-Registry-Auth", auth),
+            Docker::transpose_option(options.map(|o| o.into_array())),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// Push an image, streaming the progress reported by the docker server.
+    pub(crate) fn push_image_stream<T, K, V>(
+        &self,
+        image_name: &str,
+        options: Option<T>,
+        credentials: Option<RegistryAuth>,
+    ) -> impl Stream<Item = Result<PushImageResults, Error>>
+    where
+        T: PushImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let url = format!("/images/{}/push", image_name);
+
+        match registry_auth_header(credentials) {
+            Ok(auth) => {
                 let req = self.build_request(
                     &url,
                     Builder::new()
                         .method(Method::POST)
                         .header(CONTENT_TYPE, "application/json")
-                        .header("X-Registry-Auth", base64::encode(&ser_cred)),
+                        .header("X-Registry-Auth", auth),
                     Docker::transpose_option(options.map(|o| o.into_array())),
                     Ok(Body::empty()),
                 );
-
-                self.process_into_unit(req).await
+                self.process_into_stream(req).boxed()
             }
-            Err(e) => Err(JsonSerializeError { err: e }.into()),
+            Err(e) => stream::once(async move { Err(e) }).boxed(),
         }
     }
 
@@ -1492,7 +2311,7 @@ impl Docker {
     ///
     /// # Returns
     ///
-    ///  - [Commit Container Results](container/struct.CommitContainerResults.html), wrapped in a Future.
+    ///  - [Commit Container Results](image/struct.CommitContainerResults.html), wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -1506,7 +2325,10 @@ impl Docker {
     ///
     /// let options = CommitContainerOptions{
     ///     container: "my-running-container",
+    ///     repo: "my-cache",
+    ///     tag: "latest",
     ///     pause: true,
+    ///     changes: vec!["WORKDIR /app", "CMD [\"make\"]"],
     ///     ..Default::default()
     /// };
     ///
@@ -1552,7 +2374,9 @@ impl Docker {
     /// # Arguments
     ///
     ///  - [Build Image Options](image/struct.BuildImageOptions.html) struct.
-    ///  - Optional [Docker Credentials](auth/struct.DockerCredentials.html) struct.
+    ///  - Optional map of registry hostname to [Docker
+    ///  Credentials](auth/struct.DockerCredentials.html), e.g. converted from [Registry Auth
+    ///  Configs](auth/struct.RegistryAuthConfigs.html), sent as the `X-Registry-Config` header.
     ///  - Tar archive compressed with one of the following algorithms: identity (no compression),
     ///    gzip, bzip2, xz. Optional [Hyper Body](https://hyper.rs/hyper/master/hyper/struct.Body.html).
     ///
@@ -1576,7 +2400,7 @@ impl Docker {
     /// let options = BuildImageOptions{
     ///     dockerfile: "Dockerfile",
     ///     t: "my-image",
-    ///     rm: true,
+    ///     pull: true,
     ///     ..Default::default()
     /// };
     ///
@@ -1595,26 +2419,245 @@ impl Docker {
     where
         T: BuildImageQueryParams<K>,
         K: AsRef<str>,
+    {
+        self.build_image_query(options.into_array(), credentials, tar)
+    }
+
+    /// ---
+    ///
+    /// # Build Image From Stream
+    ///
+    /// Build an image as with the [Build Image API](struct.Docker.html#method.build_image), from
+    /// a tar archive streamed as it is produced or read, without buffering it in memory.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Build Image Options](image/struct.BuildImageOptions.html) struct.
+    ///  - Optional map of registry hostname to [Docker
+    ///  Credentials](auth/struct.DockerCredentials.html), sent as the `X-Registry-Config` header.
+    ///  - Stream of the chunks of a tar archive, compressed with one of the following algorithms:
+    ///  identity (no compression), gzip, bzip2, xz.
+    ///
+    /// # Returns
+    ///
+    ///  - [Build Image Results](image/enum.BuildImageResults.html), wrapped in an asynchronous
+    ///  Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::BuildImageOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    /// use tokio_util::codec::{BytesCodec, FramedRead};
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = BuildImageOptions{
+    ///     dockerfile: "Dockerfile",
+    ///     t: "my-image",
+    ///     target: "release",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// async move {
+    ///     let file = tokio::fs::File::open("context.tar.gz").await.unwrap();
+    ///     let context = FramedRead::new(file, BytesCodec::new()).map_ok(|chunk| chunk.freeze());
+    ///
+    ///     let results = docker
+    ///         .build_image_from_stream(options, None, context)
+    ///         .try_collect::<Vec<_>>()
+    ///         .await;
+    /// };
+    /// ```
+    pub fn build_image_from_stream<T, K, S, E>(
+        &self,
+        options: T,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        context: S,
+    ) -> impl Stream<Item = Result<BuildImageResults, Error>>
+    where
+        T: BuildImageQueryParams<K>,
+        K: AsRef<str>,
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+    {
+        self.build_image_query(
+            options.into_array(),
+            credentials,
+            Some(Body::wrap_stream(context)),
+        )
+    }
+
+    /// ---
+    ///
+    /// # Build Image With ID
+    ///
+    /// Build an image as with the [Build Image API](struct.Docker.html#method.build_image), under
+    /// a build ID that can be passed to [`cancel_build`](struct.Docker.html#method.cancel_build)
+    /// from another task.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Build Image Options](image/struct.BuildImageOptions.html) struct. If its `buildid` is
+    ///  `None`, an ID is generated.
+    ///  - Optional map of registry hostname to [Docker
+    ///  Credentials](auth/struct.DockerCredentials.html), e.g. converted from [Registry Auth
+    ///  Configs](auth/struct.RegistryAuthConfigs.html), sent as the `X-Registry-Config` header.
+    ///  - Tar archive compressed with one of the following algorithms: identity (no compression),
+    ///    gzip, bzip2, xz. Optional [Hyper Body](https://hyper.rs/hyper/master/hyper/struct.Body.html).
+    ///  - Optional timeout, after which the build is cancelled and the stream ends with a
+    ///  `BuildCancelledError`.
+    ///
+    /// # Returns
+    ///
+    ///  - A tuple of the build ID and the [Build Image Results](image/enum.BuildImageResults.html),
+    ///  wrapped in an asynchronous Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::BuildImageOptions;
+    ///
+    /// use std::default::Default;
+    /// use std::time::Duration;
+    ///
+    /// let options = BuildImageOptions{
+    ///     remote: "https://github.com/fussybeaver/bollard.git#master:resources",
+    ///     t: "my-image",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let (build_id, stream) =
+    ///     docker.build_image_with_id(options, None, None, Some(Duration::from_secs(600)));
+    /// ```
+    pub fn build_image_with_id<'a, T>(
+        &self,
+        options: BuildImageOptions<T>,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        tar: Option<Body>,
+        timeout: Option<Duration>,
+    ) -> (String, impl Stream<Item = Result<BuildImageResults, Error>>)
+    where
+        T: AsRef<str> + Eq + Hash,
+        BuildImageOptions<T>: BuildImageQueryParams<&'a str>,
+    {
+        let build_id = options.buildid.as_ref().map(|id| id.as_ref().to_owned());
+        let generated = build_id.is_none();
+        let build_id = build_id.unwrap_or_else(generate_build_id);
+
+        let query = options.into_array().map(|query| {
+            let mut query: Vec<(String, String)> = query
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect();
+            if generated {
+                query.push((String::from("buildid"), build_id.clone()));
+            }
+            query
+        });
+
+        let cancel = timeout
+            .map(|timeout| Docker::cancel_build_after(self.clone(), build_id.clone(), timeout));
+
+        let stream = CancelOnTimeout {
+            stream: self.build_image_query(query, credentials, tar),
+            cancel,
+            done: false,
+        };
+
+        (build_id, stream)
+    }
+
+    /// ---
+    ///
+    /// # Cancel Build
+    ///
+    /// Cancel a build started with a build ID. Cancelling a build that already finished is a
+    /// no-op.
+    ///
+    /// # Arguments
+    ///
+    ///  - Build ID as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.cancel_build("bollard-1");
+    /// ```
+    pub async fn cancel_build(&self, build_id: &str) -> Result<(), Error> {
+        let url = "/build/cancel";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            Ok(Some(ArrayVec::from([("id", build_id)]))),
+            Ok(Body::empty()),
+        );
+
+        match self.process_into_unit(req).await {
+            Err(e) => match e.kind() {
+                DockerResponseNotFoundError { .. } => Ok(()),
+                _ => Err(e),
+            },
+            v => v,
+        }
+    }
+
+    async fn cancel_build_after(docker: Docker, build_id: String, timeout: Duration) -> Error {
+        docker.runtime.delay(timeout).await;
+        match docker.cancel_build(&build_id).await {
+            Ok(()) => BuildCancelledError { build_id, timeout }.into(),
+            Err(e) => e,
+        }
+    }
+
+    fn build_image_query<K>(
+        &self,
+        query: Result<Vec<(K, String)>, Error>,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        tar: Option<Body>,
+    ) -> impl Stream<Item = Result<BuildImageResults, Error>>
+    where
+        K: AsRef<str>,
     {
         let url = "/build";
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| HashMap::new())) {
-            Ok(ser_cred) => {
+        let registry_config = credentials
+            .map(RegistryAuthConfigs::from)
+            .filter(|configs| !configs.is_empty())
+            .map(|configs| configs.encode())
+            .transpose();
+
+        match registry_config {
+            Ok(registry_config) => {
+                let mut builder = Builder::new()
+                    .method(Method::POST)
+                    .header(CONTENT_TYPE, "application/x-tar");
+                if let Some(registry_config) = registry_config {
+                    builder = builder.header("X-Registry-Config", registry_config);
+                }
+
                 let req = self.build_request(
                     &url,
-                    Builder::new()
-                        .method(Method::POST)
-                        .header(CONTENT_TYPE, "application/x-tar")
-                        .header("X-Registry-Config", base64::encode(&ser_cred)),
-                    options.into_array().map(|v| Some(v)),
+                    builder,
+                    query.map(|v| Some(v)),
                     Ok(tar.unwrap_or_else(|| Body::empty())),
                 );
 
                 self.process_into_stream(req).boxed()
             }
-            Err(e) => {
-                stream::once(async move { Err(JsonSerializeError { err: e }.into()) }).boxed()
-            }
+            Err(e) => stream::once(async move { Err(e) }).boxed(),
         }
     }
 
@@ -1739,4 +2782,342 @@ impl Docker {
             }
         }
     }
+
+    /// ---
+    ///
+    /// # Export Image To Path
+    ///
+    /// Export an image to a tarball on disk, streamed without buffering it in memory, see the
+    /// [export image endpoint](struct.Docker.html#method.export_image).
+    ///
+    /// The tarball is written to the path with a `.partial` suffix, and renamed once it is
+    /// complete. The incomplete file is removed if the export fails.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - Path of the tarball.
+    ///
+    /// # Returns
+    ///
+    ///  - The hex encoded sha256 digest of the tarball, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.export_image_to_path("alpine:3.11", "alpine.tar");
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn export_image_to_path<P>(&self, image_name: &str, path: P) -> Result<String, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.export_image_into(image_name, path.as_ref(), false)
+            .await
+    }
+
+    /// ---
+    ///
+    /// # Resume Export Image To Path
+    ///
+    /// Export an image to a tarball on disk like the [Export Image To Path
+    /// API](struct.Docker.html#method.export_image_to_path), continuing from the `.partial` file
+    /// left by an earlier failed export. The incomplete file is kept if the export fails again.
+    ///
+    /// The docker server cannot resume an export, so the bytes already written are skipped from
+    /// the start of the export. This relies on the export of an image being reproducible, which
+    /// should be confirmed by comparing the returned digest to a known one.
+    ///
+    /// An [ExportResumeMismatchError](errors/enum.ErrorKind.html#variant.ExportResumeMismatchError)
+    /// is returned, and the partial file removed, if it is longer than the export.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - Path of the tarball.
+    ///
+    /// # Returns
+    ///
+    ///  - The hex encoded sha256 digest of the tarball, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.resume_export_image_to_path("alpine:3.11", "alpine.tar");
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn resume_export_image_to_path<P>(
+        &self,
+        image_name: &str,
+        path: P,
+    ) -> Result<String, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.export_image_into(image_name, path.as_ref(), true)
+            .await
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn export_image_into(
+        &self,
+        image_name: &str,
+        path: &Path,
+        resume: bool,
+    ) -> Result<String, Error> {
+        let partial = partial_export_path(path);
+
+        match self
+            .export_image_partial(image_name, &partial, resume)
+            .await
+        {
+            Ok(digest) => {
+                tokio::fs::rename(&partial, path).await?;
+                Ok(digest)
+            }
+            Err(e) => {
+                if !resume {
+                    let _ = tokio::fs::remove_file(&partial).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn export_image_partial(
+        &self,
+        image_name: &str,
+        partial: &Path,
+        resume: bool,
+    ) -> Result<String, Error> {
+        let mut hasher = Sha256::new();
+        let mut skip = 0;
+
+        if resume {
+            match tokio::fs::File::open(partial).await {
+                Ok(file) => {
+                    let mut existing = FramedRead::new(file, BytesCodec::new());
+                    while let Some(chunk) = existing.next().await {
+                        let chunk = chunk?;
+                        hasher.input(&chunk);
+                        skip += chunk.len();
+                    }
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => (),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resume)
+            .truncate(!resume)
+            .open(partial)
+            .await?;
+
+        let mut stream = Box::pin(self.export_image(image_name));
+        while let Some(chunk) = stream.next().await {
+            let mut chunk = chunk?;
+            if skip > 0 {
+                let skipped = cmp::min(skip, chunk.len());
+                skip -= skipped;
+                chunk = chunk.split_off(skipped);
+                if chunk.is_empty() {
+                    continue;
+                }
+            }
+            hasher.input(&chunk);
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+
+        if skip > 0 {
+            tokio::fs::remove_file(partial).await?;
+            return Err(ExportResumeMismatchError {
+                path: partial.display().to_string(),
+            }
+            .into());
+        }
+
+        Ok(hex::encode(hasher.result()))
+    }
+
+    /// ---
+    ///
+    /// # Load Image From Path
+    ///
+    /// Load the images of a tarball on disk, streamed without buffering it in memory, see the
+    /// [import image endpoint](struct.Docker.html#method.import_image).
+    ///
+    /// # Arguments
+    ///
+    ///  - Path of the tarball.
+    ///  - Whether to suppress progress details during load.
+    ///
+    /// # Returns
+    ///
+    ///  - The hex encoded sha256 digest of the tarball, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.load_image_from_path("alpine.tar", true);
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn load_image_from_path<P>(&self, path: P, quiet: bool) -> Result<String, Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.load_image_from(path.as_ref(), quiet).await
+    }
+
+    /// ---
+    ///
+    /// # Load Verified Image From Path
+    ///
+    /// Load the images of a tarball on disk like the [Load Image From Path
+    /// API](struct.Docker.html#method.load_image_from_path), after verifying that the tarball's
+    /// `manifest.json` only references the expected images. Nothing is sent to the docker server
+    /// if the verification fails.
+    ///
+    /// Expected image names without a tag match the `latest` tag.
+    ///
+    /// An [UnexpectedArchiveImagesError](errors/enum.ErrorKind.html#variant.UnexpectedArchiveImagesError)
+    /// is returned if the tarball references other images, and an
+    /// [ImageArchiveManifestError](errors/enum.ErrorKind.html#variant.ImageArchiveManifestError)
+    /// if it has no valid `manifest.json`.
+    ///
+    /// # Arguments
+    ///
+    ///  - Path of the tarball.
+    ///  - Whether to suppress progress details during load.
+    ///  - The expected image names.
+    ///
+    /// # Returns
+    ///
+    ///  - The hex encoded sha256 digest of the tarball, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.load_verified_image_from_path("alpine.tar", true, &["alpine:3.11"]);
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn load_verified_image_from_path<P, T>(
+        &self,
+        path: P,
+        quiet: bool,
+        expected_images: &[T],
+    ) -> Result<String, Error>
+    where
+        P: AsRef<Path>,
+        T: AsRef<str>,
+    {
+        let archive = path.as_ref().to_owned();
+        let expected: Vec<String> = expected_images
+            .iter()
+            .map(|name| normalize_image_name(name.as_ref()))
+            .collect();
+        // reading the archive blocks, so it is done off the runtime's worker threads
+        tokio::task::spawn_blocking(move || verify_archive_images(&archive, &expected))
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??;
+
+        self.load_image_from(path.as_ref(), quiet).await
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn load_image_from(&self, path: &Path, quiet: bool) -> Result<String, Error> {
+        let file = tokio::fs::File::open(path).await?;
+
+        let hasher = Arc::new(Mutex::new(Sha256::new()));
+        let body_hasher = hasher.clone();
+        let body = Body::wrap_stream(FramedRead::new(file, BytesCodec::new()).map_ok(
+            move |chunk| {
+                body_hasher.lock().unwrap().input(&chunk);
+                chunk.freeze()
+            },
+        ));
+
+        self.import_image(ImportImageOptions { quiet }, body, None)
+            .try_for_each(|_| future::ok(()))
+            .await?;
+
+        let digest = hasher.lock().unwrap().clone().result();
+        Ok(hex::encode(digest))
+    }
+}
+
+#[cfg(feature = "tokio")]
+fn partial_export_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    PathBuf::from(partial)
+}
+
+#[cfg(feature = "tokio")]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageArchiveManifest {
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    repo_tags: Vec<String>,
+}
+
+#[cfg(feature = "tokio")]
+fn read_archive_manifest(path: &Path) -> Option<Vec<ImageArchiveManifest>> {
+    let mut archive = tar::Archive::new(File::open(path).ok()?);
+    for entry in archive.entries().ok()? {
+        let entry = entry.ok()?;
+        if entry.path().ok()?.as_os_str() == "manifest.json" {
+            return serde_json::from_reader(entry).ok();
+        }
+    }
+    None
+}
+
+#[cfg(feature = "tokio")]
+fn normalize_image_name(name: &str) -> String {
+    let tagged = match name.rfind(':') {
+        Some(index) => !name[index..].contains('/'),
+        None => false,
+    };
+    if tagged || name.contains('@') {
+        String::from(name)
+    } else {
+        format!("{}:latest", name)
+    }
+}
+
+/// Check that the images of an archive are among the expected images, with normalized names.
+#[cfg(feature = "tokio")]
+fn verify_archive_images(path: &Path, expected: &[String]) -> Result<(), Error> {
+    let manifest = read_archive_manifest(path).ok_or_else(|| ImageArchiveManifestError {
+        path: path.display().to_string(),
+    })?;
+
+    let unexpected: Vec<String> = manifest
+        .into_iter()
+        .flat_map(|image| image.repo_tags)
+        .filter(|tag| !expected.contains(&normalize_image_name(tag)))
+        .collect();
+
+    if unexpected.is_empty() {
+        Ok(())
+    } else {
+        Err(UnexpectedArchiveImagesError { images: unexpected }.into())
+    }
 }
@@ -0,0 +1,204 @@
+//! Layer API: wrap the requests sent to the docker server in [tower](https://docs.rs/tower)
+//! middleware, such as rate limits, circuit breakers, authentication or fault injection in tests.
+//!
+//! Layers are set with [`Docker::with_layer`](../struct.Docker.html#method.with_layer), and wrap
+//! a [`DockerService`](struct.DockerService.html) sending requests to the docker server. They see
+//! requests after the [audit hooks](../audit/index.html), and before the health check, retries
+//! and timeout of the client.
+use futures_util::future;
+use hyper::{Body, Request, Response};
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use crate::docker::Dispatch;
+use crate::errors::Error;
+use crate::errors::ErrorKind::LayerError;
+
+pub use tower_layer::Layer;
+pub use tower_service::Service;
+
+/// The future of a response returned by a [`DockerService`](struct.DockerService.html).
+pub type ResponseFuture = Pin<Box<dyn Future<Output = Result<Response<Body>, Error>> + Send>>;
+
+/// The options of the client a request is sent with, travelling with the request through the
+/// layers.
+struct DispatchSlot(Mutex<Dispatch>);
+
+/// A layered service, cloned for every request.
+trait CloneService: Send + Sync {
+    fn clone_box(&self) -> Box<dyn CloneService>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>>;
+
+    fn call(&mut self, request: Request<Body>) -> ResponseFuture;
+}
+
+impl<S> CloneService for S
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + Sync + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    S::Future: Send + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CloneService> {
+        Box::new(self.clone())
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Service::poll_ready(self, cx).map_err(into_error)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> ResponseFuture {
+        let response = Service::call(self, request);
+        Box::pin(async move { response.await.map_err(into_error) })
+    }
+}
+
+/// Errors of this crate are passed through the layers as is, other errors are wrapped in a
+/// [Layer Error](../errors/enum.ErrorKind.html#variant.LayerError).
+fn into_error<E>(err: E) -> Error
+where
+    E: Into<Box<dyn StdError + Send + Sync>>,
+{
+    match err.into().downcast::<Error>() {
+        Ok(err) => *err,
+        Err(err) => LayerError { err }.into(),
+    }
+}
+
+/// The service sending requests to the docker server, wrapped by the layers of a client.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::layer::{DockerService, Layer, Service};
+///
+/// use hyper::{Body, Request};
+///
+/// use std::task::{Context, Poll};
+///
+/// // adds a header to every request
+/// #[derive(Debug, Clone)]
+/// struct Authorize<S>(S);
+///
+/// impl<S: Service<Request<Body>>> Service<Request<Body>> for Authorize<S> {
+///     type Response = S::Response;
+///     type Error = S::Error;
+///     type Future = S::Future;
+///
+///     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+///         self.0.poll_ready(cx)
+///     }
+///
+///     fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+///         request
+///             .headers_mut()
+///             .insert("Authorization", "Bearer 0123456789".parse().unwrap());
+///         self.0.call(request)
+///     }
+/// }
+///
+/// #[derive(Debug)]
+/// struct AuthorizeLayer;
+///
+/// impl Layer<DockerService> for AuthorizeLayer {
+///     type Service = Authorize<DockerService>;
+///
+///     fn layer(&self, service: DockerService) -> Self::Service {
+///         Authorize(service)
+///     }
+/// }
+/// ```
+pub struct DockerService {
+    // the layers set before, or sending the request if none
+    inner: Option<Box<dyn CloneService>>,
+}
+
+impl DockerService {
+    pub(crate) fn new<S>(service: S) -> DockerService
+    where
+        S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + Sync + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+        S::Future: Send + 'static,
+    {
+        DockerService {
+            inner: Some(Box::new(service)),
+        }
+    }
+
+    /// The service sending requests to the docker server, without layers.
+    pub(crate) fn root() -> DockerService {
+        DockerService { inner: None }
+    }
+}
+
+impl Clone for DockerService {
+    fn clone(&self) -> DockerService {
+        DockerService {
+            inner: self.inner.as_ref().map(|inner| inner.clone_box()),
+        }
+    }
+}
+
+impl fmt::Debug for DockerService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DockerService")
+            .field("layered", &self.inner.is_some())
+            .finish()
+    }
+}
+
+impl Service<Request<Body>> for DockerService {
+    type Response = Response<Body>;
+    type Error = Error;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.inner {
+            Some(ref mut inner) => inner.poll_ready(cx),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        if let Some(ref mut inner) = self.inner {
+            return inner.call(request);
+        }
+
+        match request
+            .extensions_mut()
+            .remove::<DispatchSlot>()
+            .and_then(|slot| slot.0.into_inner().ok())
+        {
+            Some(dispatch) => Box::pin(dispatch.send(request)),
+            None => Box::pin(future::err(
+                LayerError {
+                    err: "a layer removed the extensions of the request".into(),
+                }
+                .into(),
+            )),
+        }
+    }
+}
+
+/// Send a request through the layers of a client, if any.
+pub(crate) async fn send(
+    service: Option<DockerService>,
+    dispatch: Dispatch,
+    mut request: Request<Body>,
+) -> Result<Response<Body>, Error> {
+    match service {
+        Some(mut service) => {
+            request
+                .extensions_mut()
+                .insert(DispatchSlot(Mutex::new(dispatch)));
+            future::poll_fn(|cx| Service::poll_ready(&mut service, cx)).await?;
+            Service::call(&mut service, request).await
+        }
+        None => dispatch.send(request).await,
+    }
+}
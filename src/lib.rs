@@ -84,6 +84,27 @@
 //! Docker::connect_with_http_defaults();
 //! ```
 //!
+//! ### SSH
+//!
+//! SSH is switched off by default, and can be enabled through the `ssh` cargo feature.
+//!
+//! The client will connect to the `ssh://[user@]host[:port]` location pointed to by the
+//! `DOCKER_HOST` environment variable, by running `docker system dial-stdio` on the remote host
+//! through the `ssh` command, which must be installed and able to log in without a prompt. Use
+//! the `Docker::connect_with_ssh` method API to parameterise the interface.
+//!
+//! ```rust
+//! use bollard::Docker;
+//! #[cfg(feature = "ssh")]
+//! Docker::connect_with_ssh_defaults();
+//! ```
+//!
+//! ### Proxies
+//!
+//! HTTP and HTTPS connections go through the proxies set in the `HTTP_PROXY`, `HTTPS_PROXY` and
+//! `NO_PROXY` environment variables, tunnelling HTTPS with `CONNECT`. Use the
+//! `Docker::connect_with_http_proxy` method API to set a proxy explicitly.
+//!
 //! ### SSL via openssl
 //!
 //! Openssl is switched off by default, and can be enabled through the `ssl` cargo feature.
@@ -104,6 +125,24 @@
 //! Docker::connect_with_ssl_defaults();
 //! ```
 //!
+//! ### SSL via rustls
+//!
+//! Rustls avoids linking to OpenSSL, e.g. when cross compiling or building against musl. It is
+//! switched off by default, and can be enabled through the `rustls` cargo feature.
+//!
+//! The client will connect to the location pointed to by `DOCKER_HOST` environment variable, or
+//! `localhost:2375` if missing, and load the `key.pem`, `cert.pem` and `ca.pem` certificates from
+//! the `DOCKER_CERT_PATH` directory, as with openssl.
+//!
+//! Use the `Docker::connect_with_rustls` method API
+//! to parameterise the interface.
+//!
+//! ```rust
+//! use bollard::Docker;
+//! #[cfg(feature = "rustls")]
+//! Docker::connect_with_rustls_defaults();
+//! ```
+//!
 //! ### TLS
 //!
 //! Native TLS allows you to avoid the SSL bindings.
@@ -115,8 +154,9 @@
 //! certificates - `identity.pfx` for the PKCS #12 archive and `ca.pem` for the certificate
 //! authority chain.
 //!
-//! Use the `Docker::connect_with_ssl` method API
-//! to parameterise the interface.
+//! Use the `Docker::connect_with_tls` method API to parameterise the interface, or
+//! `Docker::connect_with_tls_pem` to use the `key.pem` (in PKCS #8 format) and `cert.pem`
+//! certificates instead of a PKCS #12 archive.
 //!
 //! ```rust
 //! use bollard::Docker;
@@ -267,6 +307,30 @@
 //! let result = rt.block_on(future);
 //! ```
 //!
+//! ## A Blocking Client
+//!
+//! Programs without a runtime of their own can enable the `blocking` feature, and use the
+//! synchronous client in the [blocking](blocking/index.html) module, which owns a runtime:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "blocking")]
+//! # {
+//! use bollard::blocking::Docker;
+//! use bollard::image::ListImagesOptions;
+//!
+//! let docker = Docker::connect_with_local_defaults().unwrap();
+//! let images = docker.list_images(None::<ListImagesOptions<String>>).unwrap();
+//! # }
+//! ```
+//!
+//! ## Other Runtimes
+//!
+//! The connectors dialing the docker server, the TLS and ssh transports, and the methods reading
+//! or writing files run on tokio, behind the default `tokio` cargo feature. Without it, connect
+//! through a connector of another runtime with `Docker::connect_with_connector`, and run the
+//! client on that runtime with `Docker::with_runtime`, see the [runtime](runtime/index.html)
+//! module.
+//!
 //! # History
 //!
 //! This library stems from the [boondock rust library](https://github.com/faradayio/boondock),
@@ -312,20 +376,39 @@ extern crate serde_derive;
 extern crate log;
 
 // declare modules
+pub mod audit;
 pub mod auth;
+pub mod blocking;
+pub mod buildkit;
+pub mod checkpoint;
 pub mod container;
+mod context;
+pub mod diff;
 mod docker;
 pub mod errors;
 pub mod exec;
+mod fd;
 pub mod image;
+pub mod layer;
+mod linux_socket;
 mod named_pipe;
 pub mod network;
+pub mod node;
+pub mod progress;
+pub mod proxy;
 mod read;
+pub mod retry;
+pub mod runtime;
 pub mod service;
 pub mod service_models;
+mod shutdown;
+mod ssh;
+pub mod swarm;
 pub mod system;
 mod uri;
 pub mod volume;
 
 // publicly re-export
-pub use crate::docker::{ClientVersion, Docker, API_DEFAULT_VERSION};
+pub use crate::docker::{ClientVersion, Docker, PoolOptions, API_DEFAULT_VERSION};
+#[cfg(feature = "openssl")]
+pub use crate::docker::SslIdentity;
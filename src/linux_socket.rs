@@ -0,0 +1,139 @@
+#![cfg(all(target_os = "linux", feature = "tokio"))]
+//! Reach the docker server over sockets only addressable on Linux: `AF_VSOCK` sockets between a
+//! virtual machine and its host, and unix sockets in the abstract namespace.
+
+use tokio::net::UnixStream;
+
+use std::future::Future;
+use std::io;
+use std::mem::{self, size_of};
+use std::os::unix::io::FromRawFd;
+use std::os::unix::net::UnixStream as StdUnixStream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::InvalidVsockAddressError;
+use crate::fd::FdStream;
+
+/// The address of a socket that the standard library cannot connect to.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SocketAddress {
+    /// A `vsock://<cid>:<port>` address.
+    Vsock { cid: u32, port: u32 },
+    /// A `unix://@<name>` address, of a unix socket in the abstract namespace.
+    Abstract(String),
+}
+
+impl SocketAddress {
+    pub(crate) fn parse_vsock(addr: &str) -> Result<SocketAddress, Error> {
+        let invalid = || InvalidVsockAddressError {
+            addr: String::from(addr),
+        };
+
+        let mut parts = addr.trim_start_matches("vsock://").splitn(2, ':');
+        let cid = parts
+            .next()
+            .and_then(|cid| cid.parse().ok())
+            .ok_or_else(invalid)?;
+        let port = parts
+            .next()
+            .and_then(|port| port.parse().ok())
+            .ok_or_else(invalid)?;
+
+        Ok(SocketAddress::Vsock { cid, port })
+    }
+
+    /// Open a blocking connection to the socket.
+    fn connect(&self) -> Result<StdUnixStream, io::Error> {
+        let family = match self {
+            SocketAddress::Vsock { .. } => libc::AF_VSOCK,
+            SocketAddress::Abstract(_) => libc::AF_UNIX,
+        };
+
+        let fd = unsafe { libc::socket(family, libc::SOCK_STREAM | libc::SOCK_CLOEXEC, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // tokio only reads and writes the file descriptor of a unix stream, so it carries any
+        // connected stream socket, and closes it when dropped
+        let stream = unsafe { StdUnixStream::from_raw_fd(fd) };
+
+        let connected = match self {
+            SocketAddress::Vsock { cid, port } => {
+                let mut addr: libc::sockaddr_vm = unsafe { mem::zeroed() };
+                addr.svm_family = libc::AF_VSOCK as libc::sa_family_t;
+                addr.svm_cid = *cid;
+                addr.svm_port = *port;
+
+                let addr: *const libc::sockaddr_vm = &addr;
+                unsafe {
+                    libc::connect(
+                        fd,
+                        addr as *const libc::sockaddr,
+                        size_of::<libc::sockaddr_vm>() as libc::socklen_t,
+                    )
+                }
+            }
+            SocketAddress::Abstract(name) => {
+                let mut addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+                addr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+
+                // the name follows a leading nul byte, and is not nul terminated
+                let name = name.as_bytes();
+                if name.len() >= addr.sun_path.len() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "abstract socket name too long",
+                    ));
+                }
+                for (dst, src) in addr.sun_path[1..].iter_mut().zip(name) {
+                    *dst = libc::c_char::from_ne_bytes([*src]);
+                }
+                let len = size_of::<libc::sa_family_t>() + 1 + name.len();
+
+                let addr: *const libc::sockaddr_un = &addr;
+                unsafe { libc::connect(fd, addr as *const libc::sockaddr, len as libc::socklen_t) }
+            }
+        };
+        if connected < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        stream.set_nonblocking(true)?;
+        Ok(stream)
+    }
+}
+
+/// Connects to a vsock or abstract unix socket.
+#[derive(Debug, Clone)]
+pub struct SocketConnector {
+    address: SocketAddress,
+}
+
+impl SocketConnector {
+    pub(crate) fn new(address: SocketAddress) -> SocketConnector {
+        SocketConnector { address }
+    }
+}
+
+impl hyper::service::Service<hyper::Uri> for SocketConnector {
+    type Response = FdStream;
+    type Error = io::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _destination: hyper::Uri) -> Self::Future {
+        let address = self.address.clone();
+        Box::pin(async move {
+            let stream = tokio::task::spawn_blocking(move || address.connect())
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??;
+            Ok(FdStream::new(UnixStream::from_std(stream)?))
+        })
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg(windows)]
+#![cfg(all(windows, feature = "tokio"))]
 
 use futures_core::ready;
 use hyper::client::connect::Connected;
@@ -1,19 +1,83 @@
 //! Network API: Networks are user-defined networks that containers can be attached to.
 
 use arrayvec::ArrayVec;
+use chrono::{DateTime, Utc};
+use futures_core::Stream;
+use futures_util::stream::TryStreamExt;
 use http::request::Builder;
 use hyper::{Body, Method};
+#[cfg(feature = "ipnet")]
+use ipnet::IpNet;
 use serde::ser::Serialize;
 use serde_json;
 
 use std::cmp::Eq;
 use std::collections::HashMap;
+#[cfg(feature = "ipnet")]
+use std::convert::TryFrom;
+use std::fmt;
 use std::hash::Hash;
+#[cfg(feature = "ipnet")]
+use std::net::IpAddr;
+#[cfg(feature = "ipnet")]
+use std::str::FromStr;
 
 use super::Docker;
-use crate::docker::{FALSE_STR, TRUE_STR};
-use crate::errors::Error;
-use crate::errors::ErrorKind::JsonSerializeError;
+use crate::docker::{
+    deserialize_empty_as_none, deserialize_nonoptional_map, deserialize_nonoptional_vec, FALSE_STR,
+    TRUE_STR,
+};
+#[cfg(feature = "ipnet")]
+use crate::errors::ErrorKind::InvalidIPAMConfigError;
+use crate::errors::ErrorKind::{
+    DockerResponseConflictError, DockerResponseNotFoundError, JsonSerializeError,
+    NetworkMismatchError,
+};
+use crate::errors::{Error, NetworkMismatch};
+use crate::system::{EventsOptions, EventsQueryParams, EventsResults};
+use crate::uri::encode_path_segment;
+
+/// A reference to a network, accepted by the [Inspect Network
+/// API](../struct.Docker.html#method.inspect_network), the [Remove Network
+/// API](../struct.Docker.html#method.remove_network), the [Connect Network
+/// API](../struct.Docker.html#method.connect_network) and the [Disconnect Network
+/// API](../struct.Docker.html#method.disconnect_network), along with plain strings.
+///
+/// The docker server resolves a reference as a full ID first, then as a name, then as a unique
+/// ID prefix. Names are escaped in the request path, so that they may contain any character.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::network::{InspectNetworkOptions, NetworkRef};
+///
+/// docker.inspect_network(NetworkRef::IdPrefix("7fd5ec1c"), None::<InspectNetworkOptions>);
+/// docker.remove_network(NetworkRef::Name("team a/frontend"));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NetworkRef<T> {
+    /// The full ID of the network.
+    Id(T),
+    /// A prefix of the ID of the network, matching no other network.
+    IdPrefix(T),
+    /// The name of the network.
+    Name(T),
+}
+
+impl<T: AsRef<str>> AsRef<str> for NetworkRef<T> {
+    fn as_ref(&self) -> &str {
+        match self {
+            NetworkRef::Id(id) => id.as_ref(),
+            NetworkRef::IdPrefix(prefix) => prefix.as_ref(),
+            NetworkRef::Name(name) => name.as_ref(),
+        }
+    }
+}
+
+/// The prefix of the key of the load balancer endpoint in the containers of a swarm network.
+const LOAD_BALANCER_ENDPOINT_PREFIX: &str = "lb-";
 
 /// Network configuration used in the [Create Network API](../struct.Docker.html#method.create_network)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -48,6 +112,187 @@ where
     pub options: HashMap<T, T>,
     /// User-defined key/value metadata.
     pub labels: HashMap<T, T>,
+    /// The scope of the network. Defaults to the scope of the driver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<NetworkScope>,
+    /// Create a configuration-only network, a placeholder for the configuration of networks
+    /// created from it with `config_from`. Containers cannot be attached to it.
+    pub config_only: bool,
+    /// Create the network from the configuration of a configuration-only network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_from: Option<ConfigReference<T>>,
+}
+
+/// The mode of a `macvlan` network, set with
+/// [`CreateNetworkOptions::macvlan_mode`](struct.CreateNetworkOptions.html#method.macvlan_mode).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MacvlanMode {
+    /// Containers on the same parent interface reach each other directly, the default.
+    Bridge,
+    /// Traffic between containers is sent through the external switch.
+    Vepa,
+    /// A single container owns the parent interface.
+    Passthru,
+    /// Containers on the same parent interface cannot reach each other.
+    Private,
+}
+
+impl MacvlanMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            MacvlanMode::Bridge => "bridge",
+            MacvlanMode::Vepa => "vepa",
+            MacvlanMode::Passthru => "passthru",
+            MacvlanMode::Private => "private",
+        }
+    }
+}
+
+/// The mode of an `ipvlan` network, set with
+/// [`CreateNetworkOptions::ipvlan_mode`](struct.CreateNetworkOptions.html#method.ipvlan_mode).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IpvlanMode {
+    /// Containers share the MAC address of the parent interface, and are switched at layer 2,
+    /// the default.
+    L2,
+    /// Containers are routed at layer 3, with the host acting as a router.
+    L3,
+    /// Like `L3`, with the traffic going through the iptables of the host.
+    L3s,
+}
+
+impl IpvlanMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            IpvlanMode::L2 => "l2",
+            IpvlanMode::L3 => "l3",
+            IpvlanMode::L3s => "l3s",
+        }
+    }
+}
+
+impl<T> CreateNetworkOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + Default + From<&'static str>,
+{
+    fn with_driver(driver: &'static str, parent: Option<T>, subnet: Option<T>) -> Self {
+        let mut options = HashMap::new();
+        if let Some(parent) = parent {
+            options.insert(T::from("parent"), parent);
+        }
+
+        CreateNetworkOptions {
+            driver: T::from(driver),
+            options,
+            ipam: IPAM {
+                config: subnet
+                    .map(|subnet| {
+                        vec![IPAMConfig {
+                            subnet: Some(subnet),
+                            ..Default::default()
+                        }]
+                    })
+                    .unwrap_or_default(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// A `macvlan` network, giving containers their own MAC address on the network of a parent
+    /// interface of the host.
+    ///
+    /// # Arguments
+    ///
+    ///  - The parent interface, e.g. `eth0`, or `eth0.50` for a VLAN sub-interface.
+    ///  - The subnet of the parent network, from which container addresses are allocated.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::network::{CreateNetworkOptions, MacvlanMode};
+    ///
+    /// let options = CreateNetworkOptions {
+    ///     name: "my_macvlan_network",
+    ///     ..CreateNetworkOptions::macvlan("eth0", "192.168.1.0/24")
+    /// }
+    /// .macvlan_mode(MacvlanMode::Bridge);
+    ///
+    /// assert_eq!(options.driver, "macvlan");
+    /// assert_eq!(options.options["parent"], "eth0");
+    /// ```
+    pub fn macvlan(parent: T, subnet: T) -> Self {
+        Self::with_driver("macvlan", Some(parent), Some(subnet))
+    }
+
+    /// An `ipvlan` network, giving containers addresses on the network of a parent interface of
+    /// the host, sharing its MAC address.
+    ///
+    /// # Arguments
+    ///
+    ///  - The parent interface, e.g. `eth0`.
+    ///  - The subnet of the parent network, from which container addresses are allocated.
+    pub fn ipvlan(parent: T, subnet: T) -> Self {
+        Self::with_driver("ipvlan", Some(parent), Some(subnet))
+    }
+
+    /// An `overlay` network, spanning the nodes of a swarm. The subnet is allocated by the swarm
+    /// unless set in the [`ipam`](#structfield.ipam) field.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::network::CreateNetworkOptions;
+    ///
+    /// let options = CreateNetworkOptions {
+    ///     name: "my_overlay_network",
+    ///     attachable: true,
+    ///     ..CreateNetworkOptions::overlay()
+    /// }
+    /// .encrypted(true);
+    ///
+    /// assert!(options.options.contains_key("encrypted"));
+    /// ```
+    pub fn overlay() -> Self {
+        Self::with_driver("overlay", None, None)
+    }
+
+    /// Set the mode of a `macvlan` network.
+    pub fn macvlan_mode(mut self, mode: MacvlanMode) -> Self {
+        self.options
+            .insert(T::from("macvlan_mode"), T::from(mode.as_str()));
+        self
+    }
+
+    /// Set the mode of an `ipvlan` network.
+    pub fn ipvlan_mode(mut self, mode: IpvlanMode) -> Self {
+        self.options
+            .insert(T::from("ipvlan_mode"), T::from(mode.as_str()));
+        self
+    }
+
+    /// Encrypt the traffic between the nodes of an `overlay` network with IPsec.
+    pub fn encrypted(mut self, encrypted: bool) -> Self {
+        if encrypted {
+            // the driver only checks for the presence of the option
+            self.options.insert(T::from("encrypted"), T::from(""));
+        } else {
+            self.options.remove(&T::from("encrypted"));
+        }
+        self
+    }
+}
+
+/// The configuration-only network a network is created from, in the [Create Network
+/// Options](struct.CreateNetworkOptions.html).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ConfigReference<T>
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    /// The name of the configuration-only network.
+    pub network: T,
 }
 
 /// IPAM represents IP Address Management
@@ -66,7 +311,9 @@ where
     pub options: Option<HashMap<T, T>>,
 }
 
-/// IPAMConfig represents IPAM configurations
+/// IPAMConfig represents IPAM configurations. With the `ipnet` cargo feature, its addresses are
+/// validated before the request is sent, and it is built from a
+/// [TypedIPAMConfig](struct.TypedIPAMConfig.html).
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 #[allow(missing_docs)]
@@ -81,13 +328,114 @@ where
     pub aux_address: Option<HashMap<T, T>>,
 }
 
+/// IPAM configuration with typed addresses, enabled with the `ipnet` cargo feature. It converts
+/// into an [IPAMConfig](struct.IPAMConfig.html), and is parsed from one to validate its
+/// addresses.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use bollard::network::{IPAMConfig, TypedIPAMConfig};
+///
+/// use std::convert::TryFrom;
+///
+/// let config: IPAMConfig<String> = TypedIPAMConfig {
+///     subnet: Some("10.10.10.0/24".parse().unwrap()),
+///     gateway: Some("10.10.10.1".parse().unwrap()),
+///     ..Default::default()
+/// }
+/// .into();
+///
+/// assert!(TypedIPAMConfig::try_from(&IPAMConfig {
+///     subnet: Some("10.10.10.0/33"),
+///     ..Default::default()
+/// })
+/// .is_err());
+/// ```
+#[cfg(feature = "ipnet")]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TypedIPAMConfig {
+    /// Subnet of the network.
+    pub subnet: Option<IpNet>,
+    /// Range of the subnet that container addresses are allocated from.
+    pub ip_range: Option<IpNet>,
+    /// Gateway of the subnet.
+    pub gateway: Option<IpAddr>,
+    /// Addresses reserved for the network driver, by device name.
+    pub aux_address: HashMap<String, IpAddr>,
+}
+
+#[cfg(feature = "ipnet")]
+fn parse_ipam_field<A: FromStr>(field: &str, value: Option<&str>) -> Result<Option<A>, Error> {
+    match value {
+        Some(value) => match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(InvalidIPAMConfigError {
+                field: String::from(field),
+                value: String::from(value),
+            }
+            .into()),
+        },
+        None => Ok(None),
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl<'a, T> TryFrom<&'a IPAMConfig<T>> for TypedIPAMConfig
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    type Error = Error;
+
+    fn try_from(config: &'a IPAMConfig<T>) -> Result<TypedIPAMConfig, Error> {
+        let mut aux_address = HashMap::new();
+        for (device, address) in config.aux_address.iter().flatten() {
+            let field = format!("AuxAddress {}", device.as_ref());
+            if let Some(address) = parse_ipam_field(&field, Some(address.as_ref()))? {
+                aux_address.insert(String::from(device.as_ref()), address);
+            }
+        }
+
+        Ok(TypedIPAMConfig {
+            subnet: parse_ipam_field("Subnet", config.subnet.as_ref().map(AsRef::as_ref))?,
+            ip_range: parse_ipam_field("IPRange", config.ip_range.as_ref().map(AsRef::as_ref))?,
+            gateway: parse_ipam_field("Gateway", config.gateway.as_ref().map(AsRef::as_ref))?,
+            aux_address,
+        })
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl From<TypedIPAMConfig> for IPAMConfig<String> {
+    fn from(config: TypedIPAMConfig) -> IPAMConfig<String> {
+        IPAMConfig {
+            subnet: config.subnet.map(|subnet| subnet.to_string()),
+            ip_range: config.ip_range.map(|ip_range| ip_range.to_string()),
+            gateway: config.gateway.map(|gateway| gateway.to_string()),
+            aux_address: if config.aux_address.is_empty() {
+                None
+            } else {
+                Some(
+                    config
+                        .aux_address
+                        .into_iter()
+                        .map(|(device, address)| (device, address.to_string()))
+                        .collect(),
+                )
+            },
+        }
+    }
+}
+
 /// Result type for the [Create Network API](../struct.Docker.html#method.create_network)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct CreateNetworkResults {
+    /// The ID of the network.
     pub id: String,
-    pub warning: String,
+    /// Warning encountered when creating the network, absent if there is none.
+    #[serde(default, deserialize_with = "deserialize_empty_as_none")]
+    pub warning: Option<String>,
 }
 
 /// Parameters used in the [Inspect Network API](../struct.Docker.html#method.inspect_network)
@@ -95,28 +443,28 @@ pub struct CreateNetworkResults {
 /// ## Examples
 ///
 /// ```rust
-/// use bollard::network::InspectNetworkOptions;
+/// use bollard::network::{InspectNetworkOptions, NetworkScope};
 ///
 /// InspectNetworkOptions{
 ///     verbose: true,
-///     scope: "global",
+///     scope: Some(NetworkScope::Global),
 /// };
 /// ```
 ///
 /// ```rust
 /// # use bollard::network::InspectNetworkOptions;
 /// # use std::default::Default;
-/// InspectNetworkOptions::<&str>{
+/// InspectNetworkOptions{
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct InspectNetworkOptions<T> {
+pub struct InspectNetworkOptions {
     /// Detailed inspect output for troubleshooting.
     pub verbose: bool,
-    /// Filter the network by scope (swarm, global, or local)
-    pub scope: T,
+    /// Filter the network by scope, any scope if `None`.
+    pub scope: Option<NetworkScope>,
 }
 
 #[allow(missing_docs)]
@@ -129,20 +477,11 @@ where
     fn into_array(self) -> Result<ArrayVec<[(&'a str, V); 2]>, Error>;
 }
 
-impl<'a> InspectNetworkQueryParams<'a, &'a str> for InspectNetworkOptions<&'a str> {
+impl<'a> InspectNetworkQueryParams<'a, &'a str> for InspectNetworkOptions {
     fn into_array(self) -> Result<ArrayVec<[(&'a str, &'a str); 2]>, Error> {
         Ok(ArrayVec::from([
             ("verbose", if self.verbose { TRUE_STR } else { FALSE_STR }),
-            ("scope", self.scope),
-        ]))
-    }
-}
-
-impl<'a> InspectNetworkQueryParams<'a, String> for InspectNetworkOptions<String> {
-    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 2]>, Error> {
-        Ok(ArrayVec::from([
-            ("verbose", self.verbose.to_string()),
-            ("scope", self.scope),
+            ("scope", self.scope.map(NetworkScope::as_str).unwrap_or("")),
         ]))
     }
 }
@@ -155,7 +494,7 @@ pub struct InspectNetworkResults {
     pub name: String,
     pub id: String,
     pub created: String,
-    pub scope: String,
+    pub scope: NetworkScope,
     pub driver: String,
     #[serde(rename = "EnableIPv6")]
     pub enable_ipv6: bool,
@@ -164,11 +503,66 @@ pub struct InspectNetworkResults {
     pub internal: bool,
     pub attachable: bool,
     pub ingress: bool,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
     pub containers: HashMap<String, InspectNetworkResultsContainers>,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
     pub options: HashMap<String, String>,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
     pub labels: HashMap<String, String>,
     pub config_from: HashMap<String, String>,
     pub config_only: bool,
+    /// Nodes participating in a swarm scoped network, only populated for a verbose inspect.
+    pub peers: Option<Vec<PeerInfo>>,
+    /// Services attached to a swarm scoped network, keyed by service name, only populated for a
+    /// verbose inspect.
+    pub services: Option<HashMap<String, ServiceInfo>>,
+}
+
+/// A node participating in a swarm scoped network, returned in the [Inspect Network
+/// API](../struct.Docker.html#method.inspect_network)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PeerInfo {
+    /// The node's hostname.
+    pub name: String,
+    /// The node's IP address on the underlay network.
+    #[serde(rename = "IP")]
+    pub ip: String,
+}
+
+/// A service attached to a swarm scoped network, returned in a verbose [Inspect Network
+/// API](../struct.Docker.html#method.inspect_network)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServiceInfo {
+    /// The service's virtual IP address.
+    #[serde(rename = "VIP")]
+    pub vip: String,
+    /// Ingress ports exposed by the service.
+    pub ports: Option<Vec<String>>,
+    /// Index of the service's load balancer.
+    #[serde(rename = "LocalLBIndex")]
+    pub local_lb_index: isize,
+    /// The service's tasks attached to the network, empty if none of its tasks run on the node.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub tasks: Vec<ServiceInfoTask>,
+}
+
+/// A task of a service attached to a swarm scoped network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServiceInfoTask {
+    /// The task's container name.
+    pub name: String,
+    /// The task's endpoint ID on the network.
+    #[serde(rename = "EndpointID")]
+    pub endpoint_id: String,
+    /// The task's IP address on the network.
+    #[serde(rename = "EndpointIP")]
+    pub endpoint_ip: String,
+    /// Driver specific information about the task.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
+    pub info: HashMap<String, String>,
 }
 
 /// Result type for the [Inspect Network API](../struct.Docker.html#method.inspect_network)
@@ -194,7 +588,7 @@ pub struct ListNetworksResults {
     pub name: String,
     pub id: String,
     pub created: String,
-    pub scope: String,
+    pub scope: NetworkScope,
     pub driver: String,
     #[serde(rename = "EnableIPv6")]
     pub enable_ipv6: bool,
@@ -203,10 +597,13 @@ pub struct ListNetworksResults {
     pub ingress: bool,
     #[serde(rename = "IPAM")]
     pub ipam: IPAM<String>,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
     pub options: HashMap<String, String>,
     pub config_from: HashMap<String, String>,
     pub config_only: bool,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
     pub containers: HashMap<String, InspectNetworkResultsContainers>,
+    #[serde(default, deserialize_with = "deserialize_nonoptional_map")]
     pub labels: HashMap<String, String>,
 }
 
@@ -229,6 +626,17 @@ pub struct ListNetworksResults {
 ///
 /// ```rust
 /// # use bollard::network::ListNetworksOptions;
+/// # use std::collections::HashMap;
+/// let mut filters = HashMap::new();
+/// filters.insert("dangling", vec!("true"));
+///
+/// ListNetworksOptions{
+///     filters: filters
+/// };
+/// ```
+///
+/// ```rust
+/// # use bollard::network::ListNetworksOptions;
 /// # use std::default::Default;
 ///
 /// ListNetworksOptions::<&str> {
@@ -248,9 +656,143 @@ where
     ///  - `name=<network-name>` Matches all or part of a network name.
     ///  - `scope=["swarm"|"global"|"local"]` Filters networks by scope (`swarm`, `global`, or `local`).
     ///  - `type=["custom"|"builtin"]` Filters networks by type. The `custom` keyword returns all user-defined networks.
+    ///  - `dangling=["true"|"false"]` When set to `true`, returns all networks that are not in use by a container. When set to `false`, only networks that are used by one or more containers are returned.
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// The scope of a network, returned by the [Inspect Network
+/// API](../struct.Docker.html#method.inspect_network) and the [List Networks
+/// API](../struct.Docker.html#method.list_networks), and used by a [Network
+/// Filter](enum.NetworkFilter.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum NetworkScope {
+    Swarm,
+    Global,
+    Local,
+    /// A scope reported by the docker server that this library does not know of.
+    #[serde(other)]
+    Unknown,
+}
+
+impl NetworkScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            NetworkScope::Swarm => "swarm",
+            NetworkScope::Global => "global",
+            NetworkScope::Local => "local",
+            NetworkScope::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for NetworkScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether a network is user-defined or created by the docker server, used by a [Network
+/// Filter](enum.NetworkFilter.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NetworkType {
+    /// A user-defined network.
+    Custom,
+    /// A network created by the docker server, such as `bridge`, `host` or `none`.
+    Builtin,
+}
+
+impl NetworkType {
+    fn as_str(self) -> &'static str {
+        match self {
+            NetworkType::Custom => "custom",
+            NetworkType::Builtin => "builtin",
+        }
+    }
+}
+
+/// A filter of the [List Networks API](../struct.Docker.html#method.list_networks) and the
+/// [Prune Networks API](../struct.Docker.html#method.prune_networks), added with
+/// [`ListNetworksOptions::filter`](struct.ListNetworksOptions.html#method.filter) and
+/// [`PruneNetworksOptions::filter`](struct.PruneNetworksOptions.html#method.filter).
+///
+/// The list API accepts all filters but `Until`, and the prune API accepts `Label` and `Until`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkFilter<T> {
+    /// Matches the driver of a network.
+    Driver(T),
+    /// Matches all or part of the ID of a network.
+    Id(T),
+    /// Matches a network label, in the form `key` or `key=value`.
+    Label(T),
+    /// Matches all or part of the name of a network.
+    Name(T),
+    /// Matches the scope of a network.
+    Scope(NetworkScope),
+    /// Matches user-defined or builtin networks.
+    Type(NetworkType),
+    /// Matches networks created before a timestamp, either a Unix timestamp, a date formatted
+    /// timestamp, or a Go duration string (e.g. `10m`, `1h30m`) relative to the time of the docker
+    /// server.
+    Until(T),
+    /// Matches networks that are not, or are, in use by a container.
+    Dangling(bool),
+}
+
+impl<T> NetworkFilter<T>
+where
+    T: From<&'static str>,
+{
+    fn into_filter(self) -> (&'static str, T) {
+        match self {
+            NetworkFilter::Driver(driver) => ("driver", driver),
+            NetworkFilter::Id(id) => ("id", id),
+            NetworkFilter::Label(label) => ("label", label),
+            NetworkFilter::Name(name) => ("name", name),
+            NetworkFilter::Scope(scope) => ("scope", T::from(scope.as_str())),
+            NetworkFilter::Type(network_type) => ("type", T::from(network_type.as_str())),
+            NetworkFilter::Until(until) => ("until", until),
+            NetworkFilter::Dangling(dangling) => (
+                "dangling",
+                T::from(if dangling { TRUE_STR } else { FALSE_STR }),
+            ),
+        }
+    }
+}
+
+fn add_network_filter<T>(filters: &mut HashMap<T, Vec<T>>, filter: NetworkFilter<T>)
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    let (key, value) = filter.into_filter();
+    filters.entry(T::from(key)).or_default().push(value);
+}
+
+impl<T> ListNetworksOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    /// Only list the networks matching a filter.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::network::{ListNetworksOptions, NetworkFilter, NetworkScope, NetworkType};
+    ///
+    /// let options = ListNetworksOptions::default()
+    ///     .filter(NetworkFilter::Driver("overlay"))
+    ///     .filter(NetworkFilter::Scope(NetworkScope::Swarm))
+    ///     .filter(NetworkFilter::Type(NetworkType::Custom));
+    ///
+    /// assert_eq!(options.filters["scope"], vec!["swarm"]);
+    /// ```
+    pub fn filter(mut self, filter: NetworkFilter<T>) -> Self {
+        add_network_filter(&mut self.filters, filter);
+        self
+    }
+}
+
 #[allow(missing_docs)]
 /// Trait providing implementations for [List Networks Options](struct.ListNetworksOptions.html)
 /// struct.
@@ -285,7 +827,8 @@ where
     pub endpoint_config: EndpointSettings<T>,
 }
 
-/// Configuration for a network endpoint.
+/// Configuration for a network endpoint. Unset fields are left out of the request, and chosen by
+/// the docker server.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct EndpointSettings<T>
@@ -293,39 +836,47 @@ where
     T: AsRef<str> + Eq + Hash,
 {
     /// EndpointIPAMConfig represents an endpoint's IPAM configuration.
-    #[serde(rename = "IPAMConfig")]
-    pub ipam_config: EndpointIPAMConfig<T>,
+    #[serde(rename = "IPAMConfig", skip_serializing_if = "Option::is_none")]
+    pub ipam_config: Option<EndpointIPAMConfig<T>>,
     #[allow(missing_docs)]
-    pub links: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<T>>,
     #[allow(missing_docs)]
-    pub aliases: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<T>>,
     /// Unique ID of the network.
-    #[serde(rename = "NetworkID")]
-    pub network_id: T,
+    #[serde(rename = "NetworkID", skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<T>,
     /// Unique ID for the service endpoint in a Sandbox.
-    #[serde(rename = "EndpointID")]
-    pub endpoint_id: T,
+    #[serde(rename = "EndpointID", skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<T>,
     /// Gateway address for this network.
-    pub gateway: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<T>,
     /// IPv4 address.
-    #[serde(rename = "IPAddress")]
-    pub ip_address: T,
+    #[serde(rename = "IPAddress", skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<T>,
     /// Mask length of the IPv4 address.
-    #[serde(rename = "IPPrefixLen")]
-    pub ip_prefix_len: isize,
+    #[serde(rename = "IPPrefixLen", skip_serializing_if = "Option::is_none")]
+    pub ip_prefix_len: Option<isize>,
     /// IPv6 gateway address.
-    #[serde(rename = "IPv6Gateway")]
-    pub ipv6_gateway: T,
+    #[serde(rename = "IPv6Gateway", skip_serializing_if = "Option::is_none")]
+    pub ipv6_gateway: Option<T>,
     /// Global IPv6 address.
-    #[serde(rename = "GlobalIPv6Address")]
-    pub global_ipv6_address: T,
+    #[serde(rename = "GlobalIPv6Address", skip_serializing_if = "Option::is_none")]
+    pub global_ipv6_address: Option<T>,
     /// Mask length of the global IPv6 address.
-    #[serde(rename = "GlobalIPv6PrefixLen")]
-    pub global_ipv6_prefix_len: i64,
+    #[serde(
+        rename = "GlobalIPv6PrefixLen",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub global_ipv6_prefix_len: Option<i64>,
     /// MAC address for the endpoint on this network.
-    pub mac_address: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<T>,
     /// DriverOpts is a mapping of driver options and values. These options are passed directly to
     /// the driver and are driver specific.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub driver_opts: Option<HashMap<T, T>>,
 }
 
@@ -335,11 +886,11 @@ pub struct EndpointIPAMConfig<T>
 where
     T: AsRef<str>,
 {
-    #[serde(rename = "IPv4Address")]
+    #[serde(rename = "IPv4Address", skip_serializing_if = "Option::is_none")]
     pub ipv4_address: Option<T>,
-    #[serde(rename = "IPv6Address")]
+    #[serde(rename = "IPv6Address", skip_serializing_if = "Option::is_none")]
     pub ipv6_address: Option<T>,
-    #[serde(rename = "LinkLocalIPs")]
+    #[serde(rename = "LinkLocalIPs", skip_serializing_if = "Option::is_none")]
     pub link_local_ips: Option<Vec<T>>,
 }
 
@@ -395,6 +946,29 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+impl<T> PruneNetworksOptions<T>
+where
+    T: AsRef<str> + Eq + Hash + From<&'static str>,
+{
+    /// Only prune the networks matching a filter.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::network::{NetworkFilter, PruneNetworksOptions};
+    ///
+    /// let options = PruneNetworksOptions::default()
+    ///     .filter(NetworkFilter::Until("24h"))
+    ///     .filter(NetworkFilter::Label("maintainer=some_maintainer"));
+    ///
+    /// assert_eq!(options.filters["until"], vec!["24h"]);
+    /// ```
+    pub fn filter(mut self, filter: NetworkFilter<T>) -> Self {
+        add_network_filter(&mut self.filters, filter);
+        self
+    }
+}
+
 /// Trait providing implementations for [Prune Networks Options](struct.PruneNetworksOptions.html)
 /// struct.
 #[allow(missing_docs)]
@@ -424,6 +998,68 @@ pub struct PruneNetworksResults {
     pub networks_deleted: Option<Vec<String>>,
 }
 
+/// Action of an event returned by the [Network Events
+/// API](../struct.Docker.html#method.network_events)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkEventAction {
+    /// The network was created.
+    Create,
+    /// A container was connected to the network.
+    Connect {
+        /// The ID of the container, as found in the `container` attribute.
+        container: Option<String>,
+    },
+    /// A container was disconnected from the network.
+    Disconnect {
+        /// The ID of the container, as found in the `container` attribute.
+        container: Option<String>,
+    },
+    /// The network was removed.
+    Destroy,
+    /// Any other action, e.g. `remove` for a swarm scoped network.
+    Other(String),
+}
+
+/// Event returned by the [Network Events API](../struct.Docker.html#method.network_events)
+#[derive(Debug, Clone)]
+pub struct NetworkEvent {
+    /// The ID of the network.
+    pub id: String,
+    /// The name of the network at the time of the event.
+    pub name: Option<String>,
+    /// The typed action of the event.
+    pub action: NetworkEventAction,
+    /// The attributes of the event, e.g. the driver of the network in the `type` attribute.
+    pub attributes: HashMap<String, String>,
+    /// The time of the event.
+    pub time: DateTime<Utc>,
+}
+
+impl From<EventsResults> for NetworkEvent {
+    fn from(event: EventsResults) -> Self {
+        let attributes = event.actor.attributes;
+        let action = match event.action.as_str() {
+            "create" => NetworkEventAction::Create,
+            "connect" => NetworkEventAction::Connect {
+                container: attributes.get("container").cloned(),
+            },
+            "disconnect" => NetworkEventAction::Disconnect {
+                container: attributes.get("container").cloned(),
+            },
+            "destroy" => NetworkEventAction::Destroy,
+            _ => NetworkEventAction::Other(event.action.clone()),
+        };
+
+        NetworkEvent {
+            id: event.actor.id,
+            name: attributes.get("name").cloned(),
+            action,
+            attributes,
+            time: event.time_nano,
+        }
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -466,6 +1102,11 @@ impl Docker {
     {
         let url = "/networks/create";
 
+        #[cfg(feature = "ipnet")]
+        for ipam_config in &config.ipam.config {
+            TypedIPAMConfig::try_from(ipam_config)?;
+        }
+
         let req = self.build_request::<_, String, String>(
             &url,
             Builder::new().method(Method::POST),
@@ -478,7 +1119,141 @@ impl Docker {
 
     /// ---
     ///
-    /// # Remove a Network
+    /// # Create Network If Missing
+    ///
+    /// Create a network, unless a network with the same name already exists.
+    ///
+    /// The driver, labels, IPAM driver and IPAM subnets of an existing network are verified
+    /// against those set in the options. Fields left empty in the options are not verified, and
+    /// the existing network may carry labels beyond those set in the options.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Create Network Options](network/struct.CreateNetworkOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Create Network Results](network/struct.CreateNetworkResults.html) struct, with the ID
+    ///  of the existing or created network, wrapped in a Future.
+    ///
+    /// A [NetworkMismatchError](errors/enum.ErrorKind.html#variant.NetworkMismatchError) is
+    /// returned if the existing network differs from the options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::network::CreateNetworkOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = CreateNetworkOptions {
+    ///     name: "certs",
+    ///     driver: "bridge",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.create_network_if_missing(config);
+    /// ```
+    pub async fn create_network_if_missing<T>(
+        &self,
+        mut config: CreateNetworkOptions<T>,
+    ) -> Result<CreateNetworkResults, Error>
+    where
+        T: AsRef<str> + Eq + Hash + Serialize + Clone,
+    {
+        let name = String::from(config.name.as_ref());
+
+        if let Some(network) = self.find_network_by_name(&name).await? {
+            verify_network(&config, &network)?;
+            return Ok(CreateNetworkResults {
+                id: network.id,
+                warning: None,
+            });
+        }
+
+        // the server only rejects a duplicate name when asked to check for it
+        config.check_duplicate = true;
+        let expected = config.clone();
+
+        match self.create_network(config).await {
+            Err(e) => match e.kind() {
+                // created concurrently since the network was resolved
+                DockerResponseConflictError { .. } => match self.find_network_by_name(&name).await?
+                {
+                    Some(network) => {
+                        verify_network(&expected, &network)?;
+                        Ok(CreateNetworkResults {
+                            id: network.id,
+                            warning: None,
+                        })
+                    }
+                    None => Err(e),
+                },
+                _ => Err(e),
+            },
+            v => v,
+        }
+    }
+
+    /// ---
+    ///
+    /// # Ensure Network
+    ///
+    /// Make sure a network exists with the given configuration, creating it if missing, and
+    /// return its ID. Concurrent calls resolve to the same network.
+    ///
+    /// The existing network is verified as in [Create Network If
+    /// Missing](struct.Docker.html#method.create_network_if_missing).
+    ///
+    /// # Arguments
+    ///
+    ///  - [Create Network Options](network/struct.CreateNetworkOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - The ID of the existing or created network, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::network::CreateNetworkOptions;
+    ///
+    /// use std::collections::HashMap;
+    /// use std::default::Default;
+    ///
+    /// let mut labels = HashMap::new();
+    /// labels.insert("com.example.stack", "web");
+    ///
+    /// let config = CreateNetworkOptions {
+    ///     name: "frontend",
+    ///     driver: "bridge",
+    ///     labels,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.ensure_network(config);
+    /// ```
+    pub async fn ensure_network<T>(&self, config: CreateNetworkOptions<T>) -> Result<String, Error>
+    where
+        T: AsRef<str> + Eq + Hash + Serialize + Clone,
+    {
+        self.create_network_if_missing(config)
+            .await
+            .map(|result| result.id)
+    }
+
+    /// ---
+    ///
+    /// # Find Network By Name
+    ///
+    /// Look up a network by its exact name. The name filter of the docker server also matches on
+    /// part of a name, so networks whose name merely contains the given name are skipped.
     ///
     /// # Arguments
     ///
@@ -486,6 +1261,40 @@ impl Docker {
     ///
     /// # Returns
     ///
+    ///  - An optional [List Networks Results](network/struct.ListNetworksResults.html) struct,
+    ///  `None` if no network has the name, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.find_network_by_name("my_network_name");
+    /// ```
+    pub async fn find_network_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<ListNetworksResults>, Error> {
+        // the name filter matches substrings, so look for an exact match in the results
+        let mut filters = HashMap::new();
+        filters.insert("name", vec![name]);
+        let networks = self
+            .list_networks(Some(ListNetworksOptions { filters }))
+            .await?;
+        Ok(networks.into_iter().find(|network| network.name == name))
+    }
+
+    /// ---
+    ///
+    /// # Remove a Network
+    ///
+    /// # Arguments
+    ///
+    ///  - Network ID or name as a string slice, or a [Network Ref](network/enum.NetworkRef.html).
+    ///
+    /// # Returns
+    ///
     ///  - unit type `()`, wrapped in a Future.
     ///
     /// # Examples
@@ -496,8 +1305,11 @@ impl Docker {
     ///
     /// docker.remove_network("my_network_name");
     /// ```
-    pub async fn remove_network(&self, network_name: &str) -> Result<(), Error> {
-        let url = format!("/networks/{}", network_name);
+    pub async fn remove_network<N>(&self, network: N) -> Result<(), Error>
+    where
+        N: AsRef<str>,
+    {
+        let url = format!("/networks/{}", encode_path_segment(network.as_ref()));
 
         let req = self.build_request::<_, String, String>(
             &url,
@@ -509,13 +1321,75 @@ impl Docker {
         self.process_into_unit(req).await
     }
 
+    /// ---
+    ///
+    /// # Remove a Network Forcefully
+    ///
+    /// Remove a network, forcefully disconnecting the containers still connected to it first.
+    /// The load balancer endpoint of a swarm network is not a container and is left to the
+    /// docker server. A container connecting to the network after it was inspected still makes
+    /// the removal fail.
+    ///
+    /// # Arguments
+    ///
+    ///  - Network ID or name as a string slice, or a [Network Ref](network/enum.NetworkRef.html).
+    ///
+    /// # Returns
+    ///
+    ///  - The IDs of the disconnected containers, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.remove_network_force("my_network_name");
+    /// ```
+    pub async fn remove_network_force<N>(&self, network: N) -> Result<Vec<String>, Error>
+    where
+        N: AsRef<str>,
+    {
+        let network = self
+            .inspect_network(network, None::<InspectNetworkOptions>)
+            .await?;
+
+        let mut containers: Vec<_> = network
+            .containers
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| !id.starts_with(LOAD_BALANCER_ENDPOINT_PREFIX))
+            .collect();
+        containers.sort();
+
+        let mut disconnected = Vec::with_capacity(containers.len());
+        for container in containers {
+            let options = DisconnectNetworkOptions {
+                container: container.as_str(),
+                force: true,
+            };
+            match self.disconnect_network(&network.id, options).await {
+                Ok(()) => disconnected.push(container),
+                // removed or disconnected since the network was inspected
+                Err(e) => match e.kind() {
+                    DockerResponseNotFoundError { .. } => (),
+                    _ => return Err(e),
+                },
+            }
+        }
+
+        self.remove_network(&network.id).await?;
+
+        Ok(disconnected)
+    }
+
     /// ---
     ///
     /// # Inspect a Network
     ///
     /// # Arguments
     ///
-    ///  - Network name as a string slice.
+    ///  - Network ID or name as a string slice, or a [Network Ref](network/enum.NetworkRef.html).
     ///
     /// # Returns
     ///
@@ -534,21 +1408,22 @@ impl Docker {
     ///
     /// let config = InspectNetworkOptions {
     ///     verbose: true,
-    ///     scope: "global"
+    ///     ..Default::default()
     /// };
     ///
     /// docker.inspect_network("my_network_name", Some(config));
     /// ```
-    pub async fn inspect_network<'a, T, V>(
+    pub async fn inspect_network<'a, N, T, V>(
         &self,
-        network_name: &str,
+        network: N,
         options: Option<T>,
     ) -> Result<InspectNetworkResults, Error>
     where
+        N: AsRef<str>,
         T: InspectNetworkQueryParams<'a, V>,
         V: AsRef<str>,
     {
-        let url = format!("/networks/{}", network_name);
+        let url = format!("/networks/{}", encode_path_segment(network.as_ref()));
 
         let req = self.build_request(
             &url,
@@ -619,6 +1494,7 @@ impl Docker {
     ///
     /// # Arguments
     ///
+    ///  - Network ID or name as a string slice, or a [Network Ref](network/enum.NetworkRef.html).
     ///  - A [Connect Network Options](network/struct.ConnectNetworkOptions.html) struct.
     ///
     /// # Returns
@@ -638,26 +1514,30 @@ impl Docker {
     /// let config = ConnectNetworkOptions {
     ///     container: "3613f73ba0e4",
     ///     endpoint_config: EndpointSettings {
-    ///         ipam_config: EndpointIPAMConfig {
+    ///         ipam_config: Some(EndpointIPAMConfig {
     ///             ipv4_address: Some("172.24.56.89"),
     ///             ipv6_address: Some("2001:db8::5689"),
     ///             ..Default::default()
-    ///         },
+    ///         }),
     ///         ..Default::default()
     ///     }
     /// };
     ///
     /// docker.connect_network("my_network_name", config);
     /// ```
-    pub async fn connect_network<T>(
+    pub async fn connect_network<N, T>(
         &self,
-        network_name: &str,
+        network: N,
         config: ConnectNetworkOptions<T>,
     ) -> Result<(), Error>
     where
+        N: AsRef<str>,
         T: AsRef<str> + Eq + Hash + Serialize,
     {
-        let url = format!("/networks/{}/connect", network_name);
+        let url = format!(
+            "/networks/{}/connect",
+            encode_path_segment(network.as_ref())
+        );
 
         let req = self.build_request::<_, String, String>(
             &url,
@@ -675,6 +1555,7 @@ impl Docker {
     ///
     /// # Arguments
     ///
+    ///  - Network ID or name as a string slice, or a [Network Ref](network/enum.NetworkRef.html).
     ///  - A [Disconnect Network Options](network/struct.DisconnectNetworkOptions.html) struct.
     ///
     /// # Returns
@@ -698,15 +1579,19 @@ impl Docker {
     ///
     /// docker.disconnect_network("my_network_name", config);
     /// ```
-    pub async fn disconnect_network<T>(
+    pub async fn disconnect_network<N, T>(
         &self,
-        network_name: &str,
+        network: N,
         config: DisconnectNetworkOptions<T>,
     ) -> Result<(), Error>
     where
+        N: AsRef<str>,
         T: AsRef<str> + Serialize,
     {
-        let url = format!("/networks/{}/disconnect", network_name);
+        let url = format!(
+            "/networks/{}/disconnect",
+            encode_path_segment(network.as_ref())
+        );
 
         let req = self.build_request::<_, String, String>(
             &url,
@@ -771,4 +1656,167 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Network Events
+    ///
+    /// Stream real-time events of a single network, such as containers connecting to and
+    /// disconnecting from it.
+    ///
+    /// # Arguments
+    ///
+    ///  - Network ID or name as string slice.
+    ///  - Optional [Events Options](../system/struct.EventsOptions.html) struct, whose filters are
+    ///  combined with the filters on the network.
+    ///
+    /// # Returns
+    ///
+    ///  - [Network Event](network/struct.NetworkEvent.html), wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::network::NetworkEventAction;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let mut events = docker.network_events("my_network_name", None);
+    ///     while let Some(event) = events.try_next().await? {
+    ///         if let NetworkEventAction::Connect { container } = event.action {
+    ///             println!("connected {:?}", container);
+    ///         }
+    ///     }
+    ///     Ok::<_, bollard::errors::Error>(())
+    /// };
+    /// ```
+    pub fn network_events(
+        &self,
+        network_id: &str,
+        options: Option<EventsOptions<String>>,
+    ) -> impl Stream<Item = Result<NetworkEvent, Error>> {
+        let mut filters = options
+            .as_ref()
+            .map(|o| o.filters.clone())
+            .unwrap_or_default();
+        filters.insert(String::from("type"), vec![String::from("network")]);
+        filters.insert(String::from("network"), vec![String::from(network_id)]);
+
+        let query: Result<Vec<_>, Error> = match options {
+            Some(options) => EventsOptions { filters, ..options }
+                .into_array()
+                .map(|query| query.into_iter().collect()),
+            None => serde_json::to_string(&filters)
+                .map(|filters| vec![("filters", filters)])
+                .map_err(|e| JsonSerializeError { err: e }.into()),
+        };
+
+        let req = self.build_request(
+            "/events",
+            Builder::new().method(Method::GET),
+            query.map(Some),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_followed_stream::<EventsResults>(req, true)
+            .map_ok(NetworkEvent::from)
+    }
+}
+
+fn verify_network<T>(
+    config: &CreateNetworkOptions<T>,
+    network: &ListNetworksResults,
+) -> Result<(), Error>
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    let mismatch = |field: &str, expected: &str, actual: &str| -> Error {
+        NetworkMismatchError {
+            mismatch: Box::new(NetworkMismatch {
+                network: network.name.clone(),
+                field: String::from(field),
+                expected: String::from(expected),
+                actual: String::from(actual),
+            }),
+        }
+        .into()
+    };
+
+    let driver = config.driver.as_ref();
+    if !driver.is_empty() && driver != network.driver {
+        return Err(mismatch("Driver", driver, &network.driver));
+    }
+
+    if let Some(scope) = config.scope {
+        if scope != network.scope {
+            return Err(mismatch("Scope", scope.as_str(), network.scope.as_str()));
+        }
+    }
+
+    if config.config_only != network.config_only {
+        return Err(mismatch(
+            "ConfigOnly",
+            &config.config_only.to_string(),
+            &network.config_only.to_string(),
+        ));
+    }
+
+    if let Some(ref config_from) = config.config_from {
+        let actual = network
+            .config_from
+            .get("Network")
+            .map(String::as_str)
+            .unwrap_or_default();
+        if config_from.network.as_ref() != actual {
+            return Err(mismatch(
+                "ConfigFrom.Network",
+                config_from.network.as_ref(),
+                actual,
+            ));
+        }
+    }
+
+    let mut labels: Vec<_> = config.labels.iter().collect();
+    labels.sort_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+    for (key, value) in labels {
+        let actual = network.labels.get(key.as_ref()).map(String::as_str);
+        if actual != Some(value.as_ref()) {
+            return Err(mismatch(
+                &format!("Labels.{}", key.as_ref()),
+                value.as_ref(),
+                actual.unwrap_or_default(),
+            ));
+        }
+    }
+
+    let ipam_driver = config.ipam.driver.as_ref();
+    if !ipam_driver.is_empty() && ipam_driver != network.ipam.driver {
+        return Err(mismatch("IPAM.Driver", ipam_driver, &network.ipam.driver));
+    }
+
+    let subnets: Vec<&str> = network
+        .ipam
+        .config
+        .iter()
+        .filter_map(|config| config.subnet.as_deref())
+        .collect();
+    for subnet in config
+        .ipam
+        .config
+        .iter()
+        .filter_map(|config| config.subnet.as_ref())
+    {
+        if !subnets.contains(&subnet.as_ref()) {
+            return Err(mismatch(
+                "IPAM.Config.Subnet",
+                subnet.as_ref(),
+                &subnets.join(","),
+            ));
+        }
+    }
+
+    Ok(())
 }
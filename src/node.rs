@@ -0,0 +1,408 @@
+//! Node API: list, inspect and update the nodes of a swarm
+
+use arrayvec::ArrayVec;
+use chrono::{DateTime, Utc};
+use http::request::Builder;
+use hyper::{Body, Method};
+use serde::Serialize;
+use serde_json;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Docker;
+use crate::errors::Error;
+use crate::errors::ErrorKind::JsonSerializeError;
+use crate::service::LABEL_UPDATE_ATTEMPTS;
+use crate::service_models::ObjectVersion;
+use crate::swarm::TLSInfo;
+
+/// Parameters used in the [List Nodes API](../struct.Docker.html#method.list_nodes)
+///
+/// ## Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use bollard::node::ListNodesOptions;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("role", vec!("manager"));
+///
+/// ListNodesOptions{
+///     filters: filters,
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ListNodesOptions<T>
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    /// Filters to process on the node list, encoded as JSON. Available filters:
+    ///  - `id`=`<node id>`
+    ///  - `label`=`<engine label>`
+    ///  - `membership`=`["accepted"|"pending"]`
+    ///  - `name`=`<node name>`
+    ///  - `node.label`=`<node label>`
+    ///  - `role`=`["manager"|"worker"]`
+    pub filters: HashMap<T, Vec<T>>,
+}
+
+/// Trait providing implementations for [List Nodes Options](struct.ListNodesOptions.html)
+/// struct.
+#[allow(missing_docs)]
+pub trait ListNodesQueryParams<K, V>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 1]>, Error>;
+}
+
+impl<'a, T: AsRef<str> + Eq + Hash> ListNodesQueryParams<&'a str, String> for ListNodesOptions<T>
+where
+    T: Serialize,
+{
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 1]>, Error> {
+        Ok(ArrayVec::from([(
+            "filters",
+            serde_json::to_string(&self.filters).map_err(|e| JsonSerializeError { err: e })?,
+        )]))
+    }
+}
+
+/// Parameters used in the [Update Node API](../struct.Docker.html#method.update_node)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::node::UpdateNodeOptions;
+///
+/// UpdateNodeOptions{
+///     version: 1234,
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UpdateNodeOptions {
+    /// The version number of the node object being updated. This is required to avoid
+    /// conflicting writes.
+    pub version: u64,
+}
+
+/// Trait providing implementations for [Update Node Options](struct.UpdateNodeOptions.html).
+#[allow(missing_docs)]
+pub trait UpdateNodeQueryParams<K, V>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 1]>, Error>;
+}
+
+impl<'a> UpdateNodeQueryParams<&'a str, String> for UpdateNodeOptions {
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 1]>, Error> {
+        Ok(ArrayVec::from([("version", self.version.to_string())]))
+    }
+}
+
+/// A node of a swarm, returned by the [List Nodes API](../struct.Docker.html#method.list_nodes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Node {
+    /// The ID of the node.
+    #[serde(rename = "ID")]
+    pub id: String,
+    /// The version of the node object.
+    pub version: ObjectVersion,
+    /// Date and time at which the node was added to the swarm.
+    pub created_at: DateTime<Utc>,
+    /// Date and time at which the node was last updated.
+    pub updated_at: DateTime<Utc>,
+    /// User modifiable node configuration.
+    pub spec: NodeSpec,
+    /// Properties of the node, as reported by the node.
+    pub description: NodeDescription,
+    /// The status of the node.
+    pub status: Option<NodeStatus>,
+    /// The status of a manager node, absent for worker nodes.
+    pub manager_status: Option<ManagerStatus>,
+}
+
+/// User modifiable node configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NodeSpec {
+    /// Name of the node.
+    pub name: Option<String>,
+    /// User-defined key/value metadata.
+    pub labels: Option<HashMap<String, String>>,
+    /// Role of the node: `worker` or `manager`.
+    pub role: Option<String>,
+    /// Availability of the node: `active`, `pause` or `drain`.
+    pub availability: Option<String>,
+}
+
+/// Properties of a node, as reported by the node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NodeDescription {
+    /// The hostname of the node.
+    pub hostname: Option<String>,
+    /// The platform of the node.
+    pub platform: Option<NodePlatform>,
+    /// The engine version of the node.
+    pub engine: Option<NodeEngine>,
+    /// Information about the issuer of the node's certificate. Nodes whose issuer differs from the
+    /// issuer of the swarm still hold certificates from before a CA rotation.
+    #[serde(rename = "TLSInfo")]
+    pub tls_info: Option<TLSInfo>,
+}
+
+/// The platform of a node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NodePlatform {
+    /// Architecture of the node, e.g. `x86_64`.
+    pub architecture: Option<String>,
+    /// Operating system of the node, e.g. `linux`.
+    #[serde(rename = "OS")]
+    pub os: Option<String>,
+}
+
+/// The engine of a node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NodeEngine {
+    /// Version of the docker engine.
+    pub engine_version: Option<String>,
+    /// Labels of the docker engine.
+    pub labels: Option<HashMap<String, String>>,
+}
+
+/// The status of a node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NodeStatus {
+    /// State of the node: `unknown`, `down`, `ready` or `disconnected`.
+    pub state: Option<String>,
+    /// Message describing the state.
+    pub message: Option<String>,
+    /// IP address of the node.
+    pub addr: Option<String>,
+}
+
+/// The status of a manager node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManagerStatus {
+    /// Whether the node is the leader of the managers.
+    pub leader: Option<bool>,
+    /// Reachability of the node: `unknown`, `unreachable` or `reachable`.
+    pub reachability: Option<String>,
+    /// The IP address and port at which this node can be reached by other managers.
+    pub addr: Option<String>,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Nodes
+    ///
+    /// Returns a list of the nodes of the swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListNodesOptions](node/struct.ListNodesOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Node](node/struct.Node.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::node::ListNodesOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("role", vec!("manager"));
+    ///
+    /// let options = Some(ListNodesOptions{
+    ///     filters: filters,
+    /// });
+    ///
+    /// docker.list_nodes(options);
+    /// ```
+    pub async fn list_nodes<T, K>(&self, options: Option<T>) -> Result<Vec<Node>, Error>
+    where
+        T: ListNodesQueryParams<K, String>,
+        K: AsRef<str>,
+    {
+        let url = "/nodes";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            Docker::transpose_option(options.map(|o| o.into_array())),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+    /// ---
+    ///
+    /// # Inspect Node
+    ///
+    /// Inspect a node of the swarm.
+    ///
+    /// # Arguments
+    ///
+    ///  - Node id or name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Node](node/struct.Node.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.inspect_node("my-node");
+    /// ```
+    pub async fn inspect_node(&self, node_id: &str) -> Result<Node, Error> {
+        let url = format!("/nodes/{}", node_id);
+
+        let req = self.build_request::<_, String, String>(
+            &url,
+            Builder::new().method(Method::GET),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Update Node
+    ///
+    /// Update the configuration of a node. The spec replaces the current spec, so it should be
+    /// based on the spec returned by [Inspect Node](#method.inspect_node).
+    ///
+    /// # Arguments
+    ///
+    ///  - Node id or name as a string slice.
+    ///  - [NodeSpec](node/struct.NodeSpec.html) struct.
+    ///  - [UpdateNodeOptions](node/struct.UpdateNodeOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::node::UpdateNodeOptions;
+    ///
+    /// let result = async move {
+    ///     let node = docker.inspect_node("my-node").await?;
+    ///     let mut spec = node.spec;
+    ///     spec.availability = Some(String::from("drain"));
+    ///     let options = UpdateNodeOptions {
+    ///         version: node.version.index,
+    ///     };
+    ///
+    ///     docker.update_node("my-node", spec, options).await
+    /// };
+    /// ```
+    pub async fn update_node<T, K>(
+        &self,
+        node_id: &str,
+        spec: NodeSpec,
+        options: T,
+    ) -> Result<(), Error>
+    where
+        T: UpdateNodeQueryParams<K, String>,
+        K: AsRef<str>,
+    {
+        let url = format!("/nodes/{}/update", node_id);
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::POST),
+            Docker::transpose_option(Some(options.into_array())),
+            Docker::serialize_payload(Some(spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Set Node Labels
+    ///
+    /// Set the labels of a node, by inspecting the node and resubmitting its spec with the
+    /// current version. If the node is updated concurrently, the update is retried against the
+    /// new version up to 5 times, so that no update is lost.
+    ///
+    /// # Arguments
+    ///
+    ///  - Node id or name as a string slice.
+    ///  - Labels to set, as a `HashMap`.
+    ///  - Whether to merge the labels into the existing labels, rather than replace them.
+    ///
+    /// # Returns
+    ///
+    ///  - The labels of the node after the update, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::collections::HashMap;
+    ///
+    /// let mut labels = HashMap::new();
+    /// labels.insert(String::from("com.example.zone"), String::from("eu-west-1a"));
+    ///
+    /// docker.set_node_labels("my-node", labels, true);
+    /// ```
+    pub async fn set_node_labels(
+        &self,
+        node_id: &str,
+        labels: HashMap<String, String>,
+        merge: bool,
+    ) -> Result<HashMap<String, String>, Error> {
+        let mut attempt = 1;
+        loop {
+            let node = self.inspect_node(node_id).await?;
+
+            let mut spec = node.spec;
+            let mut result = if merge {
+                spec.labels.unwrap_or_default()
+            } else {
+                HashMap::new()
+            };
+            result.extend(labels.clone());
+            spec.labels = Some(result.clone());
+
+            let options = UpdateNodeOptions {
+                version: node.version.index,
+            };
+
+            match self.update_node(node_id, spec, options).await {
+                Ok(_) => return Ok(result),
+                Err(ref e) if attempt < LABEL_UPDATE_ATTEMPTS && Docker::is_out_of_sequence(e) => {
+                    debug!("Retrying label update of {}: {}", node_id, e);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! Progress API: report the progress of image pulls, pushes, builds and loads to a callback
+//! handler, as an alternative to consuming their streams.
+
+use futures_core::Stream;
+use futures_util::stream::StreamExt;
+use hyper::Body;
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use super::Docker;
+use crate::auth::{DockerCredentials, RegistryAuth};
+use crate::errors::Error;
+use crate::errors::ErrorKind::DockerStreamError;
+use crate::image::{
+    BuildImageQueryParams, BuildImageResults, CreateImageQueryParams, CreateImageResults,
+    ImportImageQueryParams, ImportImageResults, PushImageQueryParams, PushImageResults,
+};
+
+/// Whether to continue an operation, returned by a [Progress
+/// Handler](trait.ProgressHandler.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ControlFlow {
+    /// Continue the operation.
+    Continue,
+    /// Stop reading the progress of the operation and close the connection to the docker server.
+    Break,
+}
+
+/// Summary of an operation, passed to a [Progress Handler](trait.ProgressHandler.html) once the
+/// operation completes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ProgressSummary {
+    /// The number of progress records reported.
+    pub records: usize,
+    /// Whether the handler stopped the operation, by returning
+    /// [ControlFlow::Break](enum.ControlFlow.html#variant.Break).
+    pub cancelled: bool,
+    /// The time from the start of the operation until it completed.
+    pub elapsed: Duration,
+}
+
+/// Callbacks receiving the progress of an operation.
+///
+/// The handler is called from the task awaiting the operation. A panic in the handler closes the
+/// connection to the docker server, and is then propagated to that task.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::image::CreateImageResults;
+/// use bollard::progress::{ControlFlow, ProgressHandler};
+///
+/// struct Printer;
+///
+/// impl ProgressHandler<CreateImageResults> for Printer {
+///     fn on_progress(&mut self, record: &CreateImageResults) -> ControlFlow {
+///         println!("{:?}", record);
+///         ControlFlow::Continue
+///     }
+/// }
+/// ```
+pub trait ProgressHandler<T> {
+    /// Called for each progress record reported by the docker server. Returning
+    /// [ControlFlow::Break](enum.ControlFlow.html#variant.Break) stops the operation.
+    fn on_progress(&mut self, record: &T) -> ControlFlow;
+
+    /// Called once if the operation fails, including when the docker server reports an error
+    /// within the progress, before the error is returned.
+    fn on_error(&mut self, _err: &Error) {}
+
+    /// Called once if the operation completes, or is stopped by the handler.
+    fn on_complete(&mut self, _summary: &ProgressSummary) {}
+}
+
+impl<'a, T, H> ProgressHandler<T> for &'a mut H
+where
+    H: ProgressHandler<T>,
+{
+    fn on_progress(&mut self, record: &T) -> ControlFlow {
+        (**self).on_progress(record)
+    }
+
+    fn on_error(&mut self, err: &Error) {
+        (**self).on_error(err)
+    }
+
+    fn on_complete(&mut self, summary: &ProgressSummary) {
+        (**self).on_complete(summary)
+    }
+}
+
+/// A progress record, which may carry an error reported by the docker server.
+trait ProgressRecord {
+    fn stream_error(&self) -> Option<&str>;
+}
+
+impl ProgressRecord for CreateImageResults {
+    fn stream_error(&self) -> Option<&str> {
+        match self {
+            CreateImageResults::CreateImageError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ProgressRecord for PushImageResults {
+    fn stream_error(&self) -> Option<&str> {
+        match self {
+            PushImageResults::PushImageError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ProgressRecord for BuildImageResults {
+    fn stream_error(&self) -> Option<&str> {
+        match self {
+            BuildImageResults::BuildImageError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ProgressRecord for ImportImageResults {
+    fn stream_error(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Call the handler, and if it panics, drop the stream to close the connection before propagating
+/// the panic.
+fn call_handler<S, F, R>(stream: S, f: F) -> (S, R)
+where
+    F: FnOnce() -> R,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => (stream, result),
+        Err(payload) => {
+            drop(stream);
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+async fn drive_progress<S, T, H>(stream: S, mut handler: H) -> Result<ProgressSummary, Error>
+where
+    S: Stream<Item = Result<T, Error>>,
+    T: ProgressRecord,
+    H: ProgressHandler<T>,
+{
+    let start = Instant::now();
+    let mut stream = Box::pin(stream);
+    let mut records = 0;
+    let mut cancelled = false;
+
+    while let Some(record) = stream.next().await {
+        let record = record.and_then(|record| match record.stream_error().map(String::from) {
+            Some(error) => Err(DockerStreamError { error }.into()),
+            None => Ok(record),
+        });
+
+        match record {
+            Ok(record) => {
+                records += 1;
+                let (next, flow) = call_handler(stream, || handler.on_progress(&record));
+                stream = next;
+                if flow == ControlFlow::Break {
+                    cancelled = true;
+                    break;
+                }
+            }
+            Err(e) => {
+                drop(stream);
+                handler.on_error(&e);
+                return Err(e);
+            }
+        }
+    }
+    drop(stream);
+
+    let summary = ProgressSummary {
+        records,
+        cancelled,
+        elapsed: start.elapsed(),
+    };
+    handler.on_complete(&summary);
+
+    Ok(summary)
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Pull Image With Progress
+    ///
+    /// Pull an image with the [Create Image API](struct.Docker.html#method.create_image),
+    /// reporting its progress to a handler.
+    ///
+    /// # Arguments
+    ///
+    ///  - An optional [Create Image Options](image/struct.CreateImageOptions.html) struct.
+    ///  - An optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
+    ///  - A [Progress Handler](progress/trait.ProgressHandler.html) of [Create Image
+    ///  Results](image/enum.CreateImageResults.html).
+    ///
+    /// # Returns
+    ///
+    ///  - A [Progress Summary](progress/struct.ProgressSummary.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::{CreateImageOptions, CreateImageResults};
+    /// use bollard::progress::{ControlFlow, ProgressHandler};
+    ///
+    /// use std::default::Default;
+    ///
+    /// struct Printer;
+    ///
+    /// impl ProgressHandler<CreateImageResults> for Printer {
+    ///     fn on_progress(&mut self, record: &CreateImageResults) -> ControlFlow {
+    ///         println!("{:?}", record);
+    ///         ControlFlow::Continue
+    ///     }
+    /// }
+    ///
+    /// let options = Some(CreateImageOptions {
+    ///     from_image: "hello-world",
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.pull_image_with_progress(options, None, Printer);
+    /// ```
+    pub async fn pull_image_with_progress<T, K, V, H>(
+        &self,
+        options: Option<T>,
+        credentials: Option<RegistryAuth>,
+        handler: H,
+    ) -> Result<ProgressSummary, Error>
+    where
+        T: CreateImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        H: ProgressHandler<CreateImageResults>,
+    {
+        let stream = self.create_image(options, None, credentials);
+        drive_progress(stream, handler).await
+    }
+
+    /// ---
+    ///
+    /// # Push Image With Progress
+    ///
+    /// Push an image with the [Push Image API](struct.Docker.html#method.push_image), reporting
+    /// its progress to a handler.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///  - Optional [Push Image Options](image/struct.PushImageOptions.html) struct.
+    ///  - Optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
+    ///  - A [Progress Handler](progress/trait.ProgressHandler.html) of [Push Image
+    ///  Results](image/enum.PushImageResults.html).
+    ///
+    /// # Returns
+    ///
+    ///  - A [Progress Summary](progress/struct.ProgressSummary.html), wrapped in a Future.
+    pub async fn push_image_with_progress<T, K, V, H>(
+        &self,
+        image_name: &str,
+        options: Option<T>,
+        credentials: Option<RegistryAuth>,
+        handler: H,
+    ) -> Result<ProgressSummary, Error>
+    where
+        T: PushImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        H: ProgressHandler<PushImageResults>,
+    {
+        let stream = self.push_image_stream(image_name, options, credentials);
+        drive_progress(stream, handler).await
+    }
+
+    /// ---
+    ///
+    /// # Build Image With Progress
+    ///
+    /// Build an image with the [Build Image API](struct.Docker.html#method.build_image),
+    /// reporting its progress to a handler.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Build Image Options](image/struct.BuildImageOptions.html) struct.
+    ///  - Optional [Docker
+    ///  Credentials](auth/struct.DockerCredentials.html), keyed by registry hostname.
+    ///  - Tar archive compressed with one of the following algorithms: identity (no
+    ///    compression), gzip, bzip2, xz. Optional [Hyper
+    ///    Body](https://hyper.rs/hyper/master/hyper/struct.Body.html).
+    ///  - A [Progress Handler](progress/trait.ProgressHandler.html) of [Build Image
+    ///  Results](image/enum.BuildImageResults.html).
+    ///
+    /// # Returns
+    ///
+    ///  - A [Progress Summary](progress/struct.ProgressSummary.html), wrapped in a Future.
+    pub async fn build_image_with_progress<T, K, H>(
+        &self,
+        options: T,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        tar: Option<Body>,
+        handler: H,
+    ) -> Result<ProgressSummary, Error>
+    where
+        T: BuildImageQueryParams<K>,
+        K: AsRef<str>,
+        H: ProgressHandler<BuildImageResults>,
+    {
+        let stream = self.build_image(options, credentials, tar);
+        drive_progress(stream, handler).await
+    }
+
+    /// ---
+    ///
+    /// # Load Image With Progress
+    ///
+    /// Load a set of images with the [Import Image API](struct.Docker.html#method.import_image),
+    /// reporting its progress to a handler.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Image Import Options](image/struct.ImportImageOptions.html) struct.
+    ///  - A tar archive of the images, as a [Hyper
+    ///    Body](https://hyper.rs/hyper/master/hyper/struct.Body.html).
+    ///  - Optional [Docker
+    ///  Credentials](auth/struct.DockerCredentials.html), keyed by registry hostname.
+    ///  - A [Progress Handler](progress/trait.ProgressHandler.html) of [Import Image
+    ///  Results](image/enum.ImportImageResults.html).
+    ///
+    /// # Returns
+    ///
+    ///  - A [Progress Summary](progress/struct.ProgressSummary.html), wrapped in a Future.
+    pub async fn load_image_with_progress<T, K, V, H>(
+        &self,
+        options: T,
+        root_fs: Body,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+        handler: H,
+    ) -> Result<ProgressSummary, Error>
+    where
+        T: ImportImageQueryParams<K, V>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        H: ProgressHandler<ImportImageResults>,
+    {
+        let stream = self.import_image(options, root_fs, credentials);
+        drive_progress(stream, handler).await
+    }
+}
@@ -4,9 +4,12 @@ use hyper::body::Bytes;
 use pin_project::pin_project;
 use serde::de::DeserializeOwned;
 use serde_json;
+use std::fmt;
 use std::pin::Pin;
 use std::string::String;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 use std::{
     cmp,
     io::{self},
@@ -18,14 +21,51 @@ use tokio_util::codec::Decoder;
 use crate::container::LogOutput;
 
 use crate::errors::Error;
-use crate::errors::ErrorKind::{JsonDataError, JsonDeserializeError, StrParseError};
+use crate::errors::ErrorKind::{JsonDataError, JsonDeserializeError, StreamIdleError};
+use crate::runtime::{BoxFuture, Runtime};
 
+/// The length of the header of a frame of a multiplexed stream: the stream type, three zero bytes
+/// and the length of the frame as a big endian `u32`.
+const FRAME_HEADER_LEN: usize = 8;
+
+/// Splits the output of a container into frames: the frames of a multiplexed stream, as told
+/// apart by their header, or the lines of a raw stream of a container with a TTY.
 #[derive(Debug, Copy, Clone)]
-pub(crate) struct NewlineLogOutputDecoder {}
+pub(crate) struct NewlineLogOutputDecoder {
+    // whether the stream is multiplexed, known once its first bytes are read
+    multiplexed: Option<bool>,
+}
 
 impl NewlineLogOutputDecoder {
     pub(crate) fn new() -> NewlineLogOutputDecoder {
-        NewlineLogOutputDecoder {}
+        NewlineLogOutputDecoder { multiplexed: None }
+    }
+}
+
+impl NewlineLogOutputDecoder {
+    /// Whether the buffered bytes start with a frame header, or `None` if too few bytes are
+    /// buffered to tell.
+    fn starts_with_header(src: &BytesMut) -> Option<bool> {
+        let prefix = cmp::min(src.len(), 4);
+        let header_like = match src.first() {
+            Some(0) | Some(1) | Some(2) => src[1..prefix].iter().all(|b| *b == 0),
+            _ => false,
+        };
+        if header_like && prefix < 4 {
+            None
+        } else {
+            Some(header_like)
+        }
+    }
+
+    fn decode_frame(frame: Bytes) -> LogOutput {
+        let message = frame.slice(cmp::min(frame.len(), FRAME_HEADER_LEN)..);
+        match frame.first() {
+            Some(0) => LogOutput::StdIn { message },
+            Some(1) => LogOutput::StdOut { message },
+            // the docker server reports its own errors on stream `3`
+            _ => LogOutput::StdErr { message },
+        }
     }
 }
 
@@ -33,57 +73,63 @@ impl Decoder for NewlineLogOutputDecoder {
     type Item = LogOutput;
     type Error = Error;
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        let nl_index = src.iter().position(|b| *b == b'\n');
-
-        if src.len() > 0 {
-            let pos = nl_index.unwrap_or(src.len() - 1);
-
-            let slice = src.split_to(pos + 1);
-            let slice = &slice[..slice.len() - 1];
+        if src.is_empty() {
+            debug!("NewlineLogOutputDecoder returning due to an empty line");
+            return Ok(None);
+        }
 
-            if slice.len() == 0 {
-                Ok(Some(LogOutput::Console {
-                    message: String::new(),
-                }))
-            } else {
-                match &slice[0] {
-                    0 if slice.len() <= 8 => Ok(Some(LogOutput::StdIn {
-                        message: String::new(),
-                    })),
-                    0 => Ok(Some(LogOutput::StdIn {
-                        message: String::from_utf8_lossy(&slice[8..]).to_string(),
-                    })),
-                    1 if slice.len() <= 8 => Ok(Some(LogOutput::StdOut {
-                        message: String::new(),
-                    })),
-                    1 => Ok(Some(LogOutput::StdOut {
-                        message: String::from_utf8_lossy(&slice[8..]).to_string(),
-                    })),
-                    2 if slice.len() <= 8 => Ok(Some(LogOutput::StdErr {
-                        message: String::new(),
-                    })),
-                    2 => Ok(Some(LogOutput::StdErr {
-                        message: String::from_utf8_lossy(&slice[8..]).to_string(),
-                    })),
-                    _ =>
-                    // `start_exec` API on unix socket will emit values without a header
-                    {
-                        Ok(Some(LogOutput::Console {
-                            message: String::from_utf8_lossy(&slice).to_string(),
-                        }))
-                    }
+        let multiplexed = match self.multiplexed {
+            Some(multiplexed) => multiplexed,
+            None => match NewlineLogOutputDecoder::starts_with_header(src) {
+                Some(multiplexed) => {
+                    self.multiplexed = Some(multiplexed);
+                    multiplexed
                 }
-                .map_err(|e| {
-                    StrParseError {
-                        content: hex::encode(slice.to_owned()),
-                        err: e,
-                    }
-                    .into()
-                })
+                None => return Ok(None),
+            },
+        };
+
+        if multiplexed {
+            if src.len() < FRAME_HEADER_LEN {
+                return Ok(None);
             }
+            let len =
+                FRAME_HEADER_LEN + u32::from_be_bytes([src[4], src[5], src[6], src[7]]) as usize;
+            if src.len() < len {
+                src.reserve(len - src.len());
+                return Ok(None);
+            }
+
+            let frame = src.split_to(len).freeze();
+            Ok(Some(NewlineLogOutputDecoder::decode_frame(frame)))
         } else {
-            debug!("NewlineLogOutputDecoder returning due to an empty line");
-            Ok(None)
+            // The output of a container with a TTY, e.g. of the `start_exec` API, has no header.
+            // It is emitted up to and including a newline, or as the entire buffer if no newline
+            // is present, so that output such as an interactive prompt is not held back.
+            let pos = src
+                .iter()
+                .position(|b| *b == b'\n')
+                .map(|pos| pos + 1)
+                .unwrap_or_else(|| src.len());
+
+            Ok(Some(LogOutput::Console {
+                message: src.split_to(pos).freeze(),
+            }))
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            // a frame cut short by the end of the stream
+            None if self.multiplexed == Some(true) => Ok(Some(
+                NewlineLogOutputDecoder::decode_frame(src.split().freeze()),
+            )),
+            // a stream too short to tell whether it is multiplexed
+            None => Ok(Some(LogOutput::Console {
+                message: src.split().freeze(),
+            })),
         }
     }
 }
@@ -209,12 +255,18 @@ where
                     Poll::Pending => {
                         return Poll::Pending;
                     }
-                    Poll::Ready(Some(Err(e))) => {
-                        return Poll::Ready(Err(io::Error::new(
-                            io::ErrorKind::Other,
-                            e.to_string(),
-                        )));
-                    }
+                    // An idle stream is passed on whole, so that it can be told apart once decoded.
+                    Poll::Ready(Some(Err(e))) => match e.kind() {
+                        StreamIdleError { .. } => {
+                            return Poll::Ready(Err(io::Error::new(io::ErrorKind::TimedOut, e)));
+                        }
+                        _ => {
+                            return Poll::Ready(Err(io::Error::new(
+                                io::ErrorKind::Other,
+                                e.to_string(),
+                            )));
+                        }
+                    },
                 },
             }
 
@@ -224,3 +276,76 @@ where
         }
     }
 }
+
+/// Yields a [Stream Idle Error](../errors/enum.ErrorKind.html#variant.StreamIdleError) if the
+/// inner stream yields nothing for the idle timeout, then ends. Without a timeout, the inner
+/// stream is passed on as is.
+#[pin_project]
+pub(crate) struct IdleTimeout<S> {
+    #[pin]
+    stream: S,
+    timeout: Option<Duration>,
+    runtime: Arc<dyn Runtime>,
+    // started when the inner stream is pending, and dropped when it yields an item
+    delay: Option<BoxFuture>,
+    expired: bool,
+}
+
+impl<S: fmt::Debug> fmt::Debug for IdleTimeout<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleTimeout")
+            .field("stream", &self.stream)
+            .field("timeout", &self.timeout)
+            .field("expired", &self.expired)
+            .finish()
+    }
+}
+
+impl<S> IdleTimeout<S> {
+    pub(crate) fn new(
+        stream: S,
+        timeout: Option<Duration>,
+        runtime: Arc<dyn Runtime>,
+    ) -> IdleTimeout<S> {
+        IdleTimeout {
+            stream,
+            timeout,
+            runtime,
+            delay: None,
+            expired: false,
+        }
+    }
+}
+
+impl<S, T> Stream for IdleTimeout<S>
+where
+    S: Stream<Item = Result<T, Error>>,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.expired {
+            return Poll::Ready(None);
+        }
+
+        match (this.stream.poll_next(cx), *this.timeout) {
+            (Poll::Ready(item), _) => {
+                *this.delay = None;
+                Poll::Ready(item)
+            }
+            (Poll::Pending, None) => Poll::Pending,
+            (Poll::Pending, Some(timeout)) => {
+                let runtime = &this.runtime;
+                let delay = this.delay.get_or_insert_with(|| runtime.delay(timeout));
+                match delay.as_mut().poll(cx) {
+                    Poll::Ready(()) => {
+                        *this.expired = true;
+                        Poll::Ready(Some(Err(StreamIdleError { idle: timeout }.into())))
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+        }
+    }
+}
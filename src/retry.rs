@@ -0,0 +1,162 @@
+//! Retry API: retry requests failing with a transient error, such as a connection reset by a
+//! docker server under load, with an exponential backoff.
+use hyper::body::HttpBody;
+use hyper::{Body, Method, Request};
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::{HyperResponseError, StreamIdleError};
+
+/// Predicate deciding whether a failed request is retried.
+pub type RetryPredicate = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// When and how often to retry a request, set on a client with
+/// [`Docker::with_retry_policy`](../struct.Docker.html#method.with_retry_policy).
+///
+/// Only requests without a body are retried, and by default only `GET` and `HEAD` requests, as
+/// the docker server may have acted on a request that failed. Errors reported by the docker
+/// server, e.g. a `500` status, are returned without retrying.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::retry::RetryPolicy;
+///
+/// use std::time::Duration;
+///
+/// RetryPolicy::default()
+///     .with_max_attempts(5)
+///     .with_backoff(Duration::from_millis(200), Duration::from_secs(5));
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    methods: Vec<Method>,
+    retry_if: RetryPredicate,
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("methods", &self.methods)
+            .finish()
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Make up to 3 attempts of `GET` and `HEAD` requests failing with a [transient
+    /// error](fn.is_transient.html), waiting 100 milliseconds before the first retry.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            methods: vec![Method::GET, Method::HEAD],
+            retry_if: Arc::new(is_transient),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The number of attempts made at a request, including the first one.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Wait `initial` before the first retry, doubling the wait on each retry up to `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// The HTTP methods of the requests to retry.
+    pub fn with_methods<T>(mut self, methods: T) -> Self
+    where
+        T: IntoIterator<Item = Method>,
+    {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Retry the errors matching a predicate, in place of the [transient
+    /// errors](fn.is_transient.html).
+    pub fn with_retry_if<F>(mut self, retry_if: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if = Arc::new(retry_if);
+        self
+    }
+
+    /// Whether a request may be retried, leaving requests with a body to the caller as the body
+    /// cannot be replayed.
+    pub(crate) fn applies(&self, request: &Request<Body>) -> bool {
+        self.max_attempts > 1
+            && self.methods.contains(request.method())
+            && request.body().size_hint().exact() == Some(0)
+    }
+
+    /// Whether to make another attempt after a failed one.
+    pub(crate) fn retries(&self, attempt: u32, err: &Error) -> bool {
+        attempt < self.max_attempts && (self.retry_if)(err)
+    }
+
+    /// The wait before the given retry, counting from 1.
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| {
+                std::cmp::min(backoff, self.max_backoff)
+            })
+    }
+}
+
+/// Whether an error is a transient failure to reach the docker server: a refused connection,
+/// or a connection reset or closed before the response was received.
+pub fn is_transient(err: &Error) -> bool {
+    match err.kind() {
+        HyperResponseError { err } => {
+            if err.is_connect() || err.is_closed() || err.is_incomplete_message() {
+                return true;
+            }
+
+            let mut source = err.source();
+            while let Some(cause) = source {
+                if let Some(io_err) = cause.downcast_ref::<io::Error>() {
+                    return match io_err.kind() {
+                        io::ErrorKind::ConnectionRefused
+                        | io::ErrorKind::ConnectionReset
+                        | io::ErrorKind::ConnectionAborted
+                        | io::ErrorKind::BrokenPipe
+                        | io::ErrorKind::UnexpectedEof => true,
+                        _ => false,
+                    };
+                }
+                source = cause.source();
+            }
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Whether an error ends a followed stream that received nothing for the stream idle timeout.
+pub(crate) fn is_stream_idle(err: &Error) -> bool {
+    match err.kind() {
+        StreamIdleError { .. } => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,146 @@
+//! Runtime API: run the background tasks and timers of the client on an asynchronous runtime
+//! other than tokio, such as async-std or smol.
+//!
+//! The client spawns the background tasks of its connections, and waits on the timers of
+//! request timeouts, stream idle timeouts, retries, health checks and polling loops, through a
+//! [`Runtime`](trait.Runtime.html) set with
+//! [`Docker::with_runtime`](../struct.Docker.html#method.with_runtime). The default runtime is
+//! tokio.
+//!
+//! The parts of the client needing a tokio reactor are behind the default `tokio` feature:
+//!
+//!  - The connectors of the `connect_with_*` methods, which dial the docker server with tokio
+//!  sockets over TCP, unix sockets, vsock, named pipes or ssh, along with the TLS features.
+//!  - The methods reading or writing files, such as loading or exporting images to a path, which
+//!  use `tokio::fs` and the tokio blocking pool.
+//!
+//! Without the `tokio` feature, reach the docker server through a connector of another runtime
+//! with [`Docker::connect_with_connector`](../struct.Docker.html#method.connect_with_connector),
+//! and set that runtime with `Docker::with_runtime` before making requests.
+use futures_util::future::{self, Either};
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A future spawned on, or returned by, a [`Runtime`](trait.Runtime.html).
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The asynchronous runtime the client runs its background tasks and timers on.
+///
+/// ## Examples
+///
+/// A runtime running the client on the tokio runtime of a handle, rather than on the runtime it
+/// is called from. A runtime for async-std would call `async_std::task::spawn` and
+/// `async_std::task::sleep` in the same way.
+///
+/// ```rust
+/// use bollard::runtime::{BoxFuture, Runtime};
+/// use bollard::{Docker, API_DEFAULT_VERSION};
+///
+/// use hyper::client::HttpConnector;
+/// use tokio::runtime::Handle;
+/// use tokio::sync::oneshot;
+///
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct OnHandle(Handle);
+///
+/// impl Runtime for OnHandle {
+///     fn spawn(&self, task: BoxFuture) {
+///         self.0.spawn(task);
+///     }
+///
+///     fn delay(&self, duration: Duration) -> BoxFuture {
+///         Box::pin(self.0.enter(|| tokio::time::delay_for(duration)))
+///     }
+/// }
+///
+/// let mut background = tokio::runtime::Runtime::new().unwrap();
+/// let runtime = OnHandle(background.handle().clone());
+///
+/// let (tx, rx) = oneshot::channel();
+/// runtime.spawn(Box::pin(async move {
+///     tx.send("spawned").unwrap();
+/// }));
+/// let delay = runtime.delay(Duration::from_millis(10));
+/// assert_eq!(background.block_on(async { delay.await; rx.await }), Ok("spawned"));
+///
+/// let docker = Docker::connect_with_connector(
+///     HttpConnector::new(), "localhost:2375", 120, API_DEFAULT_VERSION)
+///     .unwrap()
+///     .with_runtime(runtime);
+/// ```
+pub trait Runtime: fmt::Debug + Send + Sync + 'static {
+    /// Run a task in the background, such as the connection of a request.
+    fn spawn(&self, task: BoxFuture);
+
+    /// A future completing once the duration has passed.
+    fn delay(&self, duration: Duration) -> BoxFuture;
+}
+
+/// The tokio runtime, which the client runs on by default.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TokioRuntime;
+
+#[cfg(feature = "tokio")]
+
+impl Runtime for TokioRuntime {
+    fn spawn(&self, task: BoxFuture) {
+        tokio::spawn(task);
+    }
+
+    fn delay(&self, duration: Duration) -> BoxFuture {
+        Box::pin(tokio::time::delay_for(duration))
+    }
+}
+
+/// The default runtime without the `tokio` feature, until a runtime is set with
+/// `Docker::with_runtime`.
+#[cfg(not(feature = "tokio"))]
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct NoRuntime;
+
+#[cfg(not(feature = "tokio"))]
+impl Runtime for NoRuntime {
+    fn spawn(&self, _: BoxFuture) {
+        panic!("no runtime set, use Docker::with_runtime or enable the tokio feature");
+    }
+
+    fn delay(&self, _: Duration) -> BoxFuture {
+        panic!("no runtime set, use Docker::with_runtime or enable the tokio feature");
+    }
+}
+
+/// Spawns the connections of a hyper client on a runtime.
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeExecutor(pub(crate) Arc<dyn Runtime>);
+
+impl<F> hyper::rt::Executor<F> for RuntimeExecutor
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    fn execute(&self, fut: F) {
+        self.0.spawn(Box::pin(fut));
+    }
+}
+
+/// Wait for a future up to the timeout, returning `None` if it elapses first.
+pub(crate) async fn timeout<F>(
+    runtime: &dyn Runtime,
+    timeout: Duration,
+    fut: F,
+) -> Option<F::Output>
+where
+    F: Future,
+{
+    let fut = Box::pin(fut);
+    match future::select(fut, runtime.delay(timeout)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
@@ -3,8 +3,8 @@
 pub use crate::service_models::*;
 
 use super::Docker;
-use crate::auth::DockerCredentials;
-use crate::docker::{FALSE_STR, TRUE_STR};
+use crate::auth::{registry_auth_header, RegistryAuth};
+use crate::docker::{deserialize_nonoptional_vec, FALSE_STR, TRUE_STR};
 use crate::errors::Error;
 use crate::errors::ErrorKind::JsonSerializeError;
 use arrayvec::ArrayVec;
@@ -15,6 +15,9 @@ use serde::Serialize;
 use serde_json;
 use std::{collections::HashMap, hash::Hash};
 
+/// Number of attempts made to update labels, when the object was updated concurrently.
+pub(crate) const LABEL_UPDATE_ATTEMPTS: usize = 5;
+
 /// Parameters used in the [List Service API](../struct.Docker.html#method.list_services)
 ///
 /// ## Examples
@@ -75,16 +78,21 @@ where
     }
 }
 
+/// Result type for the [Create Service API](../struct.Docker.html#method.create_service)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct ServiceCreateResponse {
     /// The ID of the created service.
     #[serde(rename = "ID")]
     pub id: Option<String>,
 
-    /// Optional warning message
+    /// Optional warning message, as returned by docker servers before the `Warnings` list.
+    #[serde(rename = "Warning")]
     pub warning: Option<String>,
+
+    /// Warnings encountered when creating the service.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub warnings: Vec<String>,
 }
 
 /// Parameters used in the [Inspect Service API](../struct.Docker.html#method.inspect_service)
@@ -180,12 +188,13 @@ impl<'a> UpdateServiceQueryParams<&'a str, String> for UpdateServiceOptions {
     }
 }
 
+/// Result type for the [Update Service API](../struct.Docker.html#method.update_service)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct ServiceUpdateResponse {
-    /// Optional warning message
-    pub warning: Option<String>,
+    /// Warnings encountered when updating the service.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub warnings: Vec<String>,
 }
 
 impl Docker {
@@ -252,7 +261,7 @@ impl Docker {
     /// # Arguments
     ///
     ///  - [ServiceSpec](service_models/struct.ServiceSpec.html) struct.
-    ///  - Optional [Docker Credentials](auth/struct.DockerCredentials.html) struct.
+    ///  - Optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
     ///
     /// # Returns
     ///
@@ -294,31 +303,29 @@ impl Docker {
     pub async fn create_service<Z>(
         &self,
         service_spec: ServiceSpec<Z>,
-        credentials: Option<DockerCredentials>,
+        credentials: Option<RegistryAuth>,
     ) -> Result<ServiceCreateResponse, Error>
     where
         Z: AsRef<str> + Eq + Hash + Serialize,
     {
         let url = "/services/create";
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
-                let req = self.build_request::<_, String, String>(
-                    url,
-                    Builder::new()
-                        .method(Method::POST)
-                        .header(CONTENT_TYPE, "application/json")
-                        .header("X-Registry-Auth", base64::encode(&ser_cred)),
-                    Ok(None::<ArrayVec<[(_, _); 0]>>),
-                    Docker::serialize_payload(Some(service_spec)),
-                );
-
-                self.process_into_value(req).await
-            }
-            Err(e) => Err(JsonSerializeError { err: e }.into()),
-        }
+        let auth = registry_auth_header(credentials)?;
+
+        let req = self.build_request::<_, String, String>(
+            url,
+            Builder::new()
+                .method(Method::POST)
+                .header(CONTENT_TYPE, "application/json")
+                .header("X-Registry-Auth", auth),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Docker::serialize_payload(Some(service_spec)),
+        );
+
+        let result: ServiceCreateResponse = self.process_into_value(req).await?;
+        self.emit_warnings(url, &result.warnings);
+
+        Ok(result)
     }
 
     /// ---
@@ -417,7 +424,7 @@ impl Docker {
     ///  - Service name or id as a string slice.
     ///  - [ServiceSpec](service_models/struct.ServiceSpec.html) struct.
     ///  - [UpdateServiceOptions](service/struct.UpdateServiceOptions.html) struct.
-    ///  - Optional [Docker Credentials](auth/struct.DockerCredentials.html) struct.
+    ///  - Optional [Registry Auth](auth/enum.RegistryAuth.html) enum.
     ///
     /// # Returns
     ///
@@ -467,7 +474,7 @@ impl Docker {
         service_name: &str,
         service_spec: ServiceSpec<Z>,
         options: T,
-        credentials: Option<DockerCredentials>,
+        credentials: Option<RegistryAuth>,
     ) -> Result<ServiceUpdateResponse, Error>
     where
         T: UpdateServiceQueryParams<K, String>,
@@ -476,23 +483,95 @@ impl Docker {
     {
         let url = format!("/services/{}/update", service_name);
 
-        match serde_json::to_string(&credentials.unwrap_or_else(|| DockerCredentials {
-            ..Default::default()
-        })) {
-            Ok(ser_cred) => {
-                let req = self.build_request(
-                    &url,
-                    Builder::new()
-                        .method(Method::POST)
-                        .header(CONTENT_TYPE, "application/json")
-                        .header("X-Registry-Auth", base64::encode(&ser_cred)),
-                    Docker::transpose_option(Some(options.into_array())),
-                    Docker::serialize_payload(Some(service_spec)),
-                );
-
-                self.process_into_value(req).await
+        let auth = registry_auth_header(credentials)?;
+
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::POST)
+                .header(CONTENT_TYPE, "application/json")
+                .header("X-Registry-Auth", auth),
+            Docker::transpose_option(Some(options.into_array())),
+            Docker::serialize_payload(Some(service_spec)),
+        );
+
+        let result: ServiceUpdateResponse = self.process_into_value(req).await?;
+        self.emit_warnings(&url, &result.warnings);
+
+        Ok(result)
+    }
+
+    /// ---
+    ///
+    /// # Set Service Labels
+    ///
+    /// Set the labels of an existing service, by inspecting the service and resubmitting its spec
+    /// with the current version. If the service is updated concurrently, the update is retried
+    /// against the new version up to 5 times, so that no update is lost.
+    ///
+    /// # Arguments
+    ///
+    ///  - Service name or id as a string slice.
+    ///  - Labels to set, as a `HashMap`.
+    ///  - Whether to merge the labels into the existing labels, rather than replace them.
+    ///
+    /// # Returns
+    ///
+    ///  - The labels of the service after the update, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::collections::HashMap;
+    ///
+    /// let mut labels = HashMap::new();
+    /// labels.insert(String::from("com.example.owner"), String::from("platform"));
+    ///
+    /// docker.set_service_labels("my-service", labels, true);
+    /// ```
+    pub async fn set_service_labels(
+        &self,
+        service_name: &str,
+        labels: HashMap<String, String>,
+        merge: bool,
+    ) -> Result<HashMap<String, String>, Error> {
+        let mut attempt = 1;
+        loop {
+            let service = self
+                .inspect_service(service_name, None::<InspectServiceOptions>)
+                .await?;
+
+            let mut spec = service.spec;
+            if merge {
+                spec.labels.extend(labels.clone());
+            } else {
+                spec.labels = labels.clone();
+            }
+            let result = spec.labels.clone();
+
+            let options = UpdateServiceOptions {
+                version: service.version.index,
+                registry_auth_from_previous: true,
+                ..Default::default()
+            };
+
+            match self.update_service(service_name, spec, options, None).await {
+                Ok(_) => return Ok(result),
+                Err(ref e) if attempt < LABEL_UPDATE_ATTEMPTS && Docker::is_out_of_sequence(e) => {
+                    debug!("Retrying label update of {}: {}", service_name, e);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
             }
-            Err(e) => Err(JsonSerializeError { err: e }.into()),
         }
     }
+
+    /// Whether an update was rejected because the object version is no longer current.
+    pub(crate) fn is_out_of_sequence(error: &Error) -> bool {
+        Docker::error_message(error)
+            .map(|message| message.contains("update out of sequence"))
+            .unwrap_or(false)
+    }
 }
@@ -21,6 +21,37 @@ where
     pub update_status: Option<ServiceUpdateStatus<T>>,
 }
 
+impl<T> Service<T>
+where
+    T: AsRef<str> + Eq + Hash,
+{
+    /// The port on the swarm hosts published for a target port of the service, looked up in the
+    /// ports of the endpoint and then in its spec. Returns `None` if the target port is not
+    /// published, or is published in `host` mode on a dynamically assigned port.
+    pub fn published_port_for(
+        &self,
+        target_port: isize,
+        protocol: EndpointPortConfigProtocol,
+    ) -> Option<isize> {
+        let spec_ports = self
+            .spec
+            .endpoint_spec
+            .as_ref()
+            .and_then(|spec| spec.ports.as_ref());
+
+        self.endpoint
+            .ports
+            .iter()
+            .chain(spec_ports)
+            .flatten()
+            .find(|port| {
+                port.target_port == Some(target_port)
+                    && port.protocol.unwrap_or(EndpointPortConfigProtocol::Tcp) == protocol
+            })
+            .and_then(|port| port.published_port)
+    }
+}
+
 /// The version number of the object such as node, service, etc. This is needed to avoid conflicting writes. The client must send the version number along with the modified specification when updating these objects. This approach ensures safe concurrency and determinism in that the change on the object may not be applied if the version number has changed from the last read. In other words, if two update requests specify the same base version, only one of the requests can succeed. As a result, two separate update requests that happen at the same time will not unintentionally overwrite each other.
 #[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -143,9 +174,12 @@ where
     pub stop_signal: Option<T>,
     /// Amount of time to wait for the container to terminate before forcefully killing it.
     pub stop_grace_period: Option<i64>,
+    /// A test to perform to check that the container is healthy.
+    #[serde(rename = "Healthcheck")]
     pub health_check: Option<HealthConfig<T>>,
     /// A list of hostname/IP mappings to add to the container's `hosts` file. The format of extra hosts is specified in the [hosts(5)](http://man7.org/linux/man-pages/man5/hosts.5.html) man page:      IP_address canonical_hostname [aliases...]
     pub hosts: Option<Vec<T>>,
+    /// Specification for DNS related configurations in resolver configuration file.
     #[serde(rename = "DNSConfig")]
     pub dns_config: Option<TaskSpecContainerSpecDnsConfig<T>>,
     /// Secrets contains references to zero or more secrets that will be exposed to the service.
@@ -244,7 +278,7 @@ where
 }
 
 /// The mount type. Available types:  - `bind` Mounts a file or directory from the host into the container. Must exist prior to creating the container. - `volume` Creates a volume with the given name and options (or uses a pre-existing volume with the same name and options). These are **not** removed when the container is removed. - `tmpfs` Create a tmpfs with the given options. The mount source cannot be specified for tmpfs. - `npipe` Mounts a named pipe from the host into the container. Must exist prior to creating the container.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[allow(missing_docs)]
 pub enum MountType {
@@ -266,7 +300,7 @@ pub struct MountBindOptions {
 }
 
 /// A propagation mode with the value `[r]private`, `[r]shared`, or `[r]slave`.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[allow(missing_docs)]
 pub enum MountBindOptionsPropagation {
@@ -357,6 +391,7 @@ where
     pub options: Option<Vec<T>>,
 }
 
+/// A reference to a secret that will be exposed to the service.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 #[allow(missing_docs)]
@@ -364,6 +399,7 @@ pub struct TaskSpecContainerSpecSecrets<T>
 where
     T: AsRef<str> + Eq + Hash,
 {
+    /// The file in which the secret is exposed, under `/run/secrets/` by default.
     pub file: Option<TaskSpecContainerSpecFile<T>>,
     /// SecretID represents the ID of the specific secret that we're referencing.
     #[serde(rename = "SecretID")]
@@ -388,10 +424,12 @@ where
     /// GID represents the file GID.
     #[serde(rename = "GID")]
     pub gid: Option<T>,
-    /// Mode represents the FileMode of the file.
+    /// Mode represents the FileMode of the file. The API expects the decimal value of the
+    /// permission bits, so write the mode as an octal literal, e.g. `0o440` rather than `440`.
     pub mode: Option<u32>,
 }
 
+/// A reference to a config that will be exposed to the service.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 #[allow(missing_docs)]
@@ -399,6 +437,7 @@ pub struct TaskSpecContainerSpecConfigs<T>
 where
     T: AsRef<str> + Eq + Hash,
 {
+    /// The file in which the config is exposed, at the container root by default.
     pub file: Option<TaskSpecContainerSpecFile<T>>,
     /// Runtime represents a target that is not mounted into the container but is used by the task  <p><br /><p>  > **Note**: `Configs.File` and `Configs.Runtime` are mutually exclusive
     pub runtime: Option<HashMap<(), ()>>,
@@ -650,7 +689,7 @@ where
     /// The mode of resolution to use for internal load balancing between tasks.
     pub mode: Option<EndpointSpecMode>,
     /// List of exposed ports that this service is accessible on from the outside. Ports can only be provided if `vip` resolution mode is used.
-    pub ports: Option<Vec<EndpointPortConfig<T>>>,
+    pub ports: Option<Vec<PortConfig<T>>>,
 }
 
 /// The mode of resolution to use for internal load balancing between tasks.
@@ -662,24 +701,30 @@ pub enum EndpointSpecMode {
     Dnsrr,
 }
 
+/// A port exposed by a service, found in both the endpoint spec and the ports actually published
+/// by the [Service Endpoint](struct.ServiceEndpoint.html).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 #[allow(missing_docs)]
-pub struct EndpointPortConfig<T>
+pub struct PortConfig<T>
 where
     T: AsRef<str> + Eq + Hash,
 {
     pub name: Option<T>,
+    /// The protocol of the port, `tcp` if absent.
     pub protocol: Option<EndpointPortConfigProtocol>,
     /// The port inside the container.
     pub target_port: Option<isize>,
-    /// The port on the swarm hosts.
+    /// The port on the swarm hosts. Absent for a `host` mode port assigned dynamically.
     pub published_port: Option<isize>,
     /// The mode in which port is published.  <p><br /></p>  - \"ingress\" makes the target port accessible on every node,   regardless of whether there is a task for the service running on   that node or not. - \"host\" bypasses the routing mesh and publish the port directly on   the swarm node where that service is running.
     pub publish_mode: Option<EndpointPortConfigPublishMode>,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+/// Former name of [`PortConfig`](struct.PortConfig.html).
+pub type EndpointPortConfig<T> = PortConfig<T>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[allow(missing_docs)]
 pub enum EndpointPortConfigProtocol {
@@ -689,7 +734,7 @@ pub enum EndpointPortConfigProtocol {
 }
 
 /// The mode in which port is published.  <p><br /></p>  - \"ingress\" makes the target port accessible on every node,   regardless of whether there is a task for the service running on   that node or not. - \"host\" bypasses the routing mesh and publish the port directly on   the swarm node where that service is running.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[allow(missing_docs)]
 pub enum EndpointPortConfigPublishMode {
@@ -705,7 +750,7 @@ where
     T: AsRef<str> + Eq + Hash,
 {
     pub spec: Option<EndpointSpec<T>>,
-    pub ports: Option<Vec<EndpointPortConfig<T>>>,
+    pub ports: Option<Vec<PortConfig<T>>>,
     #[serde(rename = "VirtualIPs")]
     pub virtual_ips: Option<Vec<ServiceEndpointVirtualIPs<T>>>,
 }
@@ -0,0 +1,196 @@
+//! Tracking of in-flight requests, used to shut the client down gracefully.
+use futures_core::Stream;
+use futures_util::future::{self, Either};
+use futures_util::stream::StreamExt;
+use tokio::sync::{watch, Notify};
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::ClientShuttingDownError;
+use crate::runtime::Runtime;
+
+/// State shared between all clones of a client.
+#[derive(Debug)]
+pub(crate) struct ClientState {
+    shutting_down: AtomicBool,
+    in_flight: AtomicUsize,
+    /// Notified when the last in-flight non-streaming request completes.
+    idle: Notify,
+    /// Set to `true` once the remaining requests are aborted, waking every tracked request.
+    abort: watch::Sender<bool>,
+    aborted: watch::Receiver<bool>,
+}
+
+impl Default for ClientState {
+    fn default() -> Self {
+        let (abort, aborted) = watch::channel(false);
+        ClientState {
+            shutting_down: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+            abort,
+            aborted,
+        }
+    }
+}
+
+impl ClientState {
+    /// Track a non-streaming request, which is waited on when draining.
+    pub(crate) fn track_future<F>(state: &Arc<ClientState>, fut: F) -> Tracked<F> {
+        ClientState::track(state, fut, true)
+    }
+
+    /// Track a streaming request, which is not waited on when draining.
+    pub(crate) fn track_stream<S>(state: &Arc<ClientState>, stream: S) -> Tracked<S> {
+        ClientState::track(state, stream, false)
+    }
+
+    fn track<T>(state: &Arc<ClientState>, inner: T, counted: bool) -> Tracked<T> {
+        if state.shutting_down.load(Ordering::SeqCst) {
+            return Tracked {
+                inner: None,
+                state: state.clone(),
+                aborted: None,
+                counted: false,
+                error_pending: true,
+            };
+        }
+
+        if counted {
+            state.in_flight.fetch_add(1, Ordering::SeqCst);
+        }
+
+        Tracked {
+            inner: Some(Box::pin(inner)),
+            state: state.clone(),
+            aborted: Some(state.aborted.clone()),
+            counted,
+            error_pending: false,
+        }
+    }
+
+    /// Reject new requests, wait up to the grace period for in-flight non-streaming requests to
+    /// complete, then abort all remaining requests and streams. Returns whether all non-streaming
+    /// requests completed within the grace period.
+    pub(crate) async fn shutdown(&self, grace: Duration, runtime: &dyn Runtime) -> bool {
+        self.shutting_down.store(true, Ordering::SeqCst);
+
+        let mut deadline = runtime.delay(grace);
+        while self.in_flight.load(Ordering::SeqCst) > 0 {
+            // A request completing before the wait starts leaves a permit, so it is not missed.
+            let idle = Box::pin(self.idle.notified());
+            if let Either::Right(_) = future::select(idle, &mut deadline).await {
+                break;
+            }
+        }
+        let drained = self.in_flight.load(Ordering::SeqCst) == 0;
+
+        // Only fails without tracked requests left to wake.
+        let _ = self.abort.broadcast(true);
+
+        drained
+    }
+}
+
+/// A request future or stream, which fails with a `ClientShuttingDownError` if it is started
+/// after, or aborted by, a client shutdown.
+#[derive(Debug)]
+pub(crate) struct Tracked<T> {
+    inner: Option<Pin<Box<T>>>,
+    state: Arc<ClientState>,
+    /// Registered while the request is in flight.
+    aborted: Option<watch::Receiver<bool>>,
+    counted: bool,
+    error_pending: bool,
+}
+
+impl<T> Tracked<T> {
+    /// Register interest in an abort, releasing the inner request if the client was aborted.
+    fn poll_aborted(&mut self, cx: &mut Context<'_>) -> bool {
+        if let Some(ref mut aborted) = self.aborted {
+            loop {
+                match aborted.poll_next_unpin(cx) {
+                    Poll::Ready(Some(false)) => continue,
+                    Poll::Pending => return false,
+                    _ => break,
+                }
+            }
+            self.release();
+            return true;
+        }
+        false
+    }
+
+    fn release(&mut self) {
+        // Dropping the inner request closes its connection.
+        self.inner = None;
+        if self.aborted.take().is_some()
+            && self.counted
+            && self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1
+        {
+            self.state.idle.notify();
+        }
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl<T> Unpin for Tracked<T> {}
+
+impl<F, R> Future for Tracked<F>
+where
+    F: Future<Output = Result<R, Error>>,
+{
+    type Output = Result<R, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.error_pending || this.poll_aborted(cx) {
+            this.error_pending = false;
+            return Poll::Ready(Err(ClientShuttingDownError.into()));
+        }
+
+        let result = match this.inner {
+            Some(ref mut inner) => inner.as_mut().poll(cx),
+            None => panic!("Tracked future polled after completion"),
+        };
+        if result.is_ready() {
+            this.release();
+        }
+        result
+    }
+}
+
+impl<S, R> Stream for Tracked<S>
+where
+    S: Stream<Item = Result<R, Error>>,
+{
+    type Item = Result<R, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.error_pending || this.poll_aborted(cx) {
+            this.error_pending = false;
+            return Poll::Ready(Some(Err(ClientShuttingDownError.into())));
+        }
+
+        let result = match this.inner {
+            Some(ref mut inner) => inner.as_mut().poll_next(cx),
+            None => return Poll::Ready(None),
+        };
+        if let Poll::Ready(None) = result {
+            this.release();
+        }
+        result
+    }
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "ssh")]
+//! Tunnel the Docker API through `docker system dial-stdio` on a remote host, run by a spawned
+//! `ssh` process.
+
+use hyper::client::connect::Connected;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use url::Url;
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::task::{Context, Poll};
+
+use crate::errors::Error;
+use crate::errors::ErrorKind::InvalidSshAddressError;
+
+/// The host, user and port of an `ssh://[user@]host[:port]` address.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SshDestination {
+    pub(crate) host: String,
+    pub(crate) user: Option<String>,
+    pub(crate) port: Option<u16>,
+}
+
+impl SshDestination {
+    pub(crate) fn parse(addr: &str) -> Result<SshDestination, Error> {
+        let invalid = || InvalidSshAddressError {
+            addr: String::from(addr),
+        };
+
+        let url = Url::parse(addr).map_err(|_| invalid())?;
+        if url.scheme() != "ssh" || !(url.path().is_empty() || url.path() == "/") {
+            return Err(invalid().into());
+        }
+
+        Ok(SshDestination {
+            host: url.host_str().ok_or_else(invalid)?.to_owned(),
+            user: Some(url.username())
+                .filter(|user| !user.is_empty())
+                .map(String::from),
+            port: url.port(),
+        })
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new("ssh");
+        if let Some(ref user) = self.user {
+            command.arg("-l").arg(user);
+        }
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        command
+            .arg("--")
+            .arg(&self.host)
+            .args(&["docker", "system", "dial-stdio"]);
+        command
+    }
+}
+
+/// A connection to the docker server over the standard input and output of an `ssh` process,
+/// which is killed when the connection is dropped.
+#[derive(Debug)]
+pub struct SshStream {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+}
+
+impl AsyncRead for SshStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        Pin::new(&mut self.get_mut().stdout).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SshStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        Pin::new(&mut self.get_mut().stdin).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stdin).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stdin).poll_shutdown(cx)
+    }
+}
+
+impl hyper::client::connect::Connection for SshStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SshConnector {
+    destination: SshDestination,
+}
+
+impl SshConnector {
+    pub(crate) fn new(destination: SshDestination) -> SshConnector {
+        SshConnector { destination }
+    }
+}
+
+impl hyper::service::Service<hyper::Uri> for SshConnector {
+    type Response = SshStream;
+    type Error = io::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Every connection spawns its own process, the destination uri is only used by hyper to pool
+    // the connections.
+    fn call(&mut self, _destination: hyper::Uri) -> Self::Future {
+        let mut command = self.destination.command();
+        Box::pin(async move {
+            let mut child = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()?;
+
+            let closed = || io::Error::new(io::ErrorKind::BrokenPipe, "ssh process not piped");
+            let stdin = child.stdin.take().ok_or_else(closed)?;
+            let stdout = child.stdout.take().ok_or_else(closed)?;
+
+            Ok(SshStream {
+                _child: child,
+                stdin,
+                stdout,
+            })
+        })
+    }
+}
@@ -0,0 +1,442 @@
+//! Swarm API: inspect and update the swarm, and rotate its root CA
+
+use arrayvec::ArrayVec;
+use chrono::{DateTime, Utc};
+use http::request::Builder;
+use hyper::{Body, Method};
+use tokio::time::Instant;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::Docker;
+use crate::docker::deserialize_nonoptional_vec;
+use crate::errors::Error;
+use crate::errors::ErrorKind::SwarmCARotationTimeoutError;
+use crate::service_models::ObjectVersion;
+
+/// Result type for the [Inspect Swarm API](../struct.Docker.html#method.inspect_swarm)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Swarm {
+    /// The ID of the swarm.
+    #[serde(rename = "ID")]
+    pub id: String,
+    /// The version of the swarm object, required to update the swarm.
+    pub version: ObjectVersion,
+    /// Date and time at which the swarm was initialised.
+    pub created_at: DateTime<Utc>,
+    /// Date and time at which the swarm was last updated.
+    pub updated_at: DateTime<Utc>,
+    /// User modifiable swarm configuration.
+    pub spec: SwarmSpec,
+    /// Information about the root CA of the swarm.
+    #[serde(rename = "TLSInfo")]
+    pub tls_info: TLSInfo,
+    /// Whether there is currently a root CA rotation in progress for the swarm.
+    pub root_rotation_in_progress: bool,
+    /// The UDP port number used for data path traffic.
+    pub data_path_port: Option<u32>,
+    /// Default address pools for global scope networks, in CIDR format.
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    pub default_addr_pool: Vec<String>,
+    /// The subnet size of the networks created from the default address pools.
+    pub subnet_size: Option<u32>,
+    /// The tokens workers and managers need to join the swarm.
+    pub join_tokens: Option<JoinTokens>,
+}
+
+/// The tokens workers and managers need to join the swarm.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct JoinTokens {
+    /// The token workers can use to join the swarm.
+    pub worker: String,
+    /// The token managers can use to join the swarm.
+    pub manager: String,
+}
+
+/// Information about the issuer of the certificates of a swarm or a node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TLSInfo {
+    /// The root CA certificate(s) that are used to validate leaf TLS certificates.
+    pub trust_root: Option<String>,
+    /// The base64-url-safe-encoded raw subject bytes of the issuer.
+    pub cert_issuer_subject: Option<String>,
+    /// The base64-url-safe-encoded raw public key bytes of the issuer.
+    pub cert_issuer_public_key: Option<String>,
+}
+
+/// User modifiable swarm configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpec {
+    /// Name of the swarm.
+    pub name: Option<String>,
+    /// User-defined key/value metadata.
+    pub labels: Option<HashMap<String, String>>,
+    /// Orchestration configuration.
+    pub orchestration: Option<SwarmSpecOrchestration>,
+    /// Raft configuration.
+    pub raft: Option<SwarmSpecRaft>,
+    /// Dispatcher configuration.
+    pub dispatcher: Option<SwarmSpecDispatcher>,
+    /// CA configuration.
+    #[serde(rename = "CAConfig")]
+    pub ca_config: Option<SwarmSpecCAConfig>,
+    /// Parameters related to encryption-at-rest.
+    pub encryption_config: Option<SwarmSpecEncryptionConfig>,
+    /// Defaults for creating tasks in this cluster.
+    pub task_defaults: Option<SwarmSpecTaskDefaults>,
+}
+
+/// Orchestration configuration.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecOrchestration {
+    /// The number of historic tasks to keep per instance or node. If negative, never remove
+    /// completed or failed tasks.
+    pub task_history_retention_limit: Option<i64>,
+}
+
+/// Raft configuration.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecRaft {
+    /// The number of log entries between snapshots.
+    pub snapshot_interval: Option<u64>,
+    /// The number of snapshots to keep beyond the current snapshot.
+    pub keep_old_snapshots: Option<u64>,
+    /// The number of log entries to keep around to sync up slow followers after a snapshot is
+    /// created.
+    pub log_entries_for_slow_followers: Option<u64>,
+    /// The number of ticks that a follower will wait for a message from the leader before
+    /// becoming a candidate and starting an election.
+    pub election_tick: Option<i64>,
+    /// The number of ticks between heartbeats.
+    pub heartbeat_tick: Option<i64>,
+}
+
+/// Dispatcher configuration.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecDispatcher {
+    /// The delay for an agent to send a heartbeat to the dispatcher, in nanoseconds.
+    pub heartbeat_period: Option<i64>,
+}
+
+/// CA configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecCAConfig {
+    /// The duration node certificates are issued for, in nanoseconds.
+    pub node_cert_expiry: Option<i64>,
+    /// Configuration for forwarding signing requests to an external certificate authority.
+    #[serde(
+        rename = "ExternalCAs",
+        default,
+        deserialize_with = "deserialize_nonoptional_vec"
+    )]
+    pub external_cas: Vec<SwarmSpecCAConfigExternalCA>,
+    /// The desired signing CA certificate for all swarm node TLS leaf certificates, in PEM
+    /// format.
+    #[serde(rename = "SigningCACert", skip_serializing_if = "Option::is_none")]
+    pub signing_ca_cert: Option<String>,
+    /// The desired signing CA key for all swarm node TLS leaf certificates, in PEM format.
+    #[serde(rename = "SigningCAKey", skip_serializing_if = "Option::is_none")]
+    pub signing_ca_key: Option<String>,
+    /// An integer whose purpose is to force swarm to generate a new signing CA certificate and
+    /// key, if none have been specified in `signing_ca_cert` and `signing_ca_key`.
+    pub force_rotate: Option<u64>,
+}
+
+/// An external certificate authority, to which signing requests are forwarded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecCAConfigExternalCA {
+    /// Protocol for communication with the external CA. Currently only `cfssl` is supported.
+    pub protocol: Option<String>,
+    /// URL where certificate signing requests should be sent.
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    /// An object with key/value pairs that are interpreted as protocol-specific options for the
+    /// external CA driver.
+    pub options: Option<HashMap<String, String>>,
+    /// The root CA certificate (in PEM format) this external CA uses to issue TLS certificates.
+    #[serde(rename = "CACert")]
+    pub ca_cert: Option<String>,
+}
+
+/// Parameters related to encryption-at-rest.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecEncryptionConfig {
+    /// If set, generate a key and use it to lock data stored on the managers.
+    pub auto_lock_managers: Option<bool>,
+}
+
+/// Defaults for creating tasks in this cluster.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecTaskDefaults {
+    /// The log driver to use for tasks created in the orchestrator if unspecified by a service.
+    pub log_driver: Option<SwarmSpecTaskDefaultsLogDriver>,
+}
+
+/// The log driver to use for tasks created in the orchestrator if unspecified by a service.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwarmSpecTaskDefaultsLogDriver {
+    /// The log driver to use as a default for new tasks.
+    pub name: Option<String>,
+    /// Driver-specific options for the selected log driver, specified as key/value pairs.
+    pub options: Option<HashMap<String, String>>,
+}
+
+/// Parameters used in the [Update Swarm API](../struct.Docker.html#method.update_swarm)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::UpdateSwarmOptions;
+///
+/// UpdateSwarmOptions{
+///     version: 1234,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UpdateSwarmOptions {
+    /// The version number of the swarm object being updated. This is required to avoid
+    /// conflicting writes.
+    pub version: u64,
+    /// Rotate the worker join token.
+    pub rotate_worker_token: bool,
+    /// Rotate the manager join token.
+    pub rotate_manager_token: bool,
+    /// Rotate the manager unlock key.
+    pub rotate_manager_unlock_key: bool,
+}
+
+/// Trait providing implementations for [Update Swarm Options](struct.UpdateSwarmOptions.html).
+#[allow(missing_docs)]
+pub trait UpdateSwarmQueryParams<K, V>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn into_array(self) -> Result<ArrayVec<[(K, V); 4]>, Error>;
+}
+
+impl<'a> UpdateSwarmQueryParams<&'a str, String> for UpdateSwarmOptions {
+    fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 4]>, Error> {
+        Ok(ArrayVec::from([
+            ("version", self.version.to_string()),
+            ("rotateWorkerToken", self.rotate_worker_token.to_string()),
+            ("rotateManagerToken", self.rotate_manager_token.to_string()),
+            (
+                "rotateManagerUnlockKey",
+                self.rotate_manager_unlock_key.to_string(),
+            ),
+        ]))
+    }
+}
+
+/// Parameters used in the [Rotate Swarm CA
+/// helper](../struct.Docker.html#method.rotate_swarm_ca)
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::swarm::RotateSwarmCAOptions;
+///
+/// use std::time::Duration;
+///
+/// RotateSwarmCAOptions{
+///     interval: Duration::from_millis(500),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct RotateSwarmCAOptions {
+    /// The new signing CA certificate, in PEM format. If neither a certificate nor a key is given,
+    /// the swarm generates a new CA.
+    pub signing_ca_cert: Option<String>,
+    /// The new signing CA key, in PEM format.
+    pub signing_ca_key: Option<String>,
+    /// Interval at which the swarm is inspected while waiting for the rotation. Defaults to 1
+    /// second.
+    pub interval: Duration,
+    /// Time after which to stop waiting for the rotation. Defaults to 60 seconds.
+    pub timeout: Duration,
+}
+
+impl Default for RotateSwarmCAOptions {
+    fn default() -> Self {
+        RotateSwarmCAOptions {
+            signing_ca_cert: None,
+            signing_ca_key: None,
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Inspect Swarm
+    ///
+    /// Inspect the swarm that this node is a manager of.
+    ///
+    /// # Returns
+    ///
+    ///  - [Swarm](swarm/struct.Swarm.html), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.inspect_swarm();
+    /// ```
+    pub async fn inspect_swarm(&self) -> Result<Swarm, Error> {
+        let url = "/swarm";
+
+        let req = self.build_request::<_, String, String>(
+            url,
+            Builder::new().method(Method::GET),
+            Ok(None::<ArrayVec<[(_, _); 0]>>),
+            Ok(Body::empty()),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Update Swarm
+    ///
+    /// Update the configuration of the swarm. The spec replaces the current spec, so it should be
+    /// based on the spec returned by [Inspect Swarm](#method.inspect_swarm).
+    ///
+    /// # Arguments
+    ///
+    ///  - [SwarmSpec](swarm/struct.SwarmSpec.html) struct.
+    ///  - [UpdateSwarmOptions](swarm/struct.UpdateSwarmOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::UpdateSwarmOptions;
+    ///
+    /// let result = async move {
+    ///     let swarm = docker.inspect_swarm().await?;
+    ///     let options = UpdateSwarmOptions {
+    ///         version: swarm.version.index,
+    ///         rotate_worker_token: true,
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     docker.update_swarm(swarm.spec, options).await
+    /// };
+    /// ```
+    pub async fn update_swarm<T, K>(&self, spec: SwarmSpec, options: T) -> Result<(), Error>
+    where
+        T: UpdateSwarmQueryParams<K, String>,
+        K: AsRef<str>,
+    {
+        let url = "/swarm/update";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            Docker::transpose_option(Some(options.into_array())),
+            Docker::serialize_payload(Some(spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Rotate Swarm CA
+    ///
+    /// Rotate the root CA of the swarm, by updating the swarm with the given signing CA, or by
+    /// incrementing `ForceRotate` so that the swarm generates a new one. The swarm is then
+    /// inspected at the configured interval, until its certificate issuer changes or the timeout
+    /// elapses.
+    ///
+    /// The issuer is compared by subject and public key, as a CA generated by the swarm keeps the
+    /// `swarm-ca` subject. Waiting can be cancelled by dropping the returned future. Nodes that
+    /// still hold certificates from the previous issuer can be found through the `TLSInfo` of
+    /// [List Nodes](#method.list_nodes).
+    ///
+    /// # Arguments
+    ///
+    ///  - [RotateSwarmCAOptions](swarm/struct.RotateSwarmCAOptions.html) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - The [Swarm](swarm/struct.Swarm.html) with its new issuer, wrapped in a Future. A
+    ///  [Swarm CA Rotation Timeout
+    ///  Error](errors/enum.ErrorKind.html#variant.SwarmCARotationTimeoutError) is returned if
+    ///  the issuer has not changed within the timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::swarm::RotateSwarmCAOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// docker.rotate_swarm_ca(RotateSwarmCAOptions::default());
+    /// ```
+    pub async fn rotate_swarm_ca(&self, options: RotateSwarmCAOptions) -> Result<Swarm, Error> {
+        let swarm = self.inspect_swarm().await?;
+        let issuer = swarm.tls_info;
+
+        let mut spec = swarm.spec;
+        let ca_config = spec.ca_config.get_or_insert_with(Default::default);
+        if options.signing_ca_cert.is_none() && options.signing_ca_key.is_none() {
+            ca_config.force_rotate = Some(ca_config.force_rotate.unwrap_or(0) + 1);
+        }
+        ca_config.signing_ca_cert = options.signing_ca_cert;
+        ca_config.signing_ca_key = options.signing_ca_key;
+
+        self.update_swarm(
+            spec,
+            UpdateSwarmOptions {
+                version: swarm.version.index,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let issuer = &issuer;
+        let timeout = options.timeout;
+        let deadline = Instant::now() + timeout;
+        self.poll_until(deadline, options.interval, || async move {
+            let swarm = self.inspect_swarm().await?;
+            if swarm.tls_info.cert_issuer_subject != issuer.cert_issuer_subject
+                || swarm.tls_info.cert_issuer_public_key != issuer.cert_issuer_public_key
+            {
+                Ok(Ok(swarm))
+            } else {
+                Ok(Err(()))
+            }
+        })
+        .await?
+        .map_err(|()| SwarmCARotationTimeoutError { timeout }.into())
+    }
+}
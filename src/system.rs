@@ -4,17 +4,22 @@ use arrayvec::ArrayVec;
 use chrono::serde::{ts_nanoseconds, ts_seconds};
 use chrono::{DateTime, Utc};
 use futures_core::Stream;
+use futures_util::stream::{self, StreamExt};
 use http::request::Builder;
 use hyper::{Body, Method};
 
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::pin::Pin;
+use std::time::Duration;
 
 use super::Docker;
 use crate::container::APIContainers;
+use crate::docker::serialize_timestamp;
 use crate::errors::Error;
 use crate::errors::ErrorKind::JsonSerializeError;
 use crate::image::APIImages;
+use crate::retry::{is_stream_idle, is_transient};
 
 /// Result type for the [Version API](../struct.Docker.html#method.version)
 #[derive(Debug, Serialize, Deserialize)]
@@ -93,8 +98,8 @@ where
 {
     fn into_array(self) -> Result<ArrayVec<[(&'a str, String); 3]>, Error> {
         Ok(ArrayVec::from([
-            ("since", format!("{}.{}", self.since.timestamp(), self.since.timestamp_subsec_nanos())),
-            ("until", format!("{}.{}", self.until.timestamp(), self.until.timestamp_subsec_nanos())),
+            ("since", serialize_timestamp(&self.since)),
+            ("until", serialize_timestamp(&self.until)),
             (
                 "filters",
                 serde_json::to_string(&self.filters).map_err(|e| JsonSerializeError { err: e })?,
@@ -133,10 +138,12 @@ pub struct EventsResults {
 /// Volumes returned in the [Df API](../struct.Docker.html#method.df)
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[allow(missing_docs)]
 pub struct DfVolumesUsageDataResults {
-    pub size: u64,
-    pub ref_count: usize,
+    /// Amount of disk space used by the volume, in bytes, or `-1` if the volume driver does not
+    /// report it.
+    pub size: i64,
+    /// The number of containers referencing the volume, or `-1` if it is not available.
+    pub ref_count: i64,
 }
 
 /// Volumes returned in the [Df API](../struct.Docker.html#method.df)
@@ -150,7 +157,7 @@ pub struct DfVolumesResults {
     pub labels: Option<HashMap<String, String>>,
     pub scope: String,
     pub options: Option<HashMap<String, String>>,
-    pub usage_data: DfVolumesUsageDataResults,
+    pub usage_data: Option<DfVolumesUsageDataResults>,
 }
 
 /// Result type for the [Df API](../struct.Docker.html#method.df)
@@ -222,7 +229,7 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_value(req).await
+        self.process_into_string(req).await
     }
 
     /// ---
@@ -270,7 +277,66 @@ impl Docker {
             Ok(Body::empty()),
         );
 
-        self.process_into_stream(req)
+        self.process_into_followed_stream(req, true)
+    }
+
+    /// ---
+    ///
+    /// # Events Reconnecting
+    ///
+    /// Stream real-time events like the [Events API](struct.Docker.html#method.events), and
+    /// subscribe again when the stream is interrupted, e.g. by a restart of the docker server, a
+    /// dropped connection or the [stream idle
+    /// timeout](struct.Docker.html#method.with_stream_idle_timeout) of the client, with the events
+    /// since the last one received.
+    ///
+    /// The stream ends only on an error returned by the docker server, without retrying.
+    ///
+    /// # Arguments
+    ///
+    ///  - Filters of the events, as in [Events Options](system/struct.EventsOptions.html).
+    ///
+    /// # Returns
+    ///
+    ///  - [Events Results](system/struct.EventsResults.html), wrapped in a
+    ///  Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("type", vec!["container"]);
+    ///
+    /// docker.events_reconnecting(filters);
+    /// ```
+    pub fn events_reconnecting<T>(
+        &self,
+        filters: HashMap<T, Vec<T>>,
+    ) -> impl Stream<Item = Result<EventsResults, Error>>
+    where
+        T: AsRef<str> + Eq + Hash,
+    {
+        let filters = filters
+            .iter()
+            .map(|(key, values)| {
+                let values = values.iter().map(|v| v.as_ref().to_owned()).collect();
+                (key.as_ref().to_owned(), values)
+            })
+            .collect();
+        let mut state = ReconnectingEvents {
+            docker: self.clone(),
+            filters,
+            since: None,
+            stream: Box::pin(stream::empty()),
+            done: false,
+        };
+        state.subscribe();
+
+        stream::unfold(state, ReconnectingEvents::next)
     }
 
     /// ---
@@ -304,3 +370,85 @@ impl Docker {
         self.process_into_value(req).await
     }
 }
+
+/// Interval between two attempts to reach the docker server, while reconnecting an events stream.
+const EVENTS_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of the stream returned by the [Events Reconnecting
+/// API](../struct.Docker.html#method.events_reconnecting).
+struct ReconnectingEvents {
+    docker: Docker,
+    filters: HashMap<String, Vec<String>>,
+    // the time to subscribe again from
+    since: Option<DateTime<Utc>>,
+    stream: Pin<Box<dyn Stream<Item = Result<EventsResults, Error>> + Send>>,
+    done: bool,
+}
+
+impl ReconnectingEvents {
+    fn subscribe(&mut self) {
+        let since = self.since;
+        // until an event is received, subscribe again from the local time of the first subscription
+        self.since.get_or_insert_with(Utc::now);
+
+        let query = serde_json::to_string(&self.filters)
+            .map(|filters| {
+                let mut query = vec![("filters", filters)];
+                if let Some(since) = since {
+                    query.push(("since", serialize_timestamp(&since)));
+                }
+                Some(query)
+            })
+            .map_err(|e| JsonSerializeError { err: e }.into());
+
+        let req = self.docker.build_request(
+            "/events",
+            Builder::new().method(Method::GET),
+            query,
+            Ok(Body::empty()),
+        );
+
+        self.stream = Box::pin(self.docker.process_into_followed_stream(req, true));
+    }
+
+    /// Wait until the docker server is reachable.
+    async fn reachable(&self) -> Result<(), Error> {
+        loop {
+            self.docker.runtime.delay(EVENTS_RECONNECT_INTERVAL).await;
+            match self.docker.ping().await {
+                Ok(_) => return Ok(()),
+                Err(e) if is_transient(&e) => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn next(mut self) -> Option<(Result<EventsResults, Error>, ReconnectingEvents)> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            let reachable = match self.stream.next().await {
+                Some(Ok(event)) => {
+                    // the docker server includes the events at the time given by `since`
+                    self.since = Some(event.time_nano + chrono::Duration::nanoseconds(1));
+                    return Some((Ok(event), self));
+                }
+                Some(Err(ref e)) if is_stream_idle(e) => Ok(()),
+                // the connection dropped, or the docker server shut down
+                Some(Err(ref e)) if is_transient(e) => self.reachable().await,
+                None => self.reachable().await,
+                Some(Err(e)) => Err(e),
+            };
+
+            match reachable {
+                Ok(()) => self.subscribe(),
+                Err(e) => {
+                    self.done = true;
+                    return Some((Err(e), self));
+                }
+            }
+        }
+    }
+}
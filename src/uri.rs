@@ -1,6 +1,7 @@
-#[cfg(windows)]
+#[cfg(all(windows, feature = "tokio"))]
 use hex::FromHex;
 use hyper::Uri as HyperUri;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use url::Url;
 
 use std::borrow::Cow;
@@ -9,6 +10,25 @@ use std::ffi::OsStr;
 use crate::docker::{ClientType, ClientVersion};
 use crate::errors::Error;
 
+/// The characters escaped in a segment of a request path, beyond those the url parser escapes.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Escape a user-provided value, such as a network name, for a segment of a request path.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
 #[derive(Debug)]
 pub struct Uri<'a> {
     encoded: Cow<'a, str>,
@@ -43,7 +63,6 @@ impl<'a> Uri<'a> {
             path
         );
         let mut url = Url::parse(host_str.as_ref()).unwrap();
-        url = url.join(path).unwrap();
 
         if let Some(pairs) = query {
             url.query_pairs_mut().extend_pairs(pairs);
@@ -66,28 +85,32 @@ impl<'a> Uri<'a> {
     {
         match client_type {
             ClientType::Http => socket.as_ref().to_string_lossy().into_owned(),
-            #[cfg(any(feature = "ssl", feature = "tls"))]
+            #[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
             ClientType::SSL => socket.as_ref().to_string_lossy().into_owned(),
-            #[cfg(unix)]
+            #[cfg(all(unix, feature = "tokio"))]
             ClientType::Unix => hex::encode(socket.as_ref().to_string_lossy().as_bytes()),
-            #[cfg(windows)]
+            #[cfg(all(windows, feature = "tokio"))]
             ClientType::NamedPipe => hex::encode(socket.as_ref().to_string_lossy().as_bytes()),
+            #[cfg(feature = "ssh")]
+            ClientType::Ssh => socket.as_ref().to_string_lossy().into_owned(),
         }
     }
 
     fn socket_scheme(client_type: &ClientType) -> &'a str {
         match client_type {
             ClientType::Http => "http",
-            #[cfg(any(feature = "ssl", feature = "tls"))]
+            #[cfg(any(feature = "ssl", feature = "tls", feature = "rustls"))]
             ClientType::SSL => "https",
-            #[cfg(unix)]
+            #[cfg(all(unix, feature = "tokio"))]
             ClientType::Unix => "unix",
-            #[cfg(windows)]
+            #[cfg(all(windows, feature = "tokio"))]
             ClientType::NamedPipe => "net.pipe",
+            #[cfg(feature = "ssh")]
+            ClientType::Ssh => "http",
         }
     }
 
-    #[cfg(windows)]
+    #[cfg(all(windows, feature = "tokio"))]
     fn socket_path(uri: &HyperUri) -> Option<String> {
         uri.host()
             .iter()
@@ -99,7 +122,7 @@ impl<'a> Uri<'a> {
             .next()
     }
 
-    #[cfg(windows)]
+    #[cfg(all(windows, feature = "tokio"))]
     pub(crate) fn socket_path_dest(dest: &HyperUri, client_type: &ClientType) -> Option<String> {
         format!("{}://{}", Uri::socket_scheme(client_type), dest.host().unwrap_or("UNKNOWN_HOST"))
             .parse()
@@ -12,9 +12,17 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use super::Docker;
-use crate::docker::{FALSE_STR, TRUE_STR};
+use crate::docker::{deserialize_nonoptional_vec, ClientVersion, FALSE_STR, TRUE_STR};
 use crate::errors::Error;
 use crate::errors::ErrorKind::JsonSerializeError;
+use crate::system::DfVolumesResults;
+
+/// The API version from which the [Df API](../struct.Docker.html#method.df) accepts a `type`
+/// parameter.
+const DF_TYPE_MINIMUM_VERSION: ClientVersion = ClientVersion {
+    major_version: 1,
+    minor_version: 42,
+};
 
 /// Subresult type for the [List Volumes API](../struct.Docker.html#method.list_volumes)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -205,6 +213,25 @@ pub struct PruneVolumesResults {
     pub space_reclaimed: u64,
 }
 
+/// Disk usage of a volume, returned by the [Volume Usage
+/// helper](../struct.Docker.html#method.volume_usage)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageData {
+    /// Amount of disk space used by the volume, in bytes.
+    pub size: u64,
+    /// The number of containers referencing the volume.
+    pub ref_count: u64,
+}
+
+/// The volumes of a [Df API](../struct.Docker.html#method.df) result, which may be the only
+/// field populated when filtered by type.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct VolumesDfResults {
+    #[serde(default, deserialize_with = "deserialize_nonoptional_vec")]
+    volumes: Vec<DfVolumesResults>,
+}
+
 impl Docker {
     /// ---
     ///
@@ -439,4 +466,68 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Volume Usage
+    ///
+    /// Get the disk usage of the given volumes, from the [Df API](#method.df). On API version
+    /// 1.42 and above, the daemon is asked to compute the usage of volumes only, which is much
+    /// cheaper on hosts with many images.
+    ///
+    /// # Arguments
+    ///
+    ///  - Volume names as a slice of string slices.
+    ///
+    /// # Returns
+    ///
+    ///  - A map from each of the given volume names to its
+    ///  [Usage Data](volume/struct.UsageData.html), or `None` if the volume is missing from the
+    ///  result or its driver does not report usage, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.volume_usage(&["my_volume"]);
+    /// ```
+    pub async fn volume_usage(
+        &self,
+        names: &[&str],
+    ) -> Result<HashMap<String, Option<UsageData>>, Error> {
+        let url = "/system/df";
+
+        let query = if self.client_version() >= DF_TYPE_MINIMUM_VERSION {
+            Some(ArrayVec::from([("type", "volume")]))
+        } else {
+            None
+        };
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            Ok(query),
+            Ok(Body::empty()),
+        );
+
+        let results: VolumesDfResults = self.process_into_value(req).await?;
+
+        let mut usage: HashMap<String, Option<UsageData>> =
+            names.iter().map(|name| (name.to_string(), None)).collect();
+        for volume in results.volumes {
+            if let Some(entry) = usage.get_mut(&volume.name) {
+                *entry = volume
+                    .usage_data
+                    .filter(|data| data.size >= 0 && data.ref_count >= 0)
+                    .map(|data| UsageData {
+                        size: data.size as u64,
+                        ref_count: data.ref_count as u64,
+                    });
+            }
+        }
+
+        Ok(usage)
+    }
 }
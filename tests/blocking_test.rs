@@ -0,0 +1,60 @@
+#![cfg(feature = "blocking")]
+use bollard::blocking::Docker;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use tokio::runtime::Runtime;
+
+use std::sync::mpsc;
+use std::thread;
+
+// Runs a local HTTP server on its own runtime, answering pings and image exports.
+fn mock_server() -> std::net::SocketAddr {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut rt = Runtime::new().unwrap();
+        rt.block_on(async move {
+            let make_service = make_service_fn(|_| async {
+                Ok::<_, hyper::Error>(service_fn(|req: Request<Body>| async move {
+                    let body = if req.uri().path().ends_with("/get") {
+                        Body::wrap_stream(futures_util::stream::iter(vec![
+                            Ok::<_, std::io::Error>("hello "),
+                            Ok("world"),
+                        ]))
+                    } else {
+                        Body::from("OK")
+                    };
+                    Ok::<_, hyper::Error>(Response::new(body))
+                }))
+            });
+            let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+            tx.send(server.local_addr()).unwrap();
+            server.await.unwrap();
+        });
+    });
+    rx.recv().unwrap()
+}
+
+#[test]
+fn blocking_client() {
+    let addr = mock_server();
+    let docker = Docker::new(
+        bollard::Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION)
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(docker.ping().unwrap(), "OK");
+    assert_eq!(docker.block_on(docker.inner().ping()).unwrap(), "OK");
+
+    let exported: Vec<u8> = docker
+        .export_image("hello-world")
+        .map(|chunk| chunk.unwrap().to_vec())
+        .flatten()
+        .collect();
+    assert_eq!(exported, b"hello world");
+
+    // clones share the runtime of the client
+    let clone = docker.clone();
+    thread::spawn(move || clone.ping().unwrap()).join().unwrap();
+}
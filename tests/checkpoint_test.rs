@@ -0,0 +1,98 @@
+#![cfg(feature = "experimental")]
+extern crate bollard;
+extern crate hyper;
+extern crate tokio;
+
+use bollard::checkpoint::*;
+
+use tokio::runtime::Runtime;
+
+#[macro_use]
+pub mod common;
+use crate::common::*;
+
+#[test]
+fn create_checkpoint_payload() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.method(), "POST");
+            assert!(req.uri().path().ends_with("/containers/web/checkpoints"));
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body,
+                serde_json::json!({"CheckpointID": "before-upgrade", "Exit": true})
+            );
+            hyper::Response::builder()
+                .status(201)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        docker
+            .create_checkpoint(
+                "web",
+                CreateCheckpointOptions {
+                    checkpoint_id: "before-upgrade",
+                    exit: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+    });
+}
+
+#[test]
+fn list_and_delete_checkpoints() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.uri().query(), Some("dir=%2Fvar%2Flib%2Fcheckpoints"));
+            if req.method() == "DELETE" {
+                assert!(req
+                    .uri()
+                    .path()
+                    .ends_with("/containers/web/checkpoints/before-upgrade"));
+                hyper::Response::builder()
+                    .status(204)
+                    .body(hyper::Body::empty())
+                    .unwrap()
+            } else {
+                assert!(req.uri().path().ends_with("/containers/web/checkpoints"));
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(
+                        r#"[{"Name": "before-upgrade"}, {"Name": "nightly"}]"#,
+                    ))
+                    .unwrap()
+            }
+        });
+        let options = CheckpointDirOptions {
+            dir: "/var/lib/checkpoints",
+        };
+
+        let checkpoints = docker
+            .list_checkpoints("web", Some(options.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            checkpoints,
+            vec![
+                Checkpoint {
+                    name: String::from("before-upgrade")
+                },
+                Checkpoint {
+                    name: String::from("nightly")
+                },
+            ]
+        );
+
+        docker
+            .delete_checkpoint("web", "before-upgrade", Some(options))
+            .await
+            .unwrap();
+    });
+}
@@ -1,6 +1,8 @@
 use bytes::Bytes;
 use futures_core::Stream;
 use futures_util::stream::TryStreamExt;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
 use std::future::Future;
 use tokio::runtime::Runtime;
 
@@ -10,7 +12,7 @@ use bollard::auth::DockerCredentials;
 use bollard::container::*;
 use bollard::errors::Error;
 use bollard::image::*;
-use bollard::Docker;
+use bollard::{ClientVersion, Docker, API_DEFAULT_VERSION};
 
 #[allow(unused_macros)]
 macro_rules! rt_exec {
@@ -99,6 +101,55 @@ where
         .unwrap();
 }
 
+/// Bind a local HTTP server that answers every request with the given status code and body, and
+/// return a client connected to it. Must be called within a runtime.
+#[allow(dead_code)]
+pub fn mock_docker(status: u16, body: &'static str) -> Docker {
+    mock_docker_fn(move |_| async move {
+        Response::builder()
+            .status(status)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    })
+}
+
+/// Bind a local HTTP server that answers every request with the given handler, and return a
+/// client connected to it. Must be called within a runtime.
+#[allow(dead_code)]
+pub fn mock_docker_fn<F, R>(handler: F) -> Docker
+where
+    F: Fn(Request<Body>) -> R + Clone + Send + Sync + 'static,
+    R: Future<Output = Response<Body>> + Send + 'static,
+{
+    mock_docker_fn_with_version(handler, API_DEFAULT_VERSION)
+}
+
+/// Bind a local HTTP server that answers every request with the given handler, and return a
+/// client connected to it using the given API version. Must be called within a runtime.
+#[allow(dead_code)]
+pub fn mock_docker_fn_with_version<F, R>(handler: F, version: &ClientVersion) -> Docker
+where
+    F: Fn(Request<Body>) -> R + Clone + Send + Sync + 'static,
+    R: Future<Output = Response<Body>> + Send + 'static,
+{
+    let make_service = make_service_fn(move |_| {
+        let handler = handler.clone();
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |req| {
+                let response = handler(req);
+                async move { Ok::<_, hyper::Error>(response.await) }
+            }))
+        }
+    });
+
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+
+    Docker::connect_with_http(&addr.to_string(), 10, version).unwrap()
+}
+
 #[allow(dead_code)]
 pub async fn create_container_hello_world(
     docker: &Docker,
@@ -130,7 +181,7 @@ pub async fn create_container_hello_world(
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .try_collect::<Vec<_>>()
@@ -140,6 +191,7 @@ pub async fn create_container_hello_world(
         .create_container(
             Some(CreateContainerOptions {
                 name: container_name.to_string(),
+                ..Default::default()
             }),
             Config {
                 cmd: cmd,
@@ -200,7 +252,7 @@ pub async fn create_daemon(docker: &Docker, container_name: &'static str) -> Res
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .try_collect::<Vec<_>>()
@@ -210,6 +262,7 @@ pub async fn create_daemon(docker: &Docker, container_name: &'static str) -> Res
         .create_container(
             Some(CreateContainerOptions {
                 name: container_name,
+                ..Default::default()
             }),
             Config {
                 cmd: cmd,
@@ -264,7 +317,7 @@ pub async fn create_image_hello_world(docker: &Docker) -> Result<(), Error> {
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .try_collect::<Vec<_>>()
@@ -285,11 +338,9 @@ pub async fn concat_byte_stream<S>(s: S) -> Result<Vec<u8>, Error>
 where
     S: Stream<Item = Result<Bytes, Error>>,
 {
-    s.try_fold(Vec::new(), |mut acc, chunk| {
-        async move {
-            acc.extend_from_slice(&chunk[..]);
-            Ok(acc)
-        }
+    s.try_fold(Vec::new(), |mut acc, chunk| async move {
+        acc.extend_from_slice(&chunk[..]);
+        Ok(acc)
     })
     .await
 }
@@ -1,14 +1,19 @@
 #![type_length_limit = "2097152"]
 
 use bollard::container::*;
-use bollard::errors::Error;
+use bollard::errors::{Error, ErrorKind};
 use bollard::image::*;
 use bollard::Docker;
 
 use futures_util::stream::TryStreamExt;
 use tokio::runtime::Runtime;
 
+use std::collections::HashMap;
 use std::io::Write;
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[macro_use]
 pub mod common;
@@ -59,7 +64,7 @@ async fn image_push_test(docker: Docker) -> Result<(), Error> {
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .try_collect::<Vec<_>>()
@@ -82,7 +87,7 @@ async fn image_push_test(docker: Docker) -> Result<(), Error> {
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .await?;
@@ -227,7 +232,10 @@ async fn stats_test(docker: Docker) -> Result<(), Error> {
     let vec = &docker
         .stats(
             "integration_test_stats",
-            Some(StatsOptions { stream: false }),
+            Some(StatsOptions {
+                stream: false,
+                ..Default::default()
+            }),
         )
         .try_collect::<Vec<_>>()
         .await?;
@@ -351,6 +359,16 @@ async fn pause_container_test(docker: Docker) -> Result<(), Error> {
 
     assert_eq!("paused".to_string(), result.state.status);
 
+    match docker
+        .pause_container("integration_test_pause_container")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::ContainerAlreadyPausedError { .. } => (),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
     &docker
         .unpause_container("integration_test_pause_container")
         .await?;
@@ -364,7 +382,29 @@ async fn pause_container_test(docker: Docker) -> Result<(), Error> {
 
     assert_eq!("running".to_string(), result.state.status);
 
-    kill_container(&docker, "integration_test_pause_container").await?;
+    match docker
+        .unpause_container("integration_test_pause_container")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::ContainerNotPausedError { .. } => (),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    &docker
+        .stop_container(
+            "integration_test_pause_container",
+            None::<StopContainerOptions>,
+        )
+        .await?;
+
+    &docker
+        .remove_container(
+            "integration_test_pause_container",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
 
     Ok(())
 }
@@ -429,7 +469,7 @@ async fn archive_container_test(docker: Docker) -> Result<(), Error> {
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .try_collect::<Vec<_>>()
@@ -439,6 +479,7 @@ async fn archive_container_test(docker: Docker) -> Result<(), Error> {
         .create_container(
             Some(CreateContainerOptions {
                 name: "integration_test_archive_container",
+                ..Default::default()
             }),
             Config {
                 image: Some(&image[..]),
@@ -569,7 +610,7 @@ async fn mount_volume_container_test(docker: Docker) -> Result<(), Error> {
         if cfg!(windows) {
             None
         } else {
-            Some(integration_test_registry_credentials())
+            Some(integration_test_registry_credentials().into())
         },
     );
 
@@ -577,6 +618,7 @@ async fn mount_volume_container_test(docker: Docker) -> Result<(), Error> {
         .create_container(
             Some(CreateContainerOptions {
                 name: "integration_test_mount_volume_container",
+                ..Default::default()
             }),
             Config {
                 image: Some(&image[..]),
@@ -651,11 +693,85 @@ fn integration_test_container_changes() {
     connect_to_docker_and_run!(container_changes_test);
 }
 
+#[cfg(not(windows))]
+async fn published_ports_test(docker: Docker) -> Result<(), Error> {
+    let image = format!("{}fussybeaver/uhttpd", registry_http_addr());
+
+    &docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_image: &image[..],
+                ..Default::default()
+            }),
+            None,
+            Some(integration_test_registry_credentials().into()),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut exposed_ports = HashMap::new();
+    exposed_ports.insert("80/tcp", HashMap::new());
+    let mut port_bindings = HashMap::new();
+    port_bindings.insert(
+        "80/tcp",
+        vec![PortBinding {
+            host_ip: "",
+            host_port: "0",
+        }],
+    );
+
+    &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_published_ports",
+                ..Default::default()
+            }),
+            Config {
+                cmd: Some(vec!["/usr/sbin/run_uhttpd", "-f", "-p", "80", "-h", "/www"]),
+                image: Some(&image[..]),
+                exposed_ports: Some(exposed_ports),
+                host_config: Some(HostConfig {
+                    port_bindings: Some(port_bindings),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    &docker
+        .start_container(
+            "integration_test_published_ports",
+            None::<StartContainerOptions<String>>,
+        )
+        .await?;
+
+    let ports = docker
+        .wait_for_published_ports("integration_test_published_ports", Duration::from_secs(5))
+        .await?;
+
+    let address = ports["80/tcp"][0];
+    assert_ne!(address.port(), 0);
+    TcpStream::connect(("localhost", address.port())).unwrap();
+
+    kill_container(&docker, "integration_test_published_ports")
+        .await
+        .unwrap_or(());
+
+    Ok(())
+}
+
 #[test]
 fn integration_test_stats() {
     connect_to_docker_and_run!(stats_test);
 }
 
+#[test]
+#[cfg(not(windows))]
+fn integration_test_published_ports() {
+    connect_to_docker_and_run!(published_ports_test);
+}
+
 #[test]
 fn integration_test_kill_container() {
     connect_to_docker_and_run!(kill_container_test);
@@ -699,3 +815,2600 @@ fn integration_test_inspect_containers() {
 fn integration_test_mount_volume_containers() {
     connect_to_docker_and_run!(mount_volume_container_test);
 }
+
+#[test]
+fn create_container_results_warnings() {
+    let fixture = r#"{
+        "Id": "e90e34656806",
+        "Warnings": [
+            "Your kernel does not support swap limit capabilities or the cgroup is not mounted. Memory limited without swap.",
+            "Your kernel does not support OomKillDisable. OomKillDisable discarded."
+        ]
+    }"#;
+
+    let result: CreateContainerResults = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(result.id, "e90e34656806");
+    assert_eq!(result.warnings.len(), 2);
+    assert!(result.warnings[1].contains("OomKillDisable"));
+
+    let fixture = r#"{"Id": "e90e34656806", "Warnings": null}"#;
+    let result: CreateContainerResults = serde_json::from_str(fixture).unwrap();
+
+    assert!(result.warnings.is_empty());
+}
+
+#[test]
+fn update_container_sends_only_set_fields() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body,
+                serde_json::json!({
+                    "CpuQuota": -1,
+                    "NanoCpus": 500000000,
+                    "PidsLimit": 100,
+                    "RestartPolicy": {"Name": "on-failure", "MaximumRetryCount": 3},
+                })
+            );
+            hyper::Response::builder()
+                .status(200)
+                .body(hyper::Body::from(r#"{"Warnings":null}"#))
+                .unwrap()
+        });
+
+        let result = docker
+            .update_container(
+                "web",
+                UpdateContainerOptions {
+                    cpu_quota: Some(-1),
+                    nano_cpus: Some(500_000_000),
+                    pids_limit: Some(100),
+                    restart_policy: Some(RestartPolicy::OnFailure { max_retries: 3 }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(result.warnings.is_empty());
+    });
+}
+
+#[test]
+fn log_config_wire_format() {
+    let log_config = LogConfig::new(LogDriver::JsonFile)
+        .max_size(10485760)
+        .max_file(3)
+        .compress(true)
+        .tag("{{.Name}}");
+    assert_eq!(
+        serde_json::to_value(&log_config).unwrap(),
+        serde_json::json!({
+            "Type": "json-file",
+            "Config": {
+                "max-size": "10485760",
+                "max-file": "3",
+                "compress": "true",
+                "tag": "{{.Name}}",
+            }
+        })
+    );
+
+    let log_config: LogConfig =
+        serde_json::from_str(r#"{"Type": "grafana/loki-docker-driver:latest", "Config": {}}"#)
+            .unwrap();
+    assert_eq!(
+        log_config.type_,
+        Some(LogDriver::Other(String::from(
+            "grafana/loki-docker-driver:latest"
+        )))
+    );
+
+    let log_config: LogConfig =
+        serde_json::from_str(r#"{"Type": "journald", "Config": null}"#).unwrap();
+    assert_eq!(log_config.type_, Some(LogDriver::Journald));
+    assert_eq!(log_config.config, None);
+}
+
+#[test]
+fn restart_policy_wire_format() {
+    let policies: Vec<RestartPolicy> = serde_json::from_str(
+        r#"[
+            {"Name": "", "MaximumRetryCount": 0},
+            {"Name": "no", "MaximumRetryCount": 0},
+            {"Name": "always", "MaximumRetryCount": 0},
+            {"Name": "unless-stopped", "MaximumRetryCount": 0},
+            {"Name": "on-failure", "MaximumRetryCount": 5}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        policies,
+        vec![
+            RestartPolicy::No,
+            RestartPolicy::No,
+            RestartPolicy::Always,
+            RestartPolicy::UnlessStopped,
+            RestartPolicy::OnFailure { max_retries: 5 },
+        ]
+    );
+
+    assert_eq!(
+        serde_json::to_value(RestartPolicy::UnlessStopped).unwrap(),
+        serde_json::json!({"Name": "unless-stopped", "MaximumRetryCount": 0})
+    );
+    assert_eq!(
+        serde_json::to_value(RestartPolicy::default()).unwrap(),
+        serde_json::json!({"Name": "no", "MaximumRetryCount": 0})
+    );
+}
+
+#[test]
+fn update_container_results_warnings() {
+    let fixture = r#"{
+        "Warnings": [
+            "Your kernel does not support swap limit capabilities or the cgroup is not mounted. Memory limited without swap.",
+            "Your kernel does not support CPU realtime scheduler"
+        ]
+    }"#;
+
+    let result: UpdateContainerResults = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(result.warnings.len(), 2);
+
+    let result: UpdateContainerResults = serde_json::from_str("{}").unwrap();
+
+    assert!(result.warnings.is_empty());
+}
+
+#[test]
+fn stats_windows_fixture() {
+    let fixture = r#"{
+        "read": "2020-04-14T10:21:32.4217393Z",
+        "preread": "2020-04-14T10:21:31.4103591Z",
+        "pids_stats": {},
+        "blkio_stats": {
+            "io_service_bytes_recursive": null,
+            "io_serviced_recursive": null,
+            "io_queue_recursive": null,
+            "io_service_time_recursive": null,
+            "io_wait_time_recursive": null,
+            "io_merged_recursive": null,
+            "io_time_recursive": null,
+            "sectors_recursive": null
+        },
+        "num_procs": 2,
+        "storage_stats": {
+            "read_count_normalized": 7665,
+            "read_size_bytes": 136527872,
+            "write_count_normalized": 3426,
+            "write_size_bytes": 23515136
+        },
+        "cpu_stats": {
+            "cpu_usage": {
+                "total_usage": 58906250,
+                "usage_in_kernelmode": 40625000,
+                "usage_in_usermode": 18281250
+            },
+            "throttling_data": {
+                "periods": 0,
+                "throttled_periods": 0,
+                "throttled_time": 0
+            }
+        },
+        "precpu_stats": {
+            "cpu_usage": {
+                "total_usage": 58750000,
+                "usage_in_kernelmode": 40468750,
+                "usage_in_usermode": 18281250
+            },
+            "throttling_data": {
+                "periods": 0,
+                "throttled_periods": 0,
+                "throttled_time": 0
+            }
+        },
+        "memory_stats": {
+            "commitbytes": 77803520,
+            "commitpeakbytes": 94912512,
+            "privateworkingset": 64327680
+        },
+        "name": "/integration_test_stats",
+        "id": "8c0e7c8d5e2f84d0c9a2a5b6f2f5e3f0b1c7a8d6e9f4b3a2c1d0e9f8a7b6c5d4",
+        "networks": {
+            "a5f0c3e1-2b7d-4c9e-8f6a-1d2b3c4e5f60": {
+                "rx_bytes": 3526,
+                "rx_packets": 21,
+                "rx_errors": 0,
+                "rx_dropped": 0,
+                "tx_bytes": 1432,
+                "tx_packets": 14,
+                "tx_errors": 0,
+                "tx_dropped": 0
+            }
+        }
+    }"#;
+
+    let stats: Stats = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(stats.num_procs, 2);
+    assert_eq!(stats.storage_stats.read_count_normalized, Some(7665));
+    assert_eq!(stats.memory_stats.commitbytes, Some(77803520));
+    assert_eq!(stats.memory_stats.usage, None);
+    assert!(stats.precpu_stats.system_cpu_usage.is_none());
+
+    assert!(stats.cpu_percentage().unwrap() > 0.0);
+    assert_eq!(stats.memory_usage(), Some(64327680));
+    assert_eq!(stats.memory_percentage(), None);
+}
+
+#[test]
+fn stats_partial_fixture() {
+    // a Windows daemon omitting the previous sample, the memory statistics and the error counters
+    // of a network endpoint
+    let fixture = r#"{
+        "read": "2020-04-14T10:21:31.4103591Z",
+        "preread": "0001-01-01T00:00:00Z",
+        "num_procs": 4,
+        "cpu_stats": {
+            "cpu_usage": {
+                "total_usage": 1562500
+            }
+        },
+        "name": "/integration_test_stats",
+        "id": "8c0e7c8d5e2f",
+        "networks": {
+            "a5f0c3e1-2b7d-4c9e-8f6a-1d2b3c4e5f60": {
+                "rx_bytes": 3526,
+                "rx_packets": 21,
+                "tx_bytes": 1432,
+                "tx_packets": 14,
+                "endpoint_id": "a5f0c3e1-2b7d-4c9e-8f6a-1d2b3c4e5f60",
+                "instance_id": "f2e1d0c9-b8a7-4f6e-9d5c-4b3a2f1e0d9c"
+            }
+        }
+    }"#;
+
+    let stats: Stats = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(stats.cpu_stats.cpu_usage.total_usage, 1562500);
+    assert_eq!(stats.cpu_stats.cpu_usage.usage_in_kernelmode, 0);
+    assert_eq!(stats.precpu_stats.cpu_usage.total_usage, 0);
+    assert_eq!(stats.memory_stats.usage, None);
+    assert!(stats.blkio_stats.io_service_bytes_recursive.is_none());
+    let network = &stats.networks.as_ref().unwrap()["a5f0c3e1-2b7d-4c9e-8f6a-1d2b3c4e5f60"];
+    assert_eq!(network.rx_bytes, 3526);
+    assert_eq!(network.rx_errors, 0);
+
+    assert_eq!(stats.cpu_percentage(), None);
+}
+
+#[test]
+fn stats_windows_first_sample_fixture() {
+    let fixture = r#"{
+        "read": "2020-04-14T10:21:31.4103591Z",
+        "preread": "0001-01-01T00:00:00Z",
+        "pids_stats": {},
+        "blkio_stats": {},
+        "num_procs": 2,
+        "storage_stats": {},
+        "cpu_stats": {
+            "cpu_usage": {
+                "total_usage": 58750000,
+                "usage_in_kernelmode": 40468750,
+                "usage_in_usermode": 18281250
+            },
+            "throttling_data": {
+                "periods": 0,
+                "throttled_periods": 0,
+                "throttled_time": 0
+            }
+        },
+        "precpu_stats": {
+            "cpu_usage": {
+                "total_usage": 0,
+                "usage_in_kernelmode": 0,
+                "usage_in_usermode": 0
+            },
+            "throttling_data": {
+                "periods": 0,
+                "throttled_periods": 0,
+                "throttled_time": 0
+            }
+        },
+        "memory_stats": {},
+        "name": "/integration_test_stats",
+        "id": "8c0e7c8d5e2f84d0c9a2a5b6f2f5e3f0b1c7a8d6e9f4b3a2c1d0e9f8a7b6c5d4"
+    }"#;
+
+    let stats: Stats = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(stats.cpu_percentage(), None);
+    assert_eq!(stats.memory_usage(), None);
+    assert_eq!(stats.memory_percentage(), None);
+}
+
+#[test]
+fn stats_cgroup_v1_fixture() {
+    let fixture = r#"{
+        "read": "2020-04-14T10:21:32.421739393Z",
+        "preread": "2020-04-14T10:21:31.410359163Z",
+        "pids_stats": {"current": 12},
+        "blkio_stats": {
+            "io_service_bytes_recursive": [
+                {"major": 8, "minor": 0, "op": "Read", "value": 4096000},
+                {"major": 8, "minor": 0, "op": "Write", "value": 8192},
+                {"major": 8, "minor": 0, "op": "Sync", "value": 4104192},
+                {"major": 8, "minor": 0, "op": "Async", "value": 0},
+                {"major": 8, "minor": 0, "op": "Total", "value": 4104192},
+                {"major": 8, "minor": 16, "op": "Read", "value": 1024},
+                {"major": 8, "minor": 16, "op": "Total", "value": 1024}
+            ],
+            "io_serviced_recursive": [
+                {"major": 8, "minor": 0, "op": "Read", "value": 92},
+                {"major": 8, "minor": 0, "op": "Write", "value": 2}
+            ],
+            "io_queue_recursive": [],
+            "io_service_time_recursive": [],
+            "io_wait_time_recursive": [],
+            "io_merged_recursive": [],
+            "io_time_recursive": [],
+            "sectors_recursive": []
+        },
+        "num_procs": 0,
+        "storage_stats": {},
+        "cpu_stats": {
+            "cpu_usage": {
+                "total_usage": 100215355,
+                "percpu_usage": [45718946, 54496409],
+                "usage_in_kernelmode": 30000000,
+                "usage_in_usermode": 50000000
+            },
+            "system_cpu_usage": 739306590000000,
+            "online_cpus": 2,
+            "throttling_data": {"periods": 0, "throttled_periods": 0, "throttled_time": 0}
+        },
+        "precpu_stats": {
+            "cpu_usage": {
+                "total_usage": 100093996,
+                "percpu_usage": [45597587, 54496409],
+                "usage_in_kernelmode": 30000000,
+                "usage_in_usermode": 50000000
+            },
+            "system_cpu_usage": 739304590000000,
+            "online_cpus": 2,
+            "throttling_data": {"periods": 0, "throttled_periods": 0, "throttled_time": 0}
+        },
+        "memory_stats": {
+            "usage": 6537216,
+            "max_usage": 6651904,
+            "stats": {
+                "active_anon": 6537216, "active_file": 0, "cache": 0, "dirty": 0,
+                "hierarchical_memory_limit": 67108864, "hierarchical_memsw_limit": 0,
+                "inactive_anon": 0, "inactive_file": 0, "mapped_file": 0, "pgfault": 964,
+                "pgmajfault": 0, "pgpgin": 477, "pgpgout": 414, "rss": 6537216, "rss_huge": 6291456,
+                "total_active_anon": 6537216, "total_active_file": 0, "total_cache": 0,
+                "total_dirty": 0, "total_inactive_anon": 0, "total_inactive_file": 0,
+                "total_mapped_file": 0, "total_pgfault": 964, "total_pgmajfault": 0,
+                "total_pgpgin": 477, "total_pgpgout": 414, "total_rss": 6537216,
+                "total_rss_huge": 6291456, "total_unevictable": 0, "total_writeback": 0,
+                "unevictable": 0, "writeback": 0
+            },
+            "failcnt": 0,
+            "limit": 67108864
+        },
+        "name": "/integration_test_stats",
+        "id": "b3e2c8d5e2f84d0c9a2a5b6f2f5e3f0b1c7a8d6e9f4b3a2c1d0e9f8a7b6c5d4"
+    }"#;
+
+    let stats: Stats = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(stats.pids_stats.current, Some(12));
+    assert_eq!(stats.pids_stats.limit, None);
+    assert_eq!(stats.blkio_stats.read_bytes_total(), 4097024);
+    assert_eq!(stats.blkio_stats.write_bytes_total(), 8192);
+}
+
+#[test]
+fn stats_cgroup_v2_fixture() {
+    let fixture = r#"{
+        "read": "2021-02-10T13:24:47.112418421Z",
+        "preread": "2021-02-10T13:24:46.107844064Z",
+        "pids_stats": {"current": 3, "limit": 4618},
+        "blkio_stats": {
+            "io_service_bytes_recursive": [
+                {"major": 259, "minor": 0, "op": "read", "value": 2134016},
+                {"major": 259, "minor": 0, "op": "write", "value": 4096}
+            ],
+            "io_serviced_recursive": null,
+            "io_queue_recursive": null,
+            "io_service_time_recursive": null,
+            "io_wait_time_recursive": null,
+            "io_merged_recursive": null,
+            "io_time_recursive": null,
+            "sectors_recursive": null
+        },
+        "num_procs": 0,
+        "storage_stats": {},
+        "cpu_stats": {
+            "cpu_usage": {
+                "total_usage": 48711000,
+                "usage_in_kernelmode": 13795000,
+                "usage_in_usermode": 34916000
+            },
+            "system_cpu_usage": 35243190000000,
+            "online_cpus": 4,
+            "throttling_data": {"periods": 0, "throttled_periods": 0, "throttled_time": 0}
+        },
+        "precpu_stats": {
+            "cpu_usage": {
+                "total_usage": 48711000,
+                "usage_in_kernelmode": 13795000,
+                "usage_in_usermode": 34916000
+            },
+            "system_cpu_usage": 35239180000000,
+            "online_cpus": 4,
+            "throttling_data": {"periods": 0, "throttled_periods": 0, "throttled_time": 0}
+        },
+        "memory_stats": {
+            "usage": 2650112,
+            "stats": {
+                "active_anon": 0, "active_file": 1216512, "anon": 356352, "anon_thp": 0,
+                "file": 1892352, "file_dirty": 0, "file_mapped": 1081344, "file_writeback": 0,
+                "inactive_anon": 356352, "inactive_file": 675840, "kernel_stack": 16384,
+                "pgactivate": 297, "pgdeactivate": 0, "pgfault": 1188, "pglazyfree": 0,
+                "pglazyfreed": 0, "pgmajfault": 0, "pgrefill": 0, "pgscan": 0, "pgsteal": 0,
+                "shmem": 0, "slab": 208096, "slab_reclaimable": 114048,
+                "slab_unreclaimable": 94048, "sock": 0, "thp_collapse_alloc": 0,
+                "thp_fault_alloc": 0, "unevictable": 0, "workingset_activate": 0,
+                "workingset_nodereclaim": 0, "workingset_refault": 0
+            },
+            "limit": 8229298176
+        },
+        "name": "/integration_test_stats",
+        "id": "e1d2c8d5e2f84d0c9a2a5b6f2f5e3f0b1c7a8d6e9f4b3a2c1d0e9f8a7b6c5d4"
+    }"#;
+
+    let stats: Stats = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(stats.pids_stats.current, Some(3));
+    assert_eq!(stats.pids_stats.limit, Some(4618));
+    assert!(stats.blkio_stats.io_serviced_recursive.is_none());
+    assert_eq!(stats.blkio_stats.read_bytes_total(), 2134016);
+    assert_eq!(stats.blkio_stats.write_bytes_total(), 4096);
+    assert_eq!(stats.memory_stats.stats.unwrap().inactive_file, 675840);
+}
+
+#[test]
+fn stats_options_one_shot_query() {
+    let options = StatsOptions {
+        stream: false,
+        one_shot: true,
+    }
+    .into_array()
+    .unwrap();
+
+    assert_eq!(
+        options.as_slice(),
+        &[("stream", "false"), ("one-shot", "true")]
+    );
+
+    for (stream, one_shot) in &[(false, false), (true, false), (true, true)] {
+        let options = StatsOptions {
+            stream: *stream,
+            one_shot: *one_shot,
+        }
+        .into_array()
+        .unwrap();
+
+        let stream = if *stream { "true" } else { "false" };
+        assert_eq!(options.as_slice(), &[("stream", stream)]);
+    }
+}
+
+#[test]
+fn stats_once_one_shot() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/containers/web/stats"));
+            assert_eq!(req.uri().query(), Some("stream=false&one-shot=true"));
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(
+                    r#"{
+                        "read": "2021-02-10T13:24:47.112418421Z",
+                        "preread": "0001-01-01T00:00:00Z",
+                        "pids_stats": {"current": 3},
+                        "blkio_stats": {},
+                        "memory_stats": {"usage": 6651904, "limit": 2083364864},
+                        "cpu_stats": {
+                            "cpu_usage": {
+                                "total_usage": 91342000,
+                                "usage_in_kernelmode": 31000000,
+                                "usage_in_usermode": 60342000
+                            },
+                            "system_cpu_usage": 29474880000000,
+                            "online_cpus": 2
+                        },
+                        "precpu_stats": {
+                            "cpu_usage": {
+                                "total_usage": 0,
+                                "usage_in_kernelmode": 0,
+                                "usage_in_usermode": 0
+                            }
+                        },
+                        "name": "/web",
+                        "id": "5a8d8c02a1b5"
+                    }"#,
+                ))
+                .unwrap()
+        });
+
+        let stats = docker.stats_once("web", true).await.unwrap();
+        assert_eq!(stats.id, "5a8d8c02a1b5");
+        assert_eq!(stats.pids_stats.current, Some(3));
+        assert_eq!(stats.memory_stats.usage, Some(6651904));
+        assert_eq!(stats.cpu_percentage(), None);
+    });
+}
+
+#[test]
+fn container_state_change_responses() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(204, "");
+        assert_eq!(
+            docker
+                .stop_container(
+                    "integration_test_state_change",
+                    None::<StopContainerOptions>
+                )
+                .await
+                .unwrap(),
+            ContainerStateChange::Changed
+        );
+        assert_eq!(
+            docker
+                .start_container(
+                    "integration_test_state_change",
+                    None::<StartContainerOptions<String>>
+                )
+                .await
+                .unwrap(),
+            ContainerStateChange::Changed
+        );
+
+        let docker = mock_docker(304, "");
+        let result = docker
+            .stop_container(
+                "integration_test_state_change",
+                None::<StopContainerOptions>,
+            )
+            .await
+            .unwrap();
+        assert!(result.already_in_state());
+        let result = docker
+            .start_container(
+                "integration_test_state_change",
+                None::<StartContainerOptions<String>>,
+            )
+            .await
+            .unwrap();
+        assert!(result.already_in_state());
+        let result = docker
+            .pause_container("integration_test_state_change")
+            .await
+            .unwrap();
+        assert!(result.already_in_state());
+        let result = docker
+            .unpause_container("integration_test_state_change")
+            .await
+            .unwrap();
+        assert!(result.already_in_state());
+
+        let docker = mock_docker(
+            404,
+            r#"{"message":"No such container: integration_test_state_change"}"#,
+        );
+        match docker
+            .stop_container(
+                "integration_test_state_change",
+                None::<StopContainerOptions>,
+            )
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::DockerResponseNotFoundError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let docker = mock_docker(500, r#"{"message":"cannot stop container"}"#);
+        match docker
+            .stop_container(
+                "integration_test_state_change",
+                None::<StopContainerOptions>,
+            )
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::DockerResponseServerError { status_code, .. } => {
+                assert_eq!(*status_code, 500)
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn export_container_streams_chunks() {
+    use futures_util::stream;
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/containers/web/export"));
+            let chunks = vec![vec![1u8; 4096], vec![2u8; 4096], vec![3u8; 10]];
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/x-tar")
+                .body(hyper::Body::wrap_stream(stream::iter(
+                    chunks.into_iter().map(Ok::<_, std::io::Error>),
+                )))
+                .unwrap()
+        });
+
+        let archive = docker
+            .export_container("web")
+            .try_fold(Vec::new(), |mut archive, chunk| async move {
+                archive.extend_from_slice(&chunk);
+                Ok(archive)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(archive.len(), 4096 * 2 + 10);
+        assert_eq!(archive[4096], 2);
+        assert_eq!(archive[archive.len() - 1], 3);
+    });
+}
+
+#[test]
+fn container_changes_kinds() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(
+            200,
+            r#"[{"Path":"/etc","Kind":0},{"Path":"/etc/app.conf","Kind":1},{"Path":"/tmp/build","Kind":2}]"#,
+        );
+
+        let changes = docker.container_changes("web").await.unwrap().unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.kind).collect::<Vec<_>>(),
+            vec![ChangeKind::Modified, ChangeKind::Added, ChangeKind::Deleted]
+        );
+        assert_eq!(changes[1].to_string(), "A /etc/app.conf");
+    });
+}
+
+#[test]
+fn top_processes_table() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.uri().query(), Some("ps_args=-o+pid%2Ccomm"));
+            hyper::Response::builder()
+                .status(200)
+                .body(hyper::Body::from(
+                    r#"{"Titles":["PID","COMMAND"],"Processes":[["4211","nginx"],["4260","sh -c sleep 10"]]}"#,
+                ))
+                .unwrap()
+        });
+
+        let result = docker
+            .top_processes("web", Some(TopOptions { ps_args: "-o pid,comm" }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.pids(), vec![4211, 4260]);
+        let commands: Vec<_> = result.processes().filter_map(|p| p.command()).collect();
+        assert_eq!(commands, vec!["nginx", "sh -c sleep 10"]);
+        assert_eq!(result.processes().next().unwrap().get("USER"), None);
+    });
+}
+
+#[test]
+fn resize_container_tty_query() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let status = if req.uri().path().ends_with("/containers/web/resize")
+                && req.uri().query() == Some("h=24&w=80")
+            {
+                200
+            } else {
+                400
+            };
+            hyper::Response::builder()
+                .status(status)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        docker
+            .resize_container_tty(
+                "web",
+                ResizeContainerTtyOptions {
+                    width: 80,
+                    height: 24,
+                },
+            )
+            .await
+            .unwrap();
+    });
+}
+
+#[test]
+fn wait_container_ignores_stream_idle_timeout() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        // the docker server sends the headers, then nothing until the container exits
+        let docker = mock_docker_fn(|_| async move {
+            let (mut sender, body) = hyper::Body::channel();
+            tokio::spawn(async move {
+                tokio::time::delay_for(std::time::Duration::from_millis(500)).await;
+                sender
+                    .send_data(hyper::body::Bytes::from("{\"StatusCode\":0}\n"))
+                    .await
+                    .unwrap();
+            });
+            hyper::Response::builder().status(200).body(body).unwrap()
+        })
+        .with_stream_idle_timeout(std::time::Duration::from_millis(100));
+
+        let results: Vec<_> = docker
+            .wait_container("web", None::<WaitContainerOptions<String>>)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(results[0].status_code, 0);
+    });
+}
+
+#[test]
+fn wait_container_until_condition() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let body = match req.uri().query() {
+                Some("condition=removed") => "{\"StatusCode\":0,\"Error\":null}\n",
+                Some("condition=next-exit") => "{\"StatusCode\":137}\n",
+                _ => "{\"StatusCode\":-1,\"Error\":{\"Message\":\"removal failed\"}}\n",
+            };
+            hyper::Response::builder()
+                .status(200)
+                .body(hyper::Body::from(body))
+                .unwrap()
+        });
+
+        let result = docker
+            .wait_container_until("web", WaitCondition::Removed)
+            .await
+            .unwrap();
+        assert_eq!(result.status_code, 0);
+
+        let result = docker
+            .wait_container_until("web", WaitCondition::NextExit)
+            .await
+            .unwrap();
+        assert_eq!(result.status_code, 137);
+        assert!(result.error.is_none());
+
+        let err = docker
+            .wait_container_until("web", WaitCondition::NotRunning)
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::ContainerWaitError {
+                status_code,
+                message,
+            } => {
+                assert_eq!(*status_code, -1);
+                assert_eq!(message, "removal failed");
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn attach_container_bidirectional() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const QUERY: &str =
+        "stdin=true&stdout=true&stderr=false&stream=true&logs=false&detachKeys=ctrl-x";
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.uri().query(), Some(QUERY));
+            // echo each line written to stdin as a frame of stdout
+            tokio::spawn(async move {
+                let mut upgraded = req.into_body().on_upgrade().await.unwrap();
+                let mut buf = [0; 64];
+                let n = upgraded.read(&mut buf).await.unwrap();
+                let mut frame = vec![1, 0, 0, 0, 0, 0, 0, n as u8];
+                frame.extend_from_slice(&buf[..n]);
+                upgraded.write_all(&frame).await.unwrap();
+            });
+            hyper::Response::builder()
+                .status(101)
+                .header("Connection", "Upgrade")
+                .header("Upgrade", "tcp")
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        let AttachContainerResults {
+            mut output,
+            mut input,
+        } = docker
+            .attach_container(
+                "web",
+                Some(AttachContainerOptions {
+                    stdin: true,
+                    stdout: true,
+                    stream: true,
+                    detach_keys: Some("ctrl-x"),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+
+        input.write_all(b"ping\n").await.unwrap();
+        match output.try_next().await.unwrap() {
+            Some(LogOutput::StdOut { message }) => assert_eq!(&message[..], b"ping\n"),
+            frame => panic!("unexpected frame: {:?}", frame),
+        }
+        assert!(output.try_next().await.unwrap().is_none());
+
+        let err = docker
+            .attach_container(
+                "web",
+                Some(AttachContainerOptions {
+                    detach_keys: Some("ctrl-1"),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::InvalidDetachKeysError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn logs_since_until_query() {
+    use chrono::{TimeZone, Utc};
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let query = req.uri().query().unwrap().as_bytes();
+            let params: HashMap<_, _> = url::form_urlencoded::parse(query).into_owned().collect();
+            let since = params.get("since").map(String::as_str);
+            let until = params.get("until").map(String::as_str);
+            if req.uri().path().ends_with("/containers/bounded/logs") {
+                assert_eq!(since, Some("1588888888.000005000"));
+                assert_eq!(until, Some("1588889000.250000000"));
+            } else {
+                assert_eq!((since, until), (None, None));
+            }
+            hyper::Response::builder()
+                .status(200)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        let options = LogsOptions {
+            stdout: true,
+            since: Some(Utc.timestamp(1588888888, 5_000)),
+            until: Some(Utc.timestamp(1588889000, 250_000_000)),
+            ..Default::default()
+        };
+        docker
+            .logs("bounded", Some(options))
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        docker
+            .logs("unbounded", Some(LogsOptions::default()))
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+    });
+}
+
+#[test]
+fn logs_reconnecting_resumes_since_last_timestamp() {
+    fn frame(stream: u8, message: &str) -> Vec<u8> {
+        let mut frame = vec![stream, 0, 0, 0];
+        frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        frame.extend_from_slice(message.as_bytes());
+        frame
+    }
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler_requests = requests.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let requests = handler_requests.clone();
+            async move {
+                let query = req.uri().query().unwrap_or_default().to_owned();
+                let mut requests = requests.lock().unwrap();
+                requests.push(query.clone());
+
+                let body = if req.uri().path().ends_with("/containers/web/logs") {
+                    if query.contains("since=") {
+                        frame(2, "2021-02-10T13:24:49.000000000Z second\n")
+                    } else {
+                        frame(1, "2021-02-10T13:24:47.112418421Z first\n")
+                    }
+                } else if requests.len() == 2 {
+                    br#"{"State":{"Status":"running","Running":true}}"#.to_vec()
+                } else {
+                    br#"{"State":{"Status":"exited","Running":false}}"#.to_vec()
+                };
+                hyper::Response::builder()
+                    .status(200)
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let options = LogsOptions {
+            stdout: true,
+            stderr: true,
+            tail: String::from("10"),
+            ..Default::default()
+        };
+        let items = docker
+            .logs_reconnecting("web", options)
+            .map_ok(|item| match item {
+                ReconnectingLogOutput::Output(output) => output.to_string(),
+                ReconnectingLogOutput::Reconnected { since } => {
+                    format!("reconnected since {:?}", since.map(|since| since.to_rfc3339()))
+                }
+            })
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                String::from("first"),
+                String::from(
+                    "reconnected since Some(\"2021-02-10T13:24:47.112418422+00:00\")"
+                ),
+                String::from("second"),
+            ]
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(
+            requests[0],
+            "follow=true&stdout=true&stderr=true&timestamps=true&tail=10"
+        );
+        assert_eq!(
+            requests[2],
+            "follow=true&stdout=true&stderr=true&since=1612963487.112418422&timestamps=true&tail=all"
+        );
+    });
+}
+
+#[test]
+fn logs_demultiplexed_frames() {
+    use futures_util::stream;
+
+    fn frame(output: LogOutput) -> (&'static str, Vec<u8>) {
+        match output {
+            LogOutput::StdIn { message } => ("stdin", message.to_vec()),
+            LogOutput::StdOut { message } => ("stdout", message.to_vec()),
+            LogOutput::StdErr { message } => ("stderr", message.to_vec()),
+            LogOutput::Console { message } => ("console", message.to_vec()),
+        }
+    }
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let mut body = Vec::new();
+            // a frame spanning two lines, and a frame whose length is a newline byte
+            body.extend_from_slice(b"\x01\0\0\0\0\0\0\x03a\nb");
+            body.extend_from_slice(b"\x02\0\0\0\0\0\0\x0a0123456789");
+            body.extend_from_slice(b"\x01\0\0\0\0\0\0\x05line\n");
+            let chunks = if req.uri().path().ends_with("/containers/tty/logs") {
+                vec![b"hello\nworld".to_vec()]
+            } else {
+                // a header split across chunks
+                let (head, tail) = body.split_at(5);
+                let (middle, tail) = tail.split_at(9);
+                vec![head.to_vec(), middle.to_vec(), tail.to_vec()]
+            };
+            hyper::Response::builder()
+                .status(200)
+                .body(hyper::Body::wrap_stream(stream::iter(
+                    chunks.into_iter().map(Ok::<_, std::io::Error>),
+                )))
+                .unwrap()
+        });
+
+        let frames = docker
+            .logs("web", None::<LogsOptions>)
+            .map_ok(frame)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            frames,
+            vec![
+                ("stdout", b"a\nb".to_vec()),
+                ("stderr", b"0123456789".to_vec()),
+                ("stdout", b"line\n".to_vec()),
+            ]
+        );
+
+        let frames = docker
+            .logs("tty", None::<LogsOptions>)
+            .map_ok(frame)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            frames,
+            vec![
+                ("console", b"hello\n".to_vec()),
+                ("console", b"world".to_vec()),
+            ]
+        );
+    });
+}
+
+#[test]
+fn log_output_lines_split_utf8() {
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    let frames: Vec<Result<_, Error>> = vec![
+        Ok(LogOutput::StdOut {
+            message: Bytes::from_static(b"h\xc3"),
+        }),
+        Ok(LogOutput::StdErr {
+            message: Bytes::from_static(b"warn"),
+        }),
+        Ok(LogOutput::StdOut {
+            message: Bytes::from_static(b"\xa9llo\r\nwor"),
+        }),
+        Ok(LogOutput::StdErr {
+            message: Bytes::from_static(b"ing\n"),
+        }),
+        Ok(LogOutput::StdOut {
+            message: Bytes::from_static(b"ld"),
+        }),
+    ];
+
+    let mut rt = Runtime::new().unwrap();
+    let lines = rt
+        .block_on(stream::iter(frames).lines().try_collect::<Vec<_>>())
+        .unwrap();
+
+    assert_eq!(
+        lines,
+        vec![
+            LogLine::StdOut {
+                line: String::from("héllo")
+            },
+            LogLine::StdErr {
+                line: String::from("warning")
+            },
+            LogLine::StdOut {
+                line: String::from("world")
+            },
+        ]
+    );
+}
+
+#[test]
+fn log_output_single_line_over_limit() {
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    let chunk = Bytes::from(vec![b'a'; 64 * 1024]);
+    let frames = || {
+        stream::iter(
+            (0..160)
+                .map(|_| {
+                    Ok(LogOutput::StdOut {
+                        message: chunk.clone(),
+                    })
+                })
+                .collect::<Vec<Result<_, Error>>>(),
+        )
+    };
+
+    let mut rt = Runtime::new().unwrap();
+
+    match rt
+        .block_on(frames().bytes_concatenated(1024 * 1024))
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::LogOutputLimitError { limit } => assert_eq!(*limit, 1024 * 1024),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    let output = rt
+        .block_on(frames().bytes_concatenated(10 * 1024 * 1024))
+        .unwrap();
+    assert_eq!(output.len(), 10 * 1024 * 1024);
+
+    let lines = rt
+        .block_on(frames().lines().try_collect::<Vec<_>>())
+        .unwrap();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(format!("{}", lines[0]).len(), 10 * 1024 * 1024);
+}
+
+#[test]
+fn host_config_hardening_fields() {
+    // Host config produced by `docker run --cap-drop ALL --sysctl net.ipv4.ip_forward=1 --ulimit
+    // nofile=1024:2048`
+    let fixture = r#"{
+        "CapAdd": null,
+        "CapDrop": ["ALL"],
+        "Privileged": false,
+        "ReadonlyRootfs": false,
+        "SecurityOpt": null,
+        "Sysctls": {"net.ipv4.ip_forward": "1"},
+        "Ulimits": [{"Name": "nofile", "Hard": 2048, "Soft": 1024}]
+    }"#;
+
+    let mut sysctls = std::collections::HashMap::new();
+    sysctls.insert("net.ipv4.ip_forward", "1");
+
+    let host_config = HostConfig {
+        cap_drop: Some(vec![Capability::All]),
+        sysctls: Some(sysctls),
+        ulimits: Some(vec![Ulimit {
+            name: String::from("nofile"),
+            soft: 1024,
+            hard: 2048,
+        }]),
+        ..Default::default()
+    };
+
+    let value = serde_json::to_value(&host_config).unwrap();
+    let expected: serde_json::Value = serde_json::from_str(fixture).unwrap();
+
+    for key in &["CapDrop", "Sysctls", "Ulimits"] {
+        assert_eq!(value[key], expected[key]);
+    }
+    for key in &[
+        "CapAdd",
+        "Capabilities",
+        "Privileged",
+        "ReadonlyRootfs",
+        "SecurityOpt",
+    ] {
+        assert!(value.get(key).is_none());
+    }
+
+    let host_config: HostConfig<String> = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(host_config.cap_drop, Some(vec![Capability::All]));
+    assert_eq!(host_config.privileged, Some(false));
+    assert_eq!(
+        host_config.sysctls.unwrap().get("net.ipv4.ip_forward"),
+        Some(&String::from("1"))
+    );
+    assert_eq!(host_config.ulimits.unwrap()[0].hard, 2048);
+
+    let capabilities: Vec<Capability> =
+        serde_json::from_str(r#"["CAP_NET_ADMIN", "SYS_TIME", "CAP_FUTURE"]"#).unwrap();
+
+    assert_eq!(
+        capabilities,
+        vec![
+            Capability::NetAdmin,
+            Capability::SysTime,
+            Capability::Other(String::from("CAP_FUTURE"))
+        ]
+    );
+    assert_eq!(
+        serde_json::to_string(&capabilities).unwrap(),
+        r#"["NET_ADMIN","SYS_TIME","CAP_FUTURE"]"#
+    );
+}
+
+#[test]
+fn host_config_device_requests() {
+    let mut tmpfs = HashMap::new();
+    tmpfs.insert("/run", "rw,noexec,nosuid");
+
+    let host_config = HostConfig {
+        device_requests: Some(vec![DeviceRequest {
+            count: -1,
+            capabilities: vec![vec!["gpu"]],
+            ..Default::default()
+        }]),
+        init: Some(true),
+        cgroupns_mode: Some(CgroupnsMode::Private),
+        masked_paths: Some(vec!["/proc/kcore"]),
+        tmpfs: Some(tmpfs),
+        ..Default::default()
+    };
+
+    let value = serde_json::to_value(&host_config).unwrap();
+    assert_eq!(
+        value["DeviceRequests"],
+        serde_json::json!([{
+            "Driver": "",
+            "Count": -1,
+            "DeviceIDs": [],
+            "Capabilities": [["gpu"]],
+            "Options": null
+        }])
+    );
+    assert_eq!(value["Init"], true);
+    assert_eq!(value["CgroupnsMode"], "private");
+    assert_eq!(value["MaskedPaths"], serde_json::json!(["/proc/kcore"]));
+    assert_eq!(value["Tmpfs"]["/run"], "rw,noexec,nosuid");
+    for key in &["ReadonlyPaths", "StorageOpt", "KernetMemoryTcp"] {
+        assert!(value.get(key).is_none());
+    }
+
+    let host_config: HostConfig<String> = serde_json::from_str(
+        r#"{
+            "DeviceRequests": [{
+                "Driver": "nvidia",
+                "Count": 0,
+                "DeviceIDs": ["0", "1"],
+                "Capabilities": null,
+                "Options": {}
+            }],
+            "Init": null,
+            "CgroupnsMode": "host"
+        }"#,
+    )
+    .unwrap();
+
+    let device_requests = host_config.device_requests.unwrap();
+    assert_eq!(device_requests[0].driver, "nvidia");
+    assert_eq!(device_requests[0].device_ids, vec!["0", "1"]);
+    assert!(device_requests[0].capabilities.is_empty());
+    assert_eq!(host_config.init, None);
+    assert_eq!(host_config.cgroupns_mode, Some(CgroupnsMode::Host));
+}
+
+#[test]
+fn host_config_cgroup_v2_fields() {
+    let host_config = HostConfig::<&str> {
+        ulimits: Some(vec![Ulimit::new("nofile", 1024, 2048)]),
+        ..Default::default()
+    };
+
+    let value = serde_json::to_value(&host_config).unwrap();
+    assert_eq!(
+        value["Ulimits"],
+        serde_json::json!([{"Name": "nofile", "Soft": 1024, "Hard": 2048}])
+    );
+    // unset knobs unavailable on cgroup v2 hosts are left out rather than sent as null
+    for key in &[
+        "MemorySwappiness",
+        "CpuRealtimePeriod",
+        "CpuRealtimeRuntime",
+        "CgroupnsMode",
+    ] {
+        assert!(value.get(key).is_none(), "{} is serialized", key);
+    }
+
+    // containers created before cgroup namespaces were supported report an empty mode
+    let host_config: HostConfig<String> = serde_json::from_str(
+        r#"{
+            "CgroupnsMode": "",
+            "MemorySwappiness": null,
+            "CpuRealtimePeriod": 0,
+            "Ulimits": [{"Name": "nproc", "Soft": -1, "Hard": -1}]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(host_config.cgroupns_mode, None);
+    assert_eq!(host_config.memory_swappiness, None);
+    assert_eq!(host_config.cpu_realtime_period, Some(0));
+    assert_eq!(
+        host_config.ulimits,
+        Some(vec![Ulimit::new("nproc", -1, -1)])
+    );
+}
+
+#[test]
+fn pause_container_idempotent_errors() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(500, r#"{"message":"Container 5a1f is already paused"}"#);
+        match docker
+            .pause_container("integration_test_pause_container")
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::ContainerAlreadyPausedError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let docker = mock_docker(500, r#"{"message":"Container 5a1f is not paused"}"#);
+        match docker
+            .unpause_container("integration_test_pause_container")
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::ContainerNotPausedError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let docker = mock_docker(500, r#"{"message":"cgroups: cannot find cgroup mount"}"#);
+        match docker
+            .pause_container("integration_test_pause_container")
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::DockerResponseServerError { status_code, .. } => {
+                assert_eq!(*status_code, 500)
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+const INSPECT_WITH_SIZE: &str = r#"{
+    "Id": "3a3f5e0c2b1d",
+    "Created": "2020-05-01T10:00:00.000000000Z",
+    "Path": "nginx",
+    "Args": ["-g", "daemon off;"],
+    "State": {
+        "Status": "running",
+        "Running": true,
+        "Paused": false,
+        "Restarting": false,
+        "OOMKilled": false,
+        "Dead": false,
+        "Pid": 4242,
+        "ExitCode": 0,
+        "Error": "",
+        "StartedAt": "2020-05-01T10:00:01.000000000Z",
+        "FinishedAt": "0001-01-01T00:00:00Z",
+        "Health": {
+            "Status": "unhealthy",
+            "FailingStreak": 2,
+            "Log": [
+                {
+                    "Start": "2020-05-01T10:00:31.000000000Z",
+                    "End": "2020-05-01T10:00:36.000000000Z",
+                    "ExitCode": -1,
+                    "Output": "Health check exceeded timeout (5s)"
+                },
+                {
+                    "Start": "2020-05-01T10:01:06.000000000Z",
+                    "End": "2020-05-01T10:01:06.100000000Z",
+                    "ExitCode": 1,
+                    "Output": "curl: (7) Failed to connect"
+                }
+            ]
+        }
+    },
+    "Image": "sha256:602e111c06b6",
+    "ResolvConfPath": "/var/lib/docker/containers/3a3f/resolv.conf",
+    "HostnamePath": "/var/lib/docker/containers/3a3f/hostname",
+    "HostsPath": "/var/lib/docker/containers/3a3f/hosts",
+    "LogPath": "/var/lib/docker/containers/3a3f/3a3f-json.log",
+    "Name": "/web",
+    "RestartCount": 0,
+    "Driver": "overlay2",
+    "Platform": "linux",
+    "MountLabel": "",
+    "ProcessLabel": "",
+    "AppArmorProfile": "docker-default",
+    "ExecIDs": null,
+    "HostConfig": {},
+    "GraphDriver": {"Name": "overlay2", "Data": null},
+    "SizeRw": 1048576,
+    "SizeRootFs": 134217728,
+    "Mounts": [],
+    "Config": {"Image": "nginx"},
+    "NetworkSettings": {
+        "Bridge": "",
+        "SandboxID": "",
+        "HairpinMode": false,
+        "LinkLocalIPv6Address": "",
+        "LinkLocalIPv6PrefixLen": 0,
+        "Ports": {},
+        "SandboxKey": "",
+        "SecondaryIPAddresses": null,
+        "SecondaryIPv6Addresses": null,
+        "EndpointID": "",
+        "Gateway": "",
+        "GlobalIPv6Address": "",
+        "GlobalIPv6PrefixLen": 0,
+        "IPAddress": "",
+        "IPPrefixLen": 0,
+        "IPv6Gateway": "",
+        "MacAddress": "",
+        "Networks": {}
+    }
+}"#;
+
+#[test]
+fn inspect_container_size_and_health() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/containers/web/json"));
+            assert_eq!(req.uri().query(), Some("size=true"));
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(INSPECT_WITH_SIZE))
+                .unwrap()
+        });
+
+        let container = docker
+            .inspect_container("web", Some(InspectContainerOptions { size: true }))
+            .await
+            .unwrap();
+
+        assert_eq!(container.size_rw, Some(1048576));
+        assert_eq!(container.size_root_fs, Some(134217728));
+        assert_eq!(container.graph_driver.name, "overlay2");
+        let health = container.state.health.unwrap();
+        assert_eq!(health.failing_streak, 2);
+        assert_eq!(
+            health
+                .log
+                .iter()
+                .map(|entry| entry.exit_code)
+                .collect::<Vec<_>>(),
+            vec![-1, 1]
+        );
+    });
+}
+
+#[test]
+fn graph_driver_overlay2_fixture() {
+    let fixture = r#"{
+        "Data": {
+            "LowerDir": "/var/lib/docker/overlay2/3b2c9d-init/diff:/var/lib/docker/overlay2/f4a1e2/diff",
+            "MergedDir": "/var/lib/docker/overlay2/3b2c9d/merged",
+            "UpperDir": "/var/lib/docker/overlay2/3b2c9d/diff",
+            "WorkDir": "/var/lib/docker/overlay2/3b2c9d/work"
+        },
+        "Name": "overlay2"
+    }"#;
+
+    let graph_driver: GraphDriverData = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(graph_driver.name, "overlay2");
+    assert_eq!(
+        graph_driver.data["MergedDir"],
+        "/var/lib/docker/overlay2/3b2c9d/merged"
+    );
+
+    let graph_driver: GraphDriverData =
+        serde_json::from_str(r#"{"Data": null, "Name": "vfs"}"#).unwrap();
+
+    assert!(graph_driver.data.is_empty());
+}
+
+#[test]
+fn container_mounts_fixture() {
+    use bollard::service_models::{MountBindOptionsPropagation, MountType};
+
+    let fixture = r#"[
+        {
+            "Type": "bind",
+            "Source": "/srv/backup",
+            "Destination": "/backup",
+            "Mode": "",
+            "RW": false,
+            "Propagation": "rprivate"
+        },
+        {
+            "Type": "volume",
+            "Name": "pgdata",
+            "Source": "/var/lib/docker/volumes/pgdata/_data",
+            "Destination": "/var/lib/postgresql/data",
+            "Driver": "local",
+            "Mode": "z",
+            "RW": true,
+            "Propagation": ""
+        },
+        {
+            "Type": "tmpfs",
+            "Source": "",
+            "Destination": "/run",
+            "Mode": "",
+            "RW": true,
+            "Propagation": ""
+        }
+    ]"#;
+
+    let mounts: Vec<Mount> = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(mounts[0].type_, MountType::Bind);
+    assert_eq!(mounts[0].name, None);
+    assert!(!mounts[0].rw);
+    assert_eq!(
+        mounts[0].propagation,
+        Some(MountBindOptionsPropagation::RPrivate)
+    );
+
+    assert_eq!(mounts[1].type_, MountType::Volume);
+    assert_eq!(mounts[1].name.as_deref(), Some("pgdata"));
+    assert_eq!(mounts[1].driver.as_deref(), Some("local"));
+    assert_eq!(mounts[1].propagation, None);
+
+    assert_eq!(mounts[2].type_, MountType::Tmpfs);
+    assert_eq!(mounts[2].destination, "/run");
+    assert_eq!(mounts[2].propagation, None);
+}
+
+#[test]
+fn unit_responses_ignore_body() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        for &(status, body) in &[
+            (200, ""),
+            (204, ""),
+            (200, "{\"message\":\"ignored\"}\n"),
+            (204, "stray"),
+        ] {
+            let docker = mock_docker(status, body);
+            docker
+                .remove_container(
+                    "integration_test_remove_container",
+                    None::<RemoveContainerOptions>,
+                )
+                .await
+                .unwrap_or_else(|e| panic!("{} {:?}: {}", status, body, e));
+        }
+    });
+}
+
+#[test]
+fn start_container_options_query() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(
+                req.uri().query(),
+                Some("detachKeys=ctrl-p%2Cctrl-q&checkpoint=before-upgrade&checkpoint-dir=%2Fsrv%2Fcheckpoints")
+            );
+            hyper::Response::builder()
+                .status(204)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        docker
+            .start_container(
+                "integration_test_start_container",
+                Some(StartContainerOptions {
+                    detach_keys: "ctrl-p,ctrl-q",
+                    checkpoint: Some("before-upgrade"),
+                    checkpoint_dir: Some("/srv/checkpoints"),
+                }),
+            )
+            .await
+            .unwrap();
+
+        let docker = mock_docker(204, "");
+        for detach_keys in &["ctrl-1", "ctrl-", "ctrl-pq", "ab", "a,,b"] {
+            let error = docker
+                .start_container(
+                    "integration_test_start_container",
+                    Some(StartContainerOptions {
+                        detach_keys: *detach_keys,
+                        ..Default::default()
+                    }),
+                )
+                .await
+                .unwrap_err();
+
+            match error.kind() {
+                ErrorKind::InvalidDetachKeysError { .. } => (),
+                kind => panic!("unexpected error for {}: {:?}", detach_keys, kind),
+            }
+        }
+
+        for detach_keys in &["x", "ctrl-@", "CTRL-A,z", "ctrl-\\"] {
+            docker
+                .start_container(
+                    "integration_test_start_container",
+                    Some(StartContainerOptions {
+                        detach_keys: *detach_keys,
+                        ..Default::default()
+                    }),
+                )
+                .await
+                .unwrap_or_else(|e| panic!("rejected {}: {}", detach_keys, e));
+        }
+    });
+}
+
+#[test]
+fn list_containers_filter_helpers() {
+    let options = ListContainersOptions::<&str>::default()
+        .status(ContainerStatus::Running)
+        .health(HealthStatus::Unhealthy)
+        .into_array()
+        .unwrap();
+
+    // the encoding of `docker ps --filter status=running --filter health=unhealthy`
+    assert_eq!(
+        options.iter().find(|(key, _)| *key == "filters").unwrap().1,
+        r#"{"health":{"unhealthy":true},"status":{"running":true}}"#
+    );
+
+    let options = ListContainersOptions::<String>::default()
+        .ancestor(String::from("postgres:12"))
+        .network(String::from("backend"))
+        .exited(137);
+    assert_eq!(options.filters["exited"], vec![String::from("137")]);
+    assert_eq!(
+        options.filters["ancestor"],
+        vec![String::from("postgres:12")]
+    );
+}
+
+#[test]
+fn list_containers_typed_filters() {
+    let options = ListContainersOptions::<&str> {
+        all: true,
+        size: true,
+        ..Default::default()
+    }
+    .filter(ContainerFilter::Status(ContainerStatus::Exited))
+    .filter(ContainerFilter::Status(ContainerStatus::Dead))
+    .filter(ContainerFilter::Name("worker"))
+    .filter(ContainerFilter::IsTask(false))
+    .filter(ContainerFilter::Health(HealthStatus::None))
+    .into_array()
+    .unwrap();
+
+    let params: HashMap<_, _> = options.into_iter().collect();
+    assert_eq!(params["size"], "true");
+    assert_eq!(
+        params["filters"],
+        r#"{"health":{"none":true},"is-task":{"false":true},"name":{"worker":true},"status":{"dead":true,"exited":true}}"#
+    );
+}
+
+const STAT: &str = r#"{"name":"nginx","size":4096,"mode":2147484141,"mtime":"2020-05-01T10:00:00Z","linkTarget":""}"#;
+
+#[test]
+fn stat_container_path_decodes_header() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.method(), &hyper::Method::HEAD);
+            assert!(req.uri().path().ends_with("/containers/web/archive"));
+            assert_eq!(req.uri().query(), Some("path=%2Fetc%2Fnginx"));
+            let stat = base64::encode(STAT);
+            hyper::Response::builder()
+                .status(200)
+                .header("X-Docker-Container-Path-Stat", stat)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        let stat = docker
+            .stat_container_path("web", "/etc/nginx")
+            .await
+            .unwrap();
+        assert_eq!(stat.name, "nginx");
+        assert_eq!(stat.size, 4096);
+        assert!(stat.is_dir());
+        assert!(!stat.is_symlink());
+        assert_eq!(stat.permissions(), 0o755);
+        assert_eq!(stat.mtime.to_rfc3339(), "2020-05-01T10:00:00+00:00");
+
+        let docker = mock_docker(200, "");
+        match docker.stat_container_path("web", "/etc/nginx").await {
+            Err(err) => match err.kind() {
+                ErrorKind::ContainerPathStatError { path, .. } => assert_eq!(path, "/etc/nginx"),
+                kind => panic!("unexpected error: {:?}", kind),
+            },
+            Ok(stat) => panic!("unexpected stat: {:?}", stat),
+        }
+    });
+}
+
+#[test]
+fn prune_containers_typed_filters() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/containers/prune"));
+            let query = req.uri().query().unwrap().as_bytes();
+            let (_, filters) = url::form_urlencoded::parse(query)
+                .find(|(key, _)| key == "filters")
+                .unwrap();
+            let filters: serde_json::Value = serde_json::from_str(&filters).unwrap();
+            assert_eq!(
+                filters,
+                serde_json::json!({
+                    "until": ["24h"],
+                    "label": ["janitor=allowed"],
+                    "label!": ["keep"],
+                })
+            );
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(
+                    r#"{"ContainersDeleted":["5a8d8c02a1b5","9c1f3e"],"SpaceReclaimed":1048576}"#,
+                ))
+                .unwrap()
+        });
+
+        let options = PruneContainersOptions::default()
+            .filter(PruneContainersFilter::Until("24h"))
+            .filter(PruneContainersFilter::Label("janitor=allowed"))
+            .filter(PruneContainersFilter::LabelNot("keep"));
+        let result = docker.prune_containers(Some(options)).await.unwrap();
+
+        assert_eq!(
+            result.containers_deleted.unwrap(),
+            vec!["5a8d8c02a1b5", "9c1f3e"]
+        );
+        assert_eq!(result.space_reclaimed, 1048576);
+    });
+}
+
+#[test]
+fn container_events_oom_restart() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let body = if req.uri().path().ends_with("/containers/cache/json") {
+                r#"{"Id":"5a8d8c02a1b5"}"#
+            } else {
+                assert!(req.uri().path().ends_with("/events"));
+                let query = req.uri().query().unwrap().as_bytes();
+                let (_, filters) = url::form_urlencoded::parse(query)
+                    .find(|(key, _)| key == "filters")
+                    .unwrap();
+                let filters: serde_json::Value = serde_json::from_str(&filters).unwrap();
+                assert_eq!(filters["container"][0], "5a8d8c02a1b5");
+                assert_eq!(filters["type"][0], "container");
+                r#"{"status":"start","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"start","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"image":"redis","name":"cache"}},"scope":"local","time":1591605600,"timeNano":1591605600101914800}
+{"status":"health_status: healthy","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"health_status: healthy","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"image":"redis","name":"cache"}},"scope":"local","time":1591605631,"timeNano":1591605631223017600}
+{"status":"oom","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"oom","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"image":"redis","name":"cache"}},"scope":"local","time":1591605702,"timeNano":1591605702556802100}
+{"status":"die","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"die","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"exitCode":"137","image":"redis","name":"cache"}},"scope":"local","time":1591605702,"timeNano":1591605702661438200}
+{"status":"start","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"start","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"image":"redis","name":"cache"}},"scope":"local","time":1591605703,"timeNano":1591605703112190500}
+{"status":"health_status: starting","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"health_status: starting","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"image":"redis","name":"cache"}},"scope":"local","time":1591605703,"timeNano":1591605703112190900}
+{"status":"kill","id":"5a8d8c02a1b5","from":"redis","Type":"container","Action":"kill","Actor":{"ID":"5a8d8c02a1b5","Attributes":{"image":"redis","name":"cache","signal":"15"}},"scope":"local","time":1591605790,"timeNano":1591605790001004000}
+"#
+            };
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(body))
+                .unwrap()
+        });
+
+        let events = docker
+            .container_events("cache", None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let actions: Vec<_> = events.iter().map(|event| event.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ContainerEventAction::Start,
+                ContainerEventAction::HealthStatus(HealthStatus::Healthy),
+                ContainerEventAction::Oom,
+                ContainerEventAction::Die {
+                    exit_code: Some(137)
+                },
+                ContainerEventAction::Start,
+                ContainerEventAction::HealthStatus(HealthStatus::Starting),
+                ContainerEventAction::Kill {
+                    signal: Some(String::from("15"))
+                },
+            ]
+        );
+        assert_eq!(events[0].name.as_deref(), Some("cache"));
+    });
+}
+
+#[test]
+fn wait_for_published_ports_dual_stack() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let inspects = Arc::new(AtomicUsize::new(0));
+        let handler_inspects = inspects.clone();
+
+        let docker = mock_docker_fn(move |_| {
+            let inspects = handler_inspects.clone();
+            async move {
+                // the dynamic port is not assigned yet on the first inspection
+                let body = if inspects.fetch_add(1, Ordering::SeqCst) == 0 {
+                    r#"{
+                        "Id": "5a8d8c02a1b5",
+                        "NetworkSettings": {
+                            "Ports": {
+                                "80/tcp": [{"HostIp": "0.0.0.0", "HostPort": "0"}],
+                                "443/tcp": null
+                            }
+                        }
+                    }"#
+                } else {
+                    r#"{
+                        "Id": "5a8d8c02a1b5",
+                        "NetworkSettings": {
+                            "Ports": {
+                                "80/tcp": [
+                                    {"HostIp": "0.0.0.0", "HostPort": "49153"},
+                                    {"HostIp": "::", "HostPort": "49153"}
+                                ],
+                                "443/tcp": null
+                            }
+                        }
+                    }"#
+                };
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let ports = docker
+            .wait_for_published_ports("web", Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(inspects.load(Ordering::SeqCst), 2);
+        assert_eq!(ports.len(), 1);
+        assert_eq!(
+            ports["80/tcp"],
+            vec![
+                "0.0.0.0:49153".parse::<SocketAddr>().unwrap(),
+                "[::]:49153".parse::<SocketAddr>().unwrap(),
+            ]
+        );
+    });
+}
+
+#[test]
+fn wait_until_healthy_polls() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let inspects = Arc::new(AtomicUsize::new(0));
+        let handler_inspects = inspects.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let inspects = handler_inspects.clone();
+            async move {
+                assert!(req.uri().path().ends_with("/containers/postgres/json"));
+                let body = if inspects.fetch_add(1, Ordering::SeqCst) == 0 {
+                    r#"{"State":{"Status":"running","Running":true,"Health":{"Status":"starting","FailingStreak":0,"Log":null}}}"#
+                } else {
+                    r#"{"State":{"Status":"running","Running":true,"Health":{"Status":"healthy","FailingStreak":0,"Log":[{"Start":"2021-02-10T13:24:47.112418421Z","End":"2021-02-10T13:24:47.212418421Z","ExitCode":0,"Output":"accepting connections\n"}]}}}"#
+                };
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let health = docker
+            .wait_until_healthy("postgres", Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(inspects.load(Ordering::SeqCst), 2);
+        assert_eq!(health.status, "healthy");
+        assert_eq!(health.log[0].output, "accepting connections\n");
+    });
+}
+
+#[test]
+fn wait_until_healthy_errors() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(
+            200,
+            r#"{"State":{"Status":"running","Running":true,"Health":{"Status":"unhealthy","FailingStreak":3,"Log":[{"Start":"2021-02-10T13:24:47.112418421Z","End":"2021-02-10T13:24:47.212418421Z","ExitCode":1,"Output":"no response\n"}]}}}"#,
+        );
+        let error = docker
+            .wait_until_healthy("postgres", Duration::from_millis(0))
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::ContainerHealthTimeoutError { last_check, .. } => {
+                assert_eq!(last_check.status, "unhealthy");
+                assert_eq!(last_check.output, "no response");
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let docker = mock_docker(
+            200,
+            r#"{"State":{"Status":"exited","Running":false,"Health":{"Status":"unhealthy","FailingStreak":1,"Log":[]}}}"#,
+        );
+        let error = docker
+            .wait_until_healthy("postgres", Duration::from_secs(5))
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::ContainerStoppedError { status, .. } => assert_eq!(status, "exited"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let docker = mock_docker(200, r#"{"State":{"Status":"running","Running":true}}"#);
+        let error = docker
+            .wait_until_healthy("web", Duration::from_secs(5))
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::ContainerNoHealthCheckError { container } => assert_eq!(container, "web"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn run_and_wait_collects_output() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler_requests = requests.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let requests = handler_requests.clone();
+            async move {
+                let path = req.uri().path().to_owned();
+                requests.lock().unwrap().push(format!(
+                    "{} {}",
+                    req.method(),
+                    &path[path.find("/containers").unwrap()..]
+                ));
+
+                let (status, body) = if path.ends_with("/containers/create") {
+                    (201, br#"{"Id":"4e6f2cbd1f0b","Warnings":[]}"#.to_vec())
+                } else if path.ends_with("/logs") {
+                    let mut body = b"\x01\0\0\0\0\0\0\x06Linux\n".to_vec();
+                    body.extend_from_slice(b"\x02\0\0\0\0\0\0\x08warning\n");
+                    body.extend_from_slice(b"\x01\0\0\0\0\0\0\x05done\n");
+                    (200, body)
+                } else if path.ends_with("/wait") {
+                    (200, b"{\"StatusCode\":3}\n".to_vec())
+                } else {
+                    (204, Vec::new())
+                };
+                hyper::Response::builder()
+                    .status(status)
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let config = Config {
+            image: Some("alpine:3.11"),
+            cmd: Some(vec!["uname"]),
+            ..Default::default()
+        };
+        let results = docker
+            .run_and_wait(
+                None::<CreateContainerOptions<String>>,
+                config.clone(),
+                RunOptions {
+                    remove: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.id, "4e6f2cbd1f0b");
+        assert_eq!(results.exit_code, 3);
+        assert_eq!(&results.stdout[..], b"Linux\ndone\n");
+        assert_eq!(&results.stderr[..], b"warning\n");
+        assert!(!results.truncated);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "POST /containers/create",
+                "POST /containers/4e6f2cbd1f0b/wait",
+                "POST /containers/4e6f2cbd1f0b/start",
+                "GET /containers/4e6f2cbd1f0b/logs",
+                "DELETE /containers/4e6f2cbd1f0b",
+            ]
+        );
+
+        // the output over the limit is dropped, and the exit code still collected
+        requests.lock().unwrap().clear();
+        let results = docker
+            .run_and_wait(
+                None::<CreateContainerOptions<String>>,
+                config,
+                RunOptions {
+                    remove: true,
+                    output_limit: 8,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(results.truncated);
+        assert_eq!(results.exit_code, 3);
+        assert_eq!(&results.stdout[..], b"Linux\ndo");
+        assert_eq!(&results.stderr[..], b"warning\n");
+        assert_eq!(
+            requests.lock().unwrap().last().unwrap(),
+            "DELETE /containers/4e6f2cbd1f0b"
+        );
+    });
+}
+
+#[test]
+fn create_container_name_validation() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(201, r#"{"Id":"4e6f2cbd1f0b","Warnings":[]}"#);
+        for (name, character, position) in &[
+            ("my container", ' ', 2),
+            ("-leading", '-', 0),
+            ("_leading", '_', 0),
+            ("/web:1", ':', 4),
+            ("caf\u{e9}", '\u{e9}', 3),
+        ] {
+            let error = docker
+                .create_container(
+                    Some(CreateContainerOptions {
+                        name: *name,
+                        ..Default::default()
+                    }),
+                    Config::<&str> {
+                        image: Some("hello-world"),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap_err();
+
+            match error.kind() {
+                ErrorKind::InvalidContainerNameError {
+                    character: c,
+                    position: p,
+                    ..
+                } => {
+                    assert_eq!(c, character);
+                    assert_eq!(p, position);
+                }
+                kind => panic!("unexpected error for {}: {:?}", name, kind),
+            }
+        }
+
+        for name in &["0", "/a"] {
+            let error = docker
+                .create_container(
+                    Some(CreateContainerOptions {
+                        name: *name,
+                        ..Default::default()
+                    }),
+                    Config::<&str> {
+                        image: Some("hello-world"),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap_err();
+
+            match error.kind() {
+                ErrorKind::ContainerNameTooShortError { name: n } => assert_eq!(n, name),
+                kind => panic!("unexpected error for {}: {:?}", name, kind),
+            }
+        }
+
+        for name in &["web", "/web", "web_1.blue-green", "a0", ""] {
+            docker
+                .create_container(
+                    Some(CreateContainerOptions {
+                        name: *name,
+                        ..Default::default()
+                    }),
+                    Config::<&str> {
+                        image: Some("hello-world"),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap_or_else(|e| panic!("rejected {}: {}", name, e));
+        }
+    });
+}
+
+#[test]
+fn health_config_durations() {
+    let healthcheck = HealthConfig::new(HealthCheckTest::Cmd(vec![
+        String::from("pg_isready"),
+        String::from("-q"),
+    ]))
+    .interval(Duration::from_secs(30))
+    .timeout(Duration::from_millis(1500))
+    .start_period(Duration::from_secs(5))
+    .retries(3);
+    assert_eq!(
+        healthcheck.check(),
+        Some(HealthCheckTest::Cmd(vec![
+            String::from("pg_isready"),
+            String::from("-q")
+        ]))
+    );
+
+    let config = Config::<&str> {
+        image: Some("postgres"),
+        healthcheck: Some(healthcheck),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&config).unwrap()["Healthcheck"],
+        serde_json::json!({
+            "Test": ["CMD", "pg_isready", "-q"],
+            "Interval": 30_000_000_000u64,
+            "Timeout": 1_500_000_000u64,
+            "Retries": 3,
+            "StartPeriod": 5_000_000_000u64,
+        })
+    );
+
+    let disabled = HealthConfig::new(HealthCheckTest::None);
+    assert_eq!(disabled.test, Some(vec![String::from("NONE")]));
+    assert_eq!(disabled.check(), Some(HealthCheckTest::None));
+    assert_eq!(disabled.interval, None);
+    assert_eq!(
+        HealthConfig::new(HealthCheckTest::CmdShell(String::from("exit 0"))).test,
+        Some(vec![String::from("CMD-SHELL"), String::from("exit 0")])
+    );
+}
+
+#[test]
+fn mount_spec_wire_format() {
+    use bollard::service_models::MountBindOptionsPropagation;
+
+    let specs = vec![
+        MountSpec::Bind(BindMount {
+            source: "/srv/config",
+            target: "/etc/app",
+            read_only: true,
+            propagation: Some(MountBindOptionsPropagation::RSlave),
+            ..Default::default()
+        }),
+        MountSpec::Volume(VolumeMount {
+            target: "/cache",
+            no_copy: true,
+            ..Default::default()
+        }),
+        MountSpec::Tmpfs(TmpfsMount {
+            target: "/run",
+            size_bytes: Some(65536),
+            ..Default::default()
+        }),
+        MountSpec::Npipe(NpipeMount {
+            source: r"\\.\pipe\docker_engine",
+            target: r"\\.\pipe\docker_engine",
+            ..Default::default()
+        }),
+    ];
+    let host_config = HostConfig {
+        mounts: Some(specs.into_iter().map(MountPoint::from).collect()),
+        ..Default::default()
+    };
+    let mounts = serde_json::to_value(&host_config).unwrap()["Mounts"].clone();
+
+    assert_eq!(mounts[0]["Type"], "bind");
+    assert_eq!(mounts[0]["ReadOnly"], true);
+    assert_eq!(
+        mounts[0]["BindOptions"],
+        serde_json::json!({ "Propagation": "rslave", "NonRecursive": false })
+    );
+    assert_eq!(mounts[0]["VolumeOptions"], serde_json::Value::Null);
+
+    assert_eq!(mounts[1]["Type"], "volume");
+    assert_eq!(mounts[1]["Source"], "");
+    assert_eq!(mounts[1]["VolumeOptions"]["NoCopy"], true);
+    assert_eq!(mounts[1]["BindOptions"], serde_json::Value::Null);
+
+    assert_eq!(mounts[2]["Type"], "tmpfs");
+    assert_eq!(mounts[2]["TmpfsOptions"]["SizeBytes"], 65536);
+
+    assert_eq!(mounts[3]["Type"], "npipe");
+    assert_eq!(mounts[3]["Source"], r"\\.\pipe\docker_engine");
+}
+
+#[test]
+fn port_map_wire_format() {
+    let ports = PortMap::new()
+        .tcp(8080)
+        .to_host(std::net::Ipv4Addr::UNSPECIFIED, 18080)
+        .udp(53)
+        .sctp(9899)
+        .tcp(8080)
+        .to_host("::1".parse::<std::net::IpAddr>().unwrap(), 0);
+
+    let config = Config::<String> {
+        exposed_ports: Some(ports.exposed_ports()),
+        host_config: Some(HostConfig {
+            port_bindings: Some(ports.port_bindings()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let value = serde_json::to_value(&config).unwrap();
+
+    assert_eq!(
+        value["ExposedPorts"],
+        serde_json::json!({ "8080/tcp": {}, "53/udp": {}, "9899/sctp": {} })
+    );
+    assert_eq!(
+        value["HostConfig"]["PortBindings"],
+        serde_json::json!({
+            "8080/tcp": [
+                { "HostIp": "0.0.0.0", "HostPort": "18080" },
+                { "HostIp": "::1", "HostPort": "0" },
+            ]
+        })
+    );
+}
+
+#[test]
+fn create_container_platform_query() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/containers/create"));
+            assert_eq!(
+                req.uri().query(),
+                Some("name=emulated&platform=linux%2Farm64")
+            );
+            hyper::Response::builder()
+                .status(201)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(r#"{"Id":"9d5a","Warnings":[]}"#))
+                .unwrap()
+        });
+
+        let result = docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: "emulated",
+                    platform: Some("linux/arm64"),
+                }),
+                Config::<&str> {
+                    image: Some("alpine"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.id, "9d5a");
+    });
+}
+
+#[test]
+fn create_container_name_conflict() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(
+            409,
+            r#"{"message":"Conflict. The container name \"/web\" is already in use by container \"4e6f2cbd1f0b1c6a7f5d0e9a3b2c1d4e5f60718293a4b5c6d7e8f90a1b2c3d4e\". You have to remove (or rename) that container to be able to reuse that name."}"#,
+        );
+        let error = docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: "web",
+                    ..Default::default()
+                }),
+                Config::<&str> {
+                    image: Some("hello-world"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::ContainerNameConflictError { name, existing_id } => {
+                assert_eq!(name, "web");
+                assert_eq!(
+                    existing_id,
+                    "4e6f2cbd1f0b1c6a7f5d0e9a3b2c1d4e5f60718293a4b5c6d7e8f90a1b2c3d4e"
+                );
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let docker = mock_docker(409, r#"{"message":"Conflict. Unrelated conflict"}"#);
+        let error = docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: "web",
+                    ..Default::default()
+                }),
+                Config::<&str> {
+                    image: Some("hello-world"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::DockerResponseConflictError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn kill_and_stop_with_signals() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let path = req.uri().path();
+            let expected = if path.ends_with("/containers/web/kill") {
+                "signal=SIGHUP"
+            } else if path.ends_with("/containers/web/stop") {
+                "t=5&signal=SIGRTMIN%2B3"
+            } else {
+                "t=5&signal=SIGINT"
+            };
+            let status = if req.uri().query() == Some(expected) {
+                204
+            } else {
+                400
+            };
+            hyper::Response::builder()
+                .status(status)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        docker
+            .kill_container(
+                "web",
+                Some(KillContainerOptions {
+                    signal: Signal::Hup,
+                }),
+            )
+            .await
+            .unwrap();
+        docker
+            .stop_container(
+                "web",
+                Some(StopContainerOptions {
+                    t: 5,
+                    signal: Some(Signal::from("SIGRTMIN+3")),
+                }),
+            )
+            .await
+            .unwrap();
+        docker
+            .restart_container(
+                "web",
+                Some(RestartContainerOptions {
+                    t: 5,
+                    signal: Some(Signal::Int),
+                }),
+            )
+            .await
+            .unwrap();
+    });
+
+    assert_eq!(Signal::from("TERM"), Signal::Term);
+    assert_eq!(Signal::from("9"), Signal::Custom(String::from("9")));
+    assert_eq!(
+        serde_json::from_str::<Signal>(r#""SIGWINCH""#).unwrap(),
+        Signal::Winch
+    );
+    assert_eq!(
+        serde_json::to_string(&Signal::Usr1).unwrap(),
+        r#""SIGUSR1""#
+    );
+}
+
+#[test]
+fn stop_container_timeout_duration() {
+    assert_eq!(StopContainerOptions::timeout(Duration::from_secs(90)).t, 90);
+    assert_eq!(
+        StopContainerOptions::timeout(Duration::from_millis(1500)).t,
+        2
+    );
+    assert_eq!(
+        RestartContainerOptions::timeout(Duration::from_millis(1)).t,
+        1
+    );
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        // the docker server responds once the container stopped, after the client timeout
+        let docker = mock_docker_fn(|req| async move {
+            tokio::time::delay_for(Duration::from_millis(1500)).await;
+            let status = if req.uri().query() == Some("t=2") {
+                204
+            } else {
+                400
+            };
+            hyper::Response::builder()
+                .status(status)
+                .body(hyper::Body::empty())
+                .unwrap()
+        })
+        .with_timeout(Duration::from_secs(1));
+
+        let change = docker
+            .stop_container(
+                "web",
+                Some(StopContainerOptions::timeout(Duration::from_secs(2))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(change, ContainerStateChange::Changed);
+
+        docker
+            .restart_container(
+                "web",
+                Some(RestartContainerOptions::timeout(Duration::from_secs(2))),
+            )
+            .await
+            .unwrap();
+
+        let err = docker
+            .stop_container("web", None::<StopContainerOptions>)
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::RequestTimeoutError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn with_container_paused_unpauses() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let docker = mock_docker_fn(move |req| {
+            let seen = seen.clone();
+            async move {
+                let path = req.uri().path().to_owned();
+                let path = path[path.find("/containers").unwrap()..].to_owned();
+                seen.lock().unwrap().push(path.clone());
+                let (status, body) = if path == "/containers/paused/pause" {
+                    (409, r#"{"message":"Container paused is already paused"}"#)
+                } else {
+                    (204, "")
+                };
+                hyper::Response::builder()
+                    .status(status)
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let err = docker
+            .with_container_paused("db", || async {
+                Err::<(), _>(Error::from(ErrorKind::NoCertPathError))
+            })
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::NoCertPathError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["/containers/db/pause", "/containers/db/unpause"]
+        );
+
+        requests.lock().unwrap().clear();
+        let output = docker
+            .with_container_paused("paused", || async { Ok(42) })
+            .await
+            .unwrap();
+        assert_eq!(output, 42);
+        assert_eq!(*requests.lock().unwrap(), vec!["/containers/paused/pause"]);
+
+        // dropping the future while the container is paused unpauses it in the background
+        requests.lock().unwrap().clear();
+        let dropped = tokio::time::timeout(
+            Duration::from_millis(100),
+            docker.with_container_paused("db", || futures_util::future::pending::<Result<(), _>>()),
+        )
+        .await;
+        assert!(dropped.is_err());
+        for _ in 0..50 {
+            if requests.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::delay_for(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["/containers/db/pause", "/containers/db/unpause"]
+        );
+    });
+}
+
+#[test]
+fn rename_container_name_conflict() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(
+            409,
+            r#"{"message":"Error when allocating new name: Conflict. The container name \"/web-2\" is already in use by container \"8d2f0a\". You have to remove (or rename) that container to be able to reuse that name."}"#,
+        );
+        let error = docker
+            .rename_container("web", RenameContainerOptions { name: "web-2" })
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::ContainerNameConflictError { name, existing_id } => {
+                assert_eq!(name, "web-2");
+                assert_eq!(existing_id, "8d2f0a");
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let error = docker
+            .rename_container("web", RenameContainerOptions { name: "web 2" })
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::InvalidContainerNameError { character, .. } => assert_eq!(*character, ' '),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
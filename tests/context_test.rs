@@ -0,0 +1,77 @@
+use bollard::errors::ErrorKind;
+use bollard::Docker;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Response, Server};
+use sha2::{Digest, Sha256};
+use tokio::runtime::Runtime;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn write_context(config_dir: &Path, name: &str, host: &str) {
+    let id = hex::encode(Sha256::digest(name.as_bytes()));
+    let meta_dir = config_dir.join("contexts").join("meta").join(id);
+    fs::create_dir_all(&meta_dir).unwrap();
+    fs::write(
+        meta_dir.join("meta.json"),
+        format!(
+            r#"{{"Name":"{}","Metadata":{{"Description":""}},"Endpoints":{{"docker":{{"Host":"{}","SkipTLSVerify":false}}}}}}"#,
+            name, host
+        ),
+    )
+    .unwrap();
+}
+
+// The context is resolved from environment variables, so all cases run in a single test.
+#[test]
+fn connect_with_context_store() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, hyper::Error>(service_fn(|_| async {
+                Ok::<_, hyper::Error>(Response::new(Body::from("OK")))
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let config_dir = env::temp_dir().join(format!("bollard-context-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&config_dir);
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("config.json"),
+            r#"{"auths":{},"currentContext":"staging"}"#,
+        )
+        .unwrap();
+        write_context(&config_dir, "staging", &format!("tcp://{}", addr));
+        write_context(&config_dir, "legacy", "fd://");
+
+        env::set_var("DOCKER_CONFIG", &config_dir);
+        env::remove_var("DOCKER_HOST");
+        env::remove_var("DOCKER_CONTEXT");
+
+        let docker = Docker::connect_with_current_context().unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+
+        let docker = Docker::connect_with_context("staging").unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+
+        env::set_var("DOCKER_CONTEXT", "production");
+        match Docker::connect_with_current_context().unwrap_err().kind() {
+            ErrorKind::ContextNotFoundError { name } => assert_eq!(name, "production"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        match Docker::connect_with_context("legacy").unwrap_err().kind() {
+            ErrorKind::UnsupportedContextEndpointError { host, .. } => assert_eq!(host, "fd://"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        env::remove_var("DOCKER_CONTEXT");
+        env::remove_var("DOCKER_CONFIG");
+        fs::remove_dir_all(&config_dir).unwrap();
+    });
+}
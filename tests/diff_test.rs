@@ -0,0 +1,178 @@
+use bollard::container::{Config, Container, HostConfig};
+use bollard::diff::*;
+use bollard::service::*;
+
+use serde_json::json;
+
+fn inspected_container() -> Container {
+    let fixture = r#"{
+        "Id": "3a3fd6ed2a4c6f0d6e6cd1d0b7b5b1e2a7c6e8b9f5b1b1a7d9b2c6e1d7c5b3a2",
+        "Created": "2020-04-20T09:37:58.213934386Z",
+        "Path": "redis-server",
+        "Args": [],
+        "State": {
+            "Status": "running",
+            "Running": true,
+            "Paused": false,
+            "Restarting": false,
+            "OOMKilled": false,
+            "Dead": false,
+            "Pid": 4242,
+            "ExitCode": 0,
+            "Error": "",
+            "StartedAt": "2020-04-20T09:37:58.713934386Z",
+            "FinishedAt": "0001-01-01T00:00:00Z"
+        },
+        "Image": "sha256:4cdbec704e477aab9d249262e60b9a8a25cbef48f0ff23ac5eae879a98a7ebd0",
+        "ResolvConfPath": "/var/lib/docker/containers/3a3f/resolv.conf",
+        "HostnamePath": "/var/lib/docker/containers/3a3f/hostname",
+        "HostsPath": "/var/lib/docker/containers/3a3f/hosts",
+        "LogPath": "/var/lib/docker/containers/3a3f/3a3f-json.log",
+        "Name": "/redis",
+        "RestartCount": 0,
+        "Driver": "overlay2",
+        "Platform": "linux",
+        "MountLabel": "",
+        "ProcessLabel": "",
+        "AppArmorProfile": "docker-default",
+        "ExecIDs": null,
+        "HostConfig": {
+            "Memory": 268435456,
+            "NetworkMode": "bridge",
+            "RestartPolicy": {"Name": "no", "MaximumRetryCount": 0}
+        },
+        "GraphDriver": {"Name": "overlay2", "Data": null},
+        "Mounts": [],
+        "Config": {
+            "Hostname": "3a3fd6ed2a4c",
+            "Image": "redis:6@sha256:0f724af268d0d3f5fb1d6b33d1c3ea7d2f9c3e7c0d1c28e5e8b9d8a1e2f3a4b5",
+            "Env": [
+                "REDIS_PORT=6379",
+                "REDIS_APPENDONLY=yes",
+                "PATH=/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin"
+            ],
+            "Cmd": ["redis-server"],
+            "Labels": {"maintainer": "redis"}
+        },
+        "NetworkSettings": {
+            "Bridge": "",
+            "SandboxID": "",
+            "HairpinMode": false,
+            "LinkLocalIPv6Address": "",
+            "LinkLocalIPv6PrefixLen": 0,
+            "Ports": {},
+            "SandboxKey": "",
+            "SecondaryIPAddresses": null,
+            "SecondaryIPv6Addresses": null,
+            "EndpointID": "",
+            "Gateway": "172.17.0.1",
+            "GlobalIPv6Address": "",
+            "GlobalIPv6PrefixLen": 0,
+            "IPAddress": "172.17.0.2",
+            "IPPrefixLen": 16,
+            "IPv6Gateway": "",
+            "MacAddress": "02:42:ac:11:00:02",
+            "Networks": {}
+        }
+    }"#;
+
+    serde_json::from_str(fixture).unwrap()
+}
+
+#[test]
+fn container_config_drift_env_order() {
+    let desired = Config {
+        image: Some("redis:6"),
+        env: Some(vec!["REDIS_APPENDONLY=yes", "REDIS_PORT=6379"]),
+        labels: Some(Default::default()),
+        host_config: Some(HostConfig {
+            memory: Some(268435456),
+            network_mode: Some("default"),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let drift = container_config_drift(&desired, &inspected_container()).unwrap();
+
+    assert!(drift.is_empty(), "{:?}", drift);
+}
+
+#[test]
+fn container_config_drift_memory() {
+    let desired = Config {
+        image: Some("redis:6"),
+        env: Some(vec!["REDIS_PORT=6379"]),
+        host_config: Some(HostConfig {
+            memory: Some(536870912),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let drift = container_config_drift(&desired, &inspected_container()).unwrap();
+
+    assert_eq!(
+        drift,
+        vec![Drift {
+            path: String::from("HostConfig.Memory"),
+            desired: json!(536870912),
+            actual: json!(268435456),
+        }]
+    );
+
+    let options = DriftOptions::default().ignore("HostConfig.Memory");
+    let drift =
+        container_config_drift_with_options(&desired, &inspected_container(), &options).unwrap();
+
+    assert!(drift.is_empty(), "{:?}", drift);
+}
+
+#[test]
+fn service_spec_drift_image_digest() {
+    let fixture = r#"{
+        "ID": "9mnpnzenvg8p8tdbtq4wvbkcz",
+        "Version": {"Index": 19},
+        "CreatedAt": "2020-04-20T09:37:58.213934386Z",
+        "UpdatedAt": "2020-04-20T09:37:58.213934386Z",
+        "Spec": {
+            "Name": "web",
+            "Labels": {},
+            "TaskTemplate": {
+                "ContainerSpec": {
+                    "Image": "fussybeaver/uhttpd:latest@sha256:1b3e4f7c5d9a0e2b6c8d7f1a3e5b9c0d2f4a6b8c1e3d5f7a9b0c2d4e6f8a1b3c",
+                    "Env": ["B=2", "A=1"]
+                }
+            },
+            "Mode": {"Replicated": {"Replicas": 2}}
+        },
+        "Endpoint": {"Spec": {}}
+    }"#;
+
+    let actual: Service<String> = serde_json::from_str(fixture).unwrap();
+
+    let desired = ServiceSpec {
+        name: "web",
+        task_template: TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some("fussybeaver/uhttpd:latest"),
+                env: Some(vec!["A=1", "B=2"]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        mode: Some(ServiceSpecMode::Replicated { replicas: 3 }),
+        ..Default::default()
+    };
+
+    let drift = service_spec_drift(&desired, &actual).unwrap();
+
+    assert_eq!(
+        drift,
+        vec![Drift {
+            path: String::from("Mode.Replicated.Replicas"),
+            desired: json!(3),
+            actual: json!(2),
+        }]
+    );
+}
@@ -151,3 +151,35 @@ fn integration_test_start_exec() {
 fn integration_test_inspect_exec() {
     connect_to_docker_and_run!(inspect_exec_test);
 }
+
+#[test]
+fn start_exec_results_lines() {
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    let results: Vec<Result<_, Error>> = vec![
+        Ok(StartExecResults::Attached {
+            log: LogOutput::Console {
+                message: Bytes::from_static(b"config uhttpd"),
+            },
+        }),
+        Ok(StartExecResults::Detached),
+        Ok(StartExecResults::Attached {
+            log: LogOutput::Console {
+                message: Bytes::from_static(b" main\n"),
+            },
+        }),
+    ];
+
+    let mut rt = Runtime::new().unwrap();
+    let lines = rt
+        .block_on(stream::iter(results).lines().try_collect::<Vec<_>>())
+        .unwrap();
+
+    assert_eq!(
+        lines,
+        vec![LogLine::Console {
+            line: String::from("config uhttpd main")
+        }]
+    );
+}
@@ -2,13 +2,15 @@
 
 use futures_util::future::ready;
 use futures_util::stream::{StreamExt, TryStreamExt};
+use sha2::{Digest, Sha256};
 use tokio::runtime::Runtime;
 
+use bollard::auth::{DockerCredentials, RegistryAuth, RegistryAuthConfigs};
 use bollard::container::{
     Config, CreateContainerOptions, RemoveContainerOptions, StartContainerOptions,
     WaitContainerOptions,
 };
-use bollard::errors::Error;
+use bollard::errors::{Error, ErrorKind};
 use bollard::image::*;
 use bollard::Docker;
 
@@ -16,6 +18,8 @@ use std::collections::HashMap;
 use std::default::Default;
 use std::fs::{remove_file, File};
 use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 #[macro_use]
 pub mod common;
@@ -29,10 +33,13 @@ async fn create_image_test(docker: Docker) -> Result<(), Error> {
 
 async fn search_images_test(docker: Docker) -> Result<(), Error> {
     let result = &docker
-        .search_images(SearchImagesOptions {
-            term: "hello-world",
-            ..Default::default()
-        })
+        .search_images(
+            SearchImagesOptions {
+                term: "hello-world",
+                ..Default::default()
+            },
+            None,
+        )
         .await?;
 
     assert!(result
@@ -77,12 +84,23 @@ async fn list_images_test(docker: Docker) -> Result<(), Error> {
     assert!(result.into_iter().any(|api_image| {
         api_image
             .repo_tags
-            .as_ref()
-            .unwrap_or(&vec![String::new()])
-            .into_iter()
+            .iter()
             .any(|repo_tag| repo_tag == &image)
     }));
 
+    let result = &docker
+        .list_images(Some(
+            ListImagesOptions::<String> {
+                digests: true,
+                ..Default::default()
+            }
+            .reference(image.clone()),
+        ))
+        .await?;
+
+    assert_eq!(result.len(), 1);
+    assert!(result[0].repo_tags.contains(&image));
+
     Ok(())
 }
 
@@ -136,7 +154,7 @@ async fn remove_image_test(docker: Docker) -> Result<(), Error> {
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .await?;
@@ -168,6 +186,7 @@ async fn commit_container_test(docker: Docker) -> Result<(), Error> {
         .create_container(
             Some(CreateContainerOptions {
                 name: "integration_test_commit_container",
+                ..Default::default()
             }),
             Config {
                 cmd: cmd,
@@ -210,6 +229,7 @@ async fn commit_container_test(docker: Docker) -> Result<(), Error> {
         .create_container(
             Some(CreateContainerOptions {
                 name: "integration_test_commit_container_next",
+                ..Default::default()
             }),
             Config {
                 image: Some("integration_test_commit_container_next"),
@@ -258,7 +278,7 @@ async fn commit_container_test(docker: Docker) -> Result<(), Error> {
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .await?;
@@ -327,6 +347,7 @@ RUN touch bollard.txt
         .create_container(
             Some(CreateContainerOptions {
                 name: "integration_test_build_image",
+                ..Default::default()
             }),
             Config {
                 image: Some("integration_test_build_image"),
@@ -374,7 +395,7 @@ RUN touch bollard.txt
             if cfg!(windows) {
                 None
             } else {
-                Some(integration_test_registry_credentials())
+                Some(integration_test_registry_credentials().into())
             },
         )
         .await?;
@@ -589,3 +610,892 @@ fn integration_test_issue_55() {
 fn integration_test_import_image() {
     connect_to_docker_and_run!(import_image_test);
 }
+
+#[test]
+fn create_image_options_from_reference() {
+    let digest = "sha256:cb8a924afdf0229ef7515d9e5b3024e23b3eb03ddbba287f4a19c6ac90b8d221";
+
+    let options = CreateImageOptions::from_reference(&format!("alpine:3.11@{}", digest));
+    assert_eq!(options.from_image, "alpine");
+    assert_eq!(options.tag, digest);
+
+    let options =
+        CreateImageOptions::from_reference(&format!("localhost:5000/library/alpine@{}", digest));
+    assert_eq!(options.from_image, "localhost:5000/library/alpine");
+    assert_eq!(options.tag, digest);
+
+    let options = CreateImageOptions::from_reference("localhost:5000/alpine:3.11");
+    assert_eq!(options.from_image, "localhost:5000/alpine");
+    assert_eq!(options.tag, "3.11");
+
+    let options = CreateImageOptions::from_reference("alpine");
+    assert_eq!(options.from_image, "alpine");
+    assert_eq!(options.tag, "latest");
+}
+
+#[test]
+fn list_images_nullable_fields() {
+    let fixture = r#"[{
+        "Containers": -1,
+        "Created": 1588286416,
+        "Id": "sha256:bf756fb1ae65adf866bd8c456593cd24beb6a0a061dedf42b26a993176745f6b",
+        "Labels": null,
+        "ParentId": "",
+        "RepoDigests": null,
+        "RepoTags": null,
+        "SharedSize": -1,
+        "Size": 13336,
+        "VirtualSize": 13336
+    }, {
+        "Containers": 2,
+        "Created": 1588286416,
+        "Id": "sha256:a24bb4013296f61e89ba57005a7b3e52274d8edd3ae2077d04395f806b63d83e",
+        "ParentId": "",
+        "RepoDigests": ["alpine@sha256:9a839e63dad54c3a6d1834e29692c8492d93f90c59c978c1ed79109ea4fb9a54"],
+        "RepoTags": ["alpine:latest"],
+        "Size": 5613130,
+        "VirtualSize": 5613130
+    }]"#;
+
+    let images: Vec<APIImages> = serde_json::from_str(fixture).unwrap();
+
+    assert!(images[0].repo_tags.is_empty());
+    assert!(images[0].repo_digests.is_empty());
+    assert!(images[0].labels.is_empty());
+    assert_eq!(images[0].containers, -1);
+    assert_eq!(images[0].shared_size, Some(-1));
+    assert_eq!(images[1].repo_tags, vec!["alpine:latest"]);
+    assert_eq!(images[1].containers, 2);
+    assert_eq!(images[1].shared_size, None);
+}
+
+#[test]
+fn build_image_cleanup_query() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let query: HashMap<_, _> =
+                url::form_urlencoded::parse(req.uri().query().unwrap().as_bytes())
+                    .into_owned()
+                    .collect();
+            assert_eq!(query["pull"], "true");
+            assert_eq!(query["rm"], "true");
+            assert_eq!(query["forcerm"], "true");
+            assert_eq!(query["q"], "false");
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from("{\"stream\":\"Step 1/1\"}\n"))
+                .unwrap()
+        });
+
+        let results = docker
+            .build_image(
+                BuildImageOptions {
+                    dockerfile: "Dockerfile",
+                    pull: true,
+                    forcerm: true,
+                    ..Default::default()
+                },
+                None,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+    });
+}
+
+#[test]
+fn build_image_from_stream_context() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let query: HashMap<_, _> =
+                url::form_urlencoded::parse(req.uri().query().unwrap().as_bytes())
+                    .into_owned()
+                    .collect();
+            assert_eq!(query["target"], "release");
+            assert_eq!(query["memswap"], "-1");
+            assert_eq!(query["cpuperiod"], "100000");
+            assert_eq!(query["cpuquota"], "50000");
+
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            assert_eq!(&body[..], b"first chunk, second chunk");
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(
+                    "{\"stream\":\"Step 1/1 : FROM alpine\\n\"}\n{\"aux\":{\"ID\":\"sha256:4e6f\"}}\n",
+                ))
+                .unwrap()
+        });
+
+        let context = futures_util::stream::iter(vec![
+            Ok::<_, std::io::Error>(hyper::body::Bytes::from_static(b"first chunk, ")),
+            Ok(hyper::body::Bytes::from_static(b"second chunk")),
+        ]);
+        let results = docker
+            .build_image_from_stream(
+                BuildImageOptions {
+                    t: "my-image",
+                    target: "release",
+                    memswap: Some(-1),
+                    cpuperiod: Some(100000),
+                    cpuquota: Some(50000),
+                    ..Default::default()
+                },
+                None,
+                context,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        match results.last().unwrap() {
+            BuildImageResults::BuildImageAux { aux } => assert_eq!(aux.id, "sha256:4e6f"),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn commit_container_changes_query() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/commit"));
+            let query = req.uri().query().unwrap().as_bytes();
+            let changes: Vec<_> = url::form_urlencoded::parse(query)
+                .filter(|(key, _)| key == "changes")
+                .map(|(_, value)| value.into_owned())
+                .collect();
+            assert_eq!(changes, vec!["WORKDIR /app", "ENV CACHED=1"]);
+            hyper::Response::builder()
+                .status(201)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(r#"{"Id":"sha256:4f8e2a"}"#))
+                .unwrap()
+        });
+
+        let result = docker
+            .commit_container(
+                CommitContainerOptions {
+                    container: "build",
+                    repo: "cache",
+                    changes: vec!["WORKDIR /app", "ENV CACHED=1"],
+                    ..Default::default()
+                },
+                Config::<String> {
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.id, "sha256:4f8e2a");
+    });
+}
+
+#[test]
+fn aggregate_pull_progress_replay() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        // docker pull redis:6, with the base layer already present
+        let docker = mock_docker(
+            200,
+            r#"{"status":"Pulling from library/redis","id":"6"}
+{"status":"Already exists","progressDetail":{},"id":"bb79b6b2107f"}
+{"status":"Pulling fs layer","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Pulling fs layer","progressDetail":{},"id":"5999b99cee8f"}
+{"status":"Waiting","progressDetail":{},"id":"5999b99cee8f"}
+{"status":"Downloading","progressDetail":{"current":1024,"total":4096},"progress":"[============>                                      ]  1.024kB/4.096kB","id":"1ed3521a5dcb"}
+{"status":"Downloading","progressDetail":{"current":4096,"total":4096},"progress":"[==================================================>]  4.096kB/4.096kB","id":"1ed3521a5dcb"}
+{"status":"Verifying Checksum","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Download complete","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Download complete","progressDetail":{},"id":"5999b99cee8f"}
+{"status":"Extracting","progressDetail":{"current":32768,"total":65536},"progress":"[=========================>                         ]  32.77kB/65.54kB","id":"1ed3521a5dcb"}
+{"status":"Pull complete","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Extracting","progressDetail":{"current":409,"total":409},"progress":"[==================================================>]     409B/409B","id":"5999b99cee8f"}
+{"status":"Pull complete","progressDetail":{},"id":"5999b99cee8f"}
+{"status":"Digest: sha256:800f2587bf3376cb01e6307afe599ddce9439deafbd4fb8562829da96085c9c5"}
+{"status":"Status: Downloaded newer image for redis:6"}
+"#,
+        );
+
+        let progress = aggregate_pull_progress(docker.create_image(
+            Some(CreateImageOptions {
+                from_image: "redis",
+                tag: "6",
+                ..Default::default()
+            }),
+            None,
+            None,
+        ))
+        .with_interval(std::time::Duration::from_secs(3600))
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            progress,
+            vec![
+                PullProgress {
+                    layers_done: 0,
+                    layers_total: 0,
+                    bytes_current: 0,
+                    bytes_total: 0,
+                    phase: PullPhase::Resolving,
+                },
+                PullProgress {
+                    layers_done: 1,
+                    layers_total: 2,
+                    bytes_current: 0,
+                    bytes_total: 0,
+                    phase: PullPhase::Downloading,
+                },
+                PullProgress {
+                    layers_done: 1,
+                    layers_total: 3,
+                    bytes_current: 4096,
+                    bytes_total: 4096,
+                    phase: PullPhase::Extracting,
+                },
+                PullProgress {
+                    layers_done: 3,
+                    layers_total: 3,
+                    bytes_current: 4096,
+                    bytes_total: 4096,
+                    phase: PullPhase::Complete,
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn build_image_with_id_timeout() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let cancelled = Arc::new(Mutex::new(None));
+        let handler_cancelled = cancelled.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let cancelled = handler_cancelled.clone();
+            async move {
+                let query = req.uri().query().unwrap_or_default().to_string();
+                let body = if req.uri().path().ends_with("/build/cancel") {
+                    *cancelled.lock().unwrap() = Some(query);
+                    hyper::Body::empty()
+                } else {
+                    assert!(query.contains("buildid=bollard-"));
+                    // a build that never finishes
+                    hyper::Body::wrap_stream(
+                        futures_util::stream::once(ready(Ok::<_, std::io::Error>(
+                            "{\"stream\":\"Step 1/2 : FROM alpine\"}\n",
+                        )))
+                        .chain(futures_util::stream::pending()),
+                    )
+                };
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(body)
+                    .unwrap()
+            }
+        });
+
+        let (build_id, stream) = docker.build_image_with_id(
+            BuildImageOptions {
+                dockerfile: "Dockerfile",
+                ..Default::default()
+            },
+            None,
+            None,
+            Some(Duration::from_millis(100)),
+        );
+
+        let results = stream.collect::<Vec<_>>().await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        match results[1].as_ref().unwrap_err().kind() {
+            ErrorKind::BuildCancelledError { build_id: id, .. } => assert_eq!(id, &build_id),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert_eq!(
+            cancelled.lock().unwrap().as_deref(),
+            Some(format!("id={}", build_id).as_str())
+        );
+    });
+}
+
+#[test]
+fn cancel_finished_build() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(404, r#"{"message":"no such build: bollard-1"}"#);
+
+        docker.cancel_build("bollard-1").await.unwrap();
+    });
+}
+
+#[test]
+fn build_image_registry_config_header() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            // the value sent by `docker build` for these credentials
+            assert_eq!(
+                req.headers()["X-Registry-Config"],
+                "eyJyZWdpc3RyeS5leGFtcGxlLmNvbSI6eyJ1c2VybmFtZSI6InVzZXIiLCJwYXNzd29yZCI6InA_c3MiLCJzZXJ2ZXJhZGRyZXNzIjoicmVnaXN0cnkuZXhhbXBsZS5jb20ifX0="
+            );
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from("{\"stream\":\"Step 1/1 : FROM alpine\"}\n"))
+                .unwrap()
+        });
+
+        let configs = RegistryAuthConfigs::new().with_credentials(
+            "registry.example.com",
+            DockerCredentials {
+                username: Some(String::from("user")),
+                password: Some(String::from("p?ss")),
+                serveraddress: Some(String::from("registry.example.com")),
+                ..Default::default()
+            },
+        );
+
+        docker
+            .build_image(
+                BuildImageOptions {
+                    dockerfile: "Dockerfile",
+                    ..Default::default()
+                },
+                Some(configs.into()),
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+    });
+}
+
+#[test]
+fn registry_auth_configs_from_docker_config_file() {
+    let path = std::env::temp_dir().join("bollard_registry_auth_configs.json");
+    File::create(&path)
+        .unwrap()
+        .write_all(
+            br#"{
+                "auths": {
+                    "registry.example.com": {"auth": "dXNlcjpwP3Nz"},
+                    "https://index.docker.io/v1/": {"identitytoken": "token"}
+                },
+                "credsStore": "desktop"
+            }"#,
+        )
+        .unwrap();
+
+    let configs: HashMap<String, DockerCredentials> =
+        RegistryAuthConfigs::from_docker_config_file(&path)
+            .unwrap()
+            .into();
+    remove_file(&path).unwrap();
+
+    let credentials = &configs["registry.example.com"];
+    assert_eq!(credentials.username.as_deref(), Some("user"));
+    assert_eq!(credentials.password.as_deref(), Some("p?ss"));
+    assert_eq!(credentials.auth, None);
+    assert_eq!(
+        configs["https://index.docker.io/v1/"]
+            .identitytoken
+            .as_deref(),
+        Some("token")
+    );
+}
+
+#[test]
+fn registry_auth_encoding() {
+    let decode = |auth: RegistryAuth| {
+        String::from_utf8(base64::decode(&auth.encode().unwrap()).unwrap()).unwrap()
+    };
+
+    assert_eq!(
+        decode(RegistryAuth::Basic {
+            username: String::from("user"),
+            password: String::from("secret"),
+        }),
+        r#"{"username":"user","password":"secret"}"#
+    );
+    assert_eq!(
+        decode(RegistryAuth::IdentityToken(String::from("refresh"))),
+        r#"{"identitytoken":"refresh"}"#
+    );
+    assert_eq!(
+        decode(RegistryAuth::RegistryToken(String::from("bearer"))),
+        r#"{"registrytoken":"bearer"}"#
+    );
+    assert_eq!(
+        decode(
+            DockerCredentials {
+                username: Some(String::from("user")),
+                password: Some(String::from("secret")),
+                serveraddress: Some(String::from("registry.example.com")),
+                ..Default::default()
+            }
+            .into()
+        ),
+        r#"{"username":"user","password":"secret","serveraddress":"registry.example.com"}"#
+    );
+    assert_eq!(
+        RegistryAuth::Preencoded(String::from("eyJyZWdpc3RyeXRva2VuIjoiYWJjIn0="))
+            .encode()
+            .unwrap(),
+        "eyJyZWdpc3RyeXRva2VuIjoiYWJjIn0="
+    );
+}
+
+#[test]
+fn inspect_distribution_preencoded_auth() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/distribution/alpine:3.11/json"));
+            assert_eq!(req.headers()["X-Registry-Auth"], "cHJlLWVuY29kZWQ=");
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(
+                    r#"{
+                        "Descriptor": {
+                            "mediaType": "application/vnd.docker.distribution.manifest.list.v2+json",
+                            "digest": "sha256:b276d875eeed9c7d3f1cfa7edb06b22ed22b14219a7d67c52c56612330348239",
+                            "size": 1638
+                        },
+                        "Platforms": [
+                            {"architecture": "amd64", "os": "linux"},
+                            {"architecture": "arm", "os": "linux", "variant": "v7"}
+                        ]
+                    }"#,
+                ))
+                .unwrap()
+        });
+
+        let distribution = docker
+            .inspect_distribution(
+                "alpine:3.11",
+                Some(RegistryAuth::Preencoded(String::from("cHJlLWVuY29kZWQ="))),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(distribution.descriptor.size, 1638);
+        assert_eq!(distribution.platforms.len(), 2);
+        assert_eq!(distribution.platforms[1].variant.as_deref(), Some("v7"));
+    });
+}
+
+fn image_archive(repo_tags: &str) -> Vec<u8> {
+    let manifest = format!(
+        r#"[{{"Config":"0f1c5a6f.json","RepoTags":{},"Layers":["1b3e4f7c/layer.tar"]}}]"#,
+        repo_tags
+    );
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "manifest.json", manifest.as_bytes())
+        .unwrap();
+    builder.into_inner().unwrap()
+}
+
+#[test]
+fn export_image_to_path_resume() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let archive = image_archive(r#"["alpine:3.11"]"#);
+        let response = archive.clone();
+        let docker = mock_docker_fn(move |req| {
+            let response = response.clone();
+            async move {
+                assert!(req.uri().path().ends_with("/images/alpine:3.11/get"));
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/x-tar")
+                    .body(hyper::Body::from(response))
+                    .unwrap()
+            }
+        });
+
+        let path = std::env::temp_dir().join("bollard_export_image_to_path_resume.tar");
+        let partial = std::env::temp_dir().join("bollard_export_image_to_path_resume.tar.partial");
+        File::create(&partial)
+            .unwrap()
+            .write_all(&archive[..700])
+            .unwrap();
+
+        let digest = docker
+            .resume_export_image_to_path("alpine:3.11", &path)
+            .await
+            .unwrap();
+
+        let written = std::fs::read(&path).unwrap();
+        remove_file(&path).unwrap();
+        assert_eq!(written, archive);
+        assert!(!partial.exists());
+        assert_eq!(digest, hex::encode(Sha256::digest(&archive)));
+    });
+}
+
+#[test]
+fn export_image_to_path_removes_partial() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(404, r#"{"message":"reference does not exist"}"#);
+
+        let path = std::env::temp_dir().join("bollard_export_image_to_path_removes_partial.tar");
+        let result = docker.export_image_to_path("missing:latest", &path).await;
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+        assert!(!std::env::temp_dir()
+            .join("bollard_export_image_to_path_removes_partial.tar.partial")
+            .exists());
+    });
+}
+
+#[test]
+fn load_verified_image_from_path_unexpected() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let requests = Arc::new(Mutex::new(0));
+        let handler_requests = requests.clone();
+        let docker = mock_docker_fn(move |_| {
+            *handler_requests.lock().unwrap() += 1;
+            async move {
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(
+                        r#"{"stream":"Loaded image: busybox:latest"}"#,
+                    ))
+                    .unwrap()
+            }
+        });
+
+        let path = std::env::temp_dir().join("bollard_load_verified_image_from_path.tar");
+        File::create(&path)
+            .unwrap()
+            .write_all(&image_archive(r#"["alpine:3.11","busybox"]"#))
+            .unwrap();
+
+        let result = docker
+            .load_verified_image_from_path(&path, true, &["alpine:3.11"])
+            .await;
+        remove_file(&path).unwrap();
+
+        match result.unwrap_err().kind() {
+            ErrorKind::UnexpectedArchiveImagesError { images } => {
+                assert_eq!(images, &vec![String::from("busybox")]);
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        // nothing is sent to the server
+        assert_eq!(*requests.lock().unwrap(), 0);
+    });
+}
+
+fn protobuf_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn protobuf_field(field: u64, value: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    protobuf_varint(field << 3 | 2, &mut buf);
+    protobuf_varint(value.len() as u64, &mut buf);
+    buf.extend_from_slice(value);
+    buf
+}
+
+fn protobuf_varint_field(field: u64, value: u64) -> Vec<u8> {
+    let mut buf = vec![];
+    protobuf_varint(field << 3, &mut buf);
+    protobuf_varint(value, &mut buf);
+    buf
+}
+
+#[test]
+fn build_image_buildkit_trace() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let timestamp = [
+            protobuf_varint_field(1, 1600000000),
+            protobuf_varint_field(2, 500),
+        ]
+        .concat();
+        let vertex = [
+            protobuf_field(1, b"sha256:aa"),
+            protobuf_field(2, b"sha256:bb"),
+            protobuf_field(3, b"[2/2] RUN make"),
+            protobuf_field(5, &timestamp),
+            // an unknown fixed64 field is skipped
+            vec![9 << 3 | 1, 0, 0, 0, 0, 0, 0, 0, 0],
+        ]
+        .concat();
+        let status = [
+            protobuf_field(1, b"sha256:layer"),
+            protobuf_field(2, b"sha256:aa"),
+            protobuf_varint_field(4, 512),
+            protobuf_varint_field(5, 1024),
+        ]
+        .concat();
+        let log = [
+            protobuf_field(1, b"sha256:aa"),
+            protobuf_varint_field(3, 2),
+            protobuf_field(4, b"make: done\n"),
+        ]
+        .concat();
+        let trace = base64::encode(
+            &[
+                protobuf_field(1, &vertex),
+                protobuf_field(2, &status),
+                protobuf_field(3, &log),
+            ]
+            .concat(),
+        );
+
+        let docker = mock_docker_fn(move |req| {
+            let trace = trace.clone();
+            async move {
+                let query: HashMap<_, _> =
+                    url::form_urlencoded::parse(req.uri().query().unwrap().as_bytes())
+                        .into_owned()
+                        .collect();
+                assert_eq!(query["version"], "2");
+                assert_eq!(query["session"], "3b1d8e0c");
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(format!(
+                        "{{\"id\":\"moby.buildkit.trace\",\"aux\":\"{}\"}}\n\
+                         {{\"id\":\"moby.image.id\",\"aux\":{{\"ID\":\"sha256:4e6f\"}}}}\n",
+                        trace
+                    )))
+                    .unwrap()
+            }
+        });
+
+        let results = docker
+            .build_image(
+                BuildImageOptions {
+                    t: "my-image",
+                    version: Some(BuilderVersion::V2),
+                    session: Some("3b1d8e0c"),
+                    ..Default::default()
+                },
+                None,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let status = results[0].buildkit_status().unwrap().unwrap();
+        assert_eq!(status.vertexes.len(), 1);
+        assert_eq!(status.vertexes[0].digest, "sha256:aa");
+        assert_eq!(status.vertexes[0].inputs, vec!["sha256:bb"]);
+        assert_eq!(status.vertexes[0].name, "[2/2] RUN make");
+        assert_eq!(
+            status.vertexes[0].started.unwrap().timestamp_nanos(),
+            1600000000000000500
+        );
+        assert_eq!(status.vertexes[0].completed, None);
+        assert_eq!(status.statuses[0].id, "sha256:layer");
+        assert_eq!(status.statuses[0].current, 512);
+        assert_eq!(status.statuses[0].total, 1024);
+        assert_eq!(status.logs[0].stream, 2);
+        assert_eq!(&status.logs[0].msg[..], b"make: done\n");
+
+        assert!(results[1].buildkit_status().is_none());
+        match &results[1] {
+            BuildImageResults::BuildImageAux { aux } => assert_eq!(aux.id, "sha256:4e6f"),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn build_image_buildkit_trace_invalid() {
+    let results: BuildImageResults =
+        serde_json::from_str(r#"{"id":"moby.buildkit.trace","aux":"CgM="}"#).unwrap();
+
+    match results.buildkit_status().unwrap().unwrap_err().kind() {
+        ErrorKind::BuildKitTraceError { .. } => (),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn start_session_upgrade() {
+    use bollard::buildkit::{Session, SessionOptions};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/session"));
+            assert_eq!(req.headers()["Upgrade"], "h2c");
+            assert_eq!(req.headers()["X-Docker-Expose-Session-Uuid"], "3b1d8e0c");
+            assert_eq!(req.headers()["X-Docker-Expose-Session-Name"], "my-app");
+            assert_eq!(
+                req.headers()
+                    .get_all("X-Docker-Expose-Session-Grpc-Method")
+                    .iter()
+                    .collect::<Vec<_>>(),
+                vec![
+                    "/moby.filesync.v1.FileSync/DiffCopy",
+                    "/moby.filesync.v1.Auth/Credentials"
+                ]
+            );
+            // the docker server starts the HTTP/2 connection to the client
+            tokio::spawn(async move {
+                let mut upgraded = req.into_body().on_upgrade().await.unwrap();
+                upgraded.write_all(b"PRI * HTTP/2.0").await.unwrap();
+            });
+            hyper::Response::builder()
+                .status(101)
+                .header("Connection", "Upgrade")
+                .header("Upgrade", "h2c")
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        let Session {
+            id, mut connection, ..
+        } = docker
+            .start_session(SessionOptions {
+                id: "3b1d8e0c",
+                name: "my-app",
+                shared_key: "my-app",
+                grpc_methods: vec![
+                    "/moby.filesync.v1.FileSync/DiffCopy",
+                    "/moby.filesync.v1.Auth/Credentials",
+                ],
+            })
+            .await
+            .unwrap();
+        assert_eq!(id, "3b1d8e0c");
+
+        let mut preface = Vec::new();
+        connection.read_to_end(&mut preface).await.unwrap();
+        assert_eq!(&preface[..], b"PRI * HTTP/2.0");
+
+        match docker
+            .start_session(SessionOptions {
+                id: "3b1d\n8e0c",
+                ..Default::default()
+            })
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::InvalidHeaderError { name } => {
+                assert_eq!(name, "X-Docker-Expose-Session-Uuid")
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn start_session_serve_credentials() {
+    use bollard::buildkit::{SessionOptions, AUTH_CREDENTIALS_METHOD};
+    use hyper::body::HttpBody;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(move |req| {
+            let tx = tx.lock().unwrap().take().unwrap();
+            // the docker server calls the gRPC methods of the session as an HTTP/2 client
+            tokio::spawn(async move {
+                let upgraded = req.into_body().on_upgrade().await.unwrap();
+                let (mut client, connection) = hyper::client::conn::Builder::new()
+                    .http2_only(true)
+                    .handshake::<_, hyper::Body>(upgraded)
+                    .await
+                    .unwrap();
+                tokio::spawn(connection);
+
+                // CredentialsRequest { Host: "registry.example.com" }
+                let mut frame = vec![0, 0, 0, 0, 22, 0x0a, 20];
+                frame.extend_from_slice(b"registry.example.com");
+                let request = hyper::Request::post(AUTH_CREDENTIALS_METHOD)
+                    .header("Content-Type", "application/grpc")
+                    .body(hyper::Body::from(frame))
+                    .unwrap();
+                let mut response = client.send_request(request).await.unwrap();
+                let message = response.body_mut().data().await.unwrap().unwrap();
+                let trailers = response.body_mut().trailers().await.unwrap().unwrap();
+
+                let request = hyper::Request::post("/moby.filesync.v1.FileSync/DiffCopy")
+                    .body(hyper::Body::empty())
+                    .unwrap();
+                let unimplemented = client.send_request(request).await.unwrap();
+
+                tx.send((message, trailers, unimplemented.headers().clone()))
+                    .unwrap();
+            });
+            async move {
+                hyper::Response::builder()
+                    .status(101)
+                    .header("Connection", "Upgrade")
+                    .header("Upgrade", "h2c")
+                    .body(hyper::Body::empty())
+                    .unwrap()
+            }
+        });
+
+        let session = docker
+            .start_session(SessionOptions {
+                id: "3b1d8e0c",
+                name: "my-app",
+                shared_key: "my-app",
+                grpc_methods: vec![AUTH_CREDENTIALS_METHOD],
+            })
+            .await
+            .unwrap();
+
+        let mut credentials = HashMap::new();
+        credentials.insert(
+            String::from("registry.example.com"),
+            DockerCredentials {
+                username: Some(String::from("bollard")),
+                password: Some(String::from("hunter2")),
+                ..Default::default()
+            },
+        );
+        session.serve_credentials(credentials).await.unwrap();
+
+        let (message, trailers, unimplemented) = rx.await.unwrap();
+        // CredentialsResponse { Username: "bollard", Secret: "hunter2" }
+        assert_eq!(
+            &message[..],
+            &b"\x00\x00\x00\x00\x12\x0a\x07bollard\x12\x07hunter2"[..]
+        );
+        assert_eq!(trailers["grpc-status"], "0");
+        assert_eq!(unimplemented["grpc-status"], "12");
+    });
+}
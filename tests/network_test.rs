@@ -3,13 +3,15 @@ extern crate hyper;
 extern crate tokio;
 
 use bollard::container::*;
-use bollard::errors::Error;
+use bollard::errors::{Error, ErrorKind};
 use bollard::network::*;
 use bollard::Docker;
 
 use tokio::runtime::Runtime;
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[macro_use]
 pub mod common;
@@ -40,7 +42,7 @@ async fn create_network_test(docker: Docker) -> Result<(), Error> {
     let result = &docker
         .inspect_network(
             &result.id,
-            Some(InspectNetworkOptions::<&str> {
+            Some(InspectNetworkOptions {
                 verbose: true,
                 ..Default::default()
             }),
@@ -130,10 +132,10 @@ async fn connect_network_test(docker: Docker) -> Result<(), Error> {
     let connect_network_options = ConnectNetworkOptions {
         container: "integration_test_connect_network_test",
         endpoint_config: EndpointSettings {
-            ipam_config: EndpointIPAMConfig {
+            ipam_config: Some(EndpointIPAMConfig {
                 ipv4_address: Some("10.10.10.101"),
                 ..Default::default()
-            },
+            }),
             ..Default::default()
         },
     };
@@ -151,7 +153,7 @@ async fn connect_network_test(docker: Docker) -> Result<(), Error> {
     let result = &docker
         .inspect_network(
             &id,
-            Some(InspectNetworkOptions::<&str> {
+            Some(InspectNetworkOptions {
                 verbose: true,
                 ..Default::default()
             }),
@@ -251,3 +253,739 @@ fn integration_test_connect_network() {
 fn integration_test_prune_networks() {
     connect_to_docker_and_run!(prune_networks_test);
 }
+
+#[test]
+fn inspect_network_verbose_overlay() {
+    let fixture = r#"{
+        "Name": "integration_test_overlay",
+        "Id": "qd2rpmk8kq6fgvzxw1z8r3dnb",
+        "Created": "2020-04-14T09:12:43.462538744Z",
+        "Scope": "swarm",
+        "Driver": "overlay",
+        "EnableIPv6": false,
+        "IPAM": {
+            "Driver": "default",
+            "Options": null,
+            "Config": [
+                {
+                    "Subnet": "10.0.1.0/24",
+                    "Gateway": "10.0.1.1"
+                }
+            ]
+        },
+        "Internal": false,
+        "Attachable": true,
+        "Ingress": false,
+        "ConfigFrom": {
+            "Network": ""
+        },
+        "ConfigOnly": false,
+        "Containers": null,
+        "Options": {
+            "com.docker.network.driver.overlay.vxlanid_list": "4097"
+        },
+        "Labels": null,
+        "Peers": [
+            {
+                "Name": "5e6e3b1b3e0e",
+                "IP": "192.168.65.3"
+            },
+            {
+                "Name": "9f2a1c8d7b4a",
+                "IP": "192.168.65.4"
+            }
+        ],
+        "Services": {
+            "integration_test_service": {
+                "VIP": "10.0.1.2",
+                "Ports": [],
+                "LocalLBIndex": 257,
+                "Tasks": [
+                    {
+                        "Name": "integration_test_service.1.x7vkaqz0ekj2cxz2iv6qvn9ht",
+                        "EndpointID": "0bd5a1e9b8ae6f7b6c7c3e46d3f7b5d9a1c8e3f4a5b6c7d8e9f0a1b2c3d4e5f6",
+                        "EndpointIP": "10.0.1.3",
+                        "Info": {
+                            "Host IP": "192.168.65.3"
+                        }
+                    }
+                ]
+            },
+            "integration_test_scaled_down": {
+                "VIP": "10.0.1.5",
+                "Ports": null,
+                "LocalLBIndex": 258,
+                "Tasks": null
+            }
+        }
+    }"#;
+
+    let result: InspectNetworkResults = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(result.scope, NetworkScope::Swarm);
+    assert_eq!(result.scope.to_string(), "swarm");
+    assert!(result.containers.is_empty());
+    assert!(result.labels.is_empty());
+
+    let peers = result.peers.unwrap();
+    assert_eq!(peers.len(), 2);
+    assert_eq!(peers[1].ip, "192.168.65.4");
+
+    let services = result.services.unwrap();
+    let service = services.get("integration_test_service").unwrap();
+    assert_eq!(service.vip, "10.0.1.2");
+    assert_eq!(service.tasks[0].endpoint_ip, "10.0.1.3");
+    assert_eq!(
+        service.tasks[0].info.get("Host IP").map(String::as_str),
+        Some("192.168.65.3")
+    );
+
+    let service = services.get("integration_test_scaled_down").unwrap();
+    assert_eq!(service.local_lb_index, 258);
+    assert!(service.ports.is_none());
+    assert!(service.tasks.is_empty());
+}
+
+fn list_networks_fixture(driver: &str) -> String {
+    format!(
+        r#"[
+            {{
+                "Name": "integration_test_network_if_missing_other",
+                "Id": "5d4b1e6c2f2a",
+                "Created": "2020-04-14T09:12:43.462538744Z",
+                "Scope": "local",
+                "Driver": "bridge",
+                "EnableIPv6": false,
+                "IPAM": {{"Driver": "default", "Options": null, "Config": []}},
+                "Internal": false,
+                "Attachable": false,
+                "Ingress": false,
+                "ConfigFrom": {{"Network": ""}},
+                "ConfigOnly": false,
+                "Containers": {{}},
+                "Options": {{}},
+                "Labels": {{}}
+            }},
+            {{
+                "Name": "integration_test_network_if_missing",
+                "Id": "7fd5ec1c9a3e",
+                "Created": "2020-04-14T09:12:43.462538744Z",
+                "Scope": "local",
+                "Driver": "{}",
+                "EnableIPv6": false,
+                "IPAM": {{
+                    "Driver": "default",
+                    "Options": null,
+                    "Config": [{{"Subnet": "10.10.10.0/24", "Gateway": "10.10.10.1"}}]
+                }},
+                "Internal": false,
+                "Attachable": false,
+                "Ingress": false,
+                "ConfigFrom": {{"Network": ""}},
+                "ConfigOnly": false,
+                "Containers": {{}},
+                "Options": {{}},
+                "Labels": {{}}
+            }}
+        ]"#,
+        driver
+    )
+}
+
+#[test]
+fn create_network_if_missing_existing() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.method(), "GET");
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(list_networks_fixture("bridge")))
+                .unwrap()
+        });
+
+        let result = docker
+            .create_network_if_missing(CreateNetworkOptions {
+                name: "integration_test_network_if_missing",
+                driver: "bridge",
+                ipam: IPAM {
+                    config: vec![IPAMConfig {
+                        subnet: Some("10.10.10.0/24"),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.id, "7fd5ec1c9a3e");
+        assert_eq!(result.warning, None);
+    });
+}
+
+#[test]
+fn create_network_if_missing_conflict_mismatch() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let lists = Arc::new(AtomicUsize::new(0));
+        let handler_lists = lists.clone();
+        let docker = mock_docker_fn(move |req| {
+            let lists = handler_lists.clone();
+            async move {
+                let (status, body) = if req.uri().path().ends_with("/networks/create") {
+                    let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    assert_eq!(body["CheckDuplicate"], true);
+                    (
+                        409,
+                        String::from(
+                            r#"{"message":"network with name integration_test_network_if_missing already exists"}"#,
+                        ),
+                    )
+                } else if lists.fetch_add(1, Ordering::SeqCst) == 0 {
+                    // another process creates the network after it is resolved
+                    (200, String::from("[]"))
+                } else {
+                    (200, list_networks_fixture("macvlan"))
+                };
+                hyper::Response::builder()
+                    .status(status)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let result = docker
+            .create_network_if_missing(CreateNetworkOptions {
+                name: "integration_test_network_if_missing",
+                driver: "bridge",
+                ..Default::default()
+            })
+            .await;
+
+        match result.unwrap_err().kind() {
+            ErrorKind::NetworkMismatchError { mismatch } => {
+                assert_eq!(mismatch.network, "integration_test_network_if_missing");
+                assert_eq!(mismatch.field, "Driver");
+                assert_eq!(mismatch.expected, "bridge");
+                assert_eq!(mismatch.actual, "macvlan");
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert_eq!(lists.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn create_network_config_from() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let id = if body["ConfigOnly"] == true {
+                assert_eq!(body["Scope"], "swarm");
+                assert!(body.get("ConfigFrom").is_none());
+                "config"
+            } else {
+                assert_eq!(
+                    body["ConfigFrom"]["Network"],
+                    "integration_test_config_only"
+                );
+                assert!(body.get("Scope").is_none());
+                "overlay"
+            };
+            hyper::Response::builder()
+                .status(201)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(format!(r#"{{"Id": "{}"}}"#, id)))
+                .unwrap()
+        });
+
+        let result = docker
+            .create_network(CreateNetworkOptions {
+                name: "integration_test_config_only",
+                scope: Some(NetworkScope::Swarm),
+                config_only: true,
+                ipam: IPAM {
+                    config: vec![IPAMConfig {
+                        subnet: Some("10.10.20.0/24"),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.id, "config");
+
+        let result = docker
+            .create_network(CreateNetworkOptions {
+                name: "integration_test_config_from",
+                driver: "overlay",
+                config_from: Some(ConfigReference {
+                    network: "integration_test_config_only",
+                }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.id, "overlay");
+    });
+}
+
+#[test]
+fn network_filter_helpers() {
+    let options = ListNetworksOptions::<&str>::default()
+        .filter(NetworkFilter::Dangling(true))
+        .filter(NetworkFilter::Type(NetworkType::Custom))
+        .filter(NetworkFilter::Name("integration"))
+        .into_array()
+        .unwrap();
+    let filters: serde_json::Value = serde_json::from_str(&options[0].1).unwrap();
+    assert_eq!(
+        filters,
+        serde_json::json!({"dangling": ["true"], "type": ["custom"], "name": ["integration"]})
+    );
+
+    let options = PruneNetworksOptions::<&str>::default()
+        .filter(NetworkFilter::Label("maintainer=some_maintainer"))
+        .filter(NetworkFilter::Label("stage"))
+        .into_array()
+        .unwrap();
+    assert_eq!(
+        options[0].1,
+        r#"{"label":["maintainer=some_maintainer","stage"]}"#
+    );
+}
+
+#[test]
+fn find_network_by_name() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.method(), "GET");
+            assert!(req.uri().query().unwrap().contains("filters="));
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(list_networks_fixture("bridge")))
+                .unwrap()
+        });
+
+        let network = docker
+            .find_network_by_name("integration_test_network_if_missing")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(network.id, "7fd5ec1c9a3e");
+
+        let network = docker
+            .find_network_by_name("integration_test_network")
+            .await
+            .unwrap();
+        assert!(network.is_none());
+    });
+}
+
+#[test]
+fn connect_network_omits_unset_endpoint_settings() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body,
+                serde_json::json!({
+                    "Container": "integration_test_connect_network_test",
+                    "EndpointConfig": {
+                        "IPAMConfig": {"IPv4Address": "10.10.10.101"},
+                        "Aliases": ["web"]
+                    }
+                })
+            );
+            hyper::Response::builder()
+                .status(200)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        docker
+            .connect_network(
+                "integration_test_network",
+                ConnectNetworkOptions {
+                    container: "integration_test_connect_network_test",
+                    endpoint_config: EndpointSettings {
+                        ipam_config: Some(EndpointIPAMConfig {
+                            ipv4_address: Some("10.10.10.101"),
+                            ..Default::default()
+                        }),
+                        aliases: Some(vec!["web"]),
+                        ..Default::default()
+                    },
+                },
+            )
+            .await
+            .unwrap();
+    });
+}
+
+#[cfg(feature = "ipnet")]
+#[test]
+fn typed_ipam_config() {
+    use std::convert::TryFrom;
+
+    let config: IPAMConfig<String> = TypedIPAMConfig {
+        subnet: Some("10.10.10.0/24".parse().unwrap()),
+        gateway: Some("10.10.10.1".parse().unwrap()),
+        ..Default::default()
+    }
+    .into();
+    assert_eq!(config.subnet.as_deref(), Some("10.10.10.0/24"));
+    assert_eq!(config.gateway.as_deref(), Some("10.10.10.1"));
+    assert!(config.aux_address.is_none());
+
+    let typed = TypedIPAMConfig::try_from(&IPAMConfig {
+        subnet: Some("2001:db8::/64"),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(typed.subnet, Some("2001:db8::/64".parse().unwrap()));
+
+    let err = TypedIPAMConfig::try_from(&IPAMConfig {
+        subnet: Some("10.10.10.0/24"),
+        gateway: Some("10.10.10.256"),
+        ..Default::default()
+    })
+    .unwrap_err();
+    match err.kind() {
+        ErrorKind::InvalidIPAMConfigError { field, value } => {
+            assert_eq!(field, "Gateway");
+            assert_eq!(value, "10.10.10.256");
+        }
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[cfg(feature = "ipnet")]
+#[test]
+fn create_network_invalid_subnet() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|_| async move {
+            hyper::Response::builder()
+                .status(201)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(r#"{"Id": "invalid"}"#))
+                .unwrap()
+        });
+
+        let err = docker
+            .create_network(CreateNetworkOptions {
+                name: "integration_test_invalid_subnet",
+                ipam: IPAM {
+                    config: vec![IPAMConfig {
+                        subnet: Some("10.10.10.0/33"),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::InvalidIPAMConfigError { field, .. } => assert_eq!(field, "Subnet"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn network_events_connect_disconnect() {
+    use futures_util::stream::TryStreamExt;
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert!(req.uri().path().ends_with("/events"));
+            let query = req.uri().query().unwrap().as_bytes();
+            let (_, filters) = url::form_urlencoded::parse(query)
+                .find(|(key, _)| key == "filters")
+                .unwrap();
+            let filters: serde_json::Value = serde_json::from_str(&filters).unwrap();
+            assert_eq!(filters["network"][0], "7fd5ec1c9a3e");
+            assert_eq!(filters["type"][0], "network");
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(
+                    r#"{"Type":"network","Action":"connect","Actor":{"ID":"7fd5ec1c9a3e","Attributes":{"container":"5a8d8c02a1b5","name":"integration_test_network","type":"bridge"}},"scope":"local","time":1591605600,"timeNano":1591605600101914800}
+{"Type":"network","Action":"disconnect","Actor":{"ID":"7fd5ec1c9a3e","Attributes":{"container":"5a8d8c02a1b5","name":"integration_test_network","type":"bridge"}},"scope":"local","time":1591605702,"timeNano":1591605702661438200}
+{"Type":"network","Action":"destroy","Actor":{"ID":"7fd5ec1c9a3e","Attributes":{"name":"integration_test_network","type":"bridge"}},"scope":"local","time":1591605703,"timeNano":1591605703112190500}
+"#,
+                ))
+                .unwrap()
+        });
+
+        let events = docker
+            .network_events("7fd5ec1c9a3e", None)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let actions: Vec<_> = events.iter().map(|event| event.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                NetworkEventAction::Connect {
+                    container: Some(String::from("5a8d8c02a1b5"))
+                },
+                NetworkEventAction::Disconnect {
+                    container: Some(String::from("5a8d8c02a1b5"))
+                },
+                NetworkEventAction::Destroy,
+            ]
+        );
+        assert_eq!(events[0].name.as_deref(), Some("integration_test_network"));
+        assert_eq!(events[0].attributes["type"], "bridge");
+    });
+}
+
+#[test]
+fn create_network_driver_helpers() {
+    let options = CreateNetworkOptions {
+        name: "integration_test_macvlan",
+        ..CreateNetworkOptions::macvlan("eth0.50", "192.168.50.0/24")
+    }
+    .macvlan_mode(MacvlanMode::Private);
+    let body = serde_json::to_value(&options).unwrap();
+    assert_eq!(body["Name"], "integration_test_macvlan");
+    assert_eq!(body["Driver"], "macvlan");
+    assert_eq!(
+        body["Options"],
+        serde_json::json!({"parent": "eth0.50", "macvlan_mode": "private"})
+    );
+    assert_eq!(body["IPAM"]["Config"][0]["Subnet"], "192.168.50.0/24");
+
+    let options = CreateNetworkOptions::<String>::ipvlan("eth0".into(), "10.20.0.0/16".into())
+        .ipvlan_mode(IpvlanMode::L3);
+    assert_eq!(options.driver, "ipvlan");
+    assert_eq!(options.options["ipvlan_mode"], "l3");
+
+    let options = CreateNetworkOptions::<&str>::overlay().encrypted(true);
+    assert_eq!(options.driver, "overlay");
+    assert!(options.ipam.config.is_empty());
+    assert_eq!(options.options["encrypted"], "");
+    assert!(options.encrypted(false).options.is_empty());
+}
+
+#[test]
+fn ensure_network_labels() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.method(), "GET");
+            let body = list_networks_fixture("bridge").replace(
+                r#""Labels": {}"#,
+                r#""Labels": {"com.example.stack": "web", "com.example.owner": "ops"}"#,
+            );
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(body))
+                .unwrap()
+        });
+
+        let mut labels = HashMap::new();
+        labels.insert("com.example.stack", "web");
+        let id = docker
+            .ensure_network(CreateNetworkOptions {
+                name: "integration_test_network_if_missing",
+                driver: "bridge",
+                labels: labels.clone(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(id, "7fd5ec1c9a3e");
+
+        labels.insert("com.example.stack", "api");
+        let err = docker
+            .ensure_network(CreateNetworkOptions {
+                name: "integration_test_network_if_missing",
+                labels,
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::NetworkMismatchError { mismatch } => {
+                assert_eq!(mismatch.field, "Labels.com.example.stack");
+                assert_eq!(mismatch.expected, "api");
+                assert_eq!(mismatch.actual, "web");
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let err = docker
+            .ensure_network(CreateNetworkOptions {
+                name: "integration_test_network_if_missing",
+                scope: Some(NetworkScope::Swarm),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        match err.kind() {
+            ErrorKind::NetworkMismatchError { mismatch } => {
+                assert_eq!(mismatch.field, "Scope");
+                assert_eq!(mismatch.expected, "swarm");
+                assert_eq!(mismatch.actual, "local");
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn inspect_network_scope() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.uri().query(), Some("verbose=false&scope=local"));
+            let body = list_networks_fixture("bridge");
+            let network: serde_json::Value = serde_json::from_str(&body).unwrap();
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(network[1].to_string()))
+                .unwrap()
+        });
+
+        let result = docker
+            .inspect_network(
+                "integration_test_network_if_missing",
+                Some(InspectNetworkOptions {
+                    scope: Some(NetworkScope::Local),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.scope, NetworkScope::Local);
+    });
+}
+
+#[test]
+fn remove_network_force_disconnects_containers() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler_requests = requests.clone();
+        let docker = mock_docker_fn(move |req| {
+            let requests = handler_requests.clone();
+            async move {
+                let method = req.method().clone();
+                let path = String::from(req.uri().path());
+                let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                let path = &path[path.find("/networks").unwrap()..];
+                requests
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", method, path));
+
+                let (status, body) = if method == "GET" {
+                    let body = list_networks_fixture("bridge");
+                    let mut network: serde_json::Value = serde_json::from_str(&body).unwrap();
+                    network[1]["Containers"] = serde_json::json!({
+                        "5a8d8c02a1b5": {"Name": "web"},
+                        "0c4b2d8e7f31": {"Name": "gone"},
+                        "lb-integration_test_network": {"Name": "integration_test_network-endpoint"}
+                    });
+                    (200, network[1].to_string())
+                } else if method == "POST" {
+                    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    assert_eq!(body["Force"], true);
+                    assert_ne!(body["Container"], "lb-integration_test_network");
+                    if body["Container"] == "0c4b2d8e7f31" {
+                        (404, String::from(r#"{"message": "No such container"}"#))
+                    } else {
+                        (200, String::new())
+                    }
+                } else {
+                    (204, String::new())
+                };
+                hyper::Response::builder()
+                    .status(status)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let disconnected = docker
+            .remove_network_force(NetworkRef::Name("integration_test_network_if_missing"))
+            .await
+            .unwrap();
+        assert_eq!(disconnected, vec![String::from("5a8d8c02a1b5")]);
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "GET /networks/integration_test_network_if_missing",
+                "POST /networks/7fd5ec1c9a3e/disconnect",
+                "POST /networks/7fd5ec1c9a3e/disconnect",
+                "DELETE /networks/7fd5ec1c9a3e",
+            ]
+        );
+    });
+}
+
+#[test]
+fn network_ref_escapes_names() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let path = req.uri().path();
+            assert!(
+                path.ends_with("/networks/team%20a%2Ffrontend%3F%23")
+                    || path.ends_with("/networks/7fd5ec1c"),
+                "unexpected path {}",
+                path
+            );
+            hyper::Response::builder()
+                .status(204)
+                .body(hyper::Body::empty())
+                .unwrap()
+        });
+
+        docker
+            .remove_network(NetworkRef::Name("team a/frontend?#"))
+            .await
+            .unwrap();
+        docker
+            .remove_network(NetworkRef::IdPrefix(String::from("7fd5ec1c")))
+            .await
+            .unwrap();
+    });
+}
+
+#[test]
+fn network_scope_unknown() {
+    let scopes: Vec<NetworkScope> =
+        serde_json::from_str(r#"["local", "swarm", "regional"]"#).unwrap();
+    assert_eq!(
+        scopes,
+        vec![
+            NetworkScope::Local,
+            NetworkScope::Swarm,
+            NetworkScope::Unknown
+        ]
+    );
+}
@@ -0,0 +1,156 @@
+use bollard::errors::{Error, ErrorKind};
+use bollard::image::*;
+use bollard::progress::*;
+
+use tokio::runtime::Runtime;
+
+use std::default::Default;
+
+#[macro_use]
+pub mod common;
+use crate::common::*;
+
+// docker pull redis:6, with the base layer already present
+const PULL_OUTPUT: &str = r#"{"status":"Pulling from library/redis","id":"6"}
+{"status":"Already exists","progressDetail":{},"id":"bb79b6b2107f"}
+{"status":"Pulling fs layer","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Downloading","progressDetail":{"current":1024,"total":4096},"progress":"[============>                                      ]  1.024kB/4.096kB","id":"1ed3521a5dcb"}
+{"status":"Downloading","progressDetail":{"current":4096,"total":4096},"progress":"[==================================================>]  4.096kB/4.096kB","id":"1ed3521a5dcb"}
+{"status":"Download complete","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Extracting","progressDetail":{"current":32768,"total":65536},"progress":"[=========================>                         ]  32.77kB/65.54kB","id":"1ed3521a5dcb"}
+{"status":"Pull complete","progressDetail":{},"id":"1ed3521a5dcb"}
+{"status":"Digest: sha256:800f2587bf3376cb01e6307afe599ddce9439deafbd4fb8562829da96085c9c5"}
+{"status":"Status: Downloaded newer image for redis:6"}
+"#;
+
+#[derive(Default)]
+struct CountingHandler {
+    progress: usize,
+    downloading: usize,
+    errors: Vec<String>,
+    summary: Option<ProgressSummary>,
+    break_after: Option<usize>,
+}
+
+impl ProgressHandler<CreateImageResults> for CountingHandler {
+    fn on_progress(&mut self, record: &CreateImageResults) -> ControlFlow {
+        self.progress += 1;
+        if let CreateImageResults::CreateImageProgressResponse { status, .. } = record {
+            if status == "Downloading" {
+                self.downloading += 1;
+            }
+        }
+        match self.break_after {
+            Some(count) if count == self.progress => ControlFlow::Break,
+            _ => ControlFlow::Continue,
+        }
+    }
+
+    fn on_error(&mut self, err: &Error) {
+        self.errors.push(err.to_string());
+    }
+
+    fn on_complete(&mut self, summary: &ProgressSummary) {
+        self.summary = Some(*summary);
+    }
+}
+
+fn pull_options() -> Option<CreateImageOptions<&'static str>> {
+    Some(CreateImageOptions {
+        from_image: "redis",
+        tag: "6",
+        ..Default::default()
+    })
+}
+
+#[test]
+fn pull_image_with_progress_replay() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(200, PULL_OUTPUT);
+
+        let mut handler = CountingHandler::default();
+        let summary = docker
+            .pull_image_with_progress(pull_options(), None, &mut handler)
+            .await
+            .unwrap();
+
+        assert_eq!(handler.progress, 10);
+        assert_eq!(handler.downloading, 2);
+        assert!(handler.errors.is_empty());
+        assert_eq!(handler.summary, Some(summary));
+        assert_eq!(summary.records, 10);
+        assert!(!summary.cancelled);
+    });
+}
+
+#[test]
+fn pull_image_with_progress_break() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(200, PULL_OUTPUT);
+
+        let mut handler = CountingHandler {
+            break_after: Some(3),
+            ..Default::default()
+        };
+        let summary = docker
+            .pull_image_with_progress(pull_options(), None, &mut handler)
+            .await
+            .unwrap();
+
+        assert_eq!(handler.progress, 3);
+        assert_eq!(summary.records, 3);
+        assert!(summary.cancelled);
+        assert_eq!(handler.summary, Some(summary));
+    });
+}
+
+#[test]
+fn pull_image_with_progress_error() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(
+            200,
+            r#"{"status":"Pulling from library/redis","id":"6"}
+{"errorDetail":{"message":"manifest for redis:6 not found"},"error":"manifest for redis:6 not found"}
+"#,
+        );
+
+        let mut handler = CountingHandler::default();
+        let result = docker
+            .pull_image_with_progress(pull_options(), None, &mut handler)
+            .await;
+
+        match result.unwrap_err().kind() {
+            ErrorKind::DockerStreamError { error } => {
+                assert_eq!(error, "manifest for redis:6 not found")
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert_eq!(handler.progress, 1);
+        assert_eq!(handler.errors.len(), 1);
+        assert_eq!(handler.summary, None);
+    });
+}
+
+struct PanickingHandler;
+
+impl ProgressHandler<CreateImageResults> for PanickingHandler {
+    fn on_progress(&mut self, _record: &CreateImageResults) -> ControlFlow {
+        panic!("progress bar failed");
+    }
+}
+
+#[test]
+#[should_panic(expected = "progress bar failed")]
+fn pull_image_with_progress_panic() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(200, PULL_OUTPUT);
+
+        let _ = docker
+            .pull_image_with_progress(pull_options(), None, PanickingHandler)
+            .await;
+    });
+}
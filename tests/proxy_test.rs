@@ -0,0 +1,127 @@
+use bollard::errors::ErrorKind;
+use bollard::proxy::Proxy;
+use bollard::{Docker, API_DEFAULT_VERSION};
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use tokio::runtime::Runtime;
+
+use std::net::SocketAddr;
+
+// Serves "OK" to requests in absolute form, as sent to a proxy, and refuses any other request.
+fn serve(proxy: bool) -> SocketAddr {
+    let make_service = make_service_fn(move |_| async move {
+        Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| async move {
+            let absolute = req.uri().scheme().is_some();
+            if absolute == proxy {
+                if proxy {
+                    assert_eq!(
+                        req.uri().to_string(),
+                        format!("http://docker.invalid:2375/v{}/_ping", API_DEFAULT_VERSION)
+                    );
+                }
+                Ok::<_, hyper::Error>(Response::new(Body::from("OK")))
+            } else {
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::BAD_GATEWAY;
+                Ok(response)
+            }
+        }))
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+    addr
+}
+
+#[test]
+fn connect_with_http_proxy() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let proxy_addr = serve(true);
+        let proxy = Proxy::all(&format!("http://{}", proxy_addr)).unwrap();
+
+        let docker =
+            Docker::connect_with_http_proxy("docker.invalid:2375", proxy, 10, API_DEFAULT_VERSION)
+                .unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+
+        // loopback addresses are reached directly
+        let docker_addr = serve(false);
+        let proxy = Proxy::all(&format!("http://{}", proxy_addr)).unwrap();
+        let docker = Docker::connect_with_http_proxy(
+            &format!("tcp://{}", docker_addr),
+            proxy,
+            10,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    });
+}
+
+#[test]
+fn invalid_proxy() {
+    match Proxy::all("socks5://proxy.example.com:1080")
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::InvalidProxyError { proxy } => {
+            assert_eq!(proxy, "socks5://proxy.example.com:1080")
+        }
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn connect_with_tls_proxy_credentials() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use std::path::Path;
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        // refuses to tunnel, answering with the CONNECT request it received
+        let mut listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        let request = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 403 Forbidden\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let certs = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/certs");
+        let proxy = Proxy::all(&format!("http://us%3Aer:p%40ss%20w@{}", proxy_addr)).unwrap();
+        let docker = Docker::connect_with_tls_proxy(
+            "docker.invalid:2376",
+            &certs.join("key.pem"),
+            &certs.join("cert.pem"),
+            &certs.join("ca.pem"),
+            proxy,
+            10,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        assert!(docker.ping().await.is_err());
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("CONNECT docker.invalid:2376 HTTP/1.1\r\n"));
+        assert!(request.contains(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64::encode("us:er:p@ss w")
+        )));
+    });
+}
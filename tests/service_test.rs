@@ -1,9 +1,17 @@
+use bollard::audit::{RequestInfo, ResponseInfo, REDACTED};
+use bollard::auth::RegistryAuth;
 use bollard::errors::Error;
 use bollard::service_models::ObjectVersion;
 use bollard::{service::*, Docker};
 
+use futures_util::future;
+use hyper::{Body, Response};
 use tokio::runtime::Runtime;
 
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
 #[macro_use]
 mod common;
 use crate::common::*;
@@ -138,6 +146,58 @@ async fn service_update_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn service_labels_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}nanoserver/iis", registry_http_addr())
+    } else {
+        format!("{}fussybeaver/uhttpd", registry_http_addr())
+    };
+    let spec = ServiceSpec {
+        name: "integration_test_service_labels",
+        task_template: TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(&image),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    docker.create_service(spec, None).await?;
+
+    let mut first = HashMap::new();
+    first.insert(String::from("first"), String::from("1"));
+    let mut second = HashMap::new();
+    second.insert(String::from("second"), String::from("2"));
+
+    let (first, second) = future::join(
+        docker.set_service_labels("integration_test_service_labels", first, true),
+        docker.set_service_labels("integration_test_service_labels", second, true),
+    )
+    .await;
+    first?;
+    second?;
+
+    let labels = docker
+        .inspect_service(
+            "integration_test_service_labels",
+            None::<InspectServiceOptions>,
+        )
+        .await?
+        .spec
+        .labels;
+
+    assert_eq!(labels.get("first").map(String::as_str), Some("1"));
+    assert_eq!(labels.get("second").map(String::as_str), Some("2"));
+
+    docker
+        .delete_service("integration_test_service_labels")
+        .await?;
+
+    Ok(())
+}
+
 #[test]
 #[cfg(unix)]
 fn integration_test_create_service() {
@@ -155,3 +215,345 @@ fn integration_test_list_services() {
 fn integration_test_update_service() {
     connect_to_docker_and_run!(service_update_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_service_labels() {
+    connect_to_docker_and_run!(service_labels_test);
+}
+
+#[test]
+fn service_create_response_warnings() {
+    let fixture = r#"{
+        "ID": "ak7w3gjqoa3kuz8xcpnyy0pvl",
+        "Warnings": [
+            "unable to pin image fussybeaver/uhttpd to digest: image not found",
+            "resolve image: registry unavailable"
+        ]
+    }"#;
+
+    let response: ServiceCreateResponse = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(response.id.unwrap(), "ak7w3gjqoa3kuz8xcpnyy0pvl");
+    assert_eq!(response.warnings.len(), 2);
+    assert!(response.warning.is_none());
+
+    let fixture = r#"{
+        "ID": "ak7w3gjqoa3kuz8xcpnyy0pvl",
+        "Warning": "unable to pin image fussybeaver/uhttpd to digest: image not found"
+    }"#;
+
+    let response: ServiceCreateResponse = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(
+        response.warning.unwrap(),
+        "unable to pin image fussybeaver/uhttpd to digest: image not found"
+    );
+    assert!(response.warnings.is_empty());
+}
+
+#[test]
+fn service_update_response_warnings() {
+    let fixture = r#"{
+        "Warnings": [
+            "unable to pin image fussybeaver/uhttpd to digest: image not found",
+            "resolve image: registry unavailable"
+        ]
+    }"#;
+
+    let response: ServiceUpdateResponse = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(response.warnings.len(), 2);
+
+    let response: ServiceUpdateResponse = serde_json::from_str(r#"{"Warnings": null}"#).unwrap();
+
+    assert!(response.warnings.is_empty());
+}
+
+#[test]
+fn service_create_audit_hooks() {
+    enum Event {
+        Request(RequestInfo),
+        Response(ResponseInfo),
+    }
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let request_events = events.clone();
+        let response_events = events.clone();
+
+        let docker = mock_docker_fn(|req| async move {
+            // the hook sees a redacted copy, the server still receives the credentials
+            assert_ne!(req.headers()["X-Registry-Auth"], REDACTED);
+            assert_eq!(req.headers()["Authorization"], "Bearer 0123456789");
+            Response::builder()
+                .status(201)
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#"{"ID":"ak7w3gjqoa3kuz8xcpnyy0pvl"}"#))
+                .unwrap()
+        })
+        .with_header("Authorization", "Bearer 0123456789")
+        .unwrap()
+        .with_request_hook(Arc::new(move |info| {
+            request_events.lock().unwrap().push(Event::Request(info))
+        }))
+        .with_response_hook(Arc::new(move |info| {
+            response_events.lock().unwrap().push(Event::Response(info))
+        }))
+        .with_correlation_id("deploy-42");
+
+        let spec = ServiceSpec {
+            name: "integration_test_create_service",
+            ..Default::default()
+        };
+        let credentials = RegistryAuth::Basic {
+            username: "bollard".to_string(),
+            password: "hunter2".to_string(),
+        };
+
+        docker
+            .create_service(spec, Some(credentials))
+            .await
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        match (&events[0], &events[1]) {
+            (Event::Request(request), Event::Response(response)) => {
+                assert_eq!(request.method, "POST");
+                assert!(request.path.ends_with("/services/create"));
+                assert_eq!(request.correlation_id.as_deref(), Some("deploy-42"));
+                let auth = request
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name == "x-registry-auth")
+                    .unwrap();
+                assert_eq!(auth.1, REDACTED);
+                let authorization = request
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name == "authorization")
+                    .unwrap();
+                assert_eq!(authorization.1, REDACTED);
+                assert!(request
+                    .body
+                    .as_ref()
+                    .unwrap()
+                    .contains("integration_test_create_service"));
+
+                assert_eq!(response.method, "POST");
+                assert_eq!(response.path, request.path);
+                assert_eq!(response.status, Some(201));
+                assert_eq!(response.correlation_id.as_deref(), Some("deploy-42"));
+            }
+            _ => panic!("hooks invoked out of order"),
+        }
+    });
+}
+
+#[test]
+fn service_labels_retry_out_of_sequence() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let updates = Arc::new(AtomicUsize::new(0));
+        let handler_updates = updates.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let updates = handler_updates.clone();
+            async move {
+                let (status, body) = if req.uri().path().ends_with("/update") {
+                    assert!(req.uri().query().unwrap().contains("version=11"));
+                    if updates.fetch_add(1, Ordering::SeqCst) == 0 {
+                        (500, r#"{"message":"rpc error: code = Unknown desc = update out of sequence"}"#)
+                    } else {
+                        (200, r#"{"Warnings":null}"#)
+                    }
+                } else {
+                    (
+                        200,
+                        r#"{
+                            "ID": "ak7w3gjqoa3kuz8xcpnyy0pvl",
+                            "Version": {"Index": 11},
+                            "CreatedAt": "2020-04-20T09:37:58.213934386Z",
+                            "UpdatedAt": "2020-04-20T09:37:58.213934386Z",
+                            "Spec": {
+                                "Name": "integration_test_service_labels",
+                                "Labels": {"existing": "0"},
+                                "TaskTemplate": {}
+                            },
+                            "Endpoint": {}
+                        }"#,
+                    )
+                };
+                Response::builder()
+                    .status(status)
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let mut labels = HashMap::new();
+        labels.insert(String::from("first"), String::from("1"));
+
+        let result = docker
+            .set_service_labels("integration_test_service_labels", labels.clone(), true)
+            .await
+            .unwrap();
+
+        assert_eq!(updates.load(Ordering::SeqCst), 2);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result["existing"], "0");
+
+        let result = docker
+            .set_service_labels("integration_test_service_labels", labels, false)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result["first"], "1");
+    });
+}
+
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(vec) => vec.iter_mut().for_each(strip_nulls),
+        _ => (),
+    }
+}
+
+#[test]
+fn service_container_spec_secrets_round_trip() {
+    // docker service create --name web --secret source=db_password,target=password,mode=0400 \
+    //   --config source=nginx_conf,target=/etc/nginx/nginx.conf --health-cmd "curl -f localhost" \
+    //   --host db:10.0.0.5 --dns 10.0.0.2 --init fussybeaver/uhttpd
+    let fixture = r#"{
+        "ID": "9mnpnzenvg8p8tdbtq4wvbkcz",
+        "Version": {"Index": 19},
+        "CreatedAt": "2020-04-20T09:37:58.213934386Z",
+        "UpdatedAt": "2020-04-20T09:37:58.213934386Z",
+        "Spec": {
+            "Name": "web",
+            "Labels": {},
+            "TaskTemplate": {
+                "ContainerSpec": {
+                    "Image": "fussybeaver/uhttpd:latest",
+                    "Init": true,
+                    "StopGracePeriod": 10000000000,
+                    "Healthcheck": {
+                        "Test": ["CMD-SHELL", "curl -f localhost"],
+                        "Interval": 30000000000,
+                        "Timeout": 5000000000,
+                        "Retries": 3
+                    },
+                    "Hosts": ["10.0.0.5 db"],
+                    "DNSConfig": {"Nameservers": ["10.0.0.2"]},
+                    "Secrets": [
+                        {
+                            "File": {"Name": "password", "UID": "0", "GID": "0", "Mode": 256},
+                            "SecretID": "fpjqlhnwb19zds35k8wn80lq9",
+                            "SecretName": "db_password"
+                        }
+                    ],
+                    "Configs": [
+                        {
+                            "File": {
+                                "Name": "/etc/nginx/nginx.conf",
+                                "UID": "0",
+                                "GID": "0",
+                                "Mode": 292
+                            },
+                            "ConfigID": "3ahkd2yawnqxnmtuhjw1kxhs1",
+                            "ConfigName": "nginx_conf"
+                        }
+                    ],
+                    "Isolation": "default"
+                },
+                "ForceUpdate": 0,
+                "Runtime": "container"
+            }
+        },
+        "Endpoint": {}
+    }"#;
+
+    let service: Service<String> = serde_json::from_str(fixture).unwrap();
+    let container_spec = service.spec.task_template.container_spec.unwrap();
+
+    let secret = &container_spec.secrets.as_ref().unwrap()[0];
+    assert_eq!(secret.secret_name.as_deref(), Some("db_password"));
+    assert_eq!(secret.file.as_ref().unwrap().mode, Some(0o400));
+    let config = &container_spec.configs.as_ref().unwrap()[0];
+    assert_eq!(config.file.as_ref().unwrap().mode, Some(0o444));
+    assert_eq!(container_spec.init, Some(true));
+    assert_eq!(
+        container_spec.health_check.as_ref().unwrap().retries,
+        Some(3)
+    );
+
+    let mut value = serde_json::to_value(&container_spec).unwrap();
+    strip_nulls(&mut value);
+    let original: serde_json::Value = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(value, original["Spec"]["TaskTemplate"]["ContainerSpec"]);
+}
+
+#[test]
+fn service_published_port_for() {
+    let fixture = r#"{
+        "ID": "9mnpnzenvg8p8tdbtq4wvbkcz",
+        "Version": {"Index": 19},
+        "CreatedAt": "2020-04-20T09:37:58.213934386Z",
+        "UpdatedAt": "2020-04-20T09:37:58.213934386Z",
+        "Spec": {
+            "Name": "web",
+            "Labels": {},
+            "TaskTemplate": {
+                "ContainerSpec": {"Image": "fussybeaver/uhttpd:latest"}
+            },
+            "EndpointSpec": {
+                "Mode": "vip",
+                "Ports": [
+                    {"Protocol": "tcp", "TargetPort": 80, "PublishMode": "ingress"},
+                    {"Protocol": "udp", "TargetPort": 53, "PublishMode": "host"}
+                ]
+            }
+        },
+        "Endpoint": {
+            "Spec": {},
+            "Ports": [
+                {
+                    "Protocol": "tcp",
+                    "TargetPort": 80,
+                    "PublishedPort": 30000,
+                    "PublishMode": "ingress"
+                }
+            ]
+        }
+    }"#;
+
+    let service: Service<String> = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(
+        service.published_port_for(80, EndpointPortConfigProtocol::Tcp),
+        Some(30000)
+    );
+    assert_eq!(
+        service.published_port_for(80, EndpointPortConfigProtocol::Udp),
+        None
+    );
+    // host mode port without a fixed published port
+    assert_eq!(
+        service.published_port_for(53, EndpointPortConfigProtocol::Udp),
+        None
+    );
+    let ports = service.spec.endpoint_spec.unwrap().ports.unwrap();
+    assert_eq!(
+        ports[1].publish_mode,
+        Some(EndpointPortConfigPublishMode::Host)
+    );
+}
@@ -0,0 +1,197 @@
+//! Compare the fields of the result structs with the definitions of the Docker Engine API
+//! swagger, to catch fields added to or removed from the API.
+//!
+//! The upstream swagger is read from the JSON file named by the `DOCKER_API_SWAGGER` environment
+//! variable, as fetched and converted by the `test_swagger` CI job from the API 1.40
+//! `swagger.yaml` of the moby `v19.03.8` tag, the version of the CI docker server. Bump the tag
+//! deliberately to compare against a newer API. Without the variable, the test falls back to the
+//! embedded excerpt, which only guards the comparison itself.
+use bollard::network::*;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fmt;
+use std::fs;
+
+const SWAGGER_EXCERPT: &str = include_str!("swagger/v1.40.json");
+const ALLOWLIST: &str = include_str!("swagger/allowlist.txt");
+
+/// Carries the field names of a struct out of a deserializer.
+#[derive(Debug)]
+struct FieldNames(Vec<&'static str>);
+
+impl fmt::Display for FieldNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl std::error::Error for FieldNames {}
+
+impl de::Error for FieldNames {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        FieldNames(Vec::new())
+    }
+}
+
+/// A deserializer that fails with the field names the deserialized struct asks for, as renamed
+/// by serde.
+struct FieldNamesDeserializer;
+
+impl<'de> Deserializer<'de> for FieldNamesDeserializer {
+    type Error = FieldNames;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, FieldNames> {
+        Err(FieldNames(Vec::new()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, FieldNames> {
+        Err(FieldNames(fields.to_vec()))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+fn crate_fields<'de, T: Deserialize<'de>>() -> BTreeSet<String> {
+    match T::deserialize(FieldNamesDeserializer) {
+        Err(FieldNames(fields)) => fields.iter().map(|field| String::from(*field)).collect(),
+        Ok(_) => unreachable!(),
+    }
+}
+
+fn swagger() -> Value {
+    let swagger = match env::var("DOCKER_API_SWAGGER") {
+        Ok(path) => fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read the swagger at {}: {}", path, e)),
+        Err(_) => String::from(SWAGGER_EXCERPT),
+    };
+    serde_json::from_str(&swagger).unwrap()
+}
+
+fn swagger_fields(definition: &str) -> BTreeSet<String> {
+    let swagger = swagger();
+    swagger["definitions"][definition]["properties"]
+        .as_object()
+        .unwrap_or_else(|| panic!("no definition {} in the swagger", definition))
+        .keys()
+        .cloned()
+        .collect()
+}
+
+fn allowed(type_name: &str, field: &str) -> bool {
+    let entry = format!("{}.{}", type_name, field);
+    ALLOWLIST
+        .lines()
+        .map(str::trim)
+        .any(|line| !line.starts_with('#') && line == entry)
+}
+
+/// Describe the fields of a struct missing from a swagger definition and vice versa, or return
+/// `None` if they match.
+fn parity<'de, T: Deserialize<'de>>(type_name: &str, definition: &str) -> Option<String> {
+    let crate_fields = crate_fields::<T>();
+    let swagger_fields = swagger_fields(definition);
+
+    let missing_from_crate: Vec<_> = swagger_fields
+        .difference(&crate_fields)
+        .filter(|field| !allowed(type_name, field))
+        .cloned()
+        .collect();
+    let missing_from_swagger: Vec<_> = crate_fields
+        .difference(&swagger_fields)
+        .filter(|field| !allowed(type_name, field))
+        .cloned()
+        .collect();
+
+    if missing_from_crate.is_empty() && missing_from_swagger.is_empty() {
+        return None;
+    }
+
+    let mut report = format!("{} (swagger definition {}):\n", type_name, definition);
+    if !missing_from_crate.is_empty() {
+        report.push_str(&format!(
+            "  in the swagger, missing from the crate: {}\n",
+            missing_from_crate.join(", ")
+        ));
+    }
+    if !missing_from_swagger.is_empty() {
+        report.push_str(&format!(
+            "  in the crate, missing from the swagger: {}\n",
+            missing_from_swagger.join(", ")
+        ));
+    }
+    Some(report)
+}
+
+fn assert_parity(reports: Vec<Option<String>>) {
+    let reports: Vec<_> = reports.into_iter().flatten().collect();
+    if !reports.is_empty() {
+        panic!(
+            "structs out of sync with the swagger, add or remove the fields, or list them in \
+             tests/swagger/allowlist.txt:\n{}",
+            reports.concat()
+        );
+    }
+}
+
+#[test]
+fn network_swagger_parity() {
+    assert_parity(vec![
+        parity::<InspectNetworkResults>("InspectNetworkResults", "Network"),
+        parity::<ListNetworksResults>("ListNetworksResults", "Network"),
+        parity::<IPAM<String>>("IPAM", "IPAM"),
+        parity::<InspectNetworkResultsContainers>(
+            "InspectNetworkResultsContainers",
+            "NetworkContainer",
+        ),
+        parity::<EndpointSettings<String>>("EndpointSettings", "EndpointSettings"),
+        parity::<EndpointIPAMConfig<String>>("EndpointIPAMConfig", "EndpointIPAMConfig"),
+    ]);
+}
+
+#[test]
+fn swagger_parity_report() {
+    #[derive(serde_derive::Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    #[allow(dead_code)]
+    struct OutdatedNetworkContainer {
+        name: String,
+        #[serde(rename = "EndpointID")]
+        endpoint_id: String,
+        #[serde(rename = "IPv4Address")]
+        ipv4_address: String,
+        #[serde(rename = "IPv6Address")]
+        ipv6_address: String,
+        ip_address: String,
+    }
+
+    assert_eq!(
+        parity::<OutdatedNetworkContainer>("OutdatedNetworkContainer", "NetworkContainer"),
+        Some(String::from(
+            "OutdatedNetworkContainer (swagger definition NetworkContainer):\n  \
+             in the swagger, missing from the crate: MacAddress\n  \
+             in the crate, missing from the swagger: IpAddress\n"
+        ))
+    );
+
+    assert_eq!(
+        parity::<InspectNetworkResultsContainers>(
+            "InspectNetworkResultsContainers",
+            "NetworkContainer"
+        ),
+        None
+    );
+}
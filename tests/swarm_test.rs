@@ -0,0 +1,296 @@
+use bollard::errors::ErrorKind;
+use bollard::node::*;
+use bollard::swarm::*;
+
+use hyper::{Body, Response};
+use tokio::runtime::Runtime;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[macro_use]
+pub mod common;
+use crate::common::*;
+
+fn swarm_fixture(subject: &str, public_key: &str) -> String {
+    format!(
+        r#"{{
+            "ID": "abajmipo7b4xz5ip2nrla6b11",
+            "Version": {{"Index": 373531}},
+            "CreatedAt": "2016-08-18T10:44:24.496525531Z",
+            "UpdatedAt": "2017-08-09T07:09:37.632105588Z",
+            "Spec": {{
+                "Name": "default",
+                "Labels": {{}},
+                "Orchestration": {{"TaskHistoryRetentionLimit": 10}},
+                "Raft": {{
+                    "SnapshotInterval": 10000,
+                    "KeepOldSnapshots": 0,
+                    "LogEntriesForSlowFollowers": 500,
+                    "ElectionTick": 3,
+                    "HeartbeatTick": 1
+                }},
+                "Dispatcher": {{"HeartbeatPeriod": 5000000000}},
+                "CAConfig": {{
+                    "NodeCertExpiry": 7776000000000000,
+                    "ExternalCAs": [
+                        {{
+                            "Protocol": "cfssl",
+                            "URL": "https://ca.example.com/sign",
+                            "Options": {{}},
+                            "CACert": "-----BEGIN CERTIFICATE-----"
+                        }}
+                    ],
+                    "ForceRotate": 2
+                }},
+                "EncryptionConfig": {{"AutoLockManagers": false}},
+                "TaskDefaults": {{}}
+            }},
+            "TLSInfo": {{
+                "TrustRoot": "-----BEGIN CERTIFICATE-----",
+                "CertIssuerSubject": "{}",
+                "CertIssuerPublicKey": "{}"
+            }},
+            "RootRotationInProgress": false,
+            "DataPathPort": 4789,
+            "DefaultAddrPool": ["10.10.0.0/16"],
+            "SubnetSize": 24,
+            "JoinTokens": {{"Worker": "SWMTKN-1-worker", "Manager": "SWMTKN-1-manager"}}
+        }}"#,
+        subject, public_key
+    )
+}
+
+#[test]
+fn swarm_ca_config_fixture() {
+    let swarm: Swarm =
+        serde_json::from_str(&swarm_fixture("MBMxETAPBgNV", "MFkwEwYHKoZI")).unwrap();
+
+    let ca_config = swarm.spec.ca_config.unwrap();
+    assert_eq!(ca_config.force_rotate, Some(2));
+    assert_eq!(ca_config.external_cas.len(), 1);
+    assert_eq!(
+        ca_config.external_cas[0].url.as_deref(),
+        Some("https://ca.example.com/sign")
+    );
+    assert_eq!(
+        swarm.tls_info.cert_issuer_subject.as_deref(),
+        Some("MBMxETAPBgNV")
+    );
+}
+
+#[test]
+fn rotate_swarm_ca_waits_for_new_issuer() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let inspects = Arc::new(AtomicUsize::new(0));
+        let handler_inspects = inspects.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let inspects = handler_inspects.clone();
+            async move {
+                let body = if req.uri().path().ends_with("/swarm/update") {
+                    assert!(req.uri().query().unwrap().contains("version=373531"));
+                    let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                    let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    assert_eq!(spec["CAConfig"]["ForceRotate"], 3);
+                    String::new()
+                } else if inspects.fetch_add(1, Ordering::SeqCst) < 2 {
+                    swarm_fixture("MBMxETAPBgNV", "MFkwEwYHKoZI")
+                } else {
+                    // a swarm generated CA keeps its subject, but not its key
+                    swarm_fixture("MBMxETAPBgNV", "MFkwEwYHKoZJ")
+                };
+                Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let swarm = docker
+            .rotate_swarm_ca(RotateSwarmCAOptions {
+                interval: Duration::from_millis(10),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(inspects.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            swarm.tls_info.cert_issuer_public_key.as_deref(),
+            Some("MFkwEwYHKoZJ")
+        );
+    });
+}
+
+#[test]
+fn rotate_swarm_ca_timeout() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            let body = if req.uri().path().ends_with("/swarm/update") {
+                String::new()
+            } else {
+                swarm_fixture("MBMxETAPBgNV", "MFkwEwYHKoZI")
+            };
+            Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        });
+
+        let error = docker
+            .rotate_swarm_ca(RotateSwarmCAOptions {
+                interval: Duration::from_millis(10),
+                timeout: Duration::from_millis(50),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+
+        match error.kind() {
+            ErrorKind::SwarmCARotationTimeoutError { timeout } => {
+                assert_eq!(*timeout, Duration::from_millis(50))
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn list_nodes_tls_info() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker(
+            200,
+            r#"[
+                {
+                    "ID": "24ifsmvkjbyhk",
+                    "Version": {"Index": 8},
+                    "CreatedAt": "2016-06-07T20:31:11.853781916Z",
+                    "UpdatedAt": "2016-06-07T20:31:11.999868824Z",
+                    "Spec": {
+                        "Name": "my-node",
+                        "Labels": {"foo": "bar"},
+                        "Role": "manager",
+                        "Availability": "active"
+                    },
+                    "Description": {
+                        "Hostname": "bf3067039e47",
+                        "Platform": {"Architecture": "x86_64", "OS": "linux"},
+                        "Engine": {"EngineVersion": "19.03.8"},
+                        "TLSInfo": {
+                            "TrustRoot": "-----BEGIN CERTIFICATE-----",
+                            "CertIssuerSubject": "MBMxETAPBgNV",
+                            "CertIssuerPublicKey": "MFkwEwYHKoZI"
+                        }
+                    },
+                    "Status": {"State": "ready", "Addr": "172.17.0.2"},
+                    "ManagerStatus": {
+                        "Leader": true,
+                        "Reachability": "reachable",
+                        "Addr": "10.0.0.46:2377"
+                    }
+                }
+            ]"#,
+        );
+
+        let nodes = docker
+            .list_nodes(None::<ListNodesOptions<String>>)
+            .await
+            .unwrap();
+
+        assert_eq!(nodes.len(), 1);
+        let tls_info = nodes[0].description.tls_info.as_ref().unwrap();
+        assert_eq!(
+            tls_info.cert_issuer_public_key.as_deref(),
+            Some("MFkwEwYHKoZI")
+        );
+        assert_eq!(nodes[0].manager_status.as_ref().unwrap().leader, Some(true));
+    });
+}
+
+#[test]
+fn set_node_labels_retries_out_of_sequence() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let updates = Arc::new(AtomicUsize::new(0));
+        let submitted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler_updates = updates.clone();
+        let handler_submitted = submitted.clone();
+
+        let docker = mock_docker_fn(move |req| {
+            let updates = handler_updates.clone();
+            let submitted = handler_submitted.clone();
+            async move {
+                let (status, body) = if req.uri().path().ends_with("/nodes/my-node/update") {
+                    assert_eq!(req.uri().query(), Some("version=8"));
+                    let spec = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                    submitted
+                        .lock()
+                        .unwrap()
+                        .push(serde_json::from_slice::<serde_json::Value>(&spec).unwrap());
+                    if updates.fetch_add(1, Ordering::SeqCst) == 0 {
+                        (
+                            500,
+                            r#"{"message":"rpc error: code = Unknown desc = update out of sequence"}"#,
+                        )
+                    } else {
+                        (200, "")
+                    }
+                } else {
+                    assert!(req.uri().path().ends_with("/nodes/my-node"));
+                    (
+                        200,
+                        r#"{
+                            "ID": "24ifsmvkjbyhk",
+                            "Version": {"Index": 8},
+                            "CreatedAt": "2016-06-07T20:31:11.853781916Z",
+                            "UpdatedAt": "2016-06-07T20:31:11.999868824Z",
+                            "Spec": {
+                                "Name": "my-node",
+                                "Labels": {"foo": "bar"},
+                                "Role": "manager",
+                                "Availability": "active"
+                            },
+                            "Description": {"Hostname": "bf3067039e47"}
+                        }"#,
+                    )
+                };
+                Response::builder()
+                    .status(status)
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap()
+            }
+        });
+
+        let mut labels = std::collections::HashMap::new();
+        labels.insert(String::from("zone"), String::from("a"));
+
+        let result = docker
+            .set_node_labels("my-node", labels.clone(), true)
+            .await
+            .unwrap();
+
+        assert_eq!(updates.load(Ordering::SeqCst), 2);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result["foo"], "bar");
+        let spec = submitted.lock().unwrap().pop().unwrap();
+        assert_eq!(spec["Labels"]["zone"], "a");
+        assert_eq!(spec["Labels"]["foo"], "bar");
+        assert_eq!(spec["Role"], "manager");
+
+        let result = docker
+            .set_node_labels("my-node", labels, false)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result["zone"], "a");
+    });
+}
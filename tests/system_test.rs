@@ -38,11 +38,14 @@ async fn events_test(docker: Docker) -> Result<(), Error> {
         if cfg!(windows) {
             None
         } else {
-            Some(DockerCredentials {
-                username: Some("bollard".to_string()),
-                password: std::env::var("REGISTRY_PASSWORD").ok(),
-                ..Default::default()
-            })
+            Some(
+                DockerCredentials {
+                    username: Some("bollard".to_string()),
+                    password: std::env::var("REGISTRY_PASSWORD").ok(),
+                    ..Default::default()
+                }
+                .into(),
+            )
         },
     );
 
@@ -82,13 +85,7 @@ async fn df_test(docker: Docker) -> Result<(), Error> {
     let c: Vec<&bollard::image::APIImages> = result
         .images
         .iter()
-        .filter(|c| {
-            if let Some(ref repo_tags) = c.repo_tags {
-                repo_tags.iter().any(|r| r.contains("hello-world"))
-            } else {
-                false
-            }
-        })
+        .filter(|c| c.repo_tags.iter().any(|r| r.contains("hello-world")))
         .collect();
 
     assert!(c.len() > 0);
@@ -105,3 +102,153 @@ fn integration_test_events() {
 fn integration_test_df() {
     connect_to_docker_and_run!(df_test);
 }
+
+#[test]
+fn raw_stream_newline_delimited() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let body = "{\"status\":\"Downloading\"}\n{\"status\":\"Installed plugin\"}\n";
+
+        let docker = mock_docker(200, body);
+        let items: Vec<serde_json::Value> = docker
+            .raw_stream::<serde_json::Value, _, _, _, _>(
+                hyper::Method::POST,
+                "/plugins/pull",
+                Some(vec![("remote", "vieux/sshfs")]),
+                Some(Vec::<serde_json::Value>::new()),
+            )
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["status"], "Installed plugin");
+
+        let docker = mock_docker(200, body);
+        let bytes = concat_byte_stream(docker.raw_stream_bytes(
+            hyper::Method::POST,
+            "/plugins/pull",
+            None::<Vec<(&str, &str)>>,
+            None::<()>,
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(bytes, body.as_bytes());
+
+        let docker = mock_docker(404, r#"{"message":"plugin not found"}"#);
+        let result = docker
+            .raw_stream::<serde_json::Value, _, _, _, _>(
+                hyper::Method::GET,
+                "/plugins/missing/json",
+                None::<Vec<(&str, &str)>>,
+                None::<()>,
+            )
+            .try_collect::<Vec<_>>()
+            .await;
+
+        assert!(result.is_err());
+    });
+}
+
+#[test]
+fn events_stream_idle_timeout() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        // the server sends a single event, then keeps the connection open without sending data
+        let handler = |_: hyper::Request<hyper::Body>| async move {
+            let (mut sender, body) = hyper::Body::channel();
+            tokio::spawn(async move {
+                sender
+                    .send_data(hyper::body::Bytes::from(
+                        "{\"Type\":\"container\",\"Action\":\"start\",\"Actor\":{\"ID\":\"5a1f\",\"Attributes\":{}},\"time\":1589000000,\"timeNano\":1589000000000000000,\"scope\":\"local\"}\n",
+                    ))
+                    .await
+                    .unwrap();
+                tokio::time::delay_for(std::time::Duration::from_secs(60)).await;
+                drop(sender);
+            });
+            hyper::Response::builder().status(200).body(body).unwrap()
+        };
+
+        let docker =
+            mock_docker_fn(handler).with_stream_idle_timeout(std::time::Duration::from_millis(200));
+        let mut events = Box::pin(docker.events(None::<EventsOptions<String>>));
+
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.action, "start");
+
+        match events.next().await.unwrap().unwrap_err().kind() {
+            bollard::errors::ErrorKind::StreamIdleError { idle } => {
+                assert_eq!(*idle, std::time::Duration::from_millis(200))
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert!(events.next().await.is_none());
+
+        // without the timeout, a quiet stream is left waiting
+        let docker = mock_docker_fn(handler);
+        let mut events = Box::pin(docker.events(None::<EventsOptions<String>>));
+
+        events.next().await.unwrap().unwrap();
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(500), events.next())
+            .await
+            .is_err());
+    });
+}
+
+#[test]
+fn events_reconnecting_resumes_since_last_event() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let queries = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        // every subscription receives a single event, then the connection goes quiet
+        let requests = queries.clone();
+        let handler = move |req: hyper::Request<hyper::Body>| {
+            let requests = requests.clone();
+            async move {
+                if req.uri().path().ends_with("/_ping") {
+                    return hyper::Response::new(hyper::Body::from("OK"));
+                }
+                let subscription = {
+                    let mut requests = requests.lock().unwrap();
+                    requests.push(req.uri().query().unwrap_or_default().to_owned());
+                    requests.len()
+                };
+                let (mut sender, body) = hyper::Body::channel();
+                tokio::spawn(async move {
+                    sender
+                        .send_data(hyper::body::Bytes::from(format!(
+                            "{{\"Type\":\"container\",\"Action\":\"start\",\"Actor\":{{\"ID\":\"{}\",\"Attributes\":{{}}}},\"time\":1589000000,\"timeNano\":1589000000000000000,\"scope\":\"local\"}}\n",
+                            subscription
+                        )))
+                        .await
+                        .unwrap();
+                    tokio::time::delay_for(std::time::Duration::from_secs(60)).await;
+                    drop(sender);
+                });
+                hyper::Response::builder().status(200).body(body).unwrap()
+            }
+        };
+
+        let docker =
+            mock_docker_fn(handler).with_stream_idle_timeout(std::time::Duration::from_millis(200));
+        let mut filters = std::collections::HashMap::new();
+        filters.insert("type", vec!["container"]);
+        let events: Vec<EventsResults> = docker
+            .events_reconnecting(filters)
+            .take(2)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(events[0].actor.id, "1");
+        assert_eq!(events[1].actor.id, "2");
+
+        let queries = queries.lock().unwrap();
+        assert!(!queries[0].contains("since="));
+        assert!(queries[1].contains("since=1589000000.000000001"));
+        assert!(queries[1].contains("filters="));
+    });
+}
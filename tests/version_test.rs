@@ -1,9 +1,17 @@
+use bollard::container::{ContainerStateChange, LogsOptions, StopContainerOptions};
+use bollard::errors::ErrorKind;
 use bollard::system::Version;
 use bollard::{ClientVersion, Docker};
+use futures_util::stream::StreamExt;
+use hyper::{Body, Request, Response};
 use tokio::runtime::Runtime;
+use tokio::time::delay_for;
+
+use std::time::{Duration, Instant};
 
 #[macro_use]
 mod common;
+use crate::common::*;
 
 #[cfg(windows)]
 #[test]
@@ -87,3 +95,718 @@ fn test_downversioning() {
     };
     rt.block_on(fut);
 }
+
+#[test]
+fn shutdown_drains_in_flight_requests() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|_| async {
+            delay_for(Duration::from_millis(200)).await;
+            Response::builder().status(204).body(Body::empty()).unwrap()
+        });
+
+        let client = docker.clone();
+        let request = tokio::spawn(async move {
+            client
+                .stop_container("integration_test_shutdown", None::<StopContainerOptions>)
+                .await
+        });
+        delay_for(Duration::from_millis(50)).await;
+
+        assert!(docker.shutdown(Duration::from_secs(5)).await);
+        assert_eq!(
+            request.await.unwrap().unwrap(),
+            ContainerStateChange::Changed
+        );
+
+        match docker
+            .stop_container("integration_test_shutdown", None::<StopContainerOptions>)
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::ClientShuttingDownError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn shutdown_aborts_after_grace_period() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req: Request<Body>| async move {
+            if req.uri().path().ends_with("/logs") {
+                let (mut sender, body) = Body::channel();
+                tokio::spawn(async move {
+                    sender.send_data("line\n".into()).await.unwrap();
+                    delay_for(Duration::from_secs(60)).await;
+                });
+                Response::builder().status(200).body(body).unwrap()
+            } else {
+                delay_for(Duration::from_secs(60)).await;
+                Response::builder().status(204).body(Body::empty()).unwrap()
+            }
+        });
+
+        let mut logs = docker.logs(
+            "integration_test_shutdown",
+            Some(LogsOptions {
+                follow: true,
+                stdout: true,
+                ..Default::default()
+            }),
+        );
+        assert_eq!(format!("{}", logs.next().await.unwrap().unwrap()), "line");
+
+        let client = docker.clone();
+        let request = tokio::spawn(async move {
+            client
+                .stop_container("integration_test_shutdown", None::<StopContainerOptions>)
+                .await
+        });
+        delay_for(Duration::from_millis(50)).await;
+
+        let started = Instant::now();
+        assert!(!docker.shutdown(Duration::from_millis(200)).await);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        match logs.next().await.unwrap().unwrap_err().kind() {
+            ErrorKind::ClientShuttingDownError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert!(logs.next().await.is_none());
+
+        match request.await.unwrap().unwrap_err().kind() {
+            ErrorKind::ClientShuttingDownError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[cfg(feature = "ssh")]
+#[test]
+fn connect_with_ssh_address() {
+    for addr in &["ssh://deploy@build-01.example.com:2222", "ssh://build-01"] {
+        Docker::connect_with_ssh(addr, 120, bollard::API_DEFAULT_VERSION)
+            .unwrap_or_else(|e| panic!("rejected {}: {}", addr, e));
+    }
+
+    for addr in &[
+        "tcp://build-01:2375",
+        "ssh://build-01/var/run/docker.sock",
+        "build-01",
+    ] {
+        match Docker::connect_with_ssh(addr, 120, bollard::API_DEFAULT_VERSION)
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::InvalidSshAddressError { .. } => (),
+            kind => panic!("unexpected error for {}: {:?}", addr, kind),
+        }
+    }
+}
+
+// Connects to a fixed address whatever the uri, as a connector for an unsupported transport would.
+#[derive(Clone)]
+struct FixedConnector(std::net::SocketAddr);
+
+impl hyper::service::Service<hyper::Uri> for FixedConnector {
+    type Response = tokio::net::TcpStream;
+    type Error = std::io::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: hyper::Uri) -> Self::Future {
+        assert_eq!(uri.host(), Some("docker.vsock"));
+        Box::pin(tokio::net::TcpStream::connect(self.0))
+    }
+}
+
+#[test]
+fn connect_with_connector() {
+    use hyper::service::{make_service_fn, service_fn};
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, hyper::Error>(service_fn(|req: Request<Body>| async move {
+                assert_eq!(req.uri().path(), "/v1.40/_ping");
+                Ok::<_, hyper::Error>(Response::new(Body::from("OK")))
+            }))
+        });
+        let server = hyper::Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let docker = Docker::connect_with_connector(
+            FixedConnector(addr),
+            "docker.vsock",
+            10,
+            &ClientVersion {
+                major_version: 1,
+                minor_version: 40,
+            },
+        )
+        .unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    });
+}
+
+#[test]
+fn per_request_timeout() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|_| async {
+            delay_for(Duration::from_millis(300)).await;
+            Response::new(Body::from("OK"))
+        });
+
+        match docker
+            .clone()
+            .with_timeout(Duration::from_millis(50))
+            .ping()
+            .await
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::RequestTimeoutError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        assert_eq!(docker.clone().without_timeout().ping().await.unwrap(), "OK");
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    });
+}
+
+#[test]
+fn default_headers() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req: Request<Body>| async move {
+            let header = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            Response::new(Body::from(format!(
+                "{} {} {}",
+                header("authorization"),
+                header("user-agent"),
+                header("content-type")
+            )))
+        })
+        .with_header("Authorization", "Bearer token")
+        .unwrap()
+        .with_user_agent("auditor/1.0")
+        .unwrap()
+        .with_header("Content-Type", "text/plain")
+        .unwrap();
+
+        assert_eq!(
+            docker.ping().await.unwrap(),
+            "Bearer token auditor/1.0 application/json"
+        );
+
+        match docker
+            .with_header("Authorization", "Bearer\n")
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::InvalidHeaderError { name } => assert_eq!(name, "Authorization"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+#[test]
+fn custom_runtime() {
+    use bollard::runtime::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // Runs tasks and timers on tokio, counting them.
+    #[derive(Debug, Clone, Default)]
+    struct CountingRuntime {
+        spawned: Arc<AtomicUsize>,
+        delays: Arc<AtomicUsize>,
+    }
+
+    impl bollard::runtime::Runtime for CountingRuntime {
+        fn spawn(&self, task: BoxFuture) {
+            self.spawned.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(task);
+        }
+
+        fn delay(&self, duration: Duration) -> BoxFuture {
+            self.delays.fetch_add(1, Ordering::SeqCst);
+            Box::pin(delay_for(duration))
+        }
+    }
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let runtime = CountingRuntime::default();
+        let docker = mock_docker_fn(|req: Request<Body>| async move {
+            if req.uri().path().ends_with("/version") {
+                delay_for(Duration::from_millis(300)).await;
+            }
+            Response::new(Body::from("OK"))
+        })
+        .with_runtime(runtime.clone())
+        .with_timeout(Duration::from_millis(50));
+
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert!(runtime.spawned.load(Ordering::SeqCst) > 0);
+        assert_eq!(runtime.delays.load(Ordering::SeqCst), 1);
+
+        match docker.version().await.unwrap_err().kind() {
+            ErrorKind::RequestTimeoutError => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert_eq!(runtime.delays.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn layers() {
+    use bollard::layer::{DockerService, Layer, Service};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    // Counts the requests, and fails requests for the version without sending them.
+    #[derive(Debug, Clone)]
+    struct Fault<S> {
+        inner: S,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl<S> Service<Request<Body>> for Fault<S>
+    where
+        S: Service<Request<Body>, Response = Response<Body>, Error = bollard::errors::Error>,
+        S::Future: Send + 'static,
+    {
+        type Response = Response<Body>;
+        type Error = Box<dyn std::error::Error + Send + Sync>;
+        type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx).map_err(Into::into)
+        }
+
+        fn call(&mut self, req: Request<Body>) -> Self::Future {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if req.uri().path().ends_with("/version") {
+                let err: Self::Error = "injected fault".into();
+                return Box::pin(async move { Err::<Response<Body>, _>(err) });
+            }
+            let response = self.inner.call(req);
+            Box::pin(async move {
+                let response = response.await?;
+                Ok::<_, Box<dyn std::error::Error + Send + Sync>>(response)
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct FaultLayer(Arc<AtomicUsize>);
+
+    impl Layer<DockerService> for FaultLayer {
+        type Service = Fault<DockerService>;
+
+        fn layer(&self, inner: DockerService) -> Self::Service {
+            Fault {
+                inner,
+                calls: self.0.clone(),
+            }
+        }
+    }
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let outer = Arc::new(AtomicUsize::new(0));
+        let inner = Arc::new(AtomicUsize::new(0));
+        let docker = mock_docker_fn(|_| async { Response::new(Body::from("OK")) })
+            .with_layer(FaultLayer(inner.clone()))
+            .with_layer(FaultLayer(outer.clone()));
+
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(inner.load(Ordering::SeqCst), 1);
+        assert_eq!(outer.load(Ordering::SeqCst), 1);
+
+        match docker.version().await.unwrap_err().kind() {
+            ErrorKind::LayerError { err } => assert_eq!(err.to_string(), "injected fault"),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        // the outer layer failed the request before the inner layer saw it
+        assert_eq!(inner.load(Ordering::SeqCst), 1);
+        assert_eq!(outer.load(Ordering::SeqCst), 2);
+    });
+}
+
+// Closes the first `resets` connections without responding, then answers "OK" to every request.
+async fn flaky_server(resets: usize) -> std::net::SocketAddr {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut listener =
+        tokio::net::TcpListener::bind(std::net::SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut accepted = 0;
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf).await;
+            accepted += 1;
+            if accepted > resets {
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nOK",
+                    )
+                    .await;
+            }
+        }
+    });
+    addr
+}
+
+#[test]
+fn retry_transient_errors() {
+    use bollard::retry::RetryPolicy;
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let addr = flaky_server(1).await;
+        let docker =
+            Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION).unwrap();
+        match docker.ping().await.unwrap_err().kind() {
+            ErrorKind::HyperResponseError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let addr = flaky_server(2).await;
+        let docker = Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION)
+            .unwrap()
+            .with_retry_policy(
+                RetryPolicy::default()
+                    .with_backoff(Duration::from_millis(10), Duration::from_millis(50)),
+            );
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+
+        let addr = flaky_server(2).await;
+        let docker = Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION)
+            .unwrap()
+            .with_retry_policy(RetryPolicy::default().with_max_attempts(2));
+        match docker.ping().await.unwrap_err().kind() {
+            ErrorKind::HyperResponseError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    });
+}
+
+/// Read the head of an HTTP request without a body, up to the empty line that ends it.
+#[cfg(unix)]
+fn read_request_head<R: std::io::BufRead>(reader: &mut R) {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        assert!(
+            reader.read_line(&mut line).unwrap() > 0,
+            "connection closed"
+        );
+        if line == "\r\n" {
+            return;
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn connect_with_inherited_fd() {
+    use std::io::{BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let (client, server) = UnixStream::pair().unwrap();
+    std::thread::spawn(move || {
+        let mut server = BufReader::new(server);
+        for _ in 0..2 {
+            read_request_head(&mut server);
+            server
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK")
+                .unwrap();
+        }
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = Docker::connect_with_fd(client, 10, bollard::API_DEFAULT_VERSION).unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    });
+}
+
+#[test]
+fn pool_options() {
+    use bollard::PoolOptions;
+    use hyper::service::{make_service_fn, service_fn};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        let make_service = make_service_fn(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async {
+                Ok::<_, hyper::Error>(service_fn(|_| async {
+                    Ok::<_, hyper::Error>(Response::new(Body::from("OK")))
+                }))
+            }
+        });
+        let server = hyper::Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let docker =
+            Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION).unwrap();
+        docker.ping().await.unwrap();
+        docker.ping().await.unwrap();
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+        let docker = docker.with_pool_options(PoolOptions {
+            keep_alive: false,
+            ..Default::default()
+        });
+        docker.ping().await.unwrap();
+        docker.ping().await.unwrap();
+        assert_eq!(connections.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[cfg(feature = "ssl")]
+#[test]
+fn connect_with_ssl_identity() {
+    use bollard::SslIdentity;
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkcs12::Pkcs12;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::symm::Cipher;
+    use openssl::x509::{X509NameBuilder, X509};
+    use std::fs;
+
+    let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+    let mut name = X509NameBuilder::new().unwrap();
+    name.append_entry_by_text("CN", "bollard").unwrap();
+    let name = name.build();
+    let mut cert = X509::builder().unwrap();
+    cert.set_subject_name(&name).unwrap();
+    cert.set_issuer_name(&name).unwrap();
+    cert.set_pubkey(&key).unwrap();
+    cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    cert.set_not_after(&Asn1Time::days_from_now(1).unwrap())
+        .unwrap();
+    cert.sign(&key, MessageDigest::sha256()).unwrap();
+    let cert = cert.build();
+
+    let dir = std::env::temp_dir().join(format!("bollard-identity-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("ca.pem"), cert.to_pem().unwrap()).unwrap();
+    fs::write(dir.join("cert.pem"), cert.to_pem().unwrap()).unwrap();
+    fs::write(
+        dir.join("key.pem"),
+        key.private_key_to_pem_pkcs8_passphrase(Cipher::aes_256_cbc(), b"secret")
+            .unwrap(),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("identity.p12"),
+        Pkcs12::builder()
+            .build("secret", "bollard", &key, &cert)
+            .unwrap()
+            .to_der()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let (key_path, cert_path, ca_path, archive_path) = (
+        dir.join("key.pem"),
+        dir.join("cert.pem"),
+        dir.join("ca.pem"),
+        dir.join("identity.p12"),
+    );
+    let connect = |identity| {
+        Docker::connect_with_ssl_identity(
+            "localhost:2376",
+            identity,
+            &ca_path,
+            120,
+            bollard::API_DEFAULT_VERSION,
+        )
+    };
+    let pem = |passphrase| SslIdentity::Pem {
+        key: &key_path,
+        cert: &cert_path,
+        passphrase,
+    };
+    let pkcs12 = |password| SslIdentity::Pkcs12 {
+        archive: &archive_path,
+        password,
+    };
+
+    connect(pem(Some("secret"))).unwrap();
+    connect(pkcs12("secret")).unwrap();
+    for identity in vec![pem(Some("wrong")), pkcs12("wrong")] {
+        match connect(identity).unwrap_err().kind() {
+            ErrorKind::SSLError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// Answers "OK" over kept-alive connections, until restarted: the connections opened before then
+// are closed on their next request, as by a restarted docker server.
+async fn restartable_server() -> (
+    std::net::SocketAddr,
+    std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let restarted = Arc::new(AtomicBool::new(false));
+    let mut listener =
+        tokio::net::TcpListener::bind(std::net::SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let flag = restarted.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let stale = !flag.load(Ordering::SeqCst);
+            let flag = flag.clone();
+            tokio::spawn(async move {
+                let mut buf = [0; 1024];
+                while stream.read(&mut buf).await.unwrap_or(0) > 0 {
+                    if stale && flag.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    stream
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK")
+                        .await
+                        .unwrap();
+                }
+            });
+        }
+    });
+    (addr, restarted)
+}
+
+#[test]
+fn health_check_reconnects() {
+    use std::sync::atomic::Ordering;
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let (addr, restarted) = restartable_server().await;
+        let docker =
+            Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION).unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        restarted.store(true, Ordering::SeqCst);
+        match docker.ping().await.unwrap_err().kind() {
+            ErrorKind::HyperResponseError { .. } => (),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        let (addr, restarted) = restartable_server().await;
+        let docker = Docker::connect_with_http(&addr.to_string(), 10, bollard::API_DEFAULT_VERSION)
+            .unwrap()
+            .with_health_check(Duration::from_millis(0));
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        restarted.store(true, Ordering::SeqCst);
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(docker.clone().ping().await.unwrap(), "OK");
+    });
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn connect_with_abstract_socket() {
+    use std::io::{BufReader, Write};
+    use std::os::unix::io::FromRawFd;
+    use std::os::unix::net::UnixListener;
+
+    // the standard library cannot bind an abstract socket
+    let name = format!("bollard-{}", std::process::id());
+    let listener = unsafe {
+        let fd = libc::socket(libc::AF_UNIX, libc::SOCK_STREAM | libc::SOCK_CLOEXEC, 0);
+        assert!(fd >= 0);
+        let mut addr: libc::sockaddr_un = std::mem::zeroed();
+        addr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+        for (dst, src) in addr.sun_path[1..].iter_mut().zip(name.as_bytes()) {
+            *dst = libc::c_char::from_ne_bytes([*src]);
+        }
+        let len = std::mem::size_of::<libc::sa_family_t>() + 1 + name.len();
+        let addr_ptr: *const libc::sockaddr_un = &addr;
+        assert_eq!(
+            libc::bind(
+                fd,
+                addr_ptr as *const libc::sockaddr,
+                len as libc::socklen_t
+            ),
+            0
+        );
+        assert_eq!(libc::listen(fd, 1), 0);
+        UnixListener::from_raw_fd(fd)
+    };
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut stream = BufReader::new(stream);
+        read_request_head(&mut stream);
+        stream
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK")
+            .unwrap();
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = Docker::connect_with_unix(
+            &format!("unix://@{}", name),
+            10,
+            bollard::API_DEFAULT_VERSION,
+        )
+        .unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    });
+
+    match Docker::connect_with_vsock("vsock://host", 10, bollard::API_DEFAULT_VERSION)
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::InvalidVsockAddressError { addr } => assert_eq!(addr, "vsock://host"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
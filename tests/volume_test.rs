@@ -159,3 +159,104 @@ fn integration_test_create_volume() {
 fn integration_test_prune_volumes() {
     connect_to_docker_and_run!(prune_volumes_test);
 }
+
+const VOLUME_DF_FIXTURE: &str = r#"{
+    "LayersSize": 1092588,
+    "Images": [],
+    "Containers": [],
+    "Volumes": [
+        {
+            "Name": "pgdata",
+            "Driver": "local",
+            "Mountpoint": "/var/lib/docker/volumes/pgdata/_data",
+            "Labels": null,
+            "Scope": "local",
+            "Options": null,
+            "UsageData": {"Size": 10920104, "RefCount": 2}
+        },
+        {
+            "Name": "nfs_share",
+            "Driver": "netshare",
+            "Mountpoint": "/var/lib/docker-volumes/netshare/nfs/nfs_share",
+            "Labels": null,
+            "Scope": "global",
+            "Options": null,
+            "UsageData": {"Size": -1, "RefCount": -1}
+        },
+        {
+            "Name": "unrelated",
+            "Driver": "local",
+            "Mountpoint": "/var/lib/docker/volumes/unrelated/_data",
+            "Labels": null,
+            "Scope": "local",
+            "Options": null,
+            "UsageData": {"Size": 0, "RefCount": 0}
+        }
+    ]
+}"#;
+
+#[test]
+fn volume_usage_unfiltered() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn(|req| async move {
+            assert_eq!(req.uri().query(), None);
+            hyper::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(hyper::Body::from(VOLUME_DF_FIXTURE))
+                .unwrap()
+        });
+
+        let usage = docker
+            .volume_usage(&["pgdata", "nfs_share", "missing"])
+            .await
+            .unwrap();
+
+        assert_eq!(usage.len(), 3);
+        assert_eq!(
+            usage["pgdata"],
+            Some(UsageData {
+                size: 10920104,
+                ref_count: 2
+            })
+        );
+        assert_eq!(usage["nfs_share"], None);
+        assert_eq!(usage["missing"], None);
+    });
+}
+
+#[test]
+fn volume_usage_filtered_by_type() {
+    let mut rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let docker = mock_docker_fn_with_version(
+            |req| async move {
+                assert_eq!(req.uri().query(), Some("type=volume"));
+                hyper::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "application/json")
+                    .body(hyper::Body::from(
+                        r#"{"Volumes": [{
+                            "Name": "pgdata",
+                            "Driver": "local",
+                            "Mountpoint": "/var/lib/docker/volumes/pgdata/_data",
+                            "Labels": null,
+                            "Scope": "local",
+                            "Options": null,
+                            "UsageData": {"Size": 10920104, "RefCount": 2}
+                        }]}"#,
+                    ))
+                    .unwrap()
+            },
+            &bollard::ClientVersion {
+                major_version: 1,
+                minor_version: 42,
+            },
+        );
+
+        let usage = docker.volume_usage(&["pgdata"]).await.unwrap();
+
+        assert_eq!(usage["pgdata"].unwrap().size, 10920104);
+    });
+}